@@ -0,0 +1,43 @@
+//! Build script: версия сборки для `GET /version`.
+//!
+//! Передаёт компилятору две переменные, которые читает `env!`:
+//! - `GIT_SHA` — коммит, из которого собран бинарник
+//! - `BUILD_TIMESTAMP` — время сборки в RFC 3339 (UTC)
+//!
+//! # Почему `"unknown"`, а не ошибка?
+//! Сборка из архива с исходниками (без `.git`) или на машине без `git`
+//! должна проходить: версия коммита — справка, а не условие сборки.
+
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    let sha = git(&["rev-parse", "HEAD"]).unwrap_or_else(|| "unknown".into());
+    println!("cargo:rustc-env=GIT_SHA={}", sha);
+
+    let timestamp = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    println!("cargo:rustc-env=BUILD_TIMESTAMP={}", timestamp);
+
+    // Пересобирать при смене коммита, а не при каждом изменении файлов.
+    // Несуществующий путь cargo считал бы изменённым всегда — только существующие
+    println!("cargo:rerun-if-changed=build.rs");
+    let head_ref = git(&["symbolic-ref", "-q", "HEAD"]);
+    for name in ["HEAD"].into_iter().chain(head_ref.as_deref()) {
+        if let Some(path) = git(&["rev-parse", "--git-path", name]) {
+            if Path::new(&path).exists() {
+                println!("cargo:rerun-if-changed={}", path);
+            }
+        }
+    }
+}
+
+/// Вывод `git <args>` без перевода строки; `None`, если git нет или он упал.
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    let value = stdout.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
@@ -0,0 +1,13 @@
+-- Create transactions table (ledger of balance changes)
+CREATE TABLE IF NOT EXISTS transactions (
+    id UUID PRIMARY KEY,
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    kind VARCHAR(20) NOT NULL,
+    amount BIGINT NOT NULL,
+    timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    note TEXT
+);
+
+-- History is always read per account, newest first
+CREATE INDEX IF NOT EXISTS idx_transactions_account_timestamp
+    ON transactions(account_id, timestamp DESC);
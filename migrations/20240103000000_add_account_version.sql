@@ -0,0 +1,2 @@
+-- Version counter for optimistic concurrency control
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS version BIGINT NOT NULL DEFAULT 0;
@@ -0,0 +1,11 @@
+-- Create balance_snapshots table (balance history for charts)
+CREATE TABLE IF NOT EXISTS balance_snapshots (
+    id BIGSERIAL PRIMARY KEY,
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    balance BIGINT NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- History is read per account within a time range
+CREATE INDEX IF NOT EXISTS idx_balance_snapshots_account_recorded_at
+    ON balance_snapshots(account_id, recorded_at);
@@ -0,0 +1,2 @@
+-- Soft delete: deleted accounts keep their row and history
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
@@ -0,0 +1,2 @@
+-- Optional daily withdrawal limit in cents (NULL = unlimited)
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS withdrawal_limit BIGINT;
@@ -0,0 +1,6 @@
+-- Account type (checking, savings, cash, credit) and credit limit.
+-- Existing accounts become "checking", which keeps the old no-overdraft rule.
+ALTER TABLE accounts ADD COLUMN account_type VARCHAR(20) NOT NULL DEFAULT 'checking';
+
+-- How far below zero a credit account may go; NULL means no limit
+ALTER TABLE accounts ADD COLUMN credit_limit BIGINT;
@@ -0,0 +1,2 @@
+-- Minimum balance a withdrawal may not cross; NULL means none
+ALTER TABLE accounts ADD COLUMN min_balance BIGINT;
@@ -0,0 +1,12 @@
+-- Idempotency keys for deposit/withdraw retries
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    key VARCHAR(255) PRIMARY KEY,
+    -- What the first request asked for (operation, account, body)
+    fingerprint TEXT NOT NULL,
+    -- Serialized AccountResponse; NULL while the request is in progress
+    response TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Expired keys are purged by created_at
+CREATE INDEX IF NOT EXISTS idx_idempotency_keys_created_at ON idempotency_keys (created_at);
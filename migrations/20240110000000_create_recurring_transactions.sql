@@ -0,0 +1,20 @@
+-- Standing orders: deposit/withdraw rules executed by the scheduler
+CREATE TABLE IF NOT EXISTS recurring_transactions (
+    id UUID PRIMARY KEY,
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    kind VARCHAR(20) NOT NULL,
+    amount BIGINT NOT NULL,
+    -- Interval like "1d" or "monthly:1"
+    cron_or_interval VARCHAR(50) NOT NULL,
+    next_run TIMESTAMPTZ NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_recurring_transactions_account_id
+    ON recurring_transactions (account_id);
+
+-- The scheduler polls for enabled rules that are due
+CREATE INDEX IF NOT EXISTS idx_recurring_transactions_due
+    ON recurring_transactions (next_run) WHERE enabled;
@@ -0,0 +1,5 @@
+-- Transfers are journaled as a transfer_out/transfer_in pair.
+-- Each half points at the other account and stores the amount on that side,
+-- which differs from `amount` when the transfer converted currency.
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS counterparty_id UUID;
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS counter_amount BIGINT;
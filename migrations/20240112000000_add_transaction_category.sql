@@ -0,0 +1,5 @@
+-- Optional budgeting category ("groceries", "salary"), stored trimmed and lowercased.
+-- NULL means uncategorized.
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS category VARCHAR(50);
+
+CREATE INDEX IF NOT EXISTS idx_transactions_category ON transactions(category);
@@ -0,0 +1,2 @@
+-- Archived accounts are hidden from the account list but stay reachable by id
+ALTER TABLE accounts ADD COLUMN archived BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,4 @@
+-- Account that covers a withdrawal this account cannot cover on its own.
+-- No foreign key: accounts are soft-deleted, and a backup import inserts
+-- accounts in creation order, possibly before the account they point to.
+ALTER TABLE accounts ADD COLUMN overdraft_source UUID;
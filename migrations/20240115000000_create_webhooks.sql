@@ -0,0 +1,10 @@
+-- Webhook subscriptions: POSTed to after every balance change
+CREATE TABLE IF NOT EXISTS webhooks (
+    id UUID PRIMARY KEY,
+    url VARCHAR(2048) NOT NULL,
+    -- HMAC-SHA256 key for the signature header; kept in plain text
+    -- because the server needs it to sign every delivery
+    secret VARCHAR(256) NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,3 @@
+-- Balance below which a withdrawal returns a "low balance" warning.
+-- NULL — no warning. The operation itself is never blocked.
+ALTER TABLE accounts ADD COLUMN low_balance_threshold BIGINT;
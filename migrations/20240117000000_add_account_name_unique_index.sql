@@ -0,0 +1,14 @@
+-- Active account names are unique regardless of case.
+-- The service checks the name before writing, but two concurrent requests
+-- can both pass that check; this index rejects the second write.
+-- Deleted accounts are left out: a deleted name may be reused.
+--
+-- Fails if active duplicates already exist. Find them with
+--   SELECT LOWER(name), COUNT(*) FROM accounts
+--   WHERE deleted_at IS NULL GROUP BY 1 HAVING COUNT(*) > 1;
+-- and rename or delete all but one before starting the server.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_accounts_name_unique
+    ON accounts (LOWER(name)) WHERE deleted_at IS NULL;
+
+-- The unique index serves name lookups of active accounts now
+DROP INDEX IF EXISTS idx_accounts_name;
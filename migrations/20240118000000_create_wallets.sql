@@ -0,0 +1,17 @@
+-- Multi-currency wallets: one row per wallet, balances in wallet_balances
+CREATE TABLE IF NOT EXISTS wallets (
+    id UUID PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Optimistic locking, same as accounts.version
+    version BIGINT NOT NULL DEFAULT 1
+);
+
+-- One row per currency held; a currency withdrawn to zero has no row
+CREATE TABLE IF NOT EXISTS wallet_balances (
+    wallet_id UUID NOT NULL REFERENCES wallets(id) ON DELETE CASCADE,
+    currency VARCHAR(3) NOT NULL,
+    balance BIGINT NOT NULL CHECK (balance > 0),
+    PRIMARY KEY (wallet_id, currency)
+);
@@ -0,0 +1,4 @@
+-- The activity feed reads the newest transactions across all accounts and
+-- pages with (timestamp, id) < cursor; this index serves it without a sort.
+CREATE INDEX IF NOT EXISTS idx_transactions_timestamp_id
+    ON transactions(timestamp DESC, id DESC);
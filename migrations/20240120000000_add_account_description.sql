@@ -0,0 +1,3 @@
+-- Free-text account description ("joint account with spouse").
+-- NULL — no description; the 500-character limit is enforced by the application.
+ALTER TABLE accounts ADD COLUMN description TEXT;
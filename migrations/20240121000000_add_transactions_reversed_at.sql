@@ -0,0 +1,4 @@
+-- When a deposit or withdrawal was reversed. The reversal itself is a
+-- separate, opposite transaction; this row stays in the ledger.
+-- NULL — not reversed.
+ALTER TABLE transactions ADD COLUMN reversed_at TIMESTAMPTZ;
@@ -0,0 +1,9 @@
+-- Accounts belong to a user; each user sees only their own accounts.
+-- Existing accounts go to the default user, which is also the user
+-- of the API when no keys are configured.
+ALTER TABLE accounts ADD COLUMN user_id TEXT NOT NULL DEFAULT 'default';
+
+-- Active account names are unique per user, not globally
+DROP INDEX IF EXISTS idx_accounts_name_unique;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_accounts_user_name_unique
+    ON accounts (user_id, LOWER(name)) WHERE deleted_at IS NULL;
@@ -0,0 +1,3 @@
+-- Idempotency keys are stored as "user:key", so the column must fit the
+-- user name on top of a client key of up to 255 characters
+ALTER TABLE idempotency_keys ALTER COLUMN key TYPE TEXT;
@@ -0,0 +1,14 @@
+-- Create audit_log table (history of changes to the account itself;
+-- balance changes stay in transactions)
+CREATE TABLE IF NOT EXISTS audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    action VARCHAR(40) NOT NULL,
+    old_value TEXT,
+    new_value TEXT,
+    at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- The log is always read per account, oldest first
+CREATE INDEX IF NOT EXISTS idx_audit_log_account_at
+    ON audit_log(account_id, at, id);
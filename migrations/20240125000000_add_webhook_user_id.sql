@@ -0,0 +1,5 @@
+-- Webhooks belong to a user and receive events of that user's accounts only.
+-- Existing webhooks go to the default user, who could manage them until now.
+ALTER TABLE webhooks ADD COLUMN user_id TEXT NOT NULL DEFAULT 'default';
+
+CREATE INDEX IF NOT EXISTS idx_webhooks_user_id ON webhooks(user_id);
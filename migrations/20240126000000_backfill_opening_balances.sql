@@ -0,0 +1,23 @@
+-- The ledger was created empty, so accounts that already held money did not
+-- add up: reconciliation, statements and balance-at started counting from 0.
+-- Give every account whose balance differs from its ledger sum one
+-- opening_balance entry for the difference. It is dated at the account's
+-- creation, before any other entry. Signed like currency_change, because a
+-- credit account can start below zero.
+INSERT INTO transactions (id, account_id, kind, amount, timestamp, note)
+SELECT gen_random_uuid(), a.id, 'opening_balance',
+       a.balance - COALESCE(l.total, 0), a.created_at,
+       'Balance before the ledger was introduced'
+FROM accounts a
+LEFT JOIN (
+    SELECT account_id,
+           SUM(CASE
+                   WHEN kind IN ('deposit', 'transfer_in', 'interest') THEN amount
+                   WHEN kind IN ('withdraw', 'transfer_out') THEN -amount
+                   WHEN kind IN ('currency_change', 'opening_balance') THEN amount
+                   ELSE 0
+               END) AS total
+    FROM transactions
+    GROUP BY account_id
+) l ON l.account_id = a.id
+WHERE a.balance <> COALESCE(l.total, 0);
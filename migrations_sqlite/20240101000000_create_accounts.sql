@@ -0,0 +1,20 @@
+-- SQLite schema for the account store.
+-- Mirrors the PostgreSQL accounts table after all of its migrations.
+-- UUIDs are stored as 16-byte BLOBs and timestamps as TEXT (sqlx defaults).
+CREATE TABLE IF NOT EXISTS accounts (
+    id BLOB PRIMARY KEY,
+    name TEXT NOT NULL,
+    balance INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    version INTEGER NOT NULL DEFAULT 0,
+    deleted_at TEXT,
+    withdrawal_limit INTEGER,
+    account_type TEXT NOT NULL DEFAULT 'checking',
+    credit_limit INTEGER,
+    min_balance INTEGER
+);
+
+-- LOWER() in SQLite folds ASCII letters only
+CREATE INDEX IF NOT EXISTS idx_accounts_name ON accounts(LOWER(name));
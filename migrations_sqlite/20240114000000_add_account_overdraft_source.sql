@@ -0,0 +1,2 @@
+-- Account that covers a withdrawal this account cannot cover on its own
+ALTER TABLE accounts ADD COLUMN overdraft_source BLOB;
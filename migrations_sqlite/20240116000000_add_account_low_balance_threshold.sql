@@ -0,0 +1,2 @@
+-- Balance below which a withdrawal returns a "low balance" warning
+ALTER TABLE accounts ADD COLUMN low_balance_threshold INTEGER;
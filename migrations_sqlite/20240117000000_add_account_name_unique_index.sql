@@ -0,0 +1,6 @@
+-- Active account names are unique regardless of case (ASCII only, see LOWER()).
+-- Deleted accounts are left out: a deleted name may be reused.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_accounts_name_unique
+    ON accounts (LOWER(name)) WHERE deleted_at IS NULL;
+
+DROP INDEX IF EXISTS idx_accounts_name;
@@ -0,0 +1,2 @@
+-- Free-text account description (NULL — no description)
+ALTER TABLE accounts ADD COLUMN description TEXT;
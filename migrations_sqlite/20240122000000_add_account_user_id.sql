@@ -0,0 +1,7 @@
+-- Accounts belong to a user; existing ones go to the default user.
+ALTER TABLE accounts ADD COLUMN user_id TEXT NOT NULL DEFAULT 'default';
+
+-- Active account names are unique per user, not globally
+DROP INDEX IF EXISTS idx_accounts_name_unique;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_accounts_user_name_unique
+    ON accounts (user_id, LOWER(name)) WHERE deleted_at IS NULL;
@@ -0,0 +1,13 @@
+-- Mirrors the PostgreSQL audit_log table.
+-- AUTOINCREMENT keeps ids growing, so (at, id) orders entries written together.
+CREATE TABLE IF NOT EXISTS audit_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    account_id BLOB NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    action TEXT NOT NULL,
+    old_value TEXT,
+    new_value TEXT,
+    at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_account_at
+    ON audit_log(account_id, at, id);
@@ -0,0 +1,17 @@
+-- Mirrors the PostgreSQL transactions table after all of its migrations.
+-- The account store writes ledger rows in the same transaction as the balance.
+CREATE TABLE IF NOT EXISTS transactions (
+    id BLOB PRIMARY KEY,
+    account_id BLOB NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    timestamp TEXT NOT NULL,
+    note TEXT,
+    counterparty_id BLOB,
+    counter_amount INTEGER,
+    category TEXT,
+    reversed_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_transactions_account_timestamp
+    ON transactions(account_id, timestamp DESC);
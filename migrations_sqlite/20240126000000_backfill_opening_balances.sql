@@ -0,0 +1,20 @@
+-- Same backfill as the PostgreSQL migration of this name: accounts created
+-- before the transactions table hold a balance their ledger does not explain.
+-- randomblob(16) stands in for gen_random_uuid(); ids are 16-byte BLOBs here.
+INSERT INTO transactions (id, account_id, kind, amount, timestamp, note)
+SELECT randomblob(16), a.id, 'opening_balance',
+       a.balance - COALESCE(l.total, 0), a.created_at,
+       'Balance before the ledger was introduced'
+FROM accounts a
+LEFT JOIN (
+    SELECT account_id,
+           SUM(CASE
+                   WHEN kind IN ('deposit', 'transfer_in', 'interest') THEN amount
+                   WHEN kind IN ('withdraw', 'transfer_out') THEN -amount
+                   WHEN kind IN ('currency_change', 'opening_balance') THEN amount
+                   ELSE 0
+               END) AS total
+    FROM transactions
+    GROUP BY account_id
+) l ON l.account_id = a.id
+WHERE a.balance <> COALESCE(l.total, 0);
@@ -0,0 +1,11 @@
+-- Mirrors the PostgreSQL balance_snapshots table (balance history for charts).
+-- AUTOINCREMENT keeps ids growing, so ORDER BY id is the order of writes.
+CREATE TABLE IF NOT EXISTS balance_snapshots (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    account_id BLOB NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    balance INTEGER NOT NULL,
+    recorded_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_balance_snapshots_account_recorded_at
+    ON balance_snapshots(account_id, recorded_at);
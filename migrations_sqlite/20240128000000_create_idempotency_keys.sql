@@ -0,0 +1,11 @@
+-- Mirrors the PostgreSQL idempotency_keys table after all of its migrations.
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    key TEXT PRIMARY KEY,
+    -- What the first request asked for (operation, account, body)
+    fingerprint TEXT NOT NULL,
+    -- Serialized AccountResponse; NULL while the request is in progress
+    response TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_idempotency_keys_created_at ON idempotency_keys (created_at);
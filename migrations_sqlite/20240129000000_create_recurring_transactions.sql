@@ -0,0 +1,20 @@
+-- Mirrors the PostgreSQL recurring_transactions table (standing orders).
+CREATE TABLE IF NOT EXISTS recurring_transactions (
+    id BLOB PRIMARY KEY,
+    account_id BLOB NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    -- Interval like "1d" or "monthly:1"
+    cron_or_interval TEXT NOT NULL,
+    next_run TEXT NOT NULL,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    note TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_recurring_transactions_account_id
+    ON recurring_transactions (account_id);
+
+-- The scheduler polls for enabled rules that are due
+CREATE INDEX IF NOT EXISTS idx_recurring_transactions_due
+    ON recurring_transactions (next_run) WHERE enabled;
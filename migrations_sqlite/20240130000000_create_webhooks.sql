@@ -0,0 +1,12 @@
+-- Mirrors the PostgreSQL webhooks table after all of its migrations.
+CREATE TABLE IF NOT EXISTS webhooks (
+    id BLOB PRIMARY KEY,
+    url TEXT NOT NULL,
+    -- HMAC-SHA256 key for the signature header; the server signs every delivery
+    secret TEXT NOT NULL,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    user_id TEXT NOT NULL DEFAULT 'default'
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhooks_user_id ON webhooks(user_id);
@@ -0,0 +1,17 @@
+-- Mirrors the PostgreSQL wallets and wallet_balances tables.
+CREATE TABLE IF NOT EXISTS wallets (
+    id BLOB PRIMARY KEY,
+    name TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    -- Optimistic locking, same as accounts.version
+    version INTEGER NOT NULL DEFAULT 1
+);
+
+-- One row per currency held; a currency withdrawn to zero has no row
+CREATE TABLE IF NOT EXISTS wallet_balances (
+    wallet_id BLOB NOT NULL REFERENCES wallets(id) ON DELETE CASCADE,
+    currency TEXT NOT NULL,
+    balance INTEGER NOT NULL CHECK (balance > 0),
+    PRIMARY KEY (wallet_id, currency)
+);
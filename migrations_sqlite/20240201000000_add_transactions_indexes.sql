@@ -0,0 +1,6 @@
+-- The indexes PostgreSQL got in separate migrations: the activity feed pages
+-- with (timestamp, id) < cursor, and reports filter by category.
+CREATE INDEX IF NOT EXISTS idx_transactions_timestamp_id
+    ON transactions(timestamp DESC, id DESC);
+
+CREATE INDEX IF NOT EXISTS idx_transactions_category ON transactions(category);
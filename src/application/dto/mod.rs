@@ -9,10 +9,21 @@
 //! - Request DTO: что приходит от клиента (`Deserialize`)
 //! - Response DTO: что отправляем клиенту (`Serialize`)
 
-use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use chrono::{DateTime, Months, NaiveDate, NaiveTime, Utc};
+use futures_util::stream::BoxStream;
+use rust_decimal::Decimal;
+use serde::de::{IgnoredAny, IntoDeserializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use uuid::Uuid;
 
-use crate::domain::entities::Account;
+use crate::application::ports::{AccountFilter, AccountSort, ActivityCursor, TransactionFilter};
+use crate::domain::entities::{
+    Account, AccountType, AuditAction, AuditEntry, BalanceSnapshot, RecurringKind,
+    RecurringTransaction, Transaction, TransactionKind, Wallet, Webhook,
+};
+use crate::domain::value_objects::{Currency, Money, Schedule};
 
 // ═══════════════════════════════════════════════════════════════════
 // REQUEST DTOs — входящие данные от клиента
@@ -23,49 +34,908 @@ use crate::domain::entities::Account;
 /// # Пример JSON
 /// ```json
 /// {
-///   "name": "Wallet",
-///   "currency": "USD"
+///   "name": "Credit card",
+///   "currency": "USD",
+///   "account_type": "credit",
+///   "credit_limit": "1000.00",
+///   "description": "Shared card for groceries"
 /// }
 /// ```
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CreateAccountRequest {
     pub name: String,
-    pub currency: String,
+    pub currency: Option<String>, // Нет в JSON — DEFAULT_CURRENCY
+    #[serde(default)] // Нет в JSON — AccountType::default() (checking)
+    pub account_type: AccountType,
+    pub credit_limit: Option<Money>, // Только для "credit"
+    pub description: Option<String>,
+}
+
+/// Запрос на создание нескольких счетов сразу.
+///
+/// # Пример JSON
+/// ```json
+/// {
+///   "atomic": false,
+///   "accounts": [
+///     {"name": "Wallet", "currency": "USD"},
+///     {"name": "Savings", "currency": "USD", "account_type": "savings"}
+///   ]
+/// }
+/// ```
+///
+/// # Поле `atomic`
+/// - `true` (по умолчанию) — если хоть один счёт не проходит проверку,
+///   не создаётся ни один
+/// - `false` — создаются все корректные, остальные получают ошибку
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BatchCreateAccountsRequest {
+    pub accounts: Vec<CreateAccountRequest>,
+    #[serde(default = "default_atomic")]
+    pub atomic: bool,
+}
+
+/// Запрос «счёт с этим именем должен существовать» (`PUT /api/accounts/by-name/:name`).
+///
+/// Имя — из пути. `currency` нужна только если счёт создаётся; у
+/// существующего счёта она не сверяется и не меняется. Нет в JSON —
+/// `DEFAULT_CURRENCY`, как у `CreateAccountRequest`.
+///
+/// # Пример JSON
+/// ```json
+/// { "currency": "EUR" }
+/// ```
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct EnsureAccountRequest {
+    pub currency: Option<String>,
+}
+
+/// Значение `atomic` по умолчанию.
+///
+/// `#[serde(default)]` для `bool` дал бы `false`, а безопаснее "всё или ничего".
+fn default_atomic() -> bool {
+    true
+}
+
+impl BatchCreateAccountsRequest {
+    /// Максимум счетов в одном запросе.
+    pub const MAX_ITEMS: usize = 100;
+
+    /// Проверяет размер пачки.
+    ///
+    /// # Errors
+    /// Текст ошибки для клиента, если счетов нет или их больше `MAX_ITEMS`.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=Self::MAX_ITEMS).contains(&self.accounts.len()) {
+            return Err(format!(
+                "accounts must contain 1 to {} items, got {}",
+                Self::MAX_ITEMS,
+                self.accounts.len()
+            ));
+        }
+        Ok(())
+    }
 }
 
 /// Запрос на пополнение счёта.
 ///
 /// # Поле `amount`
-/// Сумма в основных единицах валюты (доллары, рубли), НЕ в копейках.
-/// Конвертация в копейки происходит в сервисе.
+/// Сумма в основных единицах валюты строкой (`"100.50"`), НЕ в копейках.
+/// Разбирается сразу в `Money`, поэтому `"100.005"` отклоняется ещё при парсинге.
+///
+/// # Поле `note`
+/// Необязательный комментарий, попадает в журнал операций.
+/// `Option<T>` — если поля нет в JSON, serde подставит `None`.
+///
+/// # Поле `category`
+/// Необязательная категория для бюджета (`"salary"`). Хранится
+/// в нижнем регистре, см. `Transaction::normalize_category`.
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct DepositRequest {
-    pub amount: f64,
+    pub amount: Money,
+    pub note: Option<String>,
+    pub category: Option<String>,
 }
 
 /// Запрос на снятие денег.
+///
+/// `"amount": "all"` — снять весь баланс (см. `WithdrawAmount`).
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct WithdrawRequest {
-    pub amount: f64,
+    pub amount: WithdrawAmount,
+    pub note: Option<String>,
+    pub category: Option<String>,
+}
+
+/// Сколько снять: конкретную сумму или весь баланс.
+///
+/// # Зачем `All`, если баланс можно прочитать и передать?
+/// Между чтением и снятием баланс может измениться, а клиент, считающий
+/// сумму сам, легко ошибается на копейку. С `All` сервис берёт баланс
+/// того же прочитанного счёта, который сохраняет (с проверкой версии),
+/// и остаток получается ровно нулевым.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WithdrawAmount {
+    Exact(Money),
+    All,
+}
+
+/// `"all"` или сумма — так же, как в запросе (для отпечатка идемпотентности).
+impl fmt::Display for WithdrawAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WithdrawAmount::Exact(amount) => amount.fmt(f),
+            WithdrawAmount::All => f.write_str("all"),
+        }
+    }
+}
+
+/// Строка `"all"` (без учёта регистра) или сумма в формате `Money`.
+impl<'de> Deserialize<'de> for WithdrawAmount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        if raw.trim().eq_ignore_ascii_case("all") {
+            return Ok(WithdrawAmount::All);
+        }
+        // Разбор и тексты ошибок — как у самого Money
+        Money::deserialize(raw.as_str().into_deserializer()).map(WithdrawAmount::Exact)
+    }
+}
+
+/// Запрос на начисление процентов.
+///
+/// # Пример JSON
+/// ```json
+/// { "annual_rate": "5.5", "days": 30, "note": "March interest" }
+/// ```
+///
+/// - `annual_rate` — годовая ставка в процентах: `"5.5"` — это 5.5% годовых.
+///   Лучше строкой, как суммы: число из JSON сначала становится `f64`
+/// - `days` — за сколько дней начислить, от 1 до 366
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ApplyInterestRequest {
+    #[cfg_attr(feature = "openapi", schema(value_type = String, example = "5.5"))]
+    pub annual_rate: Decimal,
+    pub days: u32,
+    pub note: Option<String>,
+}
+
+/// Запрос на перевод с одного счёта на другой.
+///
+/// # Пример JSON
+/// ```json
+/// {
+///   "to_account_id": "550e8400-e29b-41d4-a716-446655440000",
+///   "amount": "10.00",
+///   "note": "Savings"
+/// }
+/// ```
+///
+/// `amount` — в валюте счёта-источника.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TransferRequest {
+    pub to_account_id: Uuid,
+    pub amount: Money,
+    pub note: Option<String>,
+}
+
+/// Запрос на закрытие счёта.
+///
+/// # Пример JSON
+/// ```json
+/// {
+///   "to_account_id": "550e8400-e29b-41d4-a716-446655440000",
+///   "note": "Card closed"
+/// }
+/// ```
+///
+/// Без `to_account_id` (`{}`) остаток списывается со счёта.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CloseAccountRequest {
+    pub to_account_id: Option<Uuid>,
+    pub note: Option<String>,
+}
+
+/// Запрос на смену валюты счёта.
+///
+/// # Пример JSON
+/// ```json
+/// { "currency": "EUR", "rate": "0.92", "note": "Moved to a EUR account" }
+/// ```
+///
+/// - `currency` — новый код ISO 4217
+/// - `rate` — сколько единиц новой валюты за единицу старой. Строкой,
+///   как `annual_rate` в `ApplyInterestRequest`
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ChangeCurrencyRequest {
+    pub currency: String,
+    #[cfg_attr(feature = "openapi", schema(value_type = String, example = "0.92"))]
+    pub rate: Decimal,
+    pub note: Option<String>,
+}
+
+/// Запрос на изменение настроек счёта (PATCH).
+///
+/// Применяются только переданные поля.
+///
+/// # Пример JSON
+/// ```json
+/// {
+///   "name": "Budget",
+///   "withdrawal_limit": "200.00",
+///   "min_balance": "100.00",
+///   "low_balance_threshold": "500.00",
+///   "overdraft_source": "550e8400-e29b-41d4-a716-446655440000",
+///   "description": "Joint account with spouse"
+/// }
+/// ```
+///
+/// # `Option<Option<Money>>` для лимита и остатка
+/// Нужно различать три случая:
+/// - поля нет в JSON → `None` — лимит не трогаем
+/// - `"withdrawal_limit": null` → `Some(None)` — снимаем лимит
+/// - `"withdrawal_limit": "200.00"` → `Some(Some(..))` — ставим лимит
+///
+/// Так же устроены `low_balance_threshold` (`null` снимает порог),
+/// `overdraft_source` (`null` снимает защиту от овердрафта)
+/// и `description` (`null` убирает описание).
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UpdateAccountRequest {
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub withdrawal_limit: Option<Option<Money>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub min_balance: Option<Option<Money>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub low_balance_threshold: Option<Option<Money>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub overdraft_source: Option<Option<Uuid>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub description: Option<Option<String>>,
+}
+
+/// Запрос на создание повторяющейся операции.
+///
+/// # Пример JSON
+/// ```json
+/// {
+///   "kind": "deposit",
+///   "amount": "1500.00",
+///   "schedule": "monthly:1",
+///   "note": "Salary"
+/// }
+/// ```
+///
+/// # Поле `starts_at`
+/// Время первого запуска. Если не указано — первый срок по расписанию
+/// после текущего момента (для `1d` — через сутки).
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateRecurringRequest {
+    pub kind: RecurringKind,
+    pub amount: Money,
+    pub schedule: Schedule,
+    pub note: Option<String>,
+    pub starts_at: Option<DateTime<Utc>>,
+}
+
+/// Запрос на изменение повторяющейся операции (PATCH).
+///
+/// Если меняется `schedule`, а `next_run` не передан, следующий запуск
+/// пересчитывается по новому расписанию от текущего момента.
+/// `note: null` удаляет комментарий (см. `UpdateAccountRequest`).
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UpdateRecurringRequest {
+    pub amount: Option<Money>,
+    pub schedule: Option<Schedule>,
+    pub next_run: Option<DateTime<Utc>>,
+    pub enabled: Option<bool>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub note: Option<Option<String>>,
+}
+
+/// Запрос на изменение операции из журнала (PATCH).
+///
+/// Меняются только комментарий и категория; `null` удаляет их
+/// (см. `UpdateAccountRequest`).
+///
+/// # Зачем поле `amount`?
+/// Сумму записанной операции менять нельзя: журнал перестал бы сходиться
+/// с балансом. Без поля serde молча пропустил бы `"amount"`, и клиент
+/// решил бы, что сумма изменилась. С ним — 400 с понятной причиной.
+/// В схеме OpenAPI поля нет.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UpdateTransactionRequest {
+    #[serde(default, deserialize_with = "double_option")]
+    pub note: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub category: Option<Option<String>>,
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(ignore))]
+    pub amount: Option<IgnoredAny>,
+}
+
+/// Запрос на создание подписки на события (webhook).
+///
+/// # Пример JSON
+/// ```json
+/// {
+///   "url": "http://billing.internal/hooks/balance",
+///   "secret": "4f6c0b9e2d7a41c8a3e5"
+/// }
+/// ```
+///
+/// `secret` — ключ HMAC-подписи, от 16 символов. Сервер его хранит,
+/// но в ответах никогда не отдаёт: сохраните его у себя.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    pub secret: String,
+    pub enabled: Option<bool>, // По умолчанию — включена
+}
+
+/// Запрос на изменение подписки (PATCH). Применяются только переданные поля.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UpdateWebhookRequest {
+    pub url: Option<String>,
+    pub secret: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+/// Запрос на создание кошелька.
+///
+/// # Пример JSON
+/// ```json
+/// {"name": "Travel cash"}
+/// ```
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateWalletRequest {
+    pub name: String,
+}
+
+/// Пополнение или снятие в одной из валют кошелька.
+///
+/// # Пример JSON
+/// ```json
+/// {"currency": "EUR", "amount": "50.00"}
+/// ```
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WalletOperationRequest {
+    pub currency: String,
+    pub amount: Money,
+}
+
+/// Десериализует присутствующее поле в `Some(...)`, даже если там `null`.
+///
+/// # Как это работает
+/// Для отсутствующего поля serde вызывает `Default` (`None`, см. `#[serde(default)]`),
+/// а эту функцию — только если поле есть. Поэтому `null` превращается в `Some(None)`.
+fn double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Параметры пагинации из query string: `?limit=20&offset=40`.
+///
+/// Оба поля необязательны — значения по умолчанию см. `DEFAULT_LIMIT`.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct Pagination {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl Pagination {
+    /// Сколько записей отдавать, если клиент не указал `limit`.
+    pub const DEFAULT_LIMIT: i64 = 50;
+
+    /// Максимальный `limit` — защищает БД от выгрузки всей таблицы.
+    pub const MAX_LIMIT: i64 = 500;
+
+    /// Эффективный лимит (с учётом значения по умолчанию).
+    pub fn limit(&self) -> i64 {
+        self.limit.unwrap_or(Self::DEFAULT_LIMIT)
+    }
+
+    /// Эффективное смещение (по умолчанию 0).
+    pub fn offset(&self) -> i64 {
+        self.offset.unwrap_or(0)
+    }
+
+    /// Проверяет границы параметров.
+    ///
+    /// # Errors
+    /// Текст ошибки для клиента, если `limit` вне `1..=MAX_LIMIT`
+    /// или `offset` отрицательный.
+    pub fn validate(&self) -> Result<(), String> {
+        let limit = self.limit();
+        if !(1..=Self::MAX_LIMIT).contains(&limit) {
+            return Err(format!(
+                "limit must be between 1 and {}, got {}",
+                Self::MAX_LIMIT,
+                limit
+            ));
+        }
+        if self.offset() < 0 {
+            return Err(format!(
+                "offset must not be negative, got {}",
+                self.offset()
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Фильтры и сортировка списка счетов из query string:
+/// `?currency=USD&name_contains=card&sort=-balance&include_archived=true`.
+///
+/// Все поля необязательны. `sort` — `name`, `balance` или `created_at`,
+/// с `-` в начале для сортировки по убыванию (по умолчанию `-created_at`).
+/// Архивные счета в список попадают только с `include_archived=true`.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct AccountListQuery {
+    pub currency: Option<String>,
+    pub name_contains: Option<String>,
+    pub sort: Option<String>,
+    #[serde(default)]
+    pub include_archived: bool,
+}
+
+impl AccountListQuery {
+    /// Проверяет параметры и собирает фильтр для репозитория.
+    ///
+    /// # Errors
+    /// Текст ошибки для клиента, если `sort` не из списка.
+    pub fn to_filter(&self) -> Result<AccountFilter, String> {
+        let sort = match &self.sort {
+            Some(sort) => sort.parse()?,
+            None => AccountSort::default(),
+        };
+
+        Ok(AccountFilter {
+            // "eur" и " EUR" — та же валюта; пустой код ничего не фильтрует
+            currency: self
+                .currency
+                .as_deref()
+                .map(|code| Currency::new(code).to_string())
+                .filter(|code| !code.is_empty()),
+            // Пустая подстрока ничего не фильтрует
+            name_contains: self.name_contains.clone().filter(|s| !s.is_empty()),
+            sort,
+            include_archived: self.include_archived,
+        })
+    }
+}
+
+/// Диапазон времени из query string: `?from=2024-01-01T00:00:00Z&to=...`.
+///
+/// Обе границы необязательны и включительны.
+/// Даты в формате RFC 3339 — chrono разбирает их через serde.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct TimeRange {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl TimeRange {
+    /// Проверяет, что `from` не позже `to`.
+    pub fn validate(&self) -> Result<(), String> {
+        match (self.from, self.to) {
+            (Some(from), Some(to)) if from > to => Err(format!(
+                "from ({}) must not be later than to ({})",
+                from.to_rfc3339(),
+                to.to_rfc3339()
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Флаг пробного запуска из query string: `?dry_run=true`.
+///
+/// Пополнение, снятие или перевод проходят все проверки, но ничего
+/// не сохраняется. В отличие от `simulate`, это тот же запрос с тем же
+/// телом: категория, `"all"`, овердрафт и курс валют проверяются так же,
+/// а ошибка возвращается обычным HTTP-статусом.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct DryRunQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Фильтр истории операций из query string: `?category=groceries&kind=withdraw`.
+///
+/// Период (`from`, `to`) и страница (`limit`, `offset`) разбираются
+/// отдельно — в `TimeRange` и `Pagination`.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct TransactionListQuery {
+    pub category: Option<String>,
+    pub kind: Option<TransactionKind>,
+}
+
+impl TransactionListQuery {
+    /// Категория для фильтра; пустой `?category=` — без фильтра.
+    pub fn category(&self) -> Option<&str> {
+        self.category
+            .as_deref()
+            .filter(|category| !category.trim().is_empty())
+    }
+
+    /// Фильтр для репозитория; категория ещё не нормализована —
+    /// это делает сервис (`get_transactions`).
+    pub fn to_filter(&self, range: &TimeRange) -> TransactionFilter {
+        TransactionFilter {
+            category: self.category().map(str::to_owned),
+            kind: self.kind,
+            from: range.from,
+            to: range.to,
+        }
+    }
+}
+
+/// Параметры ленты операций из query string: `?limit=20&before=...`.
+///
+/// `before` — курсор из `next_before` предыдущей страницы
+/// (см. `ActivityCursor`); без него — самые новые операции.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct ActivityQuery {
+    pub limit: Option<i64>,
+    pub before: Option<String>,
+}
+
+impl ActivityQuery {
+    /// Эффективный лимит — как у `Pagination`.
+    pub fn limit(&self) -> i64 {
+        self.limit.unwrap_or(Pagination::DEFAULT_LIMIT)
+    }
+
+    /// Проверяет лимит и разбирает курсор; пустой `?before=` — без курсора.
+    ///
+    /// # Errors
+    /// Текст ошибки для клиента, если `limit` вне `1..=MAX_LIMIT`
+    /// или курсор не разобрался.
+    pub fn cursor(&self) -> Result<Option<ActivityCursor>, String> {
+        let limit = self.limit();
+        if !(1..=Pagination::MAX_LIMIT).contains(&limit) {
+            return Err(format!(
+                "limit must be between 1 and {}, got {}",
+                Pagination::MAX_LIMIT,
+                limit
+            ));
+        }
+
+        self.before
+            .as_deref()
+            .filter(|before| !before.trim().is_empty())
+            .map(str::parse)
+            .transpose()
+    }
+}
+
+/// Валюта отчёта о капитале из query string: `?currency=USD`.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct NetWorthQuery {
+    pub currency: String,
+}
+
+impl NetWorthQuery {
+    /// Валюта отчёта.
+    ///
+    /// # Errors
+    /// Текст ошибки для клиента, если код валюты пустой.
+    pub fn currency(&self) -> Result<Currency, String> {
+        let currency = Currency::new(&self.currency);
+        if currency.to_string().is_empty() {
+            return Err("currency must not be empty".into());
+        }
+        Ok(currency)
+    }
+}
+
+/// Месяц выписки из query string: `?year=2024&month=1`.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct StatementQuery {
+    pub year: i32,
+    pub month: u32,
+}
+
+/// День для баланса на дату из query string: `?date=2024-01-01`.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct BalanceAtQuery {
+    pub date: NaiveDate,
+}
+
+impl BalanceAtQuery {
+    /// Граница: начало следующего дня по UTC, не включительно.
+    ///
+    /// `date` входит целиком: операция в `23:59:59.999` этого дня
+    /// учитывается, а в `00:00` следующего — уже нет.
+    ///
+    /// # Errors
+    /// Текст ошибки для клиента, если `date` — последний день chrono.
+    pub fn cutoff(&self) -> Result<DateTime<Utc>, String> {
+        let next_day = self
+            .date
+            .succ_opt()
+            .ok_or_else(|| format!("date {} is out of range", self.date))?;
+        Ok(next_day.and_time(NaiveTime::MIN).and_utc())
+    }
+}
+
+/// Поиск счетов по имени из query string: `?q=card&limit=5`.
+///
+/// Ищутся только активные неархивные счета, без учёта регистра.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct AccountSearchQuery {
+    pub q: String,
+    pub limit: Option<i64>,
+}
+
+impl AccountSearchQuery {
+    /// Сколько результатов отдавать, если клиент не указал `limit`.
+    pub const DEFAULT_LIMIT: i64 = 10;
+
+    /// Максимальный `limit`: поиск — для подсказок, не для выгрузки.
+    pub const MAX_LIMIT: i64 = 50;
+
+    /// Проверяет параметры; возвращает строку поиска без пробелов по краям
+    /// и эффективный лимит.
+    ///
+    /// # Errors
+    /// Текст ошибки для клиента, если `q` пустая или `limit`
+    /// вне `1..=MAX_LIMIT`.
+    pub fn validate(&self) -> Result<(&str, usize), String> {
+        let q = self.q.trim();
+        if q.is_empty() {
+            return Err("q must not be empty".into());
+        }
+
+        let limit = self.limit.unwrap_or(Self::DEFAULT_LIMIT);
+        if !(1..=Self::MAX_LIMIT).contains(&limit) {
+            return Err(format!(
+                "limit must be between 1 and {}, got {}",
+                Self::MAX_LIMIT,
+                limit
+            ));
+        }
+        Ok((q, limit as usize))
+    }
+}
+
+/// Параметры удаления счёта из query string: `?force=true`.
+///
+/// Без `force` счёт с ненулевым балансом не удаляется.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct DeleteAccountQuery {
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Параметры смены валюты из query string: `?force=true`.
+///
+/// Без `force` валюта счёта с активными регулярными операциями
+/// не меняется.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct ChangeCurrencyQuery {
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Проверенный период выписки: календарный месяц по UTC.
+///
+/// `start` включительно, `end` (полночь 1-го числа следующего месяца) — нет.
+#[derive(Debug, Clone, Copy)]
+pub struct StatementPeriod {
+    pub year: i32,
+    pub month: u32,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl StatementQuery {
+    /// Проверяет год и месяц и вычисляет границы периода.
+    ///
+    /// # Errors
+    /// Текст ошибки для клиента, если месяц вне `1..=12`
+    /// или год вне диапазона chrono.
+    pub fn period(&self) -> Result<StatementPeriod, String> {
+        if !(1..=12).contains(&self.month) {
+            return Err(format!(
+                "month must be between 1 and 12, got {}",
+                self.month
+            ));
+        }
+
+        let first_day = NaiveDate::from_ymd_opt(self.year, self.month, 1)
+            .ok_or_else(|| format!("year {} is out of range", self.year))?;
+        let next_month = first_day
+            .checked_add_months(Months::new(1))
+            .ok_or_else(|| format!("year {} is out of range", self.year))?;
+
+        Ok(StatementPeriod {
+            year: self.year,
+            month: self.month,
+            start: first_day.and_time(NaiveTime::MIN).and_utc(),
+            end: next_month.and_time(NaiveTime::MIN).and_utc(),
+        })
+    }
+}
+
+/// Операция, которую можно симулировать.
+///
+/// `#[serde(rename_all = "lowercase")]` — в JSON пишем `"deposit"`, а не `"Deposit"`.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum SimulatedOperation {
+    Deposit,
+    Withdraw,
+}
+
+/// Запрос на симуляцию операции (what-if).
+///
+/// # Пример JSON
+/// ```json
+/// {
+///   "operation": "withdraw",
+///   "amount": "250.00"
+/// }
+/// ```
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SimulateRequest {
+    pub operation: SimulatedOperation,
+    pub amount: Money,
 }
 
 // ═══════════════════════════════════════════════════════════════════
 // RESPONSE DTOs — исходящие данные для клиента
 // ═══════════════════════════════════════════════════════════════════
 
+/// Остаток в ответе с числом знаков валюты: `"1050"` для JPY,
+/// `"10.50"` для USD, `"1.050"` для BHD (см. `Money::format_in`).
+///
+/// # Зачем, если есть `Money`?
+/// `Money` валюты не знает и всегда пишет два знака — для иен это
+/// `"1050.00"`. Баланс в ответе должен совпадать с `formatted_balance`,
+/// поэтому строка сразу пишется в знаках валюты, а копейки лежат рядом
+/// для кода сервиса.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurrencyAmount {
+    money: Money,
+    formatted: String,
+}
+
+impl CurrencyAmount {
+    /// Сумма `money` в валюте `currency`.
+    pub fn new(money: Money, currency: &Currency) -> Self {
+        Self {
+            money,
+            formatted: money.format_in(currency),
+        }
+    }
+
+    /// Сумма в копейках/центах, как у `Money::cents`. Нужна только
+    /// тестам: сервис считает по `Account`, а не по ответу.
+    #[cfg(test)]
+    pub fn cents(&self) -> i64 {
+        self.money.cents()
+    }
+}
+
+impl fmt::Display for CurrencyAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.formatted)
+    }
+}
+
+/// В JSON — строка в знаках валюты: `"balance": "1050"`.
+impl Serialize for CurrencyAmount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Обратно из строки — для ответов, сохранённых с ключом идемпотентности.
+///
+/// Валюты здесь нет, поэтому строка остаётся как была: повтор запроса
+/// отдаёт ровно сохранённый ответ, в том числе `"1050.00"` из ответов,
+/// записанных до появления этого типа.
+impl<'de> Deserialize<'de> for CurrencyAmount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let formatted = String::deserialize(deserializer)?;
+        let money = formatted.parse().map_err(serde::de::Error::custom)?;
+        Ok(Self { money, formatted })
+    }
+}
+
 /// Ответ с информацией о счёте.
 ///
 /// # Отличия от доменной сущности
-/// - `balance` как f64 (для удобства клиента)
+/// - `balance` как `CurrencyAmount` — строка в знаках валюты (`"10.50"`, `"1050"` для JPY)
+///   без потери точности
+/// - `remaining_withdrawal_limit` — остаток дневного лимита; его заполняет
+///   сервис, потому что для расчёта нужен журнал операций
 /// - `created_at` как String (ISO 8601 формат)
-#[derive(Debug, Serialize)]
+///
+/// `Deserialize` нужен, чтобы вернуть ответ, сохранённый вместе
+/// с ключом идемпотентности.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct AccountResponse {
     pub id: Uuid,
     pub name: String,
-    pub balance: f64, // В рублях/долларах, не в копейках
+    /// Баланс в знаках валюты счёта (см. `CurrencyAmount`)
+    pub balance: CurrencyAmount,
+    /// Баланс с числом знаков по валюте счёта: `"1050"` для JPY, `"10.50"` для USD,
+    /// `"1.050"` для BHD (см. `Money::format_in`). Совпадает с `balance`
+    pub formatted_balance: String,
     pub currency: String,
     pub created_at: String, // RFC 3339 формат
     pub updated_at: String,
+    pub withdrawal_limit: Option<Money>,
+    pub remaining_withdrawal_limit: Option<Money>,
+    pub account_type: AccountType,
+    pub credit_limit: Option<Money>,
+    pub min_balance: Option<Money>,
+    #[serde(default)] // В ответах, сохранённых с ключом идемпотентности до архива
+    pub archived: bool,
+    #[serde(default)]
+    pub overdraft_source: Option<Uuid>,
+    #[serde(default)]
+    pub low_balance_threshold: Option<Money>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Предупреждения об этой операции (`"low balance"`); операция при этом
+    /// прошла. Пустой список не сериализуется
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// `true` — результат пробного запуска (`?dry_run=true`), ничего не сохранено;
+    /// в обычных ответах поля нет
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
 }
 
 /// Конвертация из доменной сущности в DTO.
@@ -79,25 +949,561 @@ impl From<Account> for AccountResponse {
     fn from(account: Account) -> Self {
         // ВАЖНО: сначала вызываем методы, потом перемещаем поля
         // Иначе получим ошибку "borrow of moved value"
-        let balance = account.balance_as_f64();
+        let balance = CurrencyAmount::new(
+            Money::from_cents(account.balance),
+            &Currency::new(&account.currency),
+        );
+        let formatted_balance = balance.to_string();
         let created_at = account.created_at.to_rfc3339();
         let updated_at = account.updated_at.to_rfc3339();
+        let withdrawal_limit = account.withdrawal_limit.map(Money::from_cents);
 
         Self {
             id: account.id,
             name: account.name, // String перемещается (move)
             balance,
+            formatted_balance,
             currency: account.currency,
             created_at,
             updated_at,
+            withdrawal_limit,
+            // Без журнала остаток неизвестен — считаем, что сегодня снятий не было.
+            // Сервис перезаписывает это поле реальным значением.
+            remaining_withdrawal_limit: withdrawal_limit,
+            account_type: account.account_type,
+            credit_limit: account.credit_limit.map(Money::from_cents),
+            min_balance: account.min_balance.map(Money::from_cents),
+            archived: account.archived,
+            overdraft_source: account.overdraft_source,
+            low_balance_threshold: account.low_balance_threshold.map(Money::from_cents),
+            description: account.description,
+            warnings: Vec::new(),
+            dry_run: false,
         }
     }
 }
 
+/// Страница результатов.
+///
+/// # Generic параметр `T`
+/// Один тип для любых списков: `PagedResponse<AccountResponse>`,
+/// `PagedResponse<TransactionResponse>` и т.д.
+///
+/// # Поле `total`
+/// Общее количество записей (без учёта limit/offset) —
+/// клиенту нужно, чтобы посчитать число страниц.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PagedResponse<T> {
+    pub items: Vec<T>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// Ответ с информацией об операции из журнала.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TransactionResponse {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub kind: TransactionKind,
+    pub amount: Money,
+    pub timestamp: String, // RFC 3339 формат
+    pub note: Option<String>,
+    pub counterparty_id: Option<Uuid>, // Только для переводов
+    pub counter_amount: Option<Money>,
+    pub category: Option<String>,
+    /// Когда операцию отменили (RFC 3339); `null` — не отменена
+    pub reversed_at: Option<String>,
+}
+
+/// Операция в ленте всех счетов — с именем и валютой счёта,
+/// чтобы клиенту не запрашивать каждый счёт отдельно.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ActivityItem {
+    #[serde(flatten)]
+    pub transaction: TransactionResponse,
+    pub account_name: String,
+    pub currency: String,
+}
+
+/// Страница ленты операций.
+///
+/// # Поле `next_before`
+/// Курсор следующей страницы — передаётся как `?before=`. `None` —
+/// страница последняя. Операции удалённых счетов в ленту не попадают,
+/// поэтому страница может быть короче `limit`, а курсор при этом есть.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ActivityPage {
+    pub items: Vec<ActivityItem>,
+    pub next_before: Option<String>,
+}
+
+/// Конвертация из доменной сущности в DTO.
+impl From<Transaction> for TransactionResponse {
+    fn from(transaction: Transaction) -> Self {
+        let timestamp = transaction.timestamp.to_rfc3339();
+
+        Self {
+            id: transaction.id,
+            account_id: transaction.account_id,
+            kind: transaction.kind,
+            amount: Money::from_cents(transaction.amount),
+            timestamp,
+            note: transaction.note,
+            counterparty_id: transaction.counterparty_id,
+            counter_amount: transaction.counter_amount.map(Money::from_cents),
+            category: transaction.category,
+            reversed_at: transaction.reversed_at.map(|at| at.to_rfc3339()),
+        }
+    }
+}
+
+/// Запись журнала изменений счёта (см. `AuditEntry`).
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AuditEntryResponse {
+    pub account_id: Uuid,
+    pub action: AuditAction,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub at: String, // RFC 3339 формат
+}
+
+impl From<AuditEntry> for AuditEntryResponse {
+    fn from(entry: AuditEntry) -> Self {
+        Self {
+            account_id: entry.account_id,
+            action: entry.action,
+            old_value: entry.old_value,
+            new_value: entry.new_value,
+            at: entry.at.to_rfc3339(),
+        }
+    }
+}
+
+/// Строка выгрузки журнала: операция и баланс счёта сразу после неё.
+#[derive(Debug)]
+pub struct LedgerEntry {
+    pub transaction: TransactionResponse,
+    pub balance_after: Money,
+}
+
+/// Выгрузка всего журнала счёта (см. `AccountService::export_transactions`).
+///
+/// Не сериализуется: `entries` — поток, а в каком формате его отдать
+/// (CSV, что-то ещё), решает presentation слой.
+pub struct TransactionExport<E> {
+    pub account_id: Uuid,
+    pub currency: Currency,
+    pub entries: BoxStream<'static, Result<LedgerEntry, E>>,
+}
+
+/// Результат создания одного счёта из пачки.
+///
+/// Заполнено ровно одно из полей `account` и `error`.
+/// `index` — позиция в массиве `accounts` запроса.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BatchItemResult {
+    pub index: usize,
+    pub account: Option<AccountResponse>,
+    pub error: Option<String>,
+}
+
+/// Ответ на создание пачки счетов.
+///
+/// # Почему 200 даже при ошибках?
+/// Ошибка одного счёта — не сбой запроса: клиенту нужен результат
+/// по каждому элементу, а не один код ответа на всех (как у `simulate`).
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BatchCreateAccountsResponse {
+    pub created: usize,
+    pub failed: usize,
+    pub results: Vec<BatchItemResult>,
+}
+
+/// Ответ с правилом повторяющейся операции.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RecurringResponse {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub kind: RecurringKind,
+    pub amount: Money,
+    pub schedule: Schedule,
+    pub next_run: String, // RFC 3339 формат
+    pub enabled: bool,
+    pub note: Option<String>,
+    pub created_at: String,
+    /// Предупреждения (сумма округлена, см. `PrecisionMode`); пустой
+    /// список не сериализуется
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl From<RecurringTransaction> for RecurringResponse {
+    fn from(rule: RecurringTransaction) -> Self {
+        Self {
+            id: rule.id,
+            account_id: rule.account_id,
+            kind: rule.kind,
+            amount: Money::from_cents(rule.amount),
+            schedule: rule.schedule,
+            next_run: rule.next_run.to_rfc3339(),
+            enabled: rule.enabled,
+            note: rule.note,
+            created_at: rule.created_at.to_rfc3339(),
+            warnings: Vec::new(),
+        }
+    }
+}
+
+/// Ответ с подпиской на события — без `secret` (см. `CreateWebhookRequest`).
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WebhookResponse {
+    pub id: Uuid,
+    pub url: String,
+    pub enabled: bool,
+    pub created_at: String, // RFC 3339 формат
+}
+
+impl From<Webhook> for WebhookResponse {
+    fn from(webhook: Webhook) -> Self {
+        Self {
+            id: webhook.id,
+            url: webhook.url,
+            enabled: webhook.enabled,
+            created_at: webhook.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Ответ с кошельком.
+///
+/// `balances` — только валюты с ненулевым остатком, по алфавиту кода.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WalletResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub balances: Vec<WalletBalanceResponse>,
+    pub created_at: String, // RFC 3339 формат
+    pub updated_at: String,
+    /// Предупреждения (сумма округлена, см. `PrecisionMode`); пустой
+    /// список не сериализуется
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Остаток кошелька в одной валюте.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WalletBalanceResponse {
+    pub currency: String,
+    /// Остаток в знаках валюты (см. `CurrencyAmount`)
+    pub balance: CurrencyAmount,
+    /// Остаток с числом знаков по валюте: `"1050"` для JPY, `"10.50"` для USD,
+    /// `"1.050"` для BHD. Совпадает с `balance`
+    pub formatted_balance: String,
+}
+
+impl From<Wallet> for WalletResponse {
+    fn from(wallet: Wallet) -> Self {
+        let mut balances: Vec<WalletBalanceResponse> = wallet
+            .balances
+            .iter()
+            .map(|(currency, &balance)| {
+                let balance = CurrencyAmount::new(Money::from_cents(balance), currency);
+                WalletBalanceResponse {
+                    currency: currency.to_string(),
+                    formatted_balance: balance.to_string(),
+                    balance,
+                }
+            })
+            .collect();
+        // HashMap не хранит порядок — сортируем, чтобы ответ не «прыгал»
+        balances.sort_by(|a, b| a.currency.cmp(&b.currency));
+
+        Self {
+            id: wallet.id,
+            name: wallet.name,
+            balances,
+            created_at: wallet.created_at.to_rfc3339(),
+            updated_at: wallet.updated_at.to_rfc3339(),
+            warnings: Vec::new(),
+        }
+    }
+}
+
+/// Результат перевода.
+///
+/// - `amount` — списано со счёта-источника, в его валюте
+/// - `converted_amount` — зачислено на счёт-получатель, в его валюте
+/// - `exchange_rate` — курс; `None`, если валюты совпадают
+///
+/// `Decimal` сериализуется строкой (`"0.923"`), как и `Money`.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TransferResponse {
+    pub from: AccountResponse,
+    pub to: AccountResponse,
+    pub amount: Money,
+    pub converted_amount: Money,
+    #[cfg_attr(feature = "openapi", schema(value_type = Option<String>, example = "0.923"))]
+    pub exchange_rate: Option<Decimal>,
+    /// `true` — пробный перевод (`?dry_run=true`), ни один счёт не изменён
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
+}
+
+/// Капитал: сумма остатков всех счетов в одной валюте.
+///
+/// - `total` — сумма `breakdown[].converted`, в `currency`
+/// - `breakdown` — остатки по валютам счетов и их пересчёт
+/// - `unconverted` — валюты без курса к `currency`; в `total` не входят
+///
+/// # Почему не пропустить валюту без курса?
+/// Тогда `total` выглядел бы полным, хотя часть денег в нём не учтена.
+/// Отдельный список показывает, чего не хватает.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct NetWorthResponse {
+    pub currency: String,
+    pub total: Money,
+    pub breakdown: Vec<NetWorthBreakdown>,
+    pub unconverted: Vec<CurrencyBalance>,
+}
+
+/// Остаток в одной валюте и его пересчёт в валюту отчёта.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct NetWorthBreakdown {
+    pub currency: String,
+    pub balance: Money, // В `currency`
+    #[cfg_attr(feature = "openapi", schema(value_type = String, example = "0.923"))]
+    pub exchange_rate: Decimal, // 1 для валюты отчёта
+    pub converted: Money, // В валюте отчёта
+}
+
+/// Сумма остатков счетов в одной валюте.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CurrencyBalance {
+    pub currency: String,
+    pub balance: Money,
+}
+
+/// Сводка по всем счетам для дашборда.
+///
+/// Удалённые и архивные счета не учитываются. Даты — `null`,
+/// если счетов нет.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct StatsResponse {
+    pub total_accounts: i64,
+    pub currencies: Vec<CurrencyStatsResponse>, // По коду валюты
+    pub oldest_account_created_at: Option<String>, // RFC 3339
+    pub newest_account_created_at: Option<String>,
+}
+
+/// Число счетов и сумма их остатков в одной валюте.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CurrencyStatsResponse {
+    pub currency: String,
+    pub count: i64,
+    pub total_balance: Money,
+}
+
+/// Точка истории баланса.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BalanceSnapshotResponse {
+    pub balance: Money,
+    pub recorded_at: String, // RFC 3339 формат
+}
+
+impl From<BalanceSnapshot> for BalanceSnapshotResponse {
+    fn from(snapshot: BalanceSnapshot) -> Self {
+        Self {
+            balance: Money::from_cents(snapshot.balance),
+            recorded_at: snapshot.recorded_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Выписка по счёту за месяц.
+///
+/// # Как считаются суммы
+/// Всё выводится из журнала операций:
+/// - `opening_balance` — сумма всех операций до начала месяца
+/// - `total_deposits` — пополнения и входящие переводы за месяц
+/// - `total_withdrawals` — снятия и исходящие переводы за месяц
+/// - `closing_balance` = opening + deposits − withdrawals, плюс разница
+///   от смены валюты (`currency_change`), если она была в этом месяце
+///
+/// Месяц без операций — `opening_balance == closing_balance`
+/// и пустой `transactions`.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct StatementResponse {
+    pub account_id: Uuid,
+    pub currency: String,
+    pub year: i32,
+    pub month: u32,
+    pub period_start: String, // RFC 3339, включительно
+    pub period_end: String,   // RFC 3339, не включительно
+    pub opening_balance: Money,
+    pub closing_balance: Money,
+    pub total_deposits: Money,
+    pub total_withdrawals: Money,
+    pub transactions: Vec<TransactionResponse>, // Старые — первыми
+}
+
+/// Результат сверки баланса с журналом.
+///
+/// - `stored` — баланс, сохранённый в счёте
+/// - `computed` — сумма всех операций журнала
+/// - `difference` = stored − computed; `matches` — разница нулевая
+///
+/// Ответ на исправление (`POST`) описывает состояние ДО него
+/// и добавляет `"fixed": true`, если баланс был исправлен.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ReconciliationResponse {
+    pub account_id: Uuid,
+    pub matches: bool,
+    pub stored: Money,
+    pub computed: Money,
+    pub difference: Money,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub fixed: bool,
+}
+
+/// Сводка о жизни счёта: возраст, последняя активность, итоги за всё время.
+///
+/// # Поля
+/// - `age_days` — полных суток с `created_at`
+/// - `last_activity_at` — время последней операции журнала; у счёта
+///   без операций — `updated_at` (переименование тоже активность)
+/// - `total_deposits` / `total_withdrawals` — все поступления и все
+///   списания за всё время, как в выписке (`StatementResponse`)
+///
+/// У нового счёта без операций итоги нулевые, а `age_days`
+/// и `days_since_last_activity` — 0.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AccountInfoResponse {
+    pub account_id: Uuid,
+    pub currency: String,
+    pub created_at: String,       // RFC 3339
+    pub last_activity_at: String, // RFC 3339
+    pub age_days: i64,
+    pub days_since_last_activity: i64,
+    pub transaction_count: i64,
+    pub total_deposits: Money,
+    pub total_withdrawals: Money,
+}
+
+/// Баланс счёта на конец дня `date` (UTC) по журналу.
+///
+/// `transaction_count` — сколько операций журнала учтено. Операций
+/// до этого дня нет — баланс 0 в валюте счёта.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BalanceAtResponse {
+    pub account_id: Uuid,
+    pub currency: String,
+    pub date: NaiveDate,
+    pub balance: Money,
+    pub transaction_count: i64,
+}
+
+/// Насколько имя счёта совпало со строкой поиска.
+///
+/// Порядок вариантов — порядок в выдаче: сначала точные совпадения,
+/// затем начинающиеся с запроса, затем содержащие его.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum NameMatch {
+    Exact,
+    Prefix,
+    Substring,
+}
+
+impl NameMatch {
+    /// Оценка для клиента: чем больше, тем ближе (3, 2, 1).
+    pub fn score(self) -> u8 {
+        match self {
+            Self::Exact => 3,
+            Self::Prefix => 2,
+            Self::Substring => 1,
+        }
+    }
+}
+
+/// Счёт из результатов поиска (`GET /api/accounts/search`).
+///
+/// `score` дублирует `match_type` числом — так UI проще сортировать
+/// и показывать релевантность.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AccountSearchResult {
+    pub score: u8,
+    pub match_type: NameMatch,
+    pub account: AccountResponse,
+}
+
+/// Строка отчёта по категориям: итоги категории в одной валюте.
+///
+/// # Почему валюта — часть строки?
+/// Складывать суммы разных валют нельзя, поэтому категория, которая
+/// встречается на счетах в USD и EUR, даёт две строки.
+///
+/// Переводы в отчёт не входят: это перемещение денег между своими
+/// счетами, а не доход или расход.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CategoryTotalResponse {
+    pub category: String, // Без категории — `Transaction::UNCATEGORIZED`
+    pub currency: String,
+    pub total_deposits: Money,
+    pub total_withdrawals: Money,
+    pub count: i64, // Число операций
+}
+
+/// Результат симуляции операции.
+///
+/// # Отличие от обычного ответа
+/// Доменная ошибка (например, недостаточно средств) — это не сбой запроса,
+/// а информативный результат: `success: false` и текст в `error`.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SimulationResponse {
+    pub operation: SimulatedOperation,
+    pub amount: Money,
+    pub success: bool,
+    pub balance_before: Money,
+    pub balance_after: Money, // Равен balance_before, если операция не прошла бы
+    pub error: Option<String>,
+    /// Предупреждения (сумма округлена, см. `PrecisionMode`); пустой
+    /// список не сериализуется
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
 /// Простой ответ с сообщением.
 ///
 /// Используется для операций без возвращаемых данных (delete).
 #[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct MessageResponse {
     pub message: String,
 }
@@ -116,3 +1522,214 @@ impl MessageResponse {
         }
     }
 }
+
+// ═══════════════════════════════════════════════════════════════════
+// BACKUP DTOs — формат резервной копии (и запрос, и ответ)
+// ═══════════════════════════════════════════════════════════════════
+
+/// Резервная копия всех данных — один JSON-документ.
+///
+/// # Пример JSON
+/// ```json
+/// {
+///   "schema_version": 1,
+///   "exported_at": "2024-01-15T10:30:00Z",
+///   "accounts": [{"id": "...", "name": "Wallet", "balance": 10050, ...}],
+///   "transactions": [...],
+///   "balance_snapshots": [...]
+/// }
+/// ```
+///
+/// # Почему доменные сущности, а не Response DTO?
+/// Копия должна восстанавливать состояние ТОЧНО: версии, `deleted_at`,
+/// лимиты. Response DTO часть этого скрывают. Суммы поэтому тоже
+/// хранятся как есть — целыми копейками, без перевода в `Money`.
+///
+/// # `schema_version`
+/// Номер формата. Импорт принимает только `BackupDocument::SCHEMA_VERSION`,
+/// чтобы копия из другой версии приложения не загрузилась молча неправильно.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BackupDocument {
+    pub schema_version: u32,
+    pub exported_at: DateTime<Utc>,
+    #[cfg_attr(feature = "openapi", schema(value_type = Vec<Object>))]
+    pub accounts: Vec<Account>,
+    #[cfg_attr(feature = "openapi", schema(value_type = Vec<Object>))]
+    pub transactions: Vec<Transaction>,
+    #[cfg_attr(feature = "openapi", schema(value_type = Vec<Object>))]
+    pub balance_snapshots: Vec<BalanceSnapshot>,
+}
+
+impl BackupDocument {
+    /// Текущая версия формата резервной копии.
+    pub const SCHEMA_VERSION: u32 = 1;
+}
+
+/// Итог импорта — сколько записей загружено.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ImportSummary {
+    pub accounts: usize,
+    pub transactions: usize,
+    pub balance_snapshots: usize,
+}
+
+/// Итог POST /api/dev/seed (feature `dev`).
+///
+/// `created: false` — демо-данные уже были; `accounts` — то, что
+/// от них осталось, ничего нового не создано.
+#[cfg(feature = "dev")]
+#[derive(Debug, Serialize)]
+pub struct SeedResponse {
+    pub created: bool,
+    pub accounts: Vec<AccountResponse>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn update(body: serde_json::Value) -> UpdateAccountRequest {
+        serde_json::from_value(body).unwrap()
+    }
+
+    #[test]
+    fn update_request_tells_an_absent_field_from_null() {
+        let absent = update(json!({}));
+        assert!(absent.name.is_none());
+        assert!(absent.withdrawal_limit.is_none());
+        assert!(absent.min_balance.is_none());
+        assert!(absent.low_balance_threshold.is_none());
+        assert!(absent.overdraft_source.is_none());
+        assert!(absent.description.is_none());
+
+        let null = update(json!({
+            "withdrawal_limit": null,
+            "min_balance": null,
+            "low_balance_threshold": null,
+            "overdraft_source": null,
+            "description": null,
+        }));
+        assert_eq!(null.withdrawal_limit, Some(None));
+        assert_eq!(null.min_balance, Some(None));
+        assert_eq!(null.low_balance_threshold, Some(None));
+        assert_eq!(null.overdraft_source, Some(None));
+        assert_eq!(null.description, Some(None));
+    }
+
+    #[test]
+    fn update_request_reads_values() {
+        let source = Uuid::new_v4();
+        let set = update(json!({
+            "name": "Budget",
+            "withdrawal_limit": "100.00",
+            "min_balance": "-5.00",
+            "low_balance_threshold": "20.00",
+            "overdraft_source": source,
+            "description": "Joint account",
+        }));
+
+        assert_eq!(set.name.as_deref(), Some("Budget"));
+        assert_eq!(set.withdrawal_limit, Some(Some(Money::from_cents(10_000))));
+        assert_eq!(set.min_balance, Some(Some(Money::from_cents(-500))));
+        assert_eq!(
+            set.low_balance_threshold,
+            Some(Some(Money::from_cents(2_000)))
+        );
+        assert_eq!(set.overdraft_source, Some(Some(source)));
+        assert_eq!(set.description, Some(Some("Joint account".into())));
+    }
+
+    #[test]
+    fn update_request_name_has_no_null() {
+        // У имени нет "снять": null — то же, что отсутствие поля
+        assert!(update(json!({ "name": null })).name.is_none());
+    }
+
+    /// Счёт в `currency` с балансом `balance` (строка суммы, как в API).
+    fn account(currency: &str, balance: &str) -> Account {
+        let mut account = Account::new("Test".into(), currency.into(), Utc::now()).unwrap();
+        account.balance = balance.parse::<Money>().unwrap().cents();
+        account
+    }
+
+    #[test]
+    fn balance_at_cutoff_includes_the_whole_day_only() {
+        let query = BalanceAtQuery {
+            date: NaiveDate::from_ymd_opt(2026, 3, 10).unwrap(),
+        };
+        let cutoff = query.cutoff().unwrap();
+        let at = |h, m, s| query.date.and_hms_opt(h, m, s).unwrap().and_utc();
+
+        // Операция входит, если она строго раньше границы
+        assert!(at(0, 0, 0) < cutoff);
+        assert!(at(23, 59, 59) < cutoff);
+        let next_midnight = NaiveDate::from_ymd_opt(2026, 3, 11)
+            .unwrap()
+            .and_time(NaiveTime::MIN)
+            .and_utc();
+        assert_eq!(cutoff, next_midnight);
+    }
+
+    #[test]
+    fn balance_at_last_chrono_day_is_an_error() {
+        let query = BalanceAtQuery {
+            date: NaiveDate::MAX,
+        };
+        assert!(query.cutoff().is_err());
+    }
+
+    #[test]
+    fn formatted_balance_uses_currency_decimal_places() {
+        let cases = [
+            ("JPY", "1050", "1050"),
+            ("USD", "10.50", "10.50"),
+            ("BHD", "1.05", "1.050"),
+        ];
+
+        for (currency, balance, formatted) in cases {
+            let response = AccountResponse::from(account(currency, balance));
+            assert_eq!(response.formatted_balance, formatted, "{currency}");
+        }
+    }
+
+    #[test]
+    fn balance_is_written_in_currency_places() {
+        for (currency, balance, json) in [
+            ("JPY", "1050", "1050"),
+            ("USD", "10.50", "10.50"),
+            ("BHD", "1.05", "1.050"),
+        ] {
+            let response = AccountResponse::from(account(currency, balance));
+            let value = serde_json::to_value(&response).unwrap();
+
+            assert_eq!(value["balance"], json, "{currency}");
+            assert_eq!(value["formatted_balance"], json, "{currency}");
+            // Ответ, сохранённый с ключом идемпотентности, читается обратно
+            let replay: AccountResponse = serde_json::from_value(value).unwrap();
+            assert_eq!(replay.balance, response.balance, "{currency}");
+        }
+    }
+
+    #[test]
+    fn wallet_balances_use_currency_decimal_places() {
+        let mut wallet = Wallet::new("Travel", Utc::now()).unwrap();
+        wallet
+            .deposit(Currency::new("JPY"), 105_000, Utc::now())
+            .unwrap();
+        wallet
+            .deposit(Currency::new("USD"), 1050, Utc::now())
+            .unwrap();
+
+        let response = WalletResponse::from(wallet);
+        let formatted: Vec<&str> = response
+            .balances
+            .iter()
+            .map(|balance| balance.formatted_balance.as_str())
+            .collect();
+        assert_eq!(formatted, ["1050", "10.50"]);
+    }
+}
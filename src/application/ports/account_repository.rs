@@ -11,9 +11,111 @@
 //! - Легко менять БД
 //! - Писать тесты с mock-репозиторием
 
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-use crate::domain::entities::Account;
+use crate::domain::entities::{Account, AuditEntry, Transaction};
+
+/// Поле, по которому можно сортировать список счетов.
+///
+/// # Почему enum, а не имя колонки строкой?
+/// `ORDER BY` нельзя передать плейсхолдером — его приходится вставлять
+/// в текст запроса. Enum — это whitelist: реализация сопоставляет
+/// каждый вариант с фиксированной колонкой, и чужая строка в SQL не попадёт.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountSortField {
+    Name,
+    Balance,
+    CreatedAt,
+}
+
+/// Порядок сортировки: поле и направление.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountSort {
+    pub field: AccountSortField,
+    pub descending: bool,
+}
+
+/// По умолчанию — новые первыми, как было до появления сортировки.
+impl Default for AccountSort {
+    fn default() -> Self {
+        Self {
+            field: AccountSortField::CreatedAt,
+            descending: true,
+        }
+    }
+}
+
+/// Разбор из query string: `name`, `-balance`, `created_at`...
+///
+/// Префикс `-` — по убыванию.
+impl FromStr for AccountSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // strip_prefix возвращает Some(остаток), если префикс был
+        let (field, descending) = match s.strip_prefix('-') {
+            Some(field) => (field, true),
+            None => (s, false),
+        };
+
+        let field = match field {
+            "name" => AccountSortField::Name,
+            "balance" => AccountSortField::Balance,
+            "created_at" => AccountSortField::CreatedAt,
+            other => {
+                return Err(format!(
+                    "Unknown sort field '{}', expected name, balance or created_at",
+                    other
+                ))
+            }
+        };
+
+        Ok(Self { field, descending })
+    }
+}
+
+/// Условия выборки списка счетов.
+///
+/// - `currency` — код валюты, нормализованный через `Currency`;
+///   сравнивается с нормализованным кодом счёта ("eur" найдёт "EUR")
+/// - `name_contains` — подстрока имени без учёта регистра
+/// - `include_archived` — показывать и архивные счета (по умолчанию нет)
+#[derive(Debug, Clone, Default)]
+pub struct AccountFilter {
+    pub currency: Option<String>,
+    pub name_contains: Option<String>,
+    pub sort: AccountSort,
+    pub include_archived: bool,
+}
+
+/// Сводка по счетам одной валюты (см. `stats_by_currency`).
+///
+/// - `currency` — нормализованный код, как у `Currency`
+/// - `balance` — сумма балансов в копейках
+/// - `oldest_created_at`, `newest_created_at` — самый старый и самый новый счёт
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurrencyStats {
+    pub currency: String,
+    pub count: i64,
+    pub balance: i64,
+    pub oldest_created_at: DateTime<Utc>,
+    pub newest_created_at: DateTime<Utc>,
+}
+
+/// Ошибка хранилища счетов, в которой сервис различает нарушение уникальности.
+///
+/// # Зачем, если сервис сам проверяет имя?
+/// Проверка (`find_by_name`) и запись — два запроса. Два параллельных
+/// запроса с одним именем оба пройдут проверку, и запись второго
+/// отклонит только уникальный индекс хранилища. Сервис не знает тип
+/// ошибки (`sqlx::Error` или другой), поэтому спрашивает через этот trait.
+pub trait AccountRepositoryError: std::error::Error + Send + Sync + 'static {
+    /// Запись нарушила уникальность имени активного счёта
+    fn is_unique_violation(&self) -> bool;
+}
 
 /// Порт для персистентности счетов.
 ///
@@ -24,31 +126,148 @@ use crate::domain::entities::Account;
 ///
 /// Нужен потому что async fn в trait требуют Send для работы с tokio.
 ///
+/// # Почему `user_id` почти в каждом методе?
+/// Счета принадлежат пользователям (`Account::user_id`), и чужой счёт
+/// для хранилища всё равно что несуществующий: `find_by_id` вернёт
+/// `None`, а не счёт, который сервис должен не забыть отфильтровать.
+/// Методы, которые получают `Account`, берут владельца из него.
+/// Без владельца работает только `find_owner` — для фоновых задач.
+///
 /// # Ассоциированный тип `Error`
 /// Каждая реализация определяет свой тип ошибки:
 /// - PostgreSQL: `sqlx::Error`
 /// - Mock: `std::convert::Infallible`
+///
+/// # Параметр `audit`
+/// Методы записи принимают записи журнала изменений (`AuditEntry`)
+/// и сохраняют их в той же транзакции, что и сам счёт: счёт не может
+/// измениться без записи в журнале, а запись — появиться без изменения.
+/// Записи сохраняются, только если изменение прошло (`update` вернул
+/// `true` и т.д.). Пополнения и переводы передают пустой срез — они
+/// в журнале операций.
+///
+/// # Параметр `ledger`
+/// `update` и `update_pair` так же принимают записи журнала операций
+/// (`Transaction`). Баланс и операции, которые его изменили, пишутся
+/// одной транзакцией: перевод не может списать деньги без записи
+/// `transfer_out`, а сбой между двумя запросами — оставить баланс,
+/// который не сходится с журналом (`reconcile`). Операция, которая уже
+/// есть в журнале, не добавляется второй раз: у неё сохраняются детали,
+/// как в `TransactionRepository::update_details` — так отмена ставит
+/// `reversed_at` в той же транзакции, что и обратную запись.
 #[trait_variant::make(AccountRepository: Send)]
 #[allow(dead_code)]
 pub trait LocalAccountRepository {
     /// Тип ошибки, который возвращает эта реализация
-    type Error: std::error::Error + Send + Sync + 'static;
+    type Error: AccountRepositoryError;
+
+    /// Создаёт новый счёт в хранилище.
+    ///
+    /// Имя уже занято активным счётом того же пользователя
+    /// (без учёта регистра) — ошибка
+    /// с `is_unique_violation()`; то же у `create_many`, `update` и `restore`.
+    async fn create(&self, account: &Account, audit: &[AuditEntry]) -> Result<(), Self::Error>;
+
+    /// Создаёт несколько счетов атомарно — все или ни одного
+    async fn create_many(
+        &self,
+        accounts: &[Account],
+        audit: &[AuditEntry],
+    ) -> Result<(), Self::Error>;
+
+    /// Находит активный счёт по ID. Возвращает None если не найден или удалён.
+    /// Архивный счёт тоже находится — архив скрывает счёт только из списка.
+    async fn find_by_id(&self, user_id: &str, id: Uuid) -> Result<Option<Account>, Self::Error>;
+
+    /// Находит активный счёт по имени (case-insensitive)
+    async fn find_by_name(&self, user_id: &str, name: &str)
+        -> Result<Option<Account>, Self::Error>;
+
+    /// Владелец активного счёта, кто бы он ни был.
+    ///
+    /// Для фоновых задач (планировщик повторяющихся операций), которые
+    /// работают со счетами всех пользователей: по владельцу они берут
+    /// сервис этого пользователя. Запросам API этот метод не нужен.
+    async fn find_owner(&self, id: Uuid) -> Result<Option<String>, Self::Error>;
+
+    /// Возвращает все активные счета, включая архивные
+    async fn find_all(&self, user_id: &str) -> Result<Vec<Account>, Self::Error>;
+
+    /// Возвращает страницу счетов, подходящих под фильтр, в порядке `filter.sort`
+    async fn find_all_paginated(
+        &self,
+        user_id: &str,
+        filter: &AccountFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Account>, Self::Error>;
+
+    /// Возвращает количество счетов, подходящих под фильтр
+    async fn count(&self, user_id: &str, filter: &AccountFilter) -> Result<i64, Self::Error>;
+
+    /// Сводка по активным неархивным счетам, по строке на валюту
+    /// в порядке кода валюты.
+    ///
+    /// Считается агрегатами в хранилище: счета целиком не читаются.
+    async fn stats_by_currency(&self, user_id: &str) -> Result<Vec<CurrencyStats>, Self::Error>;
 
-    /// Создаёт новый счёт в хранилище
-    async fn create(&self, account: &Account) -> Result<(), Self::Error>;
+    /// Обновляет существующий счёт (optimistic locking).
+    ///
+    /// Запись проходит, только если версия в хранилище равна `account.version`;
+    /// сохранённая версия становится `account.version + 1`.
+    /// Возвращает `false`, если версия не совпала (счёт изменили параллельно).
+    async fn update(
+        &self,
+        account: &Account,
+        audit: &[AuditEntry],
+        ledger: &[Transaction],
+    ) -> Result<bool, Self::Error>;
 
-    /// Находит счёт по ID. Возвращает None если не найден.
-    async fn find_by_id(&self, id: Uuid) -> Result<Option<Account>, Self::Error>;
+    /// Обновляет два счёта атомарно — оба или ни один (для переводов).
+    ///
+    /// Версии проверяются как в `update`. Возвращает `false`, если хотя бы
+    /// у одного счёта версия не совпала; тогда не меняется ни один
+    /// и не пишется ни одна операция из `ledger`.
+    async fn update_pair(
+        &self,
+        first: &Account,
+        second: &Account,
+        audit: &[AuditEntry],
+        ledger: &[Transaction],
+    ) -> Result<bool, Self::Error>;
 
-    /// Находит счёт по имени (case-insensitive)
-    async fn find_by_name(&self, name: &str) -> Result<Option<Account>, Self::Error>;
+    /// Мягко удаляет счёт по ID (проставляет `deleted_at = now`)
+    async fn delete(
+        &self,
+        user_id: &str,
+        id: Uuid,
+        now: DateTime<Utc>,
+        audit: &[AuditEntry],
+    ) -> Result<(), Self::Error>;
 
-    /// Возвращает все счета
-    async fn find_all(&self) -> Result<Vec<Account>, Self::Error>;
+    /// Находит мягко удалённый счёт по ID
+    async fn find_deleted_by_id(
+        &self,
+        user_id: &str,
+        id: Uuid,
+    ) -> Result<Option<Account>, Self::Error>;
 
-    /// Обновляет существующий счёт
-    async fn update(&self, account: &Account) -> Result<(), Self::Error>;
+    /// Восстанавливает мягко удалённый счёт (`updated_at = now`).
+    /// Возвращает `false`, если счёт не был удалён.
+    async fn restore(
+        &self,
+        user_id: &str,
+        id: Uuid,
+        now: DateTime<Utc>,
+        audit: &[AuditEntry],
+    ) -> Result<bool, Self::Error>;
 
-    /// Удаляет счёт по ID
-    async fn delete(&self, id: Uuid) -> Result<(), Self::Error>;
+    /// Журнал изменений счёта, от старых записей к новым.
+    ///
+    /// Удалённый счёт тоже: его журнал заканчивается записью `deleted`.
+    async fn find_audit(
+        &self,
+        user_id: &str,
+        account_id: Uuid,
+    ) -> Result<Vec<AuditEntry>, Self::Error>;
 }
@@ -0,0 +1,35 @@
+//! Порт (интерфейс) для резервного копирования всех данных.
+
+use crate::domain::entities::{Account, BalanceSnapshot, Transaction};
+
+/// Полный набор данных: все счета (включая удалённые), журнал и история баланса.
+#[derive(Debug, Clone, Default)]
+pub struct BackupData {
+    pub accounts: Vec<Account>,
+    pub transactions: Vec<Transaction>,
+    pub snapshots: Vec<BalanceSnapshot>,
+}
+
+/// Порт для выгрузки и загрузки всего набора данных.
+///
+/// # Почему отдельный порт, а не методы трёх существующих?
+/// И экспорт, и импорт должны видеть все таблицы согласованно:
+/// экспорт — один снимок данных, импорт — всё или ничего.
+/// Через три независимых репозитория это не выразить — нужна
+/// одна транзакция БД на всю операцию.
+#[trait_variant::make(BackupRepository: Send)]
+#[allow(dead_code)]
+pub trait LocalBackupRepository {
+    /// Тип ошибки, который возвращает эта реализация
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Выгружает все данные одним согласованным снимком.
+    async fn export_all(&self) -> Result<BackupData, Self::Error>;
+
+    /// Загружает данные атомарно: либо всё, либо ничего.
+    ///
+    /// # Возвращает
+    /// - `Ok(true)` — данные загружены
+    /// - `Ok(false)` — хранилище не пустое, ничего не записано
+    async fn import_all(&self, data: &BackupData) -> Result<bool, Self::Error>;
+}
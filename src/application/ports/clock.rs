@@ -0,0 +1,15 @@
+//! Порт (интерфейс) для получения текущего времени.
+
+use chrono::{DateTime, Utc};
+
+/// Источник текущего времени.
+///
+/// # Зачем, если есть `Utc::now()`?
+/// Сервис берёт время отсюда и передаёт его в методы сущностей.
+/// С настоящими часами (`SystemClock`) ничего не меняется, а с
+/// остановленными (`FixedClock`) `created_at`, `updated_at`, время
+/// операций и снимков баланса известны заранее — их можно проверить
+/// в тесте точным сравнением.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
@@ -0,0 +1,33 @@
+//! Порт (интерфейс) для уведомлений об изменении баланса.
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::TransactionKind;
+
+/// Событие "баланс счёта изменился" — по одному на запись в журнале.
+///
+/// Перевод даёт два события (по одному на каждый счёт), снятие
+/// с защитой от овердрафта — до трёх.
+///
+/// `user_id` — владелец счёта: событие получают только его подписки.
+#[derive(Debug, Clone)]
+pub struct BalanceChanged {
+    pub user_id: String,
+    pub account_id: Uuid,
+    pub kind: TransactionKind,
+    pub amount: i64,      // В копейках, всегда положительная
+    pub new_balance: i64, // Баланс сразу после этой операции
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Получатель событий об изменении баланса.
+///
+/// # Почему `publish` не async и ничего не возвращает?
+/// Операция к этому моменту уже сохранена, и её ответ не должен
+/// зависеть от того, доступны ли подписчики. Реализация только
+/// запускает доставку (например, в `tokio::spawn`) и сразу возвращается,
+/// а ошибки доставки обрабатывает сама.
+pub trait EventPublisher: Send + Sync {
+    fn publish(&self, event: BalanceChanged);
+}
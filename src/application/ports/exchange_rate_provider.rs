@@ -0,0 +1,24 @@
+//! Порт (интерфейс) для получения курсов валют.
+
+use rust_decimal::Decimal;
+
+use crate::domain::errors::DomainError;
+use crate::domain::value_objects::Currency;
+
+/// Источник курсов валют.
+///
+/// # Почему `DomainError`, а не ассоциированный `type Error`?
+/// У репозиториев ошибка — техническая (сбой БД), и сервис её не разбирает.
+/// Здесь же главный "сбой" — курса для пары нет, и это бизнес-ситуация:
+/// перевод невозможен, клиенту нужно внятное 422, а не 500.
+/// Реализация поверх внешнего API тоже сводит свои ошибки к
+/// `DomainError::RateUnavailable`.
+#[trait_variant::make(ExchangeRateProvider: Send)]
+#[allow(dead_code)]
+pub trait LocalExchangeRateProvider {
+    /// Сколько единиц `to` дают за одну единицу `from`.
+    ///
+    /// # Errors
+    /// `DomainError::RateUnavailable` если курса для пары нет.
+    async fn rate(&self, from: &Currency, to: &Currency) -> Result<Decimal, DomainError>;
+}
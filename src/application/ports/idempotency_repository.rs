@@ -0,0 +1,54 @@
+//! Порт (интерфейс) для хранения ключей идемпотентности.
+
+use chrono::{DateTime, Utc};
+
+use crate::application::dto::AccountResponse;
+
+/// Запись об обработанном (или обрабатываемом) запросе.
+///
+/// # Поля
+/// - `key` — значение заголовка `Idempotency-Key`
+/// - `fingerprint` — что именно просили сделать (операция, счёт, тело запроса)
+/// - `response` — результат; `None`, пока запрос ещё выполняется
+/// - `created_at` — момент первого запроса, от него считается срок жизни
+#[derive(Debug, Clone)]
+pub struct IdempotencyRecord {
+    pub key: String,
+    pub fingerprint: String,
+    pub response: Option<AccountResponse>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Порт для ключей идемпотентности.
+///
+/// # Почему reserve → complete, а не "найти, выполнить, сохранить"?
+/// Два одинаковых повтора могут прийти одновременно. При схеме
+/// "найти → выполнить" оба не найдут ключ и оба спишут деньги.
+/// `reserve` атомарно занимает ключ ДО выполнения операции —
+/// второй запрос увидит занятый ключ.
+#[trait_variant::make(IdempotencyRepository: Send)]
+#[allow(dead_code)]
+pub trait LocalIdempotencyRepository {
+    /// Тип ошибки, который возвращает эта реализация
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Атомарно занимает ключ.
+    ///
+    /// Записи, созданные раньше `expired_before`, считаются истёкшими
+    /// и удаляются — их ключ снова свободен.
+    ///
+    /// # Возвращает
+    /// - `Ok(None)` — ключ занят этим вызовом, можно выполнять операцию
+    /// - `Ok(Some(record))` — ключ уже был занят, операцию выполнять нельзя
+    async fn reserve(
+        &self,
+        record: &IdempotencyRecord,
+        expired_before: DateTime<Utc>,
+    ) -> Result<Option<IdempotencyRecord>, Self::Error>;
+
+    /// Сохраняет результат выполненной операции
+    async fn complete(&self, key: &str, response: &AccountResponse) -> Result<(), Self::Error>;
+
+    /// Освобождает ключ, если операция завершилась ошибкой
+    async fn release(&self, key: &str) -> Result<(), Self::Error>;
+}
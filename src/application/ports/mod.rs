@@ -1,3 +1,28 @@
 mod account_repository;
+mod backup_repository;
+mod clock;
+mod event_publisher;
+mod exchange_rate_provider;
+mod idempotency_repository;
+mod recurring_repository;
+mod snapshot_repository;
+mod transaction_repository;
+mod wallet_repository;
+mod webhook_repository;
 
-pub use account_repository::AccountRepository;
+pub use account_repository::{
+    AccountFilter, AccountRepository, AccountRepositoryError, AccountSort, AccountSortField,
+    CurrencyStats,
+};
+pub use backup_repository::{BackupData, BackupRepository};
+pub use clock::Clock;
+pub use event_publisher::{BalanceChanged, EventPublisher};
+pub use exchange_rate_provider::ExchangeRateProvider;
+pub use idempotency_repository::{IdempotencyRecord, IdempotencyRepository};
+pub use recurring_repository::RecurringRepository;
+pub use snapshot_repository::SnapshotRepository;
+pub use transaction_repository::{
+    ActivityCursor, CategoryTotal, KindTotal, TransactionFilter, TransactionRepository,
+};
+pub use wallet_repository::WalletRepository;
+pub use webhook_repository::WebhookRepository;
@@ -0,0 +1,40 @@
+//! Порт (интерфейс) для хранения правил повторяющихся операций.
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::RecurringTransaction;
+
+/// Порт для персистентности правил повторяющихся операций.
+#[trait_variant::make(RecurringRepository: Send)]
+#[allow(dead_code)]
+pub trait LocalRecurringRepository {
+    /// Тип ошибки, который возвращает эта реализация
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Сохраняет новое правило
+    async fn create(&self, rule: &RecurringTransaction) -> Result<(), Self::Error>;
+
+    /// Находит правило по ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RecurringTransaction>, Self::Error>;
+
+    /// Правила счёта, в порядке создания
+    async fn find_by_account(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<RecurringTransaction>, Self::Error>;
+
+    /// Включённые правила с `next_run <= now`, самые просроченные — первыми.
+    /// Не больше `limit` штук за раз.
+    async fn find_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<RecurringTransaction>, Self::Error>;
+
+    /// Обновляет правило целиком
+    async fn update(&self, rule: &RecurringTransaction) -> Result<(), Self::Error>;
+
+    /// Удаляет правило
+    async fn delete(&self, id: Uuid) -> Result<(), Self::Error>;
+}
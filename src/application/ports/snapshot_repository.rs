@@ -0,0 +1,31 @@
+//! Порт (интерфейс) для хранения снимков баланса.
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::BalanceSnapshot;
+
+/// Порт для персистентности истории баланса.
+///
+/// Как и журнал операций, снимки только добавляются.
+#[trait_variant::make(SnapshotRepository: Send)]
+#[allow(dead_code)]
+pub trait LocalSnapshotRepository {
+    /// Тип ошибки, который возвращает эта реализация
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Сохраняет снимок
+    async fn create(&self, snapshot: &BalanceSnapshot) -> Result<(), Self::Error>;
+
+    /// Последний снимок счёта. None если снимков ещё нет.
+    async fn find_latest(&self, account_id: Uuid) -> Result<Option<BalanceSnapshot>, Self::Error>;
+
+    /// Снимки счёта в диапазоне `[from, to]`, старые — первыми.
+    /// `None` у границы означает "без ограничения".
+    async fn find_in_range(
+        &self,
+        account_id: Uuid,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<BalanceSnapshot>, Self::Error>;
+}
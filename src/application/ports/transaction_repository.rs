@@ -0,0 +1,224 @@
+//! Порт (интерфейс) для работы с журналом операций.
+//!
+//! Устроен так же, как `AccountRepository`: Application слой
+//! описывает контракт, Infrastructure слой его реализует.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures_util::stream::BoxStream;
+use uuid::Uuid;
+
+use crate::domain::entities::{Transaction, TransactionKind};
+
+/// Итог по одной группе операций: счёт × категория × тип.
+///
+/// # Почему группа включает счёт?
+/// У счетов разные валюты, а складывать доллары с иенами нельзя.
+/// Валюту знает счёт, а не журнал, поэтому репозиторий группирует
+/// по счёту, а сервис уже сводит счета одной валюты вместе.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryTotal {
+    pub account_id: Uuid,
+    pub category: Option<String>, // None — без категории
+    pub kind: TransactionKind,
+    pub total: i64, // В копейках
+    pub count: i64,
+}
+
+/// Итог операций счёта одного типа: сумма и число операций.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KindTotal {
+    pub kind: TransactionKind,
+    pub total: i64, // В копейках
+    pub count: i64,
+}
+
+/// Условия выборки истории операций счёта; `None` — без ограничения.
+///
+/// - `category` — уже нормализованная (см. `normalize_category`)
+/// - `from`, `to` — границы по времени операции, обе включительно
+#[derive(Debug, Clone, Default)]
+pub struct TransactionFilter {
+    pub category: Option<String>,
+    pub kind: Option<TransactionKind>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Позиция в ленте операций всех счетов: время и ID последней
+/// показанной операции.
+///
+/// # Почему не `offset`?
+/// Лента всё время растёт сверху. Пока клиент читает первую страницу,
+/// новые операции сдвинули бы вторую на `offset`, и часть операций
+/// повторилась бы. Курсор указывает на саму операцию: следующая страница —
+/// всё, что строго старше неё. ID нужен для операций с одинаковым временем.
+///
+/// В query string — `"<RFC 3339>_<ID>"`, см. `Display` и `FromStr`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActivityCursor {
+    pub timestamp: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl ActivityCursor {
+    /// Курсор, указывающий на операцию `transaction`.
+    pub fn of(transaction: &Transaction) -> Self {
+        Self {
+            timestamp: transaction.timestamp,
+            id: transaction.id,
+        }
+    }
+}
+
+/// `2024-01-15T10:30:00.123456Z_550e8400-...` — микросекунды (точность
+/// `TIMESTAMPTZ`) и `Z` вместо `+00:00`: `+` в query string стал бы пробелом.
+impl fmt::Display for ActivityCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}_{}",
+            self.timestamp.to_rfc3339_opts(SecondsFormat::Micros, true),
+            self.id
+        )
+    }
+}
+
+impl FromStr for ActivityCursor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("before must be '<RFC 3339 time>_<id>', got '{}'", s);
+
+        let (timestamp, id) = s.trim().split_once('_').ok_or_else(invalid)?;
+        Ok(Self {
+            timestamp: DateTime::parse_from_rfc3339(timestamp)
+                .map_err(|_| invalid())?
+                .with_timezone(&Utc),
+            id: id.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// Порт для персистентности операций по счетам.
+///
+/// # Почему операции только добавляются?
+/// Журнал — это история. Операции не удаляются, а их сумма, тип и время
+/// не меняются, иначе по журналу нельзя будет проверить баланс.
+/// Меняются только комментарий, категория и отметка об отмене
+/// (`update_details`): на баланс они не влияют.
+///
+/// # Почему здесь нет `create`?
+/// Операция пишется вместе с балансом, который она изменила, — одной
+/// транзакцией в `AccountRepository::update` / `update_pair`.
+#[trait_variant::make(TransactionRepository: Send)]
+#[allow(dead_code)]
+pub trait LocalTransactionRepository {
+    /// Тип ошибки, который возвращает эта реализация
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Находит операцию по ID. Возвращает None если не найдена.
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Transaction>, Self::Error>;
+
+    /// Сохраняет `note`, `category` и `reversed_at` операции; остальные
+    /// поля не трогает. Возвращает `false`, если операции нет.
+    ///
+    /// Отметка об отмене только ставится: `None` в `reversed_at` не снимает
+    /// уже сохранённую. Иначе правка комментария, прочитанного до отмены,
+    /// стёрла бы её.
+    async fn update_details(&self, transaction: &Transaction) -> Result<bool, Self::Error>;
+
+    /// Возвращает страницу операций счёта, подходящих под фильтр, новые — первыми.
+    ///
+    /// Операции с одинаковым временем упорядочены по ID: без этого
+    /// соседние страницы могли бы повторить или потерять операцию.
+    async fn find_by_account(
+        &self,
+        account_id: Uuid,
+        filter: &TransactionFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Transaction>, Self::Error>;
+
+    /// Возвращает количество операций счёта, подходящих под фильтр
+    async fn count_by_account(
+        &self,
+        account_id: Uuid,
+        filter: &TransactionFilter,
+    ) -> Result<i64, Self::Error>;
+
+    /// Все операции счёта потоком, старые — первыми.
+    ///
+    /// # Почему поток, а не `Vec`?
+    /// Для выгрузки всей истории: у старого счёта операций может быть
+    /// столько, что `Vec` займёт заметную часть памяти сервера. Поток
+    /// отдаёт операции по мере чтения из хранилища.
+    ///
+    /// `'static` — поток не держит `&self` и может жить дольше вызова
+    /// (например, в теле HTTP-ответа, который отправляется после handler).
+    fn stream_by_account(
+        &self,
+        account_id: Uuid,
+    ) -> BoxStream<'static, Result<Transaction, Self::Error>>;
+
+    /// Операции счёта в полуинтервале `[from, to)`, старые — первыми
+    async fn find_in_range(
+        &self,
+        account_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Transaction>, Self::Error>;
+
+    /// Итоги операций счёта по типам строго до момента `before`.
+    ///
+    /// Один запрос по индексу `(account_id, timestamp)` на все типы сразу.
+    /// Типов без операций в ответе нет.
+    async fn totals_before(
+        &self,
+        account_id: Uuid,
+        before: DateTime<Utc>,
+    ) -> Result<Vec<KindTotal>, Self::Error>;
+
+    /// Итоги по категориям за `[from, to]` по счетам `account_ids`.
+    /// `None` у границы означает "без ограничения".
+    ///
+    /// Счета — все счета пользователя: журнал не знает владельцев,
+    /// поэтому сервис передаёт их сам (см. `AccountRepository::find_all`).
+    async fn totals_by_category(
+        &self,
+        account_ids: &[Uuid],
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<CategoryTotal>, Self::Error>;
+
+    /// Операции счетов `account_ids`, новые — первыми, не больше `limit`.
+    ///
+    /// С `before` — только операции строго старше курсора
+    /// (по времени, при равном времени — по ID), см. `ActivityCursor`.
+    async fn find_recent(
+        &self,
+        account_ids: &[Uuid],
+        before: Option<ActivityCursor>,
+        limit: i64,
+    ) -> Result<Vec<Transaction>, Self::Error>;
+
+    /// Сумма операций заданного типа начиная с момента `since` (в копейках).
+    /// Если операций нет — 0.
+    async fn sum_since(
+        &self,
+        account_id: Uuid,
+        kind: TransactionKind,
+        since: DateTime<Utc>,
+    ) -> Result<i64, Self::Error>;
+
+    /// Сумма операций заданного типа, отменённых начиная с момента `since`
+    /// (по `reversed_at`, а не по времени самой операции). Если таких нет — 0.
+    async fn sum_reversed_since(
+        &self,
+        account_id: Uuid,
+        kind: TransactionKind,
+        since: DateTime<Utc>,
+    ) -> Result<i64, Self::Error>;
+}
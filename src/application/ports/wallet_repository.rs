@@ -0,0 +1,30 @@
+//! Порт (интерфейс) для хранения кошельков.
+
+use uuid::Uuid;
+
+use crate::domain::entities::Wallet;
+
+/// Порт для персистентности кошельков.
+#[trait_variant::make(WalletRepository: Send)]
+#[allow(dead_code)]
+pub trait LocalWalletRepository {
+    /// Тип ошибки, который возвращает эта реализация
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Сохраняет новый кошелёк вместе с остатками
+    async fn create(&self, wallet: &Wallet) -> Result<(), Self::Error>;
+
+    /// Находит кошелёк по ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Wallet>, Self::Error>;
+
+    /// Все кошельки, в порядке создания
+    async fn find_all(&self) -> Result<Vec<Wallet>, Self::Error>;
+
+    /// Сохраняет кошелёк, если его `version` в хранилище не изменилась,
+    /// и увеличивает её. Остатки заменяются целиком.
+    /// Возвращает `false`, если кошелёк изменили параллельно.
+    async fn update(&self, wallet: &Wallet) -> Result<bool, Self::Error>;
+
+    /// Удаляет кошелёк вместе с остатками
+    async fn delete(&self, id: Uuid) -> Result<(), Self::Error>;
+}
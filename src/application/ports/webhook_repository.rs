@@ -0,0 +1,34 @@
+//! Порт (интерфейс) для хранения подписок на события (webhooks).
+
+use uuid::Uuid;
+
+use crate::domain::entities::Webhook;
+
+/// Порт для персистентности подписок.
+///
+/// Как и у `AccountRepository`, все выборки — подписки одного
+/// пользователя (`user_id`): чужая подписка не находится по ID.
+#[trait_variant::make(WebhookRepository: Send)]
+#[allow(dead_code)]
+pub trait LocalWebhookRepository {
+    /// Тип ошибки, который возвращает эта реализация
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Сохраняет новую подписку
+    async fn create(&self, webhook: &Webhook) -> Result<(), Self::Error>;
+
+    /// Находит подписку пользователя по ID
+    async fn find_by_id(&self, user_id: &str, id: Uuid) -> Result<Option<Webhook>, Self::Error>;
+
+    /// Все подписки пользователя, в порядке создания
+    async fn find_all(&self, user_id: &str) -> Result<Vec<Webhook>, Self::Error>;
+
+    /// Включённые подписки пользователя — им рассылаются события его счетов
+    async fn find_enabled(&self, user_id: &str) -> Result<Vec<Webhook>, Self::Error>;
+
+    /// Обновляет подписку целиком (владелец — `webhook.user_id`)
+    async fn update(&self, webhook: &Webhook) -> Result<(), Self::Error>;
+
+    /// Удаляет подписку пользователя
+    async fn delete(&self, user_id: &str, id: Uuid) -> Result<(), Self::Error>;
+}
@@ -8,85 +8,408 @@
 //! 4. Сохраняет через репозиторий
 //! 5. Возвращает результат (DTO)
 
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::future::Future;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use futures_util::{future, StreamExt, TryStreamExt};
+use rust_decimal::Decimal;
 use uuid::Uuid;
 
 use crate::application::dto::{
-    AccountResponse, CreateAccountRequest, DepositRequest, WithdrawRequest,
+    AccountInfoResponse, AccountResponse, AccountSearchResult, ActivityItem, ActivityPage,
+    ApplyInterestRequest, AuditEntryResponse, BalanceAtResponse, BalanceSnapshotResponse,
+    BatchCreateAccountsRequest, BatchCreateAccountsResponse, BatchItemResult,
+    CategoryTotalResponse, ChangeCurrencyRequest, CloseAccountRequest, CreateAccountRequest,
+    CurrencyBalance, CurrencyStatsResponse, DepositRequest, EnsureAccountRequest, LedgerEntry,
+    NameMatch, NetWorthBreakdown, NetWorthResponse, PagedResponse, Pagination,
+    ReconciliationResponse, SimulateRequest, SimulatedOperation, SimulationResponse,
+    StatementPeriod, StatementResponse, StatsResponse, TimeRange, TransactionExport,
+    TransactionResponse, TransferRequest, TransferResponse, UpdateAccountRequest,
+    UpdateTransactionRequest, WithdrawAmount, WithdrawRequest,
+};
+use crate::application::ports::{
+    AccountFilter, AccountRepository, AccountRepositoryError, AccountSort, AccountSortField,
+    ActivityCursor, BalanceChanged, Clock, EventPublisher, ExchangeRateProvider, IdempotencyRecord,
+    IdempotencyRepository, SnapshotRepository, TransactionFilter, TransactionRepository,
+};
+use crate::domain::entities::{
+    Account, AccountType, AuditAction, AuditEntry, BalanceSnapshot, Transaction, TransactionKind,
 };
-use crate::application::ports::AccountRepository;
-use crate::domain::entities::Account;
 use crate::domain::errors::DomainError;
+use crate::domain::value_objects::{Currency, Money, PrecisionMode, RoundingMode};
+
+/// Сколько живёт ключ идемпотентности. После — тот же ключ можно использовать снова.
+const IDEMPOTENCY_KEY_TTL: Duration = Duration::hours(24);
+
+/// Предупреждение в ответе, когда снятие опустило баланс ниже порога.
+const LOW_BALANCE_WARNING: &str = "low balance";
+
+/// Перевод с источника защиты от овердрафта: источник уже со снятой
+/// суммой (ещё не сохранён) и сама сумма в копейках.
+struct OverdraftCover {
+    source: Account,
+    amount: i64,
+}
 
 /// Сервис для операций со счетами.
 ///
-/// # Generic параметр `R`
-/// Сервис параметризован типом репозитория `R: AccountRepository`.
+/// # Generic параметры `R`, `T`, `S`, `I`, `X` и `P`
+/// Сервис параметризован типами репозиториев:
+/// - `R: AccountRepository` — хранилище счетов
+/// - `T: TransactionRepository` — журнал операций
+/// - `S: SnapshotRepository` — история баланса
+/// - `I: IdempotencyRepository` — ключи идемпотентности
+/// - `X: ExchangeRateProvider` — курсы валют для переводов
+/// - `P: EventPublisher` — уведомления об изменении баланса (webhooks)
+///
 /// Это Dependency Injection на уровне типов:
 /// ```text
 /// // В продакшене:
-/// let service = AccountService::new(PostgresAccountRepository::new(pool));
-///
-/// // В тестах:
-/// let service = AccountService::new(MockAccountRepository::new());
+/// let service = AccountService::new(
+///     PostgresAccountRepository::new(pool.clone()),
+///     PostgresTransactionRepository::new(pool.clone()),
+///     PostgresSnapshotRepository::new(pool.clone()),
+///     PostgresIdempotencyRepository::new(pool),
+///     StaticExchangeRateProvider::from_json(&rates_json)?,
+///     WebhookDispatcher::new(PostgresWebhookRepository::new(pool)),
+///     Arc::new(SystemClock),
+/// );
 /// ```
 ///
+/// # Почему часы — `Arc<dyn Clock>`, а не ещё один generic?
+/// Часы только отдают время, синхронно и дёшево — динамический вызов
+/// тут ничего не стоит. Седьмой параметр типа пришлось бы повторять
+/// в каждом alias и в `RecurringService` ради одного метода `now()`.
+///
+/// # Почему `T::Error = R::Error` (и у `S`, `I` тоже)?
+/// У сервиса один тип ошибки репозитория — `AccountServiceError<R::Error>`.
+/// Все репозитории работают с одной БД, поэтому и ошибка у них общая.
+///
 /// # Почему `#[derive(Clone)]`?
 /// Axum требует Clone для state, чтобы шарить между потоками.
 /// Это безопасно, потому что `PgPool` внутри использует `Arc`.
+///
+/// # Пользователь
+/// Сервис работает со счетами одного пользователя — `user_id`
+/// (по умолчанию `Account::DEFAULT_USER`). На каждый запрос handler
+/// берёт копию для своего пользователя (`for_user`): копия дешёвая,
+/// а забыть передать пользователя в очередной use case невозможно.
 #[derive(Clone)]
-pub struct AccountService<R: AccountRepository> {
+pub struct AccountService<R, T, S, I, X, P>
+where
+    R: AccountRepository,
+    T: TransactionRepository<Error = R::Error>,
+    S: SnapshotRepository<Error = R::Error>,
+    I: IdempotencyRepository<Error = R::Error>,
+    X: ExchangeRateProvider,
+    P: EventPublisher,
+{
     repository: R,
+    transactions: T,
+    snapshots: S,
+    idempotency: I,
+    rates: X,
+    events: P,
+    clock: Arc<dyn Clock>,
+    max_accounts: Option<u64>,
+    rounding: RoundingMode,
+    precision: PrecisionMode,
+    default_currency: Currency,
+    user_id: String,
 }
 
-impl<R: AccountRepository> AccountService<R> {
+impl<R, T, S, I, X, P> AccountService<R, T, S, I, X, P>
+where
+    R: AccountRepository,
+    T: TransactionRepository<Error = R::Error>,
+    S: SnapshotRepository<Error = R::Error>,
+    I: IdempotencyRepository<Error = R::Error>,
+    X: ExchangeRateProvider,
+    P: EventPublisher,
+{
     /// Создаёт новый экземпляр сервиса.
     ///
     /// # Arguments
     /// * `repository` — реализация `AccountRepository` (PostgreSQL, Mock, etc.)
-    pub fn new(repository: R) -> Self {
-        Self { repository }
+    /// * `transactions` — реализация `TransactionRepository`
+    /// * `snapshots` — реализация `SnapshotRepository`
+    /// * `idempotency` — реализация `IdempotencyRepository`
+    /// * `rates` — реализация `ExchangeRateProvider`
+    /// * `events` — реализация `EventPublisher`
+    /// * `clock` — источник времени (`SystemClock`, в тестах `FixedClock`)
+    pub fn new(
+        repository: R,
+        transactions: T,
+        snapshots: S,
+        idempotency: I,
+        rates: X,
+        events: P,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            repository,
+            transactions,
+            snapshots,
+            idempotency,
+            rates,
+            events,
+            clock,
+            max_accounts: None,
+            rounding: RoundingMode::default(),
+            precision: PrecisionMode::default(),
+            default_currency: Currency::new("USD"),
+            user_id: Account::DEFAULT_USER.to_string(),
+        }
+    }
+
+    /// Тот же сервис для счетов пользователя `user_id`.
+    ///
+    /// Чужие счета для него не существуют: по ID — `AccountNotFound`
+    /// (404, а не 403 — иначе ответ выдавал бы, что такой счёт есть),
+    /// в списках и отчётах их нет, а имя занято только среди своих.
+    pub fn for_user(&self, user_id: &str) -> Self
+    where
+        Self: Clone,
+    {
+        Self {
+            user_id: user_id.to_string(),
+            ..self.clone()
+        }
+    }
+
+    /// Сервис владельца счёта `account_id`; `None`, если счёта нет.
+    ///
+    /// Для фоновых задач, которые не знают пользователя
+    /// (планировщик повторяющихся операций, см. `RecurringService::run_due`).
+    pub async fn for_account_owner(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Option<Self>, AccountServiceError<R::Error>>
+    where
+        Self: Clone,
+    {
+        let owner = self
+            .repository
+            .find_owner(account_id)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        Ok(owner.map(|user_id| self.for_user(&user_id)))
+    }
+
+    /// Ограничивает число активных счетов (`None` — без ограничения).
+    ///
+    /// # Почему не аргумент `new`?
+    /// У сервиса и так семь зависимостей, а лимит нужен не везде:
+    /// тестам и однопользовательской установке хватает значения по умолчанию.
+    pub fn with_max_accounts(mut self, max_accounts: Option<u64>) -> Self {
+        self.max_accounts = max_accounts;
+        self
+    }
+
+    /// Правило округления вычисленных сумм: пересчёт по курсу
+    /// в переводах и отчёте о капитале, начисление процентов.
+    ///
+    /// По умолчанию `HalfEven` (см. `RoundingMode`).
+    pub fn with_rounding(mut self, rounding: RoundingMode) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Что делать с суммой, у которой знаков больше, чем у валюты
+    /// счёта: отклонить или округлить по `with_rounding`.
+    ///
+    /// По умолчанию `Strict` (см. `PrecisionMode`).
+    pub fn with_precision_mode(mut self, precision: PrecisionMode) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Валюта нового счёта, если в запросе её нет.
+    ///
+    /// По умолчанию `USD`. Код проверяется до вызова
+    /// (`Currency::parse` в main), здесь он уже корректный.
+    pub fn with_default_currency(mut self, currency: Currency) -> Self {
+        self.default_currency = currency;
+        self
+    }
+
+    /// Приводит сумму из запроса к точности `currency` по `PrecisionMode`.
+    ///
+    /// Возвращает сумму и предупреждение для ответа, если она округлена.
+    /// Открыт для `RecurringService`: правила подчиняются той же настройке.
+    ///
+    /// # Errors
+    /// `DomainError::PrecisionExceeded` в режиме `Strict`.
+    pub fn fit_amount(
+        &self,
+        amount: Money,
+        currency: &Currency,
+    ) -> Result<(Money, Option<String>), DomainError> {
+        self.precision.fit(amount, currency, self.rounding)
+    }
+
+    /// Текущее время по часам сервиса.
+    ///
+    /// Для тех, кто работает со счетами через этот сервис
+    /// (`RecurringService`, планировщик): у всех одни и те же часы.
+    pub fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
     }
 
     /// Use case: Создание нового счёта.
     ///
     /// # Бизнес-правила
+    /// - Имя не пустое и не длиннее 100 символов, хранится без пробелов по краям
     /// - Имя счёта должно быть уникальным
+    /// - Активных счетов не больше `max_accounts` (если лимит задан)
     ///
     /// # Поток выполнения
-    /// 1. Проверить, нет ли счёта с таким именем
-    /// 2. Создать доменную сущность `Account`
-    /// 3. Сохранить в репозиторий
-    /// 4. Вернуть DTO для API
+    /// 1. Создать доменную сущность `Account` (она проверит имя)
+    /// 2. Проверить, нет ли счёта с таким именем
+    /// 3. Проверить лимит числа счетов
+    /// 4. Сохранить в репозиторий
+    /// 5. Вернуть DTO для API
     pub async fn create_account(
         &self,
         request: CreateAccountRequest,
     ) -> Result<AccountResponse, AccountServiceError<R::Error>> {
-        // Проверяем уникальность имени
+        // Создаём доменную сущность
+        let account = self
+            .build_account(request, self.clock.now())
+            .map_err(AccountServiceError::Domain)?;
+
+        // Проверяем уникальность уже обрезанного имени
         // .await? — ждём результат и пробрасываем ошибку если есть
-        if self
-            .repository
-            .find_by_name(&request.name)
-            .await
-            .map_err(AccountServiceError::Repository)? // Конвертируем ошибку репозитория
-            .is_some() // Если нашли — значит уже существует
-        {
+        if self.name_taken(&account.name).await? {
             return Err(AccountServiceError::Domain(
-                DomainError::AccountAlreadyExists(request.name),
+                DomainError::AccountAlreadyExists(account.name),
             ));
         }
 
-        // Создаём доменную сущность
-        let account = Account::new(request.name, request.currency);
+        self.check_quota(1).await?;
 
-        // Сохраняем
+        // Сохраняем; имя мог занять параллельный запрос (см. `write_error`)
         self.repository
-            .create(&account)
+            .create(&account, &[AuditEntry::created(&account)])
             .await
-            .map_err(AccountServiceError::Repository)?;
+            .map_err(|err| Self::write_error(err, &account.name))?;
+
+        // Начальная точка истории баланса
+        self.record_snapshot(&account).await?;
 
         // Конвертируем в DTO и возвращаем
-        // .into() вызывает From<Account> for AccountResponse
-        Ok(account.into())
+        self.to_response(account).await
+    }
+
+    /// Use case: Создание нескольких счетов за один запрос.
+    ///
+    /// # Бизнес-правила
+    /// Те же, что у `create_account`, плюс имена уникальны внутри пачки
+    /// (без учёта регистра): из двух "Wallet" создаётся первый.
+    /// Если корректные счета не помещаются в лимит `max_accounts`,
+    /// не создаётся ни один — даже при `atomic: false`.
+    ///
+    /// # Поток выполнения
+    /// 1. Проверить все счета, ничего не записывая
+    /// 2. `atomic` и есть ошибки — вернуть результаты, не создав ни одного
+    /// 3. Иначе записать корректные одним `create_many` (одна транзакция БД)
+    pub async fn create_accounts(
+        &self,
+        request: BatchCreateAccountsRequest,
+    ) -> Result<BatchCreateAccountsResponse, AccountServiceError<R::Error>> {
+        let mut seen_names = HashSet::new();
+        let mut checked = Vec::with_capacity(request.accounts.len());
+
+        let now = self.clock.now();
+        for item in request.accounts {
+            let result = match self.build_account(item, now) {
+                Ok(account) => {
+                    let name_key = account.name.to_lowercase();
+                    if seen_names.contains(&name_key) || self.name_taken(&account.name).await? {
+                        Err(DomainError::AccountAlreadyExists(account.name))
+                    } else {
+                        seen_names.insert(name_key);
+                        Ok(account)
+                    }
+                }
+                Err(err) => Err(err),
+            };
+            checked.push(result);
+        }
+
+        let failed = checked.iter().filter(|result| result.is_err()).count();
+
+        if request.atomic && failed > 0 {
+            let results = checked
+                .into_iter()
+                .enumerate()
+                .map(|(index, result)| BatchItemResult {
+                    index,
+                    account: None,
+                    error: Some(match result {
+                        Ok(_) => "Not created: another account in the atomic batch failed".into(),
+                        Err(err) => err.to_string(),
+                    }),
+                })
+                .collect();
+
+            return Ok(BatchCreateAccountsResponse {
+                created: 0,
+                failed,
+                results,
+            });
+        }
+
+        let accounts: Vec<Account> = checked
+            .iter()
+            .filter_map(|result| result.as_ref().ok())
+            .cloned()
+            .collect();
+
+        self.check_quota(accounts.len() as u64).await?;
+
+        let audit: Vec<AuditEntry> = accounts.iter().map(AuditEntry::created).collect();
+        if let Err(err) = self.repository.create_many(&accounts, &audit).await {
+            if !err.is_unique_violation() {
+                return Err(AccountServiceError::Repository(err));
+            }
+            // Индекс не говорит, какое имя заняли, — находим его сами
+            for account in &accounts {
+                if self.name_taken(&account.name).await? {
+                    return Err(DomainError::AccountAlreadyExists(account.name.clone()).into());
+                }
+            }
+            return Err(AccountServiceError::Repository(err));
+        }
+
+        let mut results = Vec::with_capacity(checked.len());
+        for (index, result) in checked.into_iter().enumerate() {
+            let item = match result {
+                Ok(account) => {
+                    self.record_snapshot(&account).await?;
+                    BatchItemResult {
+                        index,
+                        account: Some(self.to_response(account).await?),
+                        error: None,
+                    }
+                }
+                Err(err) => BatchItemResult {
+                    index,
+                    account: None,
+                    error: Some(err.to_string()),
+                },
+            };
+            results.push(item);
+        }
+
+        Ok(BatchCreateAccountsResponse {
+            created: accounts.len(),
+            failed,
+            results,
+        })
     }
 
     /// Use case: Получение счёта по ID.
@@ -94,34 +417,300 @@ impl<R: AccountRepository> AccountService<R> {
         &self,
         id: Uuid,
     ) -> Result<AccountResponse, AccountServiceError<R::Error>> {
+        let account = self.find_account(id).await?;
+
+        self.to_response(account).await
+    }
+
+    /// Use case: Получение счёта по имени (без учёта регистра).
+    ///
+    /// В ответе имя в том виде, в каком оно сохранено: по `wallet`
+    /// вернётся счёт `Wallet`.
+    ///
+    /// # Почему имя однозначно?
+    /// Уникальность имени проверяется только среди активных счетов:
+    /// у удалённого счёта может быть то же имя, что у активного.
+    /// `find_by_name` ищет только активные, поэтому такой счёт не найдётся.
+    pub async fn get_account_by_name(
+        &self,
+        name: &str,
+    ) -> Result<AccountResponse, AccountServiceError<R::Error>> {
+        let name = name.trim();
         let account = self
             .repository
-            .find_by_id(id)
+            .find_by_name(&self.user_id, name)
             .await
             .map_err(AccountServiceError::Repository)?
-            // .ok_or_else() — конвертирует None в Err
-            .ok_or_else(|| {
-                AccountServiceError::Domain(DomainError::AccountNotFound(id.to_string()))
-            })?;
+            .ok_or_else(|| DomainError::AccountNotFound(name.to_string()))?;
+
+        self.to_response(account).await
+    }
+
+    /// Use case: Счёт с именем `name` — существующий или новый.
+    ///
+    /// Возвращает счёт и `true`, если он только что создан.
+    ///
+    /// # Бизнес-правила
+    /// - Есть активный счёт с таким именем (без учёта регистра) — он
+    ///   и возвращается, `request` не используется
+    /// - Нет — создаётся, как в `create_account`, с валютой из `request`
+    ///
+    /// # Почему параллельные запросы не получают 409?
+    /// Два клиента могут одновременно не найти счёт и оба попытаться
+    /// его создать. Второй запись отклонит уникальный индекс имени
+    /// (см. `write_error`); вместо `AccountAlreadyExists` счёт читается
+    /// заново, и второй клиент получает тот, что создал первый.
+    pub async fn ensure_account(
+        &self,
+        name: &str,
+        request: EnsureAccountRequest,
+    ) -> Result<(AccountResponse, bool), AccountServiceError<R::Error>> {
+        if let Some(existing) = self.find_by_name(name.trim()).await? {
+            return Ok((self.to_response(existing).await?, false));
+        }
+
+        let account = self
+            .build_account(
+                CreateAccountRequest {
+                    name: name.to_string(),
+                    currency: request.currency,
+                    account_type: AccountType::default(),
+                    credit_limit: None,
+                    description: None,
+                },
+                self.clock.now(),
+            )
+            .map_err(AccountServiceError::Domain)?;
+
+        self.check_quota(1).await?;
+
+        let audit = [AuditEntry::created(&account)];
+        if let Err(err) = self.repository.create(&account, &audit).await {
+            if err.is_unique_violation() {
+                // Имя занял параллельный запрос — отдаём его счёт
+                if let Some(existing) = self.find_by_name(&account.name).await? {
+                    return Ok((self.to_response(existing).await?, false));
+                }
+            }
+            return Err(Self::write_error(err, &account.name));
+        }
+
+        self.record_snapshot(&account).await?;
 
-        Ok(account.into())
+        Ok((self.to_response(account).await?, true))
     }
 
-    /// Use case: Получение всех счетов.
-    pub async fn get_all_accounts(
+    /// Use case: Получение страницы счетов с фильтрами и сортировкой.
+    ///
+    /// Пагинация должна быть уже провалидирована (`Pagination::validate`).
+    /// `total` — число счетов, подходящих под фильтр, а не всех.
+    /// Все условия, включая валюту, порядок и страницу применяет
+    /// хранилище (`FILTER_CONDITIONS`), так что страница и `total`
+    /// всегда посчитаны по одному фильтру.
+    pub async fn get_accounts(
         &self,
-    ) -> Result<Vec<AccountResponse>, AccountServiceError<R::Error>> {
+        filter: &AccountFilter,
+        pagination: &Pagination,
+    ) -> Result<PagedResponse<AccountResponse>, AccountServiceError<R::Error>> {
+        let (limit, offset) = (pagination.limit(), pagination.offset());
+
         let accounts = self
             .repository
-            .find_all()
+            .find_all_paginated(&self.user_id, filter, limit, offset)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+        let total = self
+            .repository
+            .count(&self.user_id, filter)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        // Конвертируем Vec<Account> в Vec<AccountResponse>.
+        // Не через .map(Into::into): to_response асинхронный (читает журнал)
+        let mut items = Vec::with_capacity(accounts.len());
+        for account in accounts {
+            items.push(self.to_response(account).await?);
+        }
+
+        Ok(PagedResponse {
+            items,
+            total,
+            limit,
+            offset,
+        })
+    }
+
+    /// Use case: Поиск счетов по имени с ранжированием.
+    ///
+    /// Кандидаты — активные неархивные счета, имя которых содержит
+    /// `query` без учёта регистра (тот же фильтр, что `name_contains`
+    /// в списке). Выдача: точные совпадения, затем по префиксу, затем
+    /// по подстроке; внутри группы — по имени. Отдаётся не больше `limit`.
+    ///
+    /// # Почему ранжирование в Rust, а не в SQL?
+    /// Оценка — три варианта сравнения строк; считать её `CASE` пришлось
+    /// бы в каждом хранилище (PostgreSQL, SQLite, память) одинаково.
+    /// Кандидатов немного: это счета одного пользователя, а их число
+    /// ограничивает `MAX_ACCOUNTS`. Поэтому читаются все кандидаты,
+    /// а не первые `limit` по имени — иначе точное совпадение могло бы
+    /// не попасть в выборку.
+    pub async fn search_accounts(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<AccountSearchResult>, AccountServiceError<R::Error>> {
+        let filter = AccountFilter {
+            name_contains: Some(query.to_string()),
+            sort: AccountSort {
+                field: AccountSortField::Name,
+                descending: false,
+            },
+            ..AccountFilter::default()
+        };
+        let candidates = self
+            .repository
+            .find_all_paginated(&self.user_id, &filter, i64::MAX, 0)
             .await
             .map_err(AccountServiceError::Repository)?;
 
-        // Конвертируем Vec<Account> в Vec<AccountResponse>
-        // .into_iter() — создаёт итератор, забирающий ownership
-        // .map(Into::into) — применяет .into() к каждому элементу
-        // .collect() — собирает обратно в Vec
-        Ok(accounts.into_iter().map(Into::into).collect())
+        let query = query.to_lowercase();
+        let mut ranked: Vec<(NameMatch, Account)> = candidates
+            .into_iter()
+            .filter_map(|account| Some((name_match(&account.name, &query)?, account)))
+            .collect();
+        // sort_by_key стабильный: внутри группы остаётся порядок по имени
+        ranked.sort_by_key(|(name_match, _)| *name_match);
+        ranked.truncate(limit);
+
+        let mut results = Vec::with_capacity(ranked.len());
+        for (match_type, account) in ranked {
+            results.push(AccountSearchResult {
+                score: match_type.score(),
+                match_type,
+                account: self.to_response(account).await?,
+            });
+        }
+        Ok(results)
+    }
+
+    /// Use case: Изменение настроек счёта (имя, описание, дневной лимит снятия).
+    ///
+    /// # Бизнес-правила
+    /// - Новое имя не должно быть занято ДРУГИМ счётом
+    /// - Переименование в текущее имя — успешный no-op
+    /// - Смена регистра ("wallet" → "Wallet") разрешена: поиск по имени
+    ///   регистронезависимый и найдёт этот же счёт, а не чужой
+    /// - Лимит снятия не может быть отрицательным; `null` снимает лимит
+    /// - Лимит снятия, неснижаемый остаток и порог уведомления — не точнее
+    ///   валюты счёта (`Money::check_precision`): для JPY — целые иены
+    /// - Счёт защиты от овердрафта — см. `check_overdraft_source`
+    /// - Запрос, который ничего не меняет (пустой или с текущими
+    ///   значениями), — успешный no-op: version и updated_at прежние
+    pub async fn update_account(
+        &self,
+        id: Uuid,
+        request: UpdateAccountRequest,
+    ) -> Result<AccountResponse, AccountServiceError<R::Error>> {
+        let mut account = self.find_account(id).await?;
+        let before = account.clone();
+        let now = self.clock.now();
+
+        // Лимиты — настройки, а не операции: лишние знаки отклоняются
+        // в любом `PrecisionMode`, молча округлять порог незачем
+        let currency = Currency::new(&account.currency);
+        for setting in [
+            &request.withdrawal_limit,
+            &request.min_balance,
+            &request.low_balance_threshold,
+        ]
+        .into_iter()
+        .flatten()
+        .flatten()
+        {
+            setting.check_precision(&currency)?;
+        }
+
+        // Сравниваем уже обрезанное имя: "Wallet " вместо "Wallet" — не переименование
+        let new_name = request
+            .name
+            .as_deref()
+            .map(Account::normalize_name)
+            .transpose()?;
+
+        // Имя не изменилось — проверять уникальность не нужно
+        if let Some(name) = new_name.filter(|name| *name != account.name) {
+            let existing = self
+                .repository
+                .find_by_name(&self.user_id, &name)
+                .await
+                .map_err(AccountServiceError::Repository)?;
+
+            // Занято, только если нашёлся ДРУГОЙ счёт
+            if existing.is_some_and(|other| other.id != account.id) {
+                return Err(AccountServiceError::Domain(
+                    DomainError::AccountAlreadyExists(name),
+                ));
+            }
+
+            account.rename(&name, now)?;
+        }
+
+        if let Some(limit) = request.withdrawal_limit {
+            account
+                .set_withdrawal_limit(limit.map(|money| money.cents()), now)
+                .map_err(AccountServiceError::Domain)?;
+        }
+
+        if let Some(min_balance) = request.min_balance {
+            account.set_min_balance(min_balance.map(|money| money.cents()), now);
+        }
+
+        if let Some(threshold) = request.low_balance_threshold {
+            account.set_low_balance_threshold(threshold.map(|money| money.cents()), now);
+        }
+
+        if let Some(source) = request.overdraft_source {
+            if let Some(source_id) = source {
+                self.check_overdraft_source(&account, source_id).await?;
+            }
+            account.set_overdraft_source(source, now)?;
+        }
+
+        if let Some(description) = request.description {
+            account.set_description(description.as_deref(), now)?;
+        }
+
+        // Журнал пуст — ни одно поле не изменилось (в том числе если
+        // пришли те же значения): не трогаем version/updated_at
+        let audit = AuditEntry::diff(&before, &account, now);
+        if audit.is_empty() {
+            return self.to_response(before).await;
+        }
+        self.save_audited(&mut account, &audit, &[]).await?;
+
+        self.to_response(account).await
+    }
+
+    /// Use case: Архивирование (`archived = true`) или возврат из архива.
+    ///
+    /// Повторный вызов с тем же значением ничего не меняет —
+    /// version и updated_at остаются прежними.
+    pub async fn set_archived(
+        &self,
+        id: Uuid,
+        archived: bool,
+    ) -> Result<AccountResponse, AccountServiceError<R::Error>> {
+        let mut account = self.find_account(id).await?;
+
+        if account.archived != archived {
+            let before = account.clone();
+            let now = self.clock.now();
+            account.set_archived(archived, now);
+            let audit = AuditEntry::diff(&before, &account, now);
+            self.save_audited(&mut account, &audit, &[]).await?;
+        }
+
+        self.to_response(account).await
     }
 
     /// Use case: Пополнение счёта.
@@ -130,102 +719,3399 @@ impl<R: AccountRepository> AccountService<R> {
     /// 1. Найти счёт
     /// 2. Вызвать доменный метод deposit()
     /// 3. Сохранить изменения
+    ///
+    /// С `idempotency_key` повтор запроса вернёт первый ответ,
+    /// не пополняя счёт ещё раз (см. `idempotent`).
+    ///
+    /// С `dry_run` операция проходит все проверки, но ничего не сохраняется:
+    /// ответ — счёт, каким он стал бы, с `dry_run: true`. Ключ
+    /// идемпотентности при этом не используется — повторять нечего.
     pub async fn deposit(
         &self,
         id: Uuid,
         request: DepositRequest,
+        idempotency_key: Option<String>,
+        dry_run: bool,
+    ) -> Result<AccountResponse, AccountServiceError<R::Error>> {
+        if dry_run {
+            return self.apply_deposit(id, request, true).await;
+        }
+        let fingerprint = format!(
+            "deposit:{}:{}:{:?}:{:?}",
+            id, request.amount, request.note, request.category
+        );
+        self.idempotent(idempotency_key, fingerprint, || {
+            self.apply_deposit(id, request, false)
+        })
+        .await
+    }
+
+    /// Use case: Снятие денег со счёта.
+    ///
+    /// Как и `deposit`, поддерживает ключ идемпотентности и `dry_run`.
+    pub async fn withdraw(
+        &self,
+        id: Uuid,
+        request: WithdrawRequest,
+        idempotency_key: Option<String>,
+        dry_run: bool,
+    ) -> Result<AccountResponse, AccountServiceError<R::Error>> {
+        if dry_run {
+            return self.apply_withdraw(id, request, true).await;
+        }
+        let fingerprint = format!(
+            "withdraw:{}:{}:{:?}:{:?}",
+            id, request.amount, request.note, request.category
+        );
+        self.idempotent(idempotency_key, fingerprint, || {
+            self.apply_withdraw(id, request, false)
+        })
+        .await
+    }
+
+    /// Use case: Начисление процентов.
+    ///
+    /// Сумму считает домен (`Account::interest`), зачисляется она
+    /// обычным `deposit` и пишется в журнал как `interest`.
+    /// Если начислять нечего (нулевой баланс или проценты округлились
+    /// до нуля), счёт возвращается как есть — без записи
+    /// в журнал и без смены версии.
+    ///
+    /// С `idempotency_key` повтор запроса не начислит проценты дважды.
+    pub async fn apply_interest(
+        &self,
+        id: Uuid,
+        request: ApplyInterestRequest,
+        idempotency_key: Option<String>,
+    ) -> Result<AccountResponse, AccountServiceError<R::Error>> {
+        let fingerprint = format!(
+            "interest:{}:{}:{}:{:?}",
+            id, request.annual_rate, request.days, request.note
+        );
+        self.idempotent(idempotency_key, fingerprint, || async move {
+            let mut account = self.find_account(id).await?;
+            let now = self.clock.now();
+
+            let interest = account.interest(request.annual_rate, request.days, self.rounding)?;
+            if interest == 0 {
+                return self.to_response(account).await;
+            }
+
+            account.deposit(interest, now)?;
+            let interest = Transaction::new(
+                account.id,
+                TransactionKind::Interest,
+                interest,
+                request.note,
+                now,
+            );
+            self.save(&mut account, std::slice::from_ref(&interest))
+                .await?;
+
+            self.publish(&interest, account.balance);
+            self.record_snapshot(&account).await?;
+
+            self.to_response(account).await
+        })
+        .await
+    }
+
+    /// Пополнение без учёта идемпотентности.
+    async fn apply_deposit(
+        &self,
+        id: Uuid,
+        request: DepositRequest,
+        dry_run: bool,
     ) -> Result<AccountResponse, AccountServiceError<R::Error>> {
+        // Категорию проверяем до изменения баланса.
+        // Ошибки — с именем поля, чтобы форма подсветила его (см. `InvalidFields`)
+        let category = normalize_category(request.category.as_deref())
+            .map_err(|err| DomainError::field("category", err))?;
+
         // Получаем счёт (mut потому что будем изменять)
-        let mut account = self
-            .repository
-            .find_by_id(id)
-            .await
-            .map_err(AccountServiceError::Repository)?
-            .ok_or_else(|| {
-                AccountServiceError::Domain(DomainError::AccountNotFound(id.to_string()))
-            })?;
+        let mut account = self.find_account(id).await?;
+        let now = self.clock.now();
 
-        // Конвертируем доллары в центы
-        // round() — округляем, чтобы избежать проблем с float
-        let amount_cents = (request.amount * 100.0).round() as i64;
+        // Формат суммы проверен при разборе в Money, а знаки после
+        // запятой — только теперь, когда известна валюта счёта
+        let (amount, precision_warning) = self
+            .fit_amount(request.amount, &Currency::new(&account.currency))
+            .map_err(|err| DomainError::field("amount", err))?;
+        let amount_cents = amount.cents();
 
         // Вызываем доменный метод (там бизнес-правила)
         account
-            .deposit(amount_cents)
-            .map_err(AccountServiceError::Domain)?;
+            .deposit(amount_cents, now)
+            .map_err(|err| DomainError::field("amount", err))?;
 
-        // Сохраняем изменения
-        self.repository
-            .update(&account)
-            .await
-            .map_err(AccountServiceError::Repository)?;
+        if dry_run {
+            let response = self.preview_response(account, 0).await?;
+            return Ok(with_warning(response, precision_warning));
+        }
+
+        // Сохраняем изменения (с проверкой версии) вместе с операцией
+        let deposit = Transaction::new(
+            account.id,
+            TransactionKind::Deposit,
+            amount_cents,
+            request.note,
+            now,
+        )
+        .with_category(category);
+        self.save(&mut account, std::slice::from_ref(&deposit))
+            .await?;
 
-        Ok(account.into())
+        // Сообщаем подписчикам и пишем точку истории баланса
+        self.publish(&deposit, account.balance);
+        self.record_snapshot(&account).await?;
+
+        let response = self.to_response(account).await?;
+        Ok(with_warning(response, precision_warning))
     }
 
-    /// Use case: Снятие денег со счёта.
-    pub async fn withdraw(
+    /// Снятие без учёта идемпотентности.
+    ///
+    /// Перед снятием считаем, сколько уже снято сегодня, —
+    /// это нужно доменной проверке дневного лимита.
+    ///
+    /// # Защита от овердрафта
+    /// Если денег не хватает, а у счёта есть `overdraft_source`,
+    /// недостающее переводится с него (см. `cover_overdraft`). Оба счёта
+    /// и все три операции сохраняются одним `update_pair`: либо перевод
+    /// и снятие прошли вместе, либо не изменилось ничего. В журнале
+    /// источника — `transfer_out`, у счёта — `transfer_in` и затем `withdraw`.
+    ///
+    /// # Низкий баланс
+    /// Если снятие опустило баланс ниже `low_balance_threshold`, операция
+    /// всё равно проходит, а в ответе — предупреждение (см. `warn_low_balance`).
+    async fn apply_withdraw(
         &self,
         id: Uuid,
         request: WithdrawRequest,
+        dry_run: bool,
     ) -> Result<AccountResponse, AccountServiceError<R::Error>> {
-        let mut account = self
-            .repository
-            .find_by_id(id)
-            .await
-            .map_err(AccountServiceError::Repository)?
-            .ok_or_else(|| {
-                AccountServiceError::Domain(DomainError::AccountNotFound(id.to_string()))
-            })?;
+        // Ошибки формы — с именем поля, как в `apply_deposit`
+        let category = normalize_category(request.category.as_deref())
+            .map_err(|err| DomainError::field("category", err))?;
 
-        let amount_cents = (request.amount * 100.0).round() as i64;
+        let mut account = self.find_account(id).await?;
+        let previous_balance = account.balance;
 
-        // withdraw() может вернуть InsufficientFunds
-        account
-            .withdraw(amount_cents)
-            .map_err(AccountServiceError::Domain)?;
+        let withdrawn_today = self.withdrawn_today(account.id).await?;
+        let now = self.clock.now();
 
-        self.repository
-            .update(&account)
-            .await
-            .map_err(AccountServiceError::Repository)?;
+        // withdraw() может вернуть LimitExceeded или InsufficientFunds
+        let mut precision_warning = None;
+        let (amount_cents, cover) = match request.amount {
+            WithdrawAmount::Exact(amount) => {
+                let (amount, warning) = self
+                    .fit_amount(amount, &Currency::new(&account.currency))
+                    .map_err(|err| DomainError::field("amount", err))?;
+                precision_warning = warning;
+                let cover = self
+                    .withdraw_covered(&mut account, amount.cents(), withdrawn_today, now)
+                    .await?;
+                (amount.cents(), cover)
+            }
+            // Сумма — баланс этого же экземпляра счёта; save() ниже
+            // проверит версию, так что параллельное изменение даст 409.
+            // Овердрафт не используется: "всё" — это то, что есть на счёте
+            WithdrawAmount::All => (account.withdraw_all(withdrawn_today, now)?, None),
+        };
+        let low_balance = account.crossed_low_balance(previous_balance);
+
+        // Все проверки пройдены, в том числе у источника овердрафта
+        if dry_run {
+            let response = self.preview_response(account, amount_cents).await?;
+            let response = warn_low_balance(response, low_balance);
+            return Ok(with_warning(response, precision_warning));
+        }
+
+        let withdrawal = Transaction::new(
+            account.id,
+            TransactionKind::Withdraw,
+            amount_cents,
+            request.note,
+            now,
+        )
+        .with_category(category);
+
+        match cover {
+            None => {
+                self.save(&mut account, std::slice::from_ref(&withdrawal))
+                    .await?
+            }
+            Some(mut cover) => {
+                let note = Some("Overdraft protection".to_string());
+                let outgoing = Transaction::new(
+                    cover.source.id,
+                    TransactionKind::TransferOut,
+                    cover.amount,
+                    note.clone(),
+                    now,
+                )
+                .with_counterparty(account.id, cover.amount);
+                let incoming = Transaction::new(
+                    account.id,
+                    TransactionKind::TransferIn,
+                    cover.amount,
+                    note,
+                    now,
+                )
+                .with_counterparty(cover.source.id, cover.amount);
+
+                // Перевод с источника и само снятие — одной транзакцией
+                let ledger = [outgoing, incoming, withdrawal.clone()];
+                let updated = self
+                    .repository
+                    .update_pair(&cover.source, &account, &[], &ledger)
+                    .await
+                    .map_err(AccountServiceError::Repository)?;
+                if !updated {
+                    return Err(DomainError::ConcurrentModification(account.id.to_string()).into());
+                }
+                account.version += 1;
+                cover.source.version += 1;
 
-        Ok(account.into())
+                let [outgoing, incoming, _] = &ledger;
+                self.publish(outgoing, cover.source.balance);
+                // Баланс после перевода — ещё до снятия
+                self.publish(incoming, account.balance + amount_cents);
+                self.record_snapshot(&cover.source).await?;
+            }
+        }
+
+        self.publish(&withdrawal, account.balance);
+        self.record_snapshot(&account).await?;
+
+        let response = self.to_response(account).await?;
+        let response = warn_low_balance(response, low_balance);
+        Ok(with_warning(response, precision_warning))
     }
 
-    /// Use case: Удаление счёта.
-    pub async fn delete_account(&self, id: Uuid) -> Result<(), AccountServiceError<R::Error>> {
-        // Сначала проверяем, существует ли счёт
-        self.repository
-            .find_by_id(id)
+    /// Снимает `amount` со счёта в памяти, при нехватке — с переводом
+    /// с источника защиты от овердрафта (см. `cover_overdraft`).
+    ///
+    /// Ничего не сохраняет: перевод возвращается, чтобы `apply_withdraw`
+    /// записал его вместе со снятием, а `simulate` — просто отбросил.
+    async fn withdraw_covered(
+        &self,
+        account: &mut Account,
+        amount: i64,
+        withdrawn_today: i64,
+        now: DateTime<Utc>,
+    ) -> Result<Option<OverdraftCover>, AccountServiceError<R::Error>> {
+        let Err(err) = account.withdraw(amount, withdrawn_today, now) else {
+            return Ok(None);
+        };
+        let cover = self.cover_overdraft(account, err, now).await?;
+        account.deposit(cover.amount, now)?;
+        account.withdraw(amount, withdrawn_today, now)?;
+        Ok(Some(cover))
+    }
+
+    /// Списывает с источника защиты от овердрафта нехватку для снятия.
+    ///
+    /// `err` — ошибка, с которой снятие не прошло. Покрывается только
+    /// `InsufficientFunds`; остальные ошибки, как и нехватка без источника,
+    /// возвращаются как есть.
+    ///
+    /// # Когда источник не помогает
+    /// Источник удалён, в другой валюте (ссылка пришла из резервной
+    /// копии в обход проверок) или сам не может отдать нехватку (не хватает
+    /// денег, дневной лимит, неснижаемый остаток) — возвращается исходная
+    /// `InsufficientFunds`: клиент снимал с этого счёта, и ошибка о нём.
+    ///
+    /// Источник источника не используется: защита покрывает один шаг,
+    /// поэтому даже цикл в настройках не приведёт к бесконечному обходу.
+    async fn cover_overdraft(
+        &self,
+        account: &Account,
+        err: DomainError,
+        now: DateTime<Utc>,
+    ) -> Result<OverdraftCover, AccountServiceError<R::Error>> {
+        let DomainError::InsufficientFunds {
+            available,
+            requested,
+            ..
+        } = err
+        else {
+            return Err(err.into());
+        };
+        let Some(source_id) = account.overdraft_source.filter(|id| *id != account.id) else {
+            return Err(err.into());
+        };
+
+        let source = self
+            .repository
+            .find_by_id(&self.user_id, source_id)
             .await
             .map_err(AccountServiceError::Repository)?
-            .ok_or_else(|| {
-                AccountServiceError::Domain(DomainError::AccountNotFound(id.to_string()))
-            })?;
+            .filter(|source| Currency::new(&source.currency) == Currency::new(&account.currency));
+        let Some(mut source) = source else {
+            return Err(err.into());
+        };
 
-        self.repository
-            .delete(id)
-            .await
-            .map_err(AccountServiceError::Repository)?;
+        let amount = requested - available;
+        let withdrawn_today = self.withdrawn_today(source.id).await?;
+        if source.withdraw(amount, withdrawn_today, now).is_err() {
+            return Err(err.into());
+        }
 
-        Ok(())
+        Ok(OverdraftCover { source, amount })
     }
-}
 
-/// Ошибки сервиса — объединяют доменные ошибки и ошибки репозитория.
-///
-/// # Generic параметр `E`
-/// Тип ошибки репозитория (sqlx::Error для PostgreSQL).
-///
-/// # Почему два варианта?
-/// - `Domain` — ошибки бизнес-логики (можно показать пользователю)
-/// - `Repository` — технические ошибки (логируем, но не показываем детали)
-#[derive(Debug, thiserror::Error)]
+    /// Проверяет счёт защиты от овердрафта перед сохранением в `account`.
+    ///
+    /// # Бизнес-правила
+    /// - Источник — активный счёт (иначе `InvalidOverdraftSource`, а не 404:
+    ///   не найден не изменяемый счёт, а значение поля)
+    /// - Валюта та же: перевод при овердрафте идёт без конвертации
+    /// - Нет цикла: A покрывается B, B покрывается A — взаимная защита,
+    ///   которая ничего не защищает (используется только один шаг)
+    ///
+    /// Ссылку на самого себя отклоняет `Account::set_overdraft_source`.
+    async fn check_overdraft_source(
+        &self,
+        account: &Account,
+        source_id: Uuid,
+    ) -> Result<(), AccountServiceError<R::Error>> {
+        let invalid = |msg: String| DomainError::InvalidOverdraftSource(msg).into();
+
+        let Some(source) = self
+            .repository
+            .find_by_id(&self.user_id, source_id)
+            .await
+            .map_err(AccountServiceError::Repository)?
+        else {
+            return Err(invalid(format!("Account {} not found", source_id)));
+        };
+
+        if Currency::new(&source.currency) != Currency::new(&account.currency) {
+            return Err(invalid(format!(
+                "Account {} is in {}, not {}",
+                source.id, source.currency, account.currency
+            )));
+        }
+
+        // Идём по цепочке источников от нового; visited — на случай цикла,
+        // уже сохранённого в других счетах (через импорт резервной копии)
+        let mut visited = HashSet::from([source.id]);
+        let mut next = source.overdraft_source;
+        while let Some(id) = next {
+            if id == account.id {
+                return Err(invalid(format!(
+                    "Account {} already draws on account {}; sources must not form a cycle",
+                    source.id, account.id
+                )));
+            }
+            if !visited.insert(id) {
+                break;
+            }
+            next = self
+                .repository
+                .find_by_id(&self.user_id, id)
+                .await
+                .map_err(AccountServiceError::Repository)?
+                .and_then(|account| account.overdraft_source);
+        }
+
+        Ok(())
+    }
+
+    /// Use case: Страница истории операций по счёту (новые — первыми).
+    ///
+    /// Категория в фильтре приводится к тому же виду, что и при записи:
+    /// `"Groceries"` найдёт `"groceries"`. Пагинация и период должны быть
+    /// уже провалидированы (`Pagination::validate`, `TimeRange::validate`).
+    /// `total` — число операций, подходящих под фильтр.
+    pub async fn get_transactions(
+        &self,
+        account_id: Uuid,
+        mut filter: TransactionFilter,
+        pagination: &Pagination,
+    ) -> Result<PagedResponse<TransactionResponse>, AccountServiceError<R::Error>> {
+        filter.category = normalize_category(filter.category.as_deref())?;
+        let (limit, offset) = (pagination.limit(), pagination.offset());
+
+        // Для несуществующего счёта — 404, а не пустой список
+        self.find_account(account_id).await?;
+
+        let transactions = self
+            .transactions
+            .find_by_account(account_id, &filter, limit, offset)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        let total = self
+            .transactions
+            .count_by_account(account_id, &filter)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        Ok(PagedResponse {
+            items: transactions.into_iter().map(Into::into).collect(),
+            total,
+            limit,
+            offset,
+        })
+    }
+
+    /// Use case: Изменение комментария и категории операции.
+    ///
+    /// # Бизнес-правила
+    /// - Сумма не меняется никогда: `amount` в запросе — 400
+    /// - Категория приводится к тому же виду, что и при записи
+    /// - Операция другого счёта — 404, как и несуществующая
+    pub async fn update_transaction(
+        &self,
+        account_id: Uuid,
+        transaction_id: Uuid,
+        request: UpdateTransactionRequest,
+    ) -> Result<TransactionResponse, AccountServiceError<R::Error>> {
+        if request.amount.is_some() {
+            return Err(DomainError::field(
+                "amount",
+                DomainError::InvalidAmount(
+                    "The amount of a recorded transaction cannot be changed; \
+                     reverse it and record a new one"
+                        .into(),
+                ),
+            )
+            .into());
+        }
+        let category = request
+            .category
+            .map(|category| normalize_category(category.as_deref()))
+            .transpose()
+            .map_err(|err| DomainError::field("category", err))?;
+
+        let mut transaction = self.find_transaction(account_id, transaction_id).await?;
+        if let Some(note) = request.note {
+            transaction.note = note;
+        }
+        if let Some(category) = category {
+            transaction.category = category;
+        }
+
+        self.store_transaction_details(&transaction).await?;
+        Ok(transaction.into())
+    }
+
+    /// Use case: Отмена пополнения или снятия.
+    ///
+    /// Баланс возвращается обратной операцией: снятием для пополнения,
+    /// пополнением для снятия (см. `Transaction::reverse`). Отменённая
+    /// операция остаётся в журнале с `reversed_at`.
+    ///
+    /// # Бизнес-правила
+    /// Обратное снятие проходит те же проверки, что и обычное, кроме
+    /// дневного лимита (см. `Account::reverse_deposit`): отменить
+    /// пополнение, деньги с которого уже потрачены, нельзя (400).
+    /// Овердрафт при этом не используется. Повторная отмена — 409.
+    pub async fn reverse_transaction(
+        &self,
+        account_id: Uuid,
+        transaction_id: Uuid,
+    ) -> Result<AccountResponse, AccountServiceError<R::Error>> {
+        let mut transaction = self.find_transaction(account_id, transaction_id).await?;
+        let mut account = self.find_account(account_id).await?;
+        let previous_balance = account.balance;
+        let now = self.clock.now();
+
+        let reversal = transaction.reverse(now)?;
+        match reversal.kind {
+            TransactionKind::Withdraw => account.reverse_deposit(reversal.amount, now)?,
+            _ => account.deposit(reversal.amount, now)?,
+        }
+        let low_balance = account.crossed_low_balance(previous_balance);
+
+        // Баланс, обратная запись и `reversed_at` — одной транзакцией.
+        // Проверка версии не даст отменить операцию дважды параллельными
+        // запросами — второй получит 409
+        self.save(&mut account, &[reversal.clone(), transaction])
+            .await?;
+        self.publish(&reversal, account.balance);
+        self.record_snapshot(&account).await?;
+
+        let response = self.to_response(account).await?;
+        Ok(warn_low_balance(response, low_balance))
+    }
+
+    /// Use case: Выгрузка всего журнала счёта, старые операции — первыми.
+    ///
+    /// Операции не собираются в `Vec`: поток из репозитория передаётся
+    /// дальше как есть, и каждая строка уходит клиенту сразу.
+    ///
+    /// # Откуда `balance_after`?
+    /// Нарастающий итог от нуля, как входящий остаток в `get_statement`:
+    /// счёт создаётся с нулевым балансом, и каждое изменение есть в журнале.
+    pub async fn export_transactions(
+        &self,
+        account_id: Uuid,
+    ) -> Result<TransactionExport<R::Error>, AccountServiceError<R::Error>> {
+        let account = self.find_account(account_id).await?;
+
+        let entries = self
+            .transactions
+            .stream_by_account(account_id)
+            .scan(0_i64, |balance, item| {
+                let entry = item.map(|transaction| {
+                    *balance = balance.saturating_add(transaction.kind.signed(transaction.amount));
+                    LedgerEntry {
+                        transaction: transaction.into(),
+                        balance_after: Money::from_cents(*balance),
+                    }
+                });
+                future::ready(Some(entry))
+            })
+            .boxed();
+
+        Ok(TransactionExport {
+            account_id,
+            currency: Currency::new(&account.currency),
+            entries,
+        })
+    }
+
+    /// Use case: Лента последних операций всех счетов пользователя.
+    ///
+    /// Лимит должен быть уже проверен (`ActivityQuery::cursor`).
+    ///
+    /// # Откуда имя и валюта счёта?
+    /// Как в `category_report`: журнал не знает о счетах, поэтому счета
+    /// читаются одним `find_all` и сопоставляются здесь. Их ID ограничивают
+    /// и саму выборку: в ленту не попадают операции чужих и удалённых счетов.
+    pub async fn activity(
+        &self,
+        before: Option<ActivityCursor>,
+        limit: i64,
+    ) -> Result<ActivityPage, AccountServiceError<R::Error>> {
+        let accounts: HashMap<Uuid, Account> = self
+            .repository
+            .find_all(&self.user_id)
+            .await
+            .map_err(AccountServiceError::Repository)?
+            .into_iter()
+            .map(|account| (account.id, account))
+            .collect();
+        let account_ids: Vec<Uuid> = accounts.keys().copied().collect();
+
+        let transactions = self
+            .transactions
+            .find_recent(&account_ids, before, limit)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        // Меньше `limit` — дальше операций нет
+        let next_before = match transactions.last() {
+            Some(last) if transactions.len() as i64 == limit => {
+                Some(ActivityCursor::of(last).to_string())
+            }
+            _ => None,
+        };
+
+        let items = transactions
+            .into_iter()
+            .filter_map(|transaction| {
+                let account = accounts.get(&transaction.account_id)?;
+                Some(ActivityItem {
+                    account_name: account.name.clone(),
+                    currency: account.currency.clone(),
+                    transaction: transaction.into(),
+                })
+            })
+            .collect();
+
+        Ok(ActivityPage { items, next_before })
+    }
+
+    /// Use case: Отчёт по категориям за период по всем счетам пользователя.
+    ///
+    /// Диапазон должен быть уже провалидирован (`TimeRange::validate`).
+    ///
+    /// # Что входит в отчёт
+    /// - Пополнения и снятия активных счетов; переводы и удалённые
+    ///   счета — нет (см. `CategoryTotalResponse`)
+    /// - Операции без категории — в строке `Transaction::UNCATEGORIZED`
+    /// - Строки отсортированы по категории, затем по валюте
+    pub async fn category_report(
+        &self,
+        range: &TimeRange,
+    ) -> Result<Vec<CategoryTotalResponse>, AccountServiceError<R::Error>> {
+        let currencies: HashMap<Uuid, Currency> = self
+            .repository
+            .find_all(&self.user_id)
+            .await
+            .map_err(AccountServiceError::Repository)?
+            .into_iter()
+            .map(|account| (account.id, Currency::new(&account.currency)))
+            .collect();
+        let account_ids: Vec<Uuid> = currencies.keys().copied().collect();
+
+        let totals = self
+            .transactions
+            .totals_by_category(&account_ids, range.from, range.to)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        // BTreeMap — строки сразу в порядке (категория, валюта).
+        // Значение: (пополнения, снятия, число операций)
+        let mut report: BTreeMap<(String, String), (i64, i64, i64)> = BTreeMap::new();
+        for total in totals {
+            let Some(currency) = currencies.get(&total.account_id) else {
+                continue; // Счёт удалён
+            };
+            let row = report
+                .entry((
+                    total
+                        .category
+                        .unwrap_or_else(|| Transaction::UNCATEGORIZED.to_string()),
+                    currency.to_string(),
+                ))
+                .or_default();
+            match total.kind {
+                // Проценты — тоже доход, хоть и без категории
+                TransactionKind::Deposit | TransactionKind::Interest => row.0 += total.total,
+                TransactionKind::Withdraw => row.1 += total.total,
+                TransactionKind::TransferIn
+                | TransactionKind::TransferOut
+                | TransactionKind::Reconciliation
+                | TransactionKind::CurrencyChange
+                | TransactionKind::OpeningBalance => continue,
+            }
+            row.2 += total.count;
+        }
+
+        Ok(report
+            .into_iter()
+            .map(
+                |((category, currency), (deposits, withdrawals, count))| CategoryTotalResponse {
+                    category,
+                    currency,
+                    total_deposits: Money::from_cents(deposits),
+                    total_withdrawals: Money::from_cents(withdrawals),
+                    count,
+                },
+            )
+            .collect())
+    }
+
+    /// Use case: Сводка по счетам — число и остатки по валютам, даты создания.
+    ///
+    /// Хранилище возвращает по строке на валюту (`stats_by_currency`),
+    /// итоги по всем валютам сводятся здесь: строк столько же, сколько
+    /// валют, а не счетов.
+    pub async fn stats(&self) -> Result<StatsResponse, AccountServiceError<R::Error>> {
+        let stats = self
+            .repository
+            .stats_by_currency(&self.user_id)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        let oldest = stats.iter().map(|row| row.oldest_created_at).min();
+        let newest = stats.iter().map(|row| row.newest_created_at).max();
+
+        Ok(StatsResponse {
+            total_accounts: stats.iter().map(|row| row.count).sum(),
+            currencies: stats
+                .into_iter()
+                .map(|row| CurrencyStatsResponse {
+                    currency: row.currency,
+                    count: row.count,
+                    total_balance: Money::from_cents(row.balance),
+                })
+                .collect(),
+            oldest_account_created_at: oldest.map(|at| at.to_rfc3339()),
+            newest_account_created_at: newest.map(|at| at.to_rfc3339()),
+        })
+    }
+
+    /// Use case: Капитал — остатки всех активных счетов в валюте `currency`.
+    ///
+    /// # Как считается
+    /// 1. Остатки складываются по валютам счетов
+    /// 2. Каждая сумма пересчитывается по курсу к `currency` — один запрос
+    ///    курса на валюту, а не на счёт, и одно округление
+    /// 3. Валюта без курса попадает в `unconverted`, а не в `total`
+    ///
+    /// Архивные счета учитываются: деньги на них всё ещё есть.
+    pub async fn net_worth(
+        &self,
+        currency: &Currency,
+    ) -> Result<NetWorthResponse, AccountServiceError<R::Error>> {
+        let accounts = self
+            .repository
+            .find_all(&self.user_id)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        // BTreeMap — разбивка сразу отсортирована по коду валюты
+        let mut balances: BTreeMap<String, i64> = BTreeMap::new();
+        for account in accounts {
+            let sum = balances
+                .entry(Currency::new(&account.currency).to_string())
+                .or_default();
+            *sum = sum
+                .checked_add(account.balance)
+                .ok_or_else(|| DomainError::InvalidAmount("Net worth is too large".into()))?;
+        }
+
+        let mut total: i64 = 0;
+        let mut breakdown = Vec::new();
+        let mut unconverted = Vec::new();
+
+        for (code, cents) in balances {
+            let from = Currency::new(&code);
+            let balance = Money::from_cents(cents);
+
+            let exchange_rate = if from == *currency {
+                Decimal::ONE
+            } else {
+                match self.rates.rate(&from, currency).await {
+                    Ok(rate) => rate,
+                    Err(DomainError::RateUnavailable { .. }) => {
+                        unconverted.push(CurrencyBalance {
+                            currency: code,
+                            balance,
+                        });
+                        continue;
+                    }
+                    Err(err) => return Err(err.into()),
+                }
+            };
+
+            let converted = balance.convert_balance(exchange_rate, currency, self.rounding)?;
+            total = total
+                .checked_add(converted.cents())
+                .ok_or_else(|| DomainError::InvalidAmount("Net worth is too large".into()))?;
+            breakdown.push(NetWorthBreakdown {
+                currency: code,
+                balance,
+                exchange_rate,
+                converted,
+            });
+        }
+
+        Ok(NetWorthResponse {
+            currency: currency.to_string(),
+            total: Money::from_cents(total),
+            breakdown,
+            unconverted,
+        })
+    }
+
+    /// Use case: История баланса за период (для графиков).
+    ///
+    /// Диапазон должен быть уже провалидирован (`TimeRange::validate`).
+    pub async fn get_balance_history(
+        &self,
+        account_id: Uuid,
+        range: &TimeRange,
+    ) -> Result<Vec<BalanceSnapshotResponse>, AccountServiceError<R::Error>> {
+        self.find_account(account_id).await?;
+
+        let snapshots = self
+            .snapshots
+            .find_in_range(account_id, range.from, range.to)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        Ok(snapshots.into_iter().map(Into::into).collect())
+    }
+
+    /// Use case: Выписка по счёту за календарный месяц.
+    ///
+    /// Период уже проверен (`StatementQuery::period`). Баланс на начало
+    /// месяца считается по журналу, а не берётся из истории баланса:
+    /// так выписка сходится сама с собой — opening плюс операции
+    /// месяца всегда равны closing.
+    pub async fn get_statement(
+        &self,
+        account_id: Uuid,
+        period: StatementPeriod,
+    ) -> Result<StatementResponse, AccountServiceError<R::Error>> {
+        let account = self.find_account(account_id).await?;
+
+        let (opening, _) = self.balance_before(account_id, period.start).await?;
+
+        let transactions = self
+            .transactions
+            .find_in_range(account_id, period.start, period.end)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        // Сверка (`Reconciliation`), смена валюты (`CurrencyChange`)
+        // и начальный остаток (`OpeningBalance`) не входят ни в пополнения,
+        // ни в снятия, но последние два меняют баланс — поэтому
+        // закрывающий считается по `signed`
+        let period_change: i64 = transactions.iter().map(|t| t.kind.signed(t.amount)).sum();
+        let total_deposits: i64 = transactions
+            .iter()
+            .filter(|t| t.kind.is_credit())
+            .map(|t| t.amount)
+            .sum();
+        let total_withdrawals: i64 = transactions
+            .iter()
+            .filter(|t| t.kind.is_debit())
+            .map(|t| t.amount)
+            .sum();
+
+        Ok(StatementResponse {
+            account_id,
+            currency: account.currency,
+            year: period.year,
+            month: period.month,
+            period_start: period.start.to_rfc3339(),
+            period_end: period.end.to_rfc3339(),
+            opening_balance: Money::from_cents(opening),
+            closing_balance: Money::from_cents(opening + period_change),
+            total_deposits: Money::from_cents(total_deposits),
+            total_withdrawals: Money::from_cents(total_withdrawals),
+            transactions: transactions.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    /// Use case: Баланс счёта на конец дня (`BalanceAtQuery::cutoff`).
+    ///
+    /// Считается по журналу, как остаток на начало выписки: сумма
+    /// операций строго до `cutoff` с их знаком (`TransactionKind::signed`).
+    /// Не из истории баланса — точек в ней может не быть на нужный день.
+    pub async fn balance_at(
+        &self,
+        account_id: Uuid,
+        date: NaiveDate,
+        cutoff: DateTime<Utc>,
+    ) -> Result<BalanceAtResponse, AccountServiceError<R::Error>> {
+        let account = self.find_account(account_id).await?;
+        let (balance, transaction_count) = self.balance_before(account_id, cutoff).await?;
+
+        Ok(BalanceAtResponse {
+            account_id,
+            currency: account.currency,
+            date,
+            balance: Money::from_cents(balance),
+            transaction_count,
+        })
+    }
+
+    /// Баланс по журналу строго до `before` и число операций до него.
+    ///
+    /// Общий для `get_statement` и `balance_at`: остаток на начало
+    /// выписки и баланс на конец предыдущего дня считаются одинаково,
+    /// одним запросом `totals_before`.
+    async fn balance_before(
+        &self,
+        account_id: Uuid,
+        before: DateTime<Utc>,
+    ) -> Result<(i64, i64), AccountServiceError<R::Error>> {
+        let totals = self
+            .transactions
+            .totals_before(account_id, before)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        Ok((
+            totals.iter().map(|t| t.kind.signed(t.total)).sum(),
+            totals.iter().map(|t| t.count).sum(),
+        ))
+    }
+
+    /// Use case: Сводка о жизни счёта (`AccountInfoResponse`).
+    ///
+    /// Журнал читается потоком за один проход, как в сверке: счётчик,
+    /// итоги и время последней операции. Операций нет — активностью
+    /// считается `updated_at`. Разница во времени не бывает
+    /// отрицательной, даже если часы сервера ушли назад.
+    pub async fn account_info(
+        &self,
+        account_id: Uuid,
+    ) -> Result<AccountInfoResponse, AccountServiceError<R::Error>> {
+        let account = self.find_account(account_id).await?;
+
+        let (count, deposits, withdrawals, last_transaction_at) = self
+            .transactions
+            .stream_by_account(account_id)
+            .try_fold(
+                (0_i64, 0_i64, 0_i64, None::<DateTime<Utc>>),
+                |(count, deposits, withdrawals, last), transaction| {
+                    let amount = transaction.amount;
+                    let (deposits, withdrawals) = if transaction.kind.is_credit() {
+                        (deposits.saturating_add(amount), withdrawals)
+                    } else if transaction.kind.is_debit() {
+                        (deposits, withdrawals.saturating_add(amount))
+                    } else {
+                        (deposits, withdrawals)
+                    };
+                    let last = last.max(Some(transaction.timestamp));
+                    future::ready(Ok((count + 1, deposits, withdrawals, last)))
+                },
+            )
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        let now = self.clock.now();
+        let last_activity_at = last_transaction_at.unwrap_or(account.updated_at);
+
+        Ok(AccountInfoResponse {
+            account_id,
+            currency: account.currency,
+            created_at: account.created_at.to_rfc3339(),
+            last_activity_at: last_activity_at.to_rfc3339(),
+            age_days: (now - account.created_at).num_days().max(0),
+            days_since_last_activity: (now - last_activity_at).num_days().max(0),
+            transaction_count: count,
+            total_deposits: Money::from_cents(deposits),
+            total_withdrawals: Money::from_cents(withdrawals),
+        })
+    }
+
+    /// Use case: Сверка сохранённого баланса с журналом операций.
+    ///
+    /// Баланс и журнал пишутся одной транзакцией (`save`, `update_pair`),
+    /// и сами операции расхождения не оставят, но его может оставить ручная
+    /// правка БД или импорт. Сверка пересчитывает
+    /// баланс по всему журналу (счёт создаётся с нулём, см.
+    /// `export_transactions`; у счетов старше журнала первая запись —
+    /// `OpeningBalance`) и сравнивает с сохранённым.
+    ///
+    /// # `fix`
+    /// С `fix = true` при расхождении сохранённый баланс заменяется
+    /// посчитанным, а в журнал пишется `reconciliation` на величину
+    /// расхождения. Журнал считается верным: он — история, баланс — её итог.
+    ///
+    /// # Ограничение
+    /// Операция, прошедшая между чтением счёта и журнала, выглядит как
+    /// расхождение. Исправление тогда отклонит проверка версии в `save` (409).
+    pub async fn reconcile(
+        &self,
+        account_id: Uuid,
+        fix: bool,
+    ) -> Result<ReconciliationResponse, AccountServiceError<R::Error>> {
+        let mut account = self.find_account(account_id).await?;
+
+        let computed = self
+            .transactions
+            .stream_by_account(account_id)
+            .try_fold(0_i64, |sum, transaction| {
+                future::ready(Ok(
+                    sum.saturating_add(transaction.kind.signed(transaction.amount))
+                ))
+            })
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        let stored = account.balance;
+        let difference = stored - computed;
+        let fixed = fix && difference != 0;
+
+        if fixed {
+            let now = self.clock.now();
+            account.correct_balance(computed, now);
+
+            let note = format!(
+                "Balance corrected from {} to {}",
+                Money::from_cents(stored),
+                Money::from_cents(computed)
+            );
+            let correction = Transaction::new(
+                account.id,
+                TransactionKind::Reconciliation,
+                difference.abs(),
+                Some(note),
+                now,
+            );
+            self.save(&mut account, std::slice::from_ref(&correction))
+                .await?;
+            self.publish(&correction, account.balance);
+            self.record_snapshot(&account).await?;
+
+            tracing::warn!(
+                "Account {} balance corrected from {} to {} by reconciliation",
+                account.id,
+                Money::from_cents(stored),
+                Money::from_cents(computed)
+            );
+        }
+
+        Ok(ReconciliationResponse {
+            account_id,
+            matches: difference == 0,
+            stored: Money::from_cents(stored),
+            computed: Money::from_cents(computed),
+            difference: Money::from_cents(difference),
+            fixed,
+        })
+    }
+
+    /// Use case: Симуляция пополнения/снятия (what-if).
+    ///
+    /// # Как это работает
+    /// Доменная логика выполняется над КОПИЕЙ счёта (`.clone()`),
+    /// поэтому все бизнес-правила проверяются, но в репозиторий ничего не пишется.
+    ///
+    /// Снятие при нехватке денег так же подтягивает недостающее с источника
+    /// защиты от овердрафта (`withdraw_covered`); источник загружается
+    /// копией и тоже не сохраняется.
+    ///
+    /// # Ошибки
+    /// Доменные ошибки операции НЕ возвращаются как `Err` — они попадают
+    /// в поле `error` ответа. `Err` возможен только если счёт не найден
+    /// или упал репозиторий.
+    pub async fn simulate(
+        &self,
+        id: Uuid,
+        request: SimulateRequest,
+    ) -> Result<SimulationResponse, AccountServiceError<R::Error>> {
+        let account = self.find_account(id).await?;
+
+        let withdrawn_today = self.withdrawn_today(account.id).await?;
+
+        // Работаем с копией — оригинал остаётся нетронутым
+        let mut simulated = account.clone();
+        let now = self.clock.now();
+        // Сумма — та, что была бы применена (после округления по PRECISION_MODE)
+        let mut amount = request.amount;
+        let mut warnings = Vec::new();
+        let result = match self.fit_amount(request.amount, &Currency::new(&account.currency)) {
+            Err(err) => Err(err),
+            Ok((fitted, warning)) => {
+                amount = fitted;
+                warnings.extend(warning);
+                match request.operation {
+                    SimulatedOperation::Deposit => simulated.deposit(fitted.cents(), now),
+                    SimulatedOperation::Withdraw => match self
+                        .withdraw_covered(&mut simulated, fitted.cents(), withdrawn_today, now)
+                        .await
+                    {
+                        Ok(_) => Ok(()),
+                        Err(AccountServiceError::Domain(err)) => Err(err),
+                        Err(err) => return Err(err),
+                    },
+                }
+            }
+        };
+
+        Ok(SimulationResponse {
+            operation: request.operation,
+            amount,
+            success: result.is_ok(),
+            balance_before: Money::from_cents(account.balance),
+            balance_after: Money::from_cents(simulated.balance),
+            // .err() — Result<T, E> → Option<E>
+            error: result.err().map(|e| e.to_string()),
+            warnings,
+        })
+    }
+
+    /// Use case: Перевод между счетами, в том числе в разных валютах.
+    ///
+    /// # Бизнес-правила
+    /// - Перевести на тот же счёт нельзя
+    /// - Для счёта-источника действуют те же проверки, что и при снятии
+    ///   (средства, минимальный остаток, дневной лимит)
+    /// - Если валюты различаются, сумма пересчитывается по курсу
+    ///   `ExchangeRateProvider`; нет курса — `RateUnavailable`
+    ///
+    /// # Атомарность
+    /// Оба счёта сохраняются одним `update_pair` вместе с двумя записями
+    /// журнала — `transfer_out` и `transfer_in`, каждая со ссылкой на
+    /// другой счёт и суммой на другой стороне. Деньги не могут уйти
+    /// с одного счёта и не дойти до другого, а перевод — пройти без записей.
+    ///
+    /// С `dry_run` — все проверки и курс, но без записи (см. `deposit`).
+    pub async fn transfer(
+        &self,
+        from_id: Uuid,
+        request: TransferRequest,
+        dry_run: bool,
+    ) -> Result<TransferResponse, AccountServiceError<R::Error>> {
+        if from_id == request.to_account_id {
+            return Err(DomainError::SameAccountTransfer(from_id.to_string()).into());
+        }
+
+        let mut from = self.find_account(from_id).await?;
+        let mut to = self.find_account(request.to_account_id).await?;
+
+        // Курс нужен только если валюты различаются
+        let from_currency = Currency::new(&from.currency);
+        let to_currency = Currency::new(&to.currency);
+        // Сумма — в валюте источника; пересчитанная округляется курсом
+        let (amount, precision_warning) = self.fit_amount(request.amount, &from_currency)?;
+        let (exchange_rate, converted) = if from_currency == to_currency {
+            (None, amount)
+        } else {
+            let rate = self.rates.rate(&from_currency, &to_currency).await?;
+            (
+                Some(rate),
+                amount.convert(rate, &to_currency, self.rounding)?,
+            )
+        };
+
+        let withdrawn_today = self.withdrawn_today(from.id).await?;
+        let now = self.clock.now();
+        let previous_balance = from.balance;
+        from.withdraw(amount.cents(), withdrawn_today, now)?;
+        to.deposit(converted.cents(), now)?;
+        let low_balance = from.crossed_low_balance(previous_balance);
+
+        if dry_run {
+            let from = self.preview_response(from, amount.cents()).await?;
+            return Ok(TransferResponse {
+                from: with_warning(warn_low_balance(from, low_balance), precision_warning),
+                to: self.preview_response(to, 0).await?,
+                amount,
+                converted_amount: converted,
+                exchange_rate,
+                dry_run: true,
+            });
+        }
+
+        let outgoing = Transaction::new(
+            from.id,
+            TransactionKind::TransferOut,
+            amount.cents(),
+            request.note.clone(),
+            now,
+        )
+        .with_counterparty(to.id, converted.cents());
+        let incoming = Transaction::new(
+            to.id,
+            TransactionKind::TransferIn,
+            converted.cents(),
+            request.note,
+            now,
+        )
+        .with_counterparty(from.id, amount.cents());
+
+        let ledger = [outgoing, incoming];
+        let updated = self
+            .repository
+            .update_pair(&from, &to, &[], &ledger)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+        if !updated {
+            return Err(DomainError::ConcurrentModification(from.id.to_string()).into());
+        }
+        from.version += 1;
+        to.version += 1;
+
+        let [outgoing, incoming] = &ledger;
+        self.publish(outgoing, from.balance);
+        self.publish(incoming, to.balance);
+        self.record_snapshot(&from).await?;
+        self.record_snapshot(&to).await?;
+
+        let from = self.to_response(from).await?;
+        Ok(TransferResponse {
+            from: with_warning(warn_low_balance(from, low_balance), precision_warning),
+            to: self.to_response(to).await?,
+            amount,
+            converted_amount: converted,
+            exchange_rate,
+            dry_run: false,
+        })
+    }
+
+    /// Use case: Удаление счёта (мягкое).
+    ///
+    /// Счёт скрывается из всех выборок, но остаётся в хранилище
+    /// вместе с историей и может быть восстановлен (`restore_account`).
+    ///
+    /// # Бизнес-правила
+    /// - Счёт с ненулевым балансом (в том числе с долгом по кредиту)
+    ///   удаляется только с `force` — иначе `AccountNotEmpty`
+    ///
+    /// # Почему не запретить совсем?
+    /// Удалённый счёт пропадает из списков, а с ним и его деньги —
+    /// случайно так потерять баланс легко. Но иногда это и нужно
+    /// (тестовый счёт, ошибочное пополнение), поэтому явный `force` разрешает.
+    pub async fn delete_account(
+        &self,
+        id: Uuid,
+        force: bool,
+    ) -> Result<(), AccountServiceError<R::Error>> {
+        // Сначала проверяем, существует ли счёт
+        let account = self.find_account(id).await?;
+
+        if account.balance != 0 && !force {
+            return Err(AccountServiceError::Domain(DomainError::AccountNotEmpty {
+                balance: account.balance,
+            }));
+        }
+
+        let now = self.clock.now();
+        let audit = [AuditEntry::new(id, AuditAction::Deleted, now)];
+        self.repository
+            .delete(&self.user_id, id, now, &audit)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        Ok(())
+    }
+
+    /// Use case: Восстановление удалённого счёта.
+    ///
+    /// # Бизнес-правила
+    /// - Восстановить можно только удалённый счёт — иначе `AccountNotFound`
+    /// - Пока счёт был удалён, его имя мог занять новый счёт —
+    ///   тогда `AccountAlreadyExists`, чтобы не получить два счёта с одним именем
+    pub async fn restore_account(
+        &self,
+        id: Uuid,
+    ) -> Result<AccountResponse, AccountServiceError<R::Error>> {
+        let account = self
+            .repository
+            .find_deleted_by_id(&self.user_id, id)
+            .await
+            .map_err(AccountServiceError::Repository)?
+            .ok_or_else(|| {
+                AccountServiceError::Domain(DomainError::AccountNotFound(id.to_string()))
+            })?;
+
+        if self
+            .repository
+            .find_by_name(&self.user_id, &account.name)
+            .await
+            .map_err(AccountServiceError::Repository)?
+            .is_some()
+        {
+            return Err(AccountServiceError::Domain(
+                DomainError::AccountAlreadyExists(account.name),
+            ));
+        }
+
+        // Восстановленный счёт снова занимает место в лимите
+        self.check_quota(1).await?;
+
+        let now = self.clock.now();
+        let audit = [AuditEntry::new(id, AuditAction::Restored, now)];
+        let restored = self
+            .repository
+            .restore(&self.user_id, id, now, &audit)
+            .await
+            .map_err(|err| Self::write_error(err, &account.name))?;
+
+        // Между чтением и восстановлением счёт могли восстановить параллельно
+        if !restored {
+            return Err(AccountServiceError::Domain(DomainError::AccountNotFound(
+                id.to_string(),
+            )));
+        }
+
+        // Перечитываем, чтобы вернуть актуальные version/updated_at
+        self.get_account(id).await
+    }
+
+    /// Use case: Журнал изменений счёта, от старых записей к новым.
+    ///
+    /// Доступен и для удалённого счёта — кто и когда его удалил,
+    /// интереснее всего как раз тогда. Счёта нет ни среди активных,
+    /// ни среди удалённых — `AccountNotFound`.
+    pub async fn account_audit(
+        &self,
+        id: Uuid,
+    ) -> Result<Vec<AuditEntryResponse>, AccountServiceError<R::Error>> {
+        let exists = self
+            .repository
+            .find_by_id(&self.user_id, id)
+            .await
+            .map_err(AccountServiceError::Repository)?
+            .is_some()
+            || self
+                .repository
+                .find_deleted_by_id(&self.user_id, id)
+                .await
+                .map_err(AccountServiceError::Repository)?
+                .is_some();
+        if !exists {
+            return Err(DomainError::AccountNotFound(id.to_string()).into());
+        }
+
+        let entries = self
+            .repository
+            .find_audit(&self.user_id, id)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        Ok(entries.into_iter().map(AuditEntryResponse::from).collect())
+    }
+
+    /// Use case: Закрытие счёта — обнулить и убрать в архив за один шаг.
+    ///
+    /// # Куда уходит остаток
+    /// - `to_account_id` задан — весь остаток переводится туда (с пересчётом
+    ///   по курсу, как в `transfer`); в журнале `transfer_out`/`transfer_in`
+    /// - не задан — остаток списывается: `withdraw` в журнале, а долг
+    ///   по кредиту закрывается записью `deposit`
+    ///
+    /// # Бизнес-правила
+    /// - Уже закрытый счёт (см. `Account::is_closed`) возвращается как есть:
+    ///   повтор запроса — не ошибка
+    /// - Долг нельзя перевести на другой счёт — только погасить
+    ///   или списать, закрыв счёт без `to_account_id`
+    /// - Закрытый счёт-получатель — `AccountClosed`: зачисление сделало бы
+    ///   его баланс ненулевым, а он остался бы в архиве
+    /// - Остаток, который по курсу округляется до нуля в валюте получателя,
+    ///   списывается, как без `to_account_id`: переводить нечего
+    /// - Дневной лимит и неснижаемый остаток не проверяются
+    ///   (см. `Account::close`)
+    ///
+    /// # Атомарность
+    /// Обнуление, архив, зачисление на другой счёт и обе записи журнала
+    /// сохраняются одним `update_pair`, как перевод. Без другого счёта —
+    /// одним `save` вместе со списанием остатка.
+    pub async fn close_account(
+        &self,
+        id: Uuid,
+        request: CloseAccountRequest,
+    ) -> Result<AccountResponse, AccountServiceError<R::Error>> {
+        if request.to_account_id == Some(id) {
+            return Err(DomainError::SameAccountTransfer(id.to_string()).into());
+        }
+
+        let mut account = self.find_account(id).await?;
+        if account.is_closed() {
+            return self.to_response(account).await;
+        }
+
+        let target = match request.to_account_id {
+            Some(to_id) => Some(self.find_account(to_id).await?),
+            None => None,
+        };
+        if let Some(to) = target.as_ref().filter(|to| to.is_closed()) {
+            return Err(DomainError::AccountClosed(to.id.to_string()).into());
+        }
+
+        let now = self.clock.now();
+        let balance = account.close(now);
+        // Не `diff`: `archived` здесь — следствие закрытия, а не отдельное действие
+        let audit = [AuditEntry::new(account.id, AuditAction::Closed, now)];
+
+        // Получатель и сумма зачисления в его валюте; `None` — остаток списывается
+        let transfer = match target {
+            Some(to) if balance != 0 => {
+                if balance < 0 {
+                    return Err(DomainError::InvalidAmount(format!(
+                        "Account has a debt of {}; repay it or close the account \
+                         without to_account_id to write it off",
+                        Money::from_cents(-balance)
+                    ))
+                    .into());
+                }
+
+                let from_currency = Currency::new(&account.currency);
+                let to_currency = Currency::new(&to.currency);
+                let converted = if from_currency == to_currency {
+                    Money::from_cents(balance)
+                } else {
+                    let rate = self.rates.rate(&from_currency, &to_currency).await?;
+                    // Не `convert`: ноль здесь не ошибка, а остаток на списание
+                    Money::from_cents(balance).convert_balance(rate, &to_currency, self.rounding)?
+                };
+                (converted.cents() > 0).then_some((to, converted))
+            }
+            _ => None,
+        };
+
+        match transfer {
+            Some((mut to, converted)) => {
+                to.deposit(converted.cents(), now)?;
+
+                let outgoing = Transaction::new(
+                    account.id,
+                    TransactionKind::TransferOut,
+                    balance,
+                    request.note.clone(),
+                    now,
+                )
+                .with_counterparty(to.id, converted.cents());
+                let incoming = Transaction::new(
+                    to.id,
+                    TransactionKind::TransferIn,
+                    converted.cents(),
+                    request.note,
+                    now,
+                )
+                .with_counterparty(account.id, balance);
+
+                let ledger = [outgoing, incoming];
+                let updated = self
+                    .repository
+                    .update_pair(&account, &to, &audit, &ledger)
+                    .await
+                    .map_err(AccountServiceError::Repository)?;
+                if !updated {
+                    return Err(DomainError::ConcurrentModification(account.id.to_string()).into());
+                }
+                account.version += 1;
+                to.version += 1;
+
+                let [outgoing, incoming] = &ledger;
+                self.publish(outgoing, 0);
+                self.publish(incoming, to.balance);
+                self.record_snapshot(&account).await?;
+                self.record_snapshot(&to).await?;
+            }
+            None => {
+                // Остаток снимается (или долг списывается) записью в журнале
+                let sweep = (balance != 0).then(|| {
+                    let kind = if balance > 0 {
+                        TransactionKind::Withdraw
+                    } else {
+                        TransactionKind::Deposit
+                    };
+                    let note = request.note.or_else(|| Some("Account closed".into()));
+                    Transaction::new(account.id, kind, balance.abs(), note, now)
+                });
+                self.save_audited(&mut account, &audit, sweep.as_slice())
+                    .await?;
+
+                if let Some(sweep) = &sweep {
+                    self.publish(sweep, 0);
+                    self.record_snapshot(&account).await?;
+                }
+            }
+        }
+
+        self.to_response(account).await
+    }
+
+    /// Use case: Смена валюты счёта по заданному курсу.
+    ///
+    /// Баланс и лимиты пересчитываются (см. `Account::change_currency`),
+    /// а в журнал пишется `currency_change` на разницу нового и старого
+    /// баланса — так сумма журнала по-прежнему сходится с балансом
+    /// (`reconcile`). Старая и новая валюта и курс — в заметке записи.
+    ///
+    /// Регулярные операции счёта здесь не проверяются — это делает
+    /// `RecurringService::change_currency`.
+    pub async fn change_currency(
+        &self,
+        id: Uuid,
+        request: ChangeCurrencyRequest,
+    ) -> Result<AccountResponse, AccountServiceError<R::Error>> {
+        let currency = Currency::parse(&request.currency)?;
+
+        let mut account = self.find_account(id).await?;
+        let now = self.clock.now();
+
+        let before = account.clone();
+        let old_currency = account.currency.clone();
+        let previous = account.change_currency(&currency, request.rate, self.rounding, now)?;
+        // Валюта и пересчитанные лимиты — по записи на поле
+        let audit = AuditEntry::diff(&before, &account, now);
+
+        let note = request.note.unwrap_or_else(|| {
+            format!(
+                "Currency changed from {} to {} at rate {}: {} {} -> {} {}",
+                old_currency,
+                account.currency,
+                request.rate,
+                Money::from_cents(previous),
+                old_currency,
+                Money::from_cents(account.balance),
+                account.currency
+            )
+        });
+        let change = Transaction::new(
+            account.id,
+            TransactionKind::CurrencyChange,
+            account.balance - previous,
+            Some(note),
+            now,
+        );
+        self.save_audited(&mut account, &audit, std::slice::from_ref(&change))
+            .await?;
+        self.publish(&change, account.balance);
+        self.record_snapshot(&account).await?;
+
+        self.to_response(account).await
+    }
+
+    /// Выполняет операцию не больше одного раза на ключ идемпотентности.
+    ///
+    /// # Поток
+    /// 1. Ключа нет — просто выполняем операцию
+    /// 2. Занимаем ключ (`reserve`). Уже занят:
+    ///    - другим запросом (`fingerprint` не совпал) — `IdempotencyKeyReused`
+    ///    - тем же, который ещё выполняется — `IdempotencyKeyInProgress`
+    ///    - тем же, который завершился — возвращаем сохранённый ответ
+    /// 3. Выполняем операцию; успех сохраняем, при ошибке ключ освобождаем,
+    ///    чтобы клиент мог повторить запрос с тем же ключом
+    ///
+    /// # Generic `F` и `Fut`
+    /// `F` — замыкание, которое создаёт future операции. Future создаётся
+    /// только после успешного `reserve`, поэтому при повторе операция
+    /// даже не начинается.
+    async fn idempotent<F, Fut>(
+        &self,
+        key: Option<String>,
+        fingerprint: String,
+        operation: F,
+    ) -> Result<AccountResponse, AccountServiceError<R::Error>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<AccountResponse, AccountServiceError<R::Error>>>,
+    {
+        let Some(key) = key else {
+            return operation().await;
+        };
+
+        let now = self.clock.now();
+        // У каждого пользователя свои ключи: одинаковый ключ двух
+        // пользователей — два разных запроса, а не повтор
+        let stored_key = format!("{}:{}", self.user_id, key);
+        let record = IdempotencyRecord {
+            key: stored_key.clone(),
+            fingerprint,
+            response: None,
+            created_at: now,
+        };
+
+        let existing = self
+            .idempotency
+            .reserve(&record, now - IDEMPOTENCY_KEY_TTL)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        if let Some(existing) = existing {
+            if existing.fingerprint != record.fingerprint {
+                return Err(AccountServiceError::IdempotencyKeyReused(key));
+            }
+            return existing
+                .response
+                .ok_or(AccountServiceError::IdempotencyKeyInProgress(key));
+        }
+
+        match operation().await {
+            Ok(response) => {
+                self.idempotency
+                    .complete(&stored_key, &response)
+                    .await
+                    .map_err(AccountServiceError::Repository)?;
+                Ok(response)
+            }
+            Err(err) => {
+                self.idempotency
+                    .release(&stored_key)
+                    .await
+                    .map_err(AccountServiceError::Repository)?;
+                Err(err)
+            }
+        }
+    }
+
+    /// Занято ли имя активным счётом (без учёта регистра).
+    async fn name_taken(&self, name: &str) -> Result<bool, AccountServiceError<R::Error>> {
+        Ok(self
+            .repository
+            .find_by_name(&self.user_id, name)
+            .await
+            .map_err(AccountServiceError::Repository)? // Конвертируем ошибку репозитория
+            .is_some())
+    }
+
+    /// Проверяет, что ещё `adding` счетов поместятся в `max_accounts`.
+    ///
+    /// Считаются все активные счета, включая архивные: архив только
+    /// прячет счёт из списка. Удалённые не считаются.
+    ///
+    /// # Почему лимит мягкий?
+    /// Между `COUNT(*)` и записью параллельный запрос может создать свой
+    /// счёт, и лимит превысится на пару счетов. Защите от массового
+    /// создания это не мешает, а строгий лимит потребовал бы блокировки
+    /// всей таблицы на каждое создание.
+    async fn check_quota(&self, adding: u64) -> Result<(), AccountServiceError<R::Error>> {
+        let Some(limit) = self.max_accounts else {
+            return Ok(());
+        };
+
+        let filter = AccountFilter {
+            include_archived: true,
+            ..AccountFilter::default()
+        };
+        let existing = self
+            .repository
+            .count(&self.user_id, &filter)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        if existing.max(0) as u64 + adding > limit {
+            return Err(DomainError::QuotaExceeded { limit }.into());
+        }
+        Ok(())
+    }
+
+    /// Собирает новый счёт из запроса (без записи в хранилище).
+    ///
+    /// Валюта не указана — берётся `default_currency`.
+    /// Сначала проверяются все поля, и ошибки собираются вместе:
+    /// пустое имя и неверная валюта придут в одном ответе.
+    ///
+    /// # Errors
+    /// `InvalidFields` — поле → причина: некорректное имя, код валюты,
+    /// кредитный лимит, несовместимый с типом счёта, или слишком длинное описание.
+    fn build_account(
+        &self,
+        request: CreateAccountRequest,
+        now: DateTime<Utc>,
+    ) -> Result<Account, DomainError> {
+        let credit_limit = request.credit_limit.map(|limit| limit.cents());
+        // Нет валюты в запросе — DEFAULT_CURRENCY (см. `with_default_currency`)
+        let currency = request
+            .currency
+            .unwrap_or_else(|| self.default_currency.to_string());
+
+        let mut fields = BTreeMap::new();
+        if let Err(err) = Account::normalize_name(&request.name) {
+            fields.insert("name".to_string(), err.reason());
+        }
+        if let Err(err) = Currency::parse(&currency) {
+            fields.insert("currency".to_string(), err.reason());
+        }
+        if let Err(err) = Account::check_credit_limit(request.account_type, credit_limit) {
+            fields.insert("credit_limit".to_string(), err.reason());
+        }
+        if let Some(Err(err)) = request
+            .description
+            .as_deref()
+            .map(Account::normalize_description)
+        {
+            fields.insert("description".to_string(), err.reason());
+        }
+        if !fields.is_empty() {
+            return Err(DomainError::InvalidFields(fields));
+        }
+
+        let mut account = Account::new(request.name, currency, now)?;
+        account.user_id = self.user_id.clone();
+        account.set_account_type(request.account_type, credit_limit, now)?;
+        account.set_description(request.description.as_deref(), now)?;
+        Ok(account)
+    }
+
+    /// Находит операцию счёта `account_id` или возвращает `TransactionNotFound`.
+    ///
+    /// Операция другого счёта — тоже `TransactionNotFound`: по чужому
+    /// счёту в пути нельзя ни найти, ни изменить операцию. Счёт другого
+    /// пользователя — `AccountNotFound`: журнал не разделён по
+    /// пользователям, поэтому сначала проверяется сам счёт.
+    async fn find_transaction(
+        &self,
+        account_id: Uuid,
+        transaction_id: Uuid,
+    ) -> Result<Transaction, AccountServiceError<R::Error>> {
+        self.find_account(account_id).await?;
+
+        self.transactions
+            .find_by_id(transaction_id)
+            .await
+            .map_err(AccountServiceError::Repository)?
+            .filter(|transaction| transaction.account_id == account_id)
+            .ok_or_else(|| DomainError::TransactionNotFound(transaction_id.to_string()).into())
+    }
+
+    /// Сохраняет комментарий, категорию и отметку об отмене операции.
+    async fn store_transaction_details(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<(), AccountServiceError<R::Error>> {
+        let updated = self
+            .transactions
+            .update_details(transaction)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+        if !updated {
+            return Err(DomainError::TransactionNotFound(transaction.id.to_string()).into());
+        }
+        Ok(())
+    }
+
+    /// Активный счёт с именем `name` (без учёта регистра), если он есть.
+    async fn find_by_name(
+        &self,
+        name: &str,
+    ) -> Result<Option<Account>, AccountServiceError<R::Error>> {
+        self.repository
+            .find_by_name(&self.user_id, name)
+            .await
+            .map_err(AccountServiceError::Repository)
+    }
+
+    /// Находит активный счёт или возвращает `AccountNotFound`.
+    async fn find_account(&self, id: Uuid) -> Result<Account, AccountServiceError<R::Error>> {
+        self.repository
+            .find_by_id(&self.user_id, id)
+            .await
+            .map_err(AccountServiceError::Repository)?
+            // .ok_or_else() — конвертирует None в Err
+            .ok_or_else(|| {
+                AccountServiceError::Domain(DomainError::AccountNotFound(id.to_string()))
+            })
+    }
+
+    /// Сколько снято со счёта с начала текущих суток (UTC), в копейках.
+    ///
+    /// Исходящие переводы тоже считаются: иначе лимит снятия
+    /// обходился бы переводом на другой счёт.
+    async fn withdrawn_today(
+        &self,
+        account_id: Uuid,
+    ) -> Result<i64, AccountServiceError<R::Error>> {
+        // Полночь сегодняшнего дня по UTC
+        let start_of_day = self
+            .clock
+            .now()
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is a valid time")
+            .and_utc();
+
+        let withdrawn = self
+            .transactions
+            .sum_since(account_id, TransactionKind::Withdraw, start_of_day)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+        let transferred = self
+            .transactions
+            .sum_since(account_id, TransactionKind::TransferOut, start_of_day)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+        // Каждое пополнение, отменённое сегодня, дало сегодня же обратное
+        // снятие на ту же сумму — оно не тратит лимит (`reverse_deposit`)
+        let reversed_deposits = self
+            .transactions
+            .sum_reversed_since(account_id, TransactionKind::Deposit, start_of_day)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        Ok(withdrawn + transferred - reversed_deposits)
+    }
+
+    /// Конвертирует счёт в DTO с актуальным остатком дневного лимита.
+    ///
+    /// # Почему не просто `account.into()`?
+    /// Остаток лимита зависит от сегодняшних снятий, а их знает
+    /// только журнал операций. Журнал читаем, только если лимит задан.
+    async fn to_response(
+        &self,
+        account: Account,
+    ) -> Result<AccountResponse, AccountServiceError<R::Error>> {
+        let remaining = match account.withdrawal_limit {
+            Some(_) => {
+                let withdrawn_today = self.withdrawn_today(account.id).await?;
+                account
+                    .remaining_withdrawal_limit(withdrawn_today)
+                    .map(Money::from_cents)
+            }
+            None => None,
+        };
+
+        let mut response = AccountResponse::from(account);
+        response.remaining_withdrawal_limit = remaining;
+        Ok(response)
+    }
+
+    /// Ответ пробного запуска: счёт после операции, которая не сохранена.
+    ///
+    /// `withdrawn_now` — сколько сняла бы эта операция. В журнале её нет,
+    /// поэтому к сегодняшним снятиям для остатка лимита она прибавляется здесь.
+    async fn preview_response(
+        &self,
+        account: Account,
+        withdrawn_now: i64,
+    ) -> Result<AccountResponse, AccountServiceError<R::Error>> {
+        let remaining = match account.withdrawal_limit {
+            Some(_) => {
+                let withdrawn_today = self.withdrawn_today(account.id).await? + withdrawn_now;
+                account
+                    .remaining_withdrawal_limit(withdrawn_today)
+                    .map(Money::from_cents)
+            }
+            None => None,
+        };
+
+        let mut response = AccountResponse::from(account);
+        response.remaining_withdrawal_limit = remaining;
+        response.dry_run = true;
+        Ok(response)
+    }
+
+    /// Сохраняет изменённый счёт с проверкой версии.
+    ///
+    /// # Optimistic locking
+    /// Если между чтением и записью счёт успел сохранить кто-то другой,
+    /// репозиторий вернёт `false` — отдаём `ConcurrentModification` (HTTP 409),
+    /// вместо того чтобы молча затереть чужое изменение.
+    ///
+    /// После успешной записи увеличиваем версию в памяти,
+    /// чтобы она совпадала с сохранённой.
+    ///
+    /// `ledger` — операции, которые изменили баланс: они пишутся в той же
+    /// транзакции, что и счёт (см. `AccountRepository`). Подписчикам
+    /// о них сообщает `publish` — уже после записи.
+    async fn save(
+        &self,
+        account: &mut Account,
+        ledger: &[Transaction],
+    ) -> Result<(), AccountServiceError<R::Error>> {
+        self.save_audited(account, &[], ledger).await
+    }
+
+    /// `save` с записями журнала изменений — они сохраняются
+    /// в той же транзакции, что и счёт с операциями.
+    ///
+    /// Для use cases, меняющих сам счёт, а не (только) баланс.
+    async fn save_audited(
+        &self,
+        account: &mut Account,
+        audit: &[AuditEntry],
+        ledger: &[Transaction],
+    ) -> Result<(), AccountServiceError<R::Error>> {
+        let updated = self
+            .repository
+            .update(account, audit, ledger)
+            .await
+            .map_err(|err| Self::write_error(err, &account.name))?;
+
+        if !updated {
+            return Err(AccountServiceError::Domain(
+                DomainError::ConcurrentModification(account.id.to_string()),
+            ));
+        }
+
+        account.version += 1;
+        Ok(())
+    }
+
+    /// Ошибка записи счёта с именем `name`.
+    ///
+    /// Имя проверяется до записи (`name_taken`), но между проверкой
+    /// и записью его может занять параллельный запрос. Тогда запись
+    /// отклоняет уникальный индекс, и клиент получает тот же
+    /// `AccountAlreadyExists` (409), что и при обычной проверке.
+    fn write_error(err: R::Error, name: &str) -> AccountServiceError<R::Error> {
+        if err.is_unique_violation() {
+            DomainError::AccountAlreadyExists(name.to_string()).into()
+        } else {
+            AccountServiceError::Repository(err)
+        }
+    }
+
+    /// Записывает снимок баланса после изменения счёта.
+    ///
+    /// # Дедупликация
+    /// Если баланс не изменился с последнего снимка — ничего не пишем.
+    /// Иначе периоды без движения раздували бы таблицу одинаковыми строками,
+    /// а на графике ничего бы не добавили.
+    async fn record_snapshot(
+        &self,
+        account: &Account,
+    ) -> Result<(), AccountServiceError<R::Error>> {
+        let latest = self
+            .snapshots
+            .find_latest(account.id)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        if latest.is_some_and(|snapshot| snapshot.balance == account.balance) {
+            return Ok(());
+        }
+
+        self.snapshots
+            .create(&BalanceSnapshot::new(
+                account.id,
+                account.balance,
+                self.clock.now(),
+            ))
+            .await
+            .map_err(AccountServiceError::Repository)
+    }
+
+    /// Сообщает подписчикам об операции, уже сохранённой вместе
+    /// со счётом (`save`, `update_pair`).
+    ///
+    /// `balance_after` — баланс счёта сразу после этой операции
+    /// (у снятия с защитой от овердрафта он у перевода и у самого
+    /// снятия разный).
+    ///
+    /// # Почему событие после записи?
+    /// Подписчик может сразу запросить историю операций — запись
+    /// уже должна там быть.
+    fn publish(&self, transaction: &Transaction, balance_after: i64) {
+        self.events.publish(BalanceChanged {
+            user_id: self.user_id.clone(),
+            account_id: transaction.account_id,
+            kind: transaction.kind,
+            amount: transaction.amount,
+            new_balance: balance_after,
+            timestamp: transaction.timestamp,
+        });
+    }
+}
+
+/// Добавляет в ответ предупреждение о низком балансе, если `crossed`.
+///
+/// Пишем и в лог: предупреждение в ответе видит только тот, кто снимал,
+/// а повторяющееся снятие (`RecurringService`) ответ никому не показывает.
+/// Пробный запуск ничего не изменил — его в лог не пишем.
+fn warn_low_balance(mut response: AccountResponse, crossed: bool) -> AccountResponse {
+    if !crossed {
+        return response;
+    }
+    if !response.dry_run {
+        tracing::warn!(
+            "Account {} balance {} dropped below its low balance threshold",
+            response.id,
+            response.balance
+        );
+    }
+    response.warnings.push(LOW_BALANCE_WARNING.to_string());
+    response
+}
+
+/// Добавляет в ответ предупреждение, если оно есть (см. `fit_amount`).
+fn with_warning(mut response: AccountResponse, warning: Option<String>) -> AccountResponse {
+    response.warnings.extend(warning);
+    response
+}
+
+/// Нормализует необязательную категорию (см. `Transaction::normalize_category`).
+fn normalize_category(category: Option<&str>) -> Result<Option<String>, DomainError> {
+    category.map(Transaction::normalize_category).transpose()
+}
+
+/// Как имя счёта совпало с `query` (уже в нижнем регистре).
+///
+/// `None` — не совпало: хранилище сравнивает по-своему (`LOWER` в SQL),
+/// и на редких символах результат может разойтись с `to_lowercase`.
+fn name_match(name: &str, query: &str) -> Option<NameMatch> {
+    let name = name.to_lowercase();
+    if name == query {
+        Some(NameMatch::Exact)
+    } else if name.starts_with(query) {
+        Some(NameMatch::Prefix)
+    } else if name.contains(query) {
+        Some(NameMatch::Substring)
+    } else {
+        None
+    }
+}
+
+/// Ошибки сервиса — объединяют доменные ошибки и ошибки репозитория.
+///
+/// # Generic параметр `E`
+/// Тип ошибки репозитория (sqlx::Error для PostgreSQL).
+///
+/// # Варианты
+/// - `Domain` — ошибки бизнес-логики (можно показать пользователю)
+/// - `IdempotencyKey*` — ошибки протокола повторов, а не бизнес-правил,
+///   поэтому они здесь, а не в `DomainError`
+/// - `Repository` — технические ошибки (логируем, но не показываем детали)
+#[derive(Debug, thiserror::Error)]
 pub enum AccountServiceError<E: std::error::Error> {
     #[error("Domain error: {0}")]
     Domain(#[from] DomainError), // #[from] — автоматическая конвертация через .into()
 
+    #[error("Idempotency key '{0}' was already used for a different request")]
+    IdempotencyKeyReused(String),
+
+    #[error("Request with idempotency key '{0}' is still being processed")]
+    IdempotencyKeyInProgress(String),
+
     #[error("Repository error: {0}")]
     Repository(E),
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use serde_json::json;
+
+    use super::*;
+    use crate::application::dto::{BalanceAtQuery, StatementQuery};
+    use crate::application::services::test_support::{request, Harness};
+
+    fn withdraw(amount: &str) -> WithdrawRequest {
+        request(json!({ "amount": amount }))
+    }
+
+    /// Операции счёта `(вид, сумма)`, по виду и сумме: часы в тестах
+    /// стоят, и порядок операций с одним временем в журнале не определён.
+    async fn ledger(h: &Harness, id: Uuid) -> Vec<(TransactionKind, i64)> {
+        let mut rows: Vec<_> = h
+            .service
+            .get_transactions(id, TransactionFilter::default(), &Pagination::default())
+            .await
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|t| (t.kind, t.amount.cents()))
+            .collect();
+        rows.sort_by_key(|(kind, amount)| (kind.as_str(), *amount));
+        rows
+    }
+
+    /// Операция счёта с видом `kind` и суммой `cents`.
+    async fn find_operation(
+        h: &Harness,
+        id: Uuid,
+        kind: TransactionKind,
+        cents: i64,
+    ) -> TransactionResponse {
+        h.service
+            .get_transactions(id, TransactionFilter::default(), &Pagination::default())
+            .await
+            .unwrap()
+            .items
+            .into_iter()
+            .find(|t| t.kind == kind && t.amount.cents() == cents)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn create_rejects_name_taken_in_another_case() {
+        let h = Harness::new();
+        h.open("Savings", "USD", "0").await;
+
+        let err = h
+            .service
+            .create_account(request(json!({ "name": "  SAVINGS " })))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            AccountServiceError::Domain(DomainError::AccountAlreadyExists(name)) if name == "SAVINGS"
+        ));
+    }
+
+    #[tokio::test]
+    async fn deposit_and_withdraw_write_ledger_history_and_events() {
+        let h = Harness::new();
+        let account = h.open("Wallet", "USD", "100.00").await;
+
+        // Разное время — иначе порядок в журнале решал бы случайный ID
+        h.clock.advance(Duration::minutes(1));
+        let account = h
+            .service
+            .withdraw(account.id, withdraw("30.50"), None, false)
+            .await
+            .unwrap();
+        assert_eq!(account.balance.cents(), 6950);
+
+        let ledger = h
+            .service
+            .get_transactions(
+                account.id,
+                TransactionFilter::default(),
+                &Pagination::default(),
+            )
+            .await
+            .unwrap();
+        let kinds: Vec<_> = ledger
+            .items
+            .iter()
+            .map(|t| (t.kind, t.amount.cents()))
+            .collect();
+        // Новые — первыми
+        assert_eq!(
+            kinds,
+            [
+                (TransactionKind::Withdraw, 3050),
+                (TransactionKind::Deposit, 10000)
+            ]
+        );
+
+        let history = h
+            .service
+            .get_balance_history(account.id, &TimeRange::default())
+            .await
+            .unwrap();
+        let balances: Vec<_> = history.iter().map(|s| s.balance.cents()).collect();
+        assert_eq!(balances, [0, 10000, 6950]);
+
+        let events: Vec<_> = h.events.events().iter().map(|e| e.new_balance).collect();
+        assert_eq!(events, [10000, 6950]);
+    }
+
+    #[tokio::test]
+    async fn failed_withdraw_changes_nothing() {
+        let h = Harness::new();
+        let account = h.open("Wallet", "USD", "10.00").await;
+
+        let err = h
+            .service
+            .withdraw(account.id, withdraw("10.01"), None, false)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AccountServiceError::Domain(DomainError::InsufficientFunds { .. })
+        ));
+
+        let account = h.service.get_account(account.id).await.unwrap();
+        assert_eq!(account.balance.cents(), 1000);
+        assert_eq!(h.events.events().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn another_users_account_and_transactions_are_not_found() {
+        let h = Harness::new();
+        let account = h.open("Wallet", "USD", "10.00").await;
+        let history = h
+            .service
+            .get_transactions(
+                account.id,
+                TransactionFilter::default(),
+                &Pagination::default(),
+            )
+            .await
+            .unwrap();
+        let transaction_id = history.items[0].id;
+
+        let bob = h.service.for_user("bob");
+        let not_found = |err| {
+            matches!(
+                err,
+                AccountServiceError::Domain(DomainError::AccountNotFound(_))
+            )
+        };
+
+        assert!(not_found(bob.get_account(account.id).await.unwrap_err()));
+        assert!(not_found(
+            bob.get_transactions(
+                account.id,
+                TransactionFilter::default(),
+                &Pagination::default()
+            )
+            .await
+            .unwrap_err()
+        ));
+        assert!(not_found(
+            bob.reverse_transaction(account.id, transaction_id)
+                .await
+                .unwrap_err()
+        ));
+        assert!(not_found(
+            bob.deposit(
+                account.id,
+                request(json!({ "amount": "1.00" })),
+                None,
+                false
+            )
+            .await
+            .unwrap_err()
+        ));
+        let accounts = bob
+            .get_accounts(&AccountFilter::default(), &Pagination::default())
+            .await
+            .unwrap();
+        assert_eq!(accounts.total, 0);
+
+        // Счёт владельца не изменился
+        let account = h.service.get_account(account.id).await.unwrap();
+        assert_eq!(account.balance.cents(), 1000);
+    }
+
+    #[tokio::test]
+    async fn currency_filter_pages_accounts_in_that_currency() {
+        let h = Harness::new();
+        for (name, currency) in [
+            ("Beta", "EUR"),
+            ("alpha", "EUR"),
+            ("Gamma", "EUR"),
+            ("Cash", "USD"),
+        ] {
+            h.open(name, currency, "0").await;
+        }
+        // Код валюты в старой строке — как пришёл от клиента
+        let mut legacy =
+            Account::new("Delta".to_string(), "EUR".to_string(), h.clock.now()).unwrap();
+        legacy.currency = " eur".to_string();
+        h.accounts.create(&legacy, &[]).await.unwrap();
+
+        let names = |page: PagedResponse<AccountResponse>| -> (Vec<String>, i64) {
+            (page.items.into_iter().map(|a| a.name).collect(), page.total)
+        };
+        let mut filter = AccountFilter {
+            currency: Some("EUR".to_string()),
+            sort: "name".parse().unwrap(),
+            ..AccountFilter::default()
+        };
+        let pagination = Pagination {
+            limit: Some(2),
+            offset: Some(1),
+        };
+
+        let page = h.service.get_accounts(&filter, &pagination).await.unwrap();
+        assert_eq!(
+            names(page),
+            (vec!["Beta".to_string(), "Delta".to_string()], 4)
+        );
+
+        filter.name_contains = Some("MM".to_string());
+        let page = h
+            .service
+            .get_accounts(&filter, &Pagination::default())
+            .await
+            .unwrap();
+        assert_eq!(names(page), (vec!["Gamma".to_string()], 1));
+    }
+
+    #[tokio::test]
+    async fn balance_at_counts_the_last_second_but_not_the_next_midnight() {
+        let h = Harness::new();
+        let account = h.open("Wallet", "USD", "0").await;
+
+        // Часы стоят на 2026-03-10 12:00: до 23:59:59 и до полуночи 11-го
+        h.clock.advance(Duration::hours(12) - Duration::seconds(1));
+        h.deposit(account.id, "1.00").await;
+        h.clock.advance(Duration::seconds(1));
+        h.deposit(account.id, "2.00").await;
+
+        let query = BalanceAtQuery {
+            date: NaiveDate::from_ymd_opt(2026, 3, 10).unwrap(),
+        };
+        let balance = h
+            .service
+            .balance_at(account.id, query.date, query.cutoff().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(balance.balance.cents(), 100);
+        assert_eq!(balance.transaction_count, 1);
+    }
+
+    #[tokio::test]
+    async fn statement_opens_at_the_previous_day_balance() {
+        let h = Harness::new();
+        let account = h.open("Wallet", "USD", "50.00").await;
+        h.service
+            .withdraw(account.id, withdraw("20.00"), None, false)
+            .await
+            .unwrap();
+        // С 10 марта на 4 апреля
+        h.clock.advance(Duration::days(25));
+        h.deposit(account.id, "5.00").await;
+
+        let period = StatementQuery {
+            year: 2026,
+            month: 4,
+        }
+        .period()
+        .unwrap();
+        let statement = h.service.get_statement(account.id, period).await.unwrap();
+        assert_eq!(statement.opening_balance.cents(), 3000);
+        assert_eq!(statement.closing_balance.cents(), 3500);
+
+        let march_31 = BalanceAtQuery {
+            date: NaiveDate::from_ymd_opt(2026, 3, 31).unwrap(),
+        };
+        let balance = h
+            .service
+            .balance_at(account.id, march_31.date, march_31.cutoff().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(balance.balance, statement.opening_balance);
+    }
+
+    #[tokio::test]
+    async fn unique_violation_on_write_is_a_taken_name() {
+        use crate::application::services::test_support::RecordingPublisher;
+        use crate::infrastructure::database::test_support::unique_violation;
+        use crate::infrastructure::database::{
+            AnyAccountRepository, AnyIdempotencyRepository, AnySnapshotRepository,
+            AnyTransactionRepository,
+        };
+        use crate::infrastructure::exchange_rates::StaticExchangeRateProvider;
+
+        // Сервис с ошибками sqlx — как в работе; запись не выполняется
+        type SqlxService = AccountService<
+            AnyAccountRepository,
+            AnyTransactionRepository,
+            AnySnapshotRepository,
+            AnyIdempotencyRepository,
+            StaticExchangeRateProvider,
+            RecordingPublisher,
+        >;
+
+        let err = SqlxService::write_error(unique_violation().await, "Wallet");
+        assert!(matches!(
+            err,
+            AccountServiceError::Domain(DomainError::AccountAlreadyExists(name)) if name == "Wallet"
+        ));
+
+        let err = SqlxService::write_error(sqlx::Error::PoolTimedOut, "Wallet");
+        assert!(matches!(err, AccountServiceError::Repository(_)));
+    }
+
+    #[tokio::test]
+    async fn stale_save_is_rejected_as_concurrent_modification() {
+        let h = Harness::new();
+        let account = h.open("Wallet", "USD", "10.00").await;
+
+        // Два запроса прочитали одну версию; первый успел сохранить
+        let mut stale = h.service.find_account(account.id).await.unwrap();
+        h.deposit(account.id, "5.00").await;
+
+        let now = h.clock.now();
+        stale.deposit(100, now).unwrap();
+        let tx = Transaction::new(stale.id, TransactionKind::Deposit, 100, None, now);
+        let err = h
+            .service
+            .save(&mut stale, std::slice::from_ref(&tx))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AccountServiceError::Domain(DomainError::ConcurrentModification(id))
+                if id == account.id.to_string()
+        ));
+
+        // Первое изменение не затёрто, операция второго не записана
+        let account = h.service.get_account(account.id).await.unwrap();
+        assert_eq!(account.balance.cents(), 1500);
+        assert_eq!(
+            ledger(&h, account.id).await,
+            [
+                (TransactionKind::Deposit, 500),
+                (TransactionKind::Deposit, 1000)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn negative_and_zero_amounts_are_rejected() {
+        let h = Harness::new();
+        let account = h.open("Wallet", "USD", "10.00").await;
+
+        for amount in ["-5.00", "0"] {
+            let err = h
+                .service
+                .deposit(
+                    account.id,
+                    request(json!({ "amount": amount })),
+                    None,
+                    false,
+                )
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(&err, AccountServiceError::Domain(DomainError::InvalidFields(fields))
+                    if fields.contains_key("amount")),
+                "{amount}: {err:?}"
+            );
+            let err = h
+                .service
+                .withdraw(account.id, withdraw(amount), None, false)
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    AccountServiceError::Domain(DomainError::InvalidAmount(_))
+                ),
+                "{amount}"
+            );
+        }
+
+        let account = h.service.get_account(account.id).await.unwrap();
+        assert_eq!(account.balance.cents(), 1000);
+        assert_eq!(h.events.events().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn withdraw_reports_form_errors_by_field() {
+        let h = Harness::new();
+        let account = h.open("Yen", "JPY", "1000").await;
+
+        let err = h
+            .service
+            .withdraw(account.id, withdraw("1.50"), None, false)
+            .await
+            .unwrap_err();
+        let AccountServiceError::Domain(DomainError::InvalidFields(fields)) = err else {
+            panic!("expected InvalidFields, got {err:?}");
+        };
+        assert_eq!(fields["amount"], "JPY accepts at most 0 decimal places");
+
+        let err = h
+            .service
+            .withdraw(
+                account.id,
+                request(json!({ "amount": "100", "category": " " })),
+                None,
+                false,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AccountServiceError::Domain(DomainError::InvalidFields(fields))
+                if fields.contains_key("category")
+        ));
+    }
+
+    #[tokio::test]
+    async fn overdraft_cover_is_saved_with_its_ledger_rows() {
+        let h = Harness::new();
+        let source = h.open("Savings", "USD", "100.00").await;
+        let account = h.open("Checking", "USD", "10.00").await;
+        h.service
+            .update_account(
+                account.id,
+                request(json!({ "overdraft_source": source.id })),
+            )
+            .await
+            .unwrap();
+
+        let account = h
+            .service
+            .withdraw(account.id, withdraw("30.00"), None, false)
+            .await
+            .unwrap();
+        assert_eq!(account.balance.cents(), 0);
+        let source = h.service.get_account(source.id).await.unwrap();
+        assert_eq!(source.balance.cents(), 8000);
+
+        assert_eq!(
+            ledger(&h, account.id).await,
+            [
+                (TransactionKind::Deposit, 1000),
+                (TransactionKind::TransferIn, 2000),
+                (TransactionKind::Withdraw, 3000),
+            ]
+        );
+        assert_eq!(
+            ledger(&h, source.id).await,
+            [
+                (TransactionKind::Deposit, 10000),
+                (TransactionKind::TransferOut, 2000),
+            ]
+        );
+
+        // Баланс после перевода — до снятия
+        let events: Vec<_> = h.events.events()[2..]
+            .iter()
+            .map(|e| (e.kind, e.new_balance))
+            .collect();
+        assert_eq!(
+            events,
+            [
+                (TransactionKind::TransferOut, 8000),
+                (TransactionKind::TransferIn, 3000),
+                (TransactionKind::Withdraw, 0),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn simulated_withdraw_models_overdraft_cover() {
+        let h = Harness::new();
+        let source = h.open("Savings", "USD", "100.00").await;
+        let account = h.open("Checking", "USD", "10.00").await;
+        h.service
+            .update_account(
+                account.id,
+                request(json!({ "overdraft_source": source.id })),
+            )
+            .await
+            .unwrap();
+
+        let simulation = h
+            .service
+            .simulate(
+                account.id,
+                request(json!({ "operation": "withdraw", "amount": "30.00" })),
+            )
+            .await
+            .unwrap();
+        assert!(simulation.success);
+        assert_eq!(simulation.balance_after.cents(), 0);
+
+        // Ни один из счетов не изменился
+        let source = h.service.get_account(source.id).await.unwrap();
+        assert_eq!(source.balance.cents(), 10000);
+        assert_eq!(ledger(&h, source.id).await.len(), 1);
+        assert_eq!(ledger(&h, account.id).await.len(), 1);
+
+        // Источнику тоже не хватает — симуляция не проходит
+        let simulation = h
+            .service
+            .simulate(
+                account.id,
+                request(json!({ "operation": "withdraw", "amount": "120.00" })),
+            )
+            .await
+            .unwrap();
+        assert!(!simulation.success);
+        assert_eq!(simulation.balance_after.cents(), 1000);
+    }
+
+    #[tokio::test]
+    async fn overdraft_source_cycles_are_rejected_and_not_followed() {
+        let h = Harness::new();
+        let a = h.open("A", "USD", "10.00").await;
+        let b = h.open("B", "USD", "100.00").await;
+
+        let err = h
+            .service
+            .update_account(a.id, request(json!({ "overdraft_source": a.id })))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AccountServiceError::Domain(DomainError::InvalidOverdraftSource(_))
+        ));
+
+        h.service
+            .update_account(a.id, request(json!({ "overdraft_source": b.id })))
+            .await
+            .unwrap();
+        let err = h
+            .service
+            .update_account(b.id, request(json!({ "overdraft_source": a.id })))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AccountServiceError::Domain(DomainError::InvalidOverdraftSource(_))
+        ));
+
+        // Ссылка на себя в обход проверок — как после импорта резервной копии
+        let mut stored = h
+            .accounts
+            .find_by_id(&h.service.user_id, a.id)
+            .await
+            .unwrap()
+            .unwrap();
+        stored.overdraft_source = Some(a.id);
+        assert!(h.accounts.update(&stored, &[], &[]).await.unwrap());
+
+        let err = h
+            .service
+            .withdraw(a.id, withdraw("30.00"), None, false)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AccountServiceError::Domain(DomainError::InsufficientFunds { .. })
+        ));
+        let simulation = h
+            .service
+            .simulate(
+                a.id,
+                request(json!({ "operation": "withdraw", "amount": "30.00" })),
+            )
+            .await
+            .unwrap();
+        assert!(!simulation.success);
+        assert_eq!(ledger(&h, a.id).await, [(TransactionKind::Deposit, 1000)]);
+    }
+
+    #[tokio::test]
+    async fn daily_withdrawal_limit_resets_at_midnight() {
+        let h = Harness::new();
+        let account = h.open("Wallet", "USD", "500.00").await;
+        h.service
+            .update_account(account.id, request(json!({ "withdrawal_limit": "100.00" })))
+            .await
+            .unwrap();
+
+        h.service
+            .withdraw(account.id, withdraw("80.00"), None, false)
+            .await
+            .unwrap();
+        let err = h
+            .service
+            .withdraw(account.id, withdraw("30.00"), None, false)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AccountServiceError::Domain(DomainError::LimitExceeded { .. })
+        ));
+
+        // 12:00 + 12 ч — полночь следующих суток
+        h.clock.advance(Duration::hours(12));
+        let account = h
+            .service
+            .withdraw(account.id, withdraw("30.00"), None, false)
+            .await
+            .unwrap();
+        assert_eq!(account.balance.cents(), 39000);
+        assert_eq!(
+            account.remaining_withdrawal_limit.map(|m| m.cents()),
+            Some(7000)
+        );
+    }
+
+    #[tokio::test]
+    async fn deposits_and_withdrawals_can_each_be_reversed_once() {
+        let h = Harness::new();
+        let account = h.open("Wallet", "USD", "0").await;
+        h.deposit(account.id, "100.00").await;
+        h.service
+            .withdraw(account.id, withdraw("30.00"), None, false)
+            .await
+            .unwrap();
+        let deposit = find_operation(&h, account.id, TransactionKind::Deposit, 10000).await;
+        let withdrawal = find_operation(&h, account.id, TransactionKind::Withdraw, 3000).await;
+
+        let reversed = h
+            .service
+            .reverse_transaction(account.id, withdrawal.id)
+            .await
+            .unwrap();
+        assert_eq!(reversed.balance.cents(), 10000);
+        let reversed = h
+            .service
+            .reverse_transaction(account.id, deposit.id)
+            .await
+            .unwrap();
+        assert_eq!(reversed.balance.cents(), 0);
+
+        // Отметка записана вместе с обратной операцией
+        for (kind, cents) in [
+            (TransactionKind::Deposit, 10000),
+            (TransactionKind::Withdraw, 3000),
+        ] {
+            let original = find_operation(&h, account.id, kind, cents).await;
+            assert!(original.reversed_at.is_some(), "{kind:?}");
+        }
+        assert_eq!(
+            ledger(&h, account.id).await,
+            [
+                (TransactionKind::Deposit, 3000),
+                (TransactionKind::Deposit, 10000),
+                (TransactionKind::Withdraw, 3000),
+                (TransactionKind::Withdraw, 10000),
+            ]
+        );
+
+        for id in [deposit.id, withdrawal.id] {
+            let err = h
+                .service
+                .reverse_transaction(account.id, id)
+                .await
+                .unwrap_err();
+            assert!(matches!(
+                err,
+                AccountServiceError::Domain(DomainError::TransactionNotReversible(_))
+            ));
+        }
+        let account = h.service.get_account(account.id).await.unwrap();
+        assert_eq!(account.balance.cents(), 0);
+        assert_eq!(ledger(&h, account.id).await.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn deposit_reversal_ignores_the_daily_withdrawal_limit() {
+        let h = Harness::new();
+        let account = h.open("Wallet", "USD", "300.00").await;
+        h.deposit(account.id, "150.00").await;
+        h.service
+            .update_account(account.id, request(json!({ "withdrawal_limit": "100.00" })))
+            .await
+            .unwrap();
+        h.service
+            .withdraw(account.id, withdraw("80.00"), None, false)
+            .await
+            .unwrap();
+
+        let deposit = find_operation(&h, account.id, TransactionKind::Deposit, 15000).await;
+        let account = h
+            .service
+            .reverse_transaction(account.id, deposit.id)
+            .await
+            .unwrap();
+        assert_eq!(account.balance.cents(), 22000);
+        // Отмена не снятие: остаток лимита тот же
+        assert_eq!(
+            account.remaining_withdrawal_limit.map(|m| m.cents()),
+            Some(2000)
+        );
+    }
+
+    /// Счёт, каким его сохранил репозиторий (в ответе нет `version`).
+    async fn stored(h: &Harness, id: Uuid) -> Account {
+        h.accounts
+            .find_by_id(Account::DEFAULT_USER, id)
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn update_account_tells_an_absent_field_from_null() {
+        let h = Harness::new();
+        let source = h.open("Savings", "USD", "0").await;
+        let account = h.open("Wallet", "USD", "0").await;
+
+        type Read = fn(&Account) -> Option<String>;
+        let fields: [(&str, serde_json::Value, Option<String>, Read); 5] = [
+            (
+                "withdrawal_limit",
+                json!("100.00"),
+                Some("10000".into()),
+                |a| a.withdrawal_limit.map(|c| c.to_string()),
+            ),
+            ("min_balance", json!("-5.00"), Some("-500".into()), |a| {
+                a.min_balance.map(|c| c.to_string())
+            }),
+            (
+                "low_balance_threshold",
+                json!("20.00"),
+                Some("2000".into()),
+                |a| a.low_balance_threshold.map(|c| c.to_string()),
+            ),
+            (
+                "overdraft_source",
+                json!(source.id),
+                Some(source.id.to_string()),
+                |a| a.overdraft_source.map(|id| id.to_string()),
+            ),
+            (
+                "description",
+                json!("Joint account"),
+                Some("Joint account".into()),
+                |a| a.description.clone(),
+            ),
+        ];
+
+        for (field, value, expected, read) in fields {
+            let set = stored(&h, account.id).await;
+            h.service
+                .update_account(account.id, request(json!({ field: value })))
+                .await
+                .unwrap();
+            let after_value = stored(&h, account.id).await;
+            assert_eq!(read(&after_value), expected, "{field}: value");
+            assert_eq!(after_value.version, set.version + 1, "{field}: value");
+
+            // Поля нет в запросе — значение остаётся
+            h.service
+                .update_account(account.id, request(json!({ "name": "Wallet" })))
+                .await
+                .unwrap();
+            let after_absent = stored(&h, account.id).await;
+            assert_eq!(read(&after_absent), expected, "{field}: absent");
+            assert_eq!(after_absent.version, after_value.version, "{field}: absent");
+
+            // null — значение снимается
+            h.service
+                .update_account(account.id, request(json!({ field: null })))
+                .await
+                .unwrap();
+            let after_null = stored(&h, account.id).await;
+            assert_eq!(read(&after_null), None, "{field}: null");
+            assert_eq!(after_null.version, after_value.version + 1, "{field}: null");
+        }
+    }
+
+    #[tokio::test]
+    async fn update_account_renames_only_when_a_name_is_given() {
+        let h = Harness::new();
+        let account = h.open("Wallet", "USD", "0").await;
+
+        let renamed = h
+            .service
+            .update_account(account.id, request(json!({ "name": " Budget " })))
+            .await
+            .unwrap();
+        assert_eq!(renamed.name, "Budget");
+
+        // У имени нет "снять": и null, и отсутствие поля его не трогают
+        for body in [json!({}), json!({ "name": null })] {
+            let account = h
+                .service
+                .update_account(account.id, request(body))
+                .await
+                .unwrap();
+            assert_eq!(account.name, "Budget");
+        }
+    }
+
+    #[tokio::test]
+    async fn update_account_without_changes_keeps_the_version() {
+        let h = Harness::new();
+        let account = h.open("Wallet", "USD", "0").await;
+        let body = json!({
+            "name": "Wallet",
+            "withdrawal_limit": "100.00",
+            "min_balance": "0.00",
+            "low_balance_threshold": null,
+            "overdraft_source": null,
+            "description": "Daily spending",
+        });
+        h.service
+            .update_account(account.id, request(body.clone()))
+            .await
+            .unwrap();
+        let before = stored(&h, account.id).await;
+
+        h.clock.advance(Duration::minutes(5));
+        for body in [json!({}), body] {
+            let response = h
+                .service
+                .update_account(account.id, request(body))
+                .await
+                .unwrap();
+            let after = stored(&h, account.id).await;
+            assert_eq!(after.version, before.version);
+            assert_eq!(after.updated_at, before.updated_at);
+            assert_eq!(response.updated_at, before.updated_at.to_rfc3339());
+        }
+    }
+
+    #[tokio::test]
+    async fn idempotency_key_replays_the_first_response() {
+        let h = Harness::new();
+        let account = h.open("Wallet", "USD", "0").await;
+        let key = Some("deposit-1".to_string());
+
+        h.service
+            .deposit(
+                account.id,
+                request(json!({ "amount": "25.00" })),
+                key.clone(),
+                false,
+            )
+            .await
+            .unwrap();
+        let replay = h
+            .service
+            .deposit(
+                account.id,
+                request(json!({ "amount": "25.00" })),
+                key.clone(),
+                false,
+            )
+            .await
+            .unwrap();
+        assert_eq!(replay.balance.cents(), 2500);
+        assert_eq!(h.events.events().len(), 1);
+
+        let err = h
+            .service
+            .deposit(
+                account.id,
+                request(json!({ "amount": "99.00" })),
+                key,
+                false,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AccountServiceError::IdempotencyKeyReused(_)));
+
+        let account = h.service.get_account(account.id).await.unwrap();
+        assert_eq!(account.balance.cents(), 2500);
+    }
+
+    #[tokio::test]
+    async fn transfer_moves_money_and_links_both_ledger_rows() {
+        let h = Harness::new();
+        let from = h.open("Checking", "USD", "100.00").await;
+        let to = h.open("Savings", "USD", "0").await;
+
+        let transfer = h
+            .service
+            .transfer(
+                from.id,
+                request(json!({ "to_account_id": to.id, "amount": "40.00" })),
+                false,
+            )
+            .await
+            .unwrap();
+        assert_eq!(transfer.from.balance.cents(), 6000);
+        assert_eq!(transfer.to.balance.cents(), 4000);
+
+        let incoming = h
+            .service
+            .get_transactions(to.id, TransactionFilter::default(), &Pagination::default())
+            .await
+            .unwrap();
+        assert_eq!(incoming.items.len(), 1);
+        assert_eq!(incoming.items[0].kind, TransactionKind::TransferIn);
+        assert_eq!(incoming.items[0].counterparty_id, Some(from.id));
+    }
+
+    #[tokio::test]
+    async fn cross_currency_transfer_rounds_converted_amount() {
+        let h = Harness::new();
+        let from = h.open("Checking", "USD", "100.00").await;
+        let to = h.open("Euro", "EUR", "0").await;
+
+        // 10.00 × 0.923 = 9.23 ровно; 10.01 × 0.923 = 9.23923 → 9.24
+        for (amount, converted) in [("10.00", 923), ("10.01", 924)] {
+            let transfer = h
+                .service
+                .transfer(
+                    from.id,
+                    request(json!({ "to_account_id": to.id, "amount": amount })),
+                    false,
+                )
+                .await
+                .unwrap();
+            assert_eq!(transfer.converted_amount.cents(), converted);
+            assert_eq!(transfer.exchange_rate, Some("0.923".parse().unwrap()));
+        }
+
+        assert_eq!(
+            ledger(&h, from.id).await,
+            [
+                (TransactionKind::Deposit, 10000),
+                (TransactionKind::TransferOut, 1000),
+                (TransactionKind::TransferOut, 1001),
+            ]
+        );
+        let incoming = h
+            .service
+            .get_transactions(to.id, TransactionFilter::default(), &Pagination::default())
+            .await
+            .unwrap();
+        let mut amounts: Vec<_> = incoming
+            .items
+            .iter()
+            .map(|t| (t.amount.cents(), t.counter_amount.map(|m| m.cents())))
+            .collect();
+        amounts.sort();
+        assert_eq!(amounts, [(923, Some(1000)), (924, Some(1001))]);
+
+        let to = h.service.get_account(to.id).await.unwrap();
+        assert_eq!(to.balance.cents(), 1847);
+    }
+
+    #[tokio::test]
+    async fn conversion_to_jpy_rounds_to_whole_yen() {
+        let h = Harness::with_rates(r#"{"USD/JPY": "150"}"#);
+        let from = h.open("Checking", "USD", "100.00").await;
+        let yen = h.open("Yen", "JPY", "0").await;
+
+        // 1.01 × 150 = 151.50 иены → 152 (HalfEven), копеек у иены нет
+        let transfer = h
+            .service
+            .transfer(
+                from.id,
+                request(json!({ "to_account_id": yen.id, "amount": "1.01" })),
+                false,
+            )
+            .await
+            .unwrap();
+        assert_eq!(transfer.converted_amount.cents(), 15200);
+
+        // Закрытие переводит остаток по тому же правилу
+        let closing = h.open("Travel", "USD", "1.01").await;
+        h.service
+            .close_account(closing.id, request(json!({ "to_account_id": yen.id })))
+            .await
+            .unwrap();
+        let yen = h.service.get_account(yen.id).await.unwrap();
+        assert_eq!(yen.balance.cents(), 30400);
+
+        // 304 иены × 5% × 30 / 365 = 1.249… иены → 1
+        let yen = h
+            .service
+            .apply_interest(
+                yen.id,
+                request(json!({ "annual_rate": "5", "days": 30 })),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(yen.balance.cents(), 30500);
+    }
+
+    #[tokio::test]
+    async fn currency_change_rounds_to_the_new_currency() {
+        let h = Harness::new();
+        // (валюта, курс, баланс, лимит снятия) после смены; до — 10.01 и 1.01 USD
+        for (currency, rate, balance, limit) in [
+            // 1504.503 → 1505 иен, 151.5 → 152 (HalfEven)
+            ("JPY", "150.3", 150500, 15200),
+            // 3.77377 и 0.38077 BHD: третий знак `Money` не хранит
+            ("BHD", "0.377", 377, 38),
+        ] {
+            let account = h.open(currency, "USD", "10.01").await;
+            h.service
+                .update_account(account.id, request(json!({ "withdrawal_limit": "1.01" })))
+                .await
+                .unwrap();
+
+            let changed = h
+                .service
+                .change_currency(
+                    account.id,
+                    request(json!({ "currency": currency, "rate": rate })),
+                )
+                .await
+                .unwrap();
+            assert_eq!(changed.currency, currency);
+            assert_eq!(changed.balance.cents(), balance, "{currency}");
+            assert_eq!(
+                changed.withdrawal_limit.map(|m| m.cents()),
+                Some(limit),
+                "{currency}"
+            );
+            // Журнал сходится с округлённым балансом
+            let report = h.service.reconcile(account.id, false).await.unwrap();
+            assert!(report.matches, "{currency}");
+        }
+    }
+
+    #[tokio::test]
+    async fn limits_finer_than_the_account_currency_are_rejected() {
+        let h = Harness::new();
+        let yen = h.open("Yen", "JPY", "0").await;
+
+        for field in ["withdrawal_limit", "min_balance", "low_balance_threshold"] {
+            let err = h
+                .service
+                .update_account(yen.id, request(json!({ field: "100.50" })))
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    AccountServiceError::Domain(DomainError::PrecisionExceeded {
+                        max_decimals: 0,
+                        ..
+                    })
+                ),
+                "{field}"
+            );
+            h.service
+                .update_account(yen.id, request(json!({ field: "100" })))
+                .await
+                .unwrap();
+        }
+        let stored = stored(&h, yen.id).await;
+        assert_eq!(stored.withdrawal_limit, Some(10000));
+        assert_eq!(stored.min_balance, Some(10000));
+        assert_eq!(stored.low_balance_threshold, Some(10000));
+    }
+
+    #[tokio::test]
+    async fn transfer_without_rate_changes_nothing() {
+        let h = Harness::new();
+        let from = h.open("Checking", "USD", "100.00").await;
+        let to = h.open("Yen", "JPY", "0").await;
+
+        let err = h
+            .service
+            .transfer(
+                from.id,
+                request(json!({ "to_account_id": to.id, "amount": "10.00" })),
+                false,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AccountServiceError::Domain(DomainError::RateUnavailable { .. })
+        ));
+        assert_eq!(
+            ledger(&h, from.id).await,
+            [(TransactionKind::Deposit, 10000)]
+        );
+        assert!(ledger(&h, to.id).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn close_sweeps_balance_with_its_ledger_rows() {
+        let h = Harness::new();
+        let account = h.open("Old", "USD", "25.00").await;
+        let target = h.open("Main", "USD", "0").await;
+
+        let closed = h
+            .service
+            .close_account(account.id, request(json!({ "to_account_id": target.id })))
+            .await
+            .unwrap();
+        assert_eq!(closed.balance.cents(), 0);
+        assert!(closed.archived);
+
+        assert_eq!(
+            ledger(&h, account.id).await,
+            [
+                (TransactionKind::Deposit, 2500),
+                (TransactionKind::TransferOut, 2500),
+            ]
+        );
+        assert_eq!(
+            ledger(&h, target.id).await,
+            [(TransactionKind::TransferIn, 2500)]
+        );
+
+        // Повтор — не ошибка и не второе списание
+        h.service
+            .close_account(account.id, request(json!({ "to_account_id": target.id })))
+            .await
+            .unwrap();
+        let target = h.service.get_account(target.id).await.unwrap();
+        assert_eq!(target.balance.cents(), 2500);
+    }
+
+    #[tokio::test]
+    async fn close_without_target_withdraws_the_rest() {
+        let h = Harness::new();
+        let account = h.open("Old", "USD", "25.00").await;
+
+        h.service
+            .close_account(account.id, request(json!({})))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            ledger(&h, account.id).await,
+            [
+                (TransactionKind::Deposit, 2500),
+                (TransactionKind::Withdraw, 2500),
+            ]
+        );
+        let reconciliation = h.service.reconcile(account.id, false).await.unwrap();
+        assert!(reconciliation.matches);
+    }
+
+    #[tokio::test]
+    async fn close_into_a_closed_account_is_rejected() {
+        let h = Harness::new();
+        let account = h.open("Old", "USD", "25.00").await;
+        let closed = h.open("Closed", "USD", "0").await;
+        h.service
+            .close_account(closed.id, request(json!({})))
+            .await
+            .unwrap();
+
+        let err = h
+            .service
+            .close_account(account.id, request(json!({ "to_account_id": closed.id })))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AccountServiceError::Domain(DomainError::AccountClosed(id)) if id == closed.id.to_string()
+        ));
+
+        // Ни один из счетов не изменился
+        let account = h.service.get_account(account.id).await.unwrap();
+        assert_eq!((account.balance.cents(), account.archived), (2500, false));
+        let closed = h.service.get_account(closed.id).await.unwrap();
+        assert_eq!(closed.balance.cents(), 0);
+    }
+
+    #[tokio::test]
+    async fn close_writes_off_a_balance_that_converts_to_zero() {
+        // 1 иена по курсу 0.004 — это 0.4 цента, после округления ноль
+        let h = Harness::with_rates(r#"{"JPY/USD": "0.004"}"#);
+        let account = h.open("Yen", "JPY", "1").await;
+        let target = h.open("Main", "USD", "0").await;
+
+        let closed = h
+            .service
+            .close_account(account.id, request(json!({ "to_account_id": target.id })))
+            .await
+            .unwrap();
+        assert_eq!(closed.balance.cents(), 0);
+        assert!(closed.archived);
+
+        assert_eq!(
+            ledger(&h, account.id).await,
+            [
+                (TransactionKind::Deposit, 100),
+                (TransactionKind::Withdraw, 100),
+            ]
+        );
+        assert!(ledger(&h, target.id).await.is_empty());
+        let target = h.service.get_account(target.id).await.unwrap();
+        assert_eq!(target.balance.cents(), 0);
+    }
+
+    #[tokio::test]
+    async fn low_balance_warns_only_when_crossing_the_threshold() {
+        let h = Harness::new();
+        let account = h.open("Wallet", "USD", "100.00").await;
+        h.service
+            .update_account(
+                account.id,
+                request(json!({ "low_balance_threshold": "50.00" })),
+            )
+            .await
+            .unwrap();
+        async fn warnings(h: &Harness, id: Uuid, amount: &str) -> Vec<String> {
+            h.service
+                .withdraw(id, withdraw(amount), None, false)
+                .await
+                .unwrap()
+                .warnings
+        }
+
+        // 100 → 40: пересекли порог
+        assert_eq!(
+            warnings(&h, account.id, "60.00").await,
+            [LOW_BALANCE_WARNING]
+        );
+        // 40 → 30: уже ниже порога, повторно не предупреждаем
+        assert!(warnings(&h, account.id, "10.00").await.is_empty());
+        // 30 → 60: пополнение выше порога само не предупреждает
+        assert!(h.deposit(account.id, "30.00").await.warnings.is_empty());
+        // 60 → 40: снова пересекли
+        assert_eq!(
+            warnings(&h, account.id, "20.00").await,
+            [LOW_BALANCE_WARNING]
+        );
+    }
+
+    #[tokio::test]
+    async fn reconcile_reports_and_fixes_a_corrupted_balance() {
+        let h = Harness::new();
+        let account = h.open("Wallet", "USD", "100.00").await;
+
+        // Баланс правят в обход API — как ручной правкой БД
+        let mut stored = h
+            .accounts
+            .find_by_id(&h.service.user_id, account.id)
+            .await
+            .unwrap()
+            .unwrap();
+        stored.balance = 15000;
+        assert!(h.accounts.update(&stored, &[], &[]).await.unwrap());
+
+        let report = h.service.reconcile(account.id, false).await.unwrap();
+        assert!(!report.matches);
+        assert!(!report.fixed);
+        assert_eq!(report.stored.cents(), 15000);
+        assert_eq!(report.computed.cents(), 10000);
+        assert_eq!(report.difference.cents(), 5000);
+        // Без fix ничего не меняется
+        let unchanged = h.service.get_account(account.id).await.unwrap();
+        assert_eq!(unchanged.balance.cents(), 15000);
+
+        let fixed = h.service.reconcile(account.id, true).await.unwrap();
+        assert!(fixed.fixed);
+        assert_eq!(fixed.difference.cents(), 5000);
+        let corrected = h.service.get_account(account.id).await.unwrap();
+        assert_eq!(corrected.balance.cents(), 10000);
+        assert_eq!(
+            ledger(&h, account.id).await,
+            [
+                (TransactionKind::Deposit, 10000),
+                (TransactionKind::Reconciliation, 5000),
+            ]
+        );
+        assert!(
+            h.service
+                .reconcile(account.id, false)
+                .await
+                .unwrap()
+                .matches
+        );
+    }
+
+    #[tokio::test]
+    async fn reconcile_counts_the_opening_balance_of_a_legacy_account() {
+        let h = Harness::new();
+        let account = h.open("Legacy", "USD", "0").await;
+
+        // Счёт из времени до журнала после миграции с начальным остатком
+        let mut stored = h
+            .accounts
+            .find_by_id(&h.service.user_id, account.id)
+            .await
+            .unwrap()
+            .unwrap();
+        stored.balance = 5000;
+        let opening = Transaction::new(
+            account.id,
+            TransactionKind::OpeningBalance,
+            5000,
+            None,
+            stored.created_at,
+        );
+        assert!(h.accounts.update(&stored, &[], &[opening]).await.unwrap());
+
+        let fixed = h.service.reconcile(account.id, true).await.unwrap();
+        assert!(fixed.matches);
+        assert!(!fixed.fixed);
+        let kept = h.service.get_account(account.id).await.unwrap();
+        assert_eq!(kept.balance.cents(), 5000);
+    }
+
+    #[tokio::test]
+    async fn delete_requires_force_for_non_empty_account() {
+        let h = Harness::new();
+        let account = h.open("Wallet", "USD", "1.00").await;
+
+        let err = h
+            .service
+            .delete_account(account.id, false)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AccountServiceError::Domain(DomainError::AccountNotEmpty { balance: 100 })
+        ));
+
+        h.service.delete_account(account.id, true).await.unwrap();
+        let err = h.service.get_account(account.id).await.unwrap_err();
+        assert!(matches!(
+            err,
+            AccountServiceError::Domain(DomainError::AccountNotFound(_))
+        ));
+
+        let restored = h.service.restore_account(account.id).await.unwrap();
+        assert_eq!(restored.balance.cents(), 100);
+        let actions: Vec<_> = h
+            .service
+            .account_audit(account.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.action)
+            .collect();
+        assert_eq!(
+            actions,
+            [
+                AuditAction::Created,
+                AuditAction::Deleted,
+                AuditAction::Restored
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_and_restore_are_stamped_by_the_clock() {
+        let h = Harness::new();
+        let account = h.open("Wallet", "USD", "0").await;
+
+        h.clock.advance(Duration::hours(1));
+        let deleted_at = h.clock.now();
+        h.service.delete_account(account.id, false).await.unwrap();
+        let deleted = h
+            .accounts
+            .find_deleted_by_id(Account::DEFAULT_USER, account.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(deleted.deleted_at, Some(deleted_at));
+
+        h.clock.advance(Duration::hours(1));
+        let restored_at = h.clock.now();
+        let restored = h.service.restore_account(account.id).await.unwrap();
+        assert_eq!(restored.updated_at, restored_at.to_rfc3339());
+        assert_eq!(stored(&h, account.id).await.updated_at, restored_at);
+
+        // Журнал — с тем же временем, что и сам счёт
+        let times: Vec<_> = h
+            .service
+            .account_audit(account.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .skip(1)
+            .map(|entry| entry.at)
+            .collect();
+        assert_eq!(times, [deleted_at.to_rfc3339(), restored_at.to_rfc3339()]);
+    }
+}
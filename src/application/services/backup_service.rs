@@ -0,0 +1,170 @@
+//! Сервис резервного копирования — экспорт и импорт всех данных.
+
+use std::collections::HashSet;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::application::dto::{BackupDocument, ImportSummary};
+use crate::application::ports::{BackupData, BackupRepository};
+
+/// Сервис экспорта/импорта.
+///
+/// # Почему не часть `AccountService`?
+/// Резервная копия работает со всеми данными сразу и не использует
+/// ни одного use case счёта. Отдельный сервис — отдельная зависимость
+/// (`BackupRepository`), которую не нужно тащить в `AccountService`.
+#[derive(Clone)]
+pub struct BackupService<B>
+where
+    B: BackupRepository,
+{
+    repository: B,
+}
+
+impl<B> BackupService<B>
+where
+    B: BackupRepository,
+{
+    /// Создаёт новый экземпляр сервиса.
+    pub fn new(repository: B) -> Self {
+        Self { repository }
+    }
+
+    /// Use case: Выгрузка всех данных в один документ.
+    pub async fn export(&self) -> Result<BackupDocument, BackupServiceError<B::Error>> {
+        let data = self
+            .repository
+            .export_all()
+            .await
+            .map_err(BackupServiceError::Repository)?;
+
+        Ok(BackupDocument {
+            schema_version: BackupDocument::SCHEMA_VERSION,
+            exported_at: Utc::now(),
+            accounts: data.accounts,
+            transactions: data.transactions,
+            balance_snapshots: data.snapshots,
+        })
+    }
+
+    /// Use case: Загрузка резервной копии в пустое хранилище.
+    ///
+    /// # Бизнес-правила
+    /// - `schema_version` должна совпадать с текущей
+    /// - ID счетов и операций уникальны в пределах документа
+    /// - Операции и снимки ссылаются только на счета из документа
+    /// - Имена активных счетов уникальны (без учёта регистра)
+    /// - Хранилище должно быть пустым, иначе ничего не записывается
+    ///
+    /// # Почему сначала валидация, потом запись?
+    /// Плохой документ отклоняется целиком ещё до обращения к БД.
+    /// Сама запись идёт одной транзакцией — даже сбой посередине
+    /// не оставит половину данных.
+    pub async fn import(
+        &self,
+        document: BackupDocument,
+    ) -> Result<ImportSummary, BackupServiceError<B::Error>> {
+        Self::validate(&document)?;
+
+        let summary = ImportSummary {
+            accounts: document.accounts.len(),
+            transactions: document.transactions.len(),
+            balance_snapshots: document.balance_snapshots.len(),
+        };
+
+        let data = BackupData {
+            accounts: document.accounts,
+            transactions: document.transactions,
+            snapshots: document.balance_snapshots,
+        };
+
+        let imported = self
+            .repository
+            .import_all(&data)
+            .await
+            .map_err(BackupServiceError::Repository)?;
+
+        if !imported {
+            return Err(BackupServiceError::NotEmpty);
+        }
+
+        Ok(summary)
+    }
+
+    /// Проверяет документ целиком, не обращаясь к хранилищу.
+    fn validate(document: &BackupDocument) -> Result<(), BackupServiceError<B::Error>> {
+        if document.schema_version != BackupDocument::SCHEMA_VERSION {
+            return Err(BackupServiceError::UnsupportedSchemaVersion {
+                found: document.schema_version,
+                expected: BackupDocument::SCHEMA_VERSION,
+            });
+        }
+
+        // HashSet::insert возвращает false, если значение уже было
+        let mut account_ids = HashSet::new();
+        let mut names = HashSet::new();
+        for account in &document.accounts {
+            if !account_ids.insert(account.id) {
+                return Err(duplicate("account", account.id));
+            }
+            if account.deleted_at.is_none() && !names.insert(account.name.to_lowercase()) {
+                return Err(BackupServiceError::InvalidDocument(format!(
+                    "Duplicate account name '{}'",
+                    account.name
+                )));
+            }
+        }
+
+        let mut transaction_ids = HashSet::new();
+        for transaction in &document.transactions {
+            if !transaction_ids.insert(transaction.id) {
+                return Err(duplicate("transaction", transaction.id));
+            }
+            if !account_ids.contains(&transaction.account_id) {
+                return Err(unknown_account("Transaction", transaction.account_id));
+            }
+        }
+
+        for snapshot in &document.balance_snapshots {
+            if !account_ids.contains(&snapshot.account_id) {
+                return Err(unknown_account("Balance snapshot", snapshot.account_id));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn duplicate<E: std::error::Error>(what: &str, id: Uuid) -> BackupServiceError<E> {
+    BackupServiceError::InvalidDocument(format!("Duplicate {} id {}", what, id))
+}
+
+fn unknown_account<E: std::error::Error>(what: &str, account_id: Uuid) -> BackupServiceError<E> {
+    BackupServiceError::InvalidDocument(format!(
+        "{} references unknown account {}",
+        what, account_id
+    ))
+}
+
+/// Ошибки резервного копирования.
+///
+/// # Варианты
+/// - `UnsupportedSchemaVersion` — документ другого формата
+/// - `InvalidDocument` — документ внутренне противоречив
+/// - `NotEmpty` — хранилище уже содержит данные
+/// - `Repository` — технические ошибки (логируем, но не показываем детали)
+#[derive(Debug, thiserror::Error)]
+pub enum BackupServiceError<E: std::error::Error> {
+    #[error("Unsupported schema version {found}, expected {expected}")]
+    UnsupportedSchemaVersion { found: u32, expected: u32 },
+
+    #[error("Invalid backup: {0}")]
+    InvalidDocument(String),
+
+    #[error("Import requires an empty database")]
+    NotEmpty,
+
+    #[error("Repository error: {0}")]
+    Repository(E),
+}
@@ -0,0 +1,179 @@
+//! Демо-данные для знакомства с API (feature `dev`).
+//!
+//! Несколько счетов в разных валютах с парой операций на каждом —
+//! чтобы сразу было что показать в списках, отчётах и выписках.
+
+use crate::application::dto::{
+    AccountResponse, CreateAccountRequest, DepositRequest, SeedResponse, WithdrawAmount,
+    WithdrawRequest,
+};
+use crate::application::ports::{
+    AccountRepository, EventPublisher, ExchangeRateProvider, IdempotencyRepository,
+    SnapshotRepository, TransactionRepository,
+};
+use crate::domain::entities::AccountType;
+use crate::domain::errors::DomainError;
+use crate::domain::value_objects::Money;
+
+use super::{AccountService, AccountServiceError};
+
+/// Счёт-маркер: он создаётся первым, и если он есть — демо-данные уже были.
+///
+/// # Почему маркер, а не проверка всех демо-счетов?
+/// Пользователь мог удалить или переименовать часть демо-счетов.
+/// Повторный seed не должен возвращать их или падать на занятом
+/// имени — он просто ничего не делает, пока есть `Demo`.
+const MARKER: &str = "Demo";
+
+/// Операция демо-счёта: пополнение или снятие, сумма, комментарий, категория.
+enum DemoOperation {
+    Deposit(&'static str, &'static str, &'static str),
+    Withdraw(&'static str, &'static str, &'static str),
+}
+
+/// Демо-счёт и его операции по порядку.
+struct DemoAccount {
+    name: &'static str,
+    currency: &'static str,
+    account_type: AccountType,
+    operations: &'static [DemoOperation],
+}
+
+/// Демо-данные. Маркер — первым (см. `MARKER`).
+const DEMO_ACCOUNTS: &[DemoAccount] = &[
+    DemoAccount {
+        name: MARKER,
+        currency: "USD",
+        account_type: AccountType::Checking,
+        operations: &[
+            DemoOperation::Deposit("2500.00", "Salary", "salary"),
+            DemoOperation::Withdraw("84.20", "Weekly shopping", "groceries"),
+            DemoOperation::Withdraw("45.00", "Internet", "utilities"),
+        ],
+    },
+    DemoAccount {
+        name: "Demo Savings",
+        currency: "EUR",
+        account_type: AccountType::Savings,
+        operations: &[
+            DemoOperation::Deposit("1000.00", "Opening balance", "savings"),
+            DemoOperation::Deposit("250.00", "Monthly saving", "savings"),
+        ],
+    },
+    DemoAccount {
+        name: "Demo Cash",
+        currency: "GBP",
+        account_type: AccountType::Cash,
+        operations: &[
+            DemoOperation::Deposit("200.00", "ATM", "cash"),
+            DemoOperation::Withdraw("12.50", "Lunch", "food"),
+        ],
+    },
+];
+
+/// Описание у каждого демо-счёта — чтобы их было видно среди настоящих.
+const DESCRIPTION: &str = "Demo data, safe to delete";
+
+impl<R, T, S, I, X, P> AccountService<R, T, S, I, X, P>
+where
+    R: AccountRepository,
+    T: TransactionRepository<Error = R::Error>,
+    S: SnapshotRepository<Error = R::Error>,
+    I: IdempotencyRepository<Error = R::Error>,
+    X: ExchangeRateProvider,
+    P: EventPublisher,
+{
+    /// Use case: Демо-счета с операциями.
+    ///
+    /// Всё создаётся обычными `create_account`, `deposit` и `withdraw`,
+    /// поэтому у демо-счетов настоящие журнал, история баланса и события.
+    ///
+    /// # Повторный запуск
+    /// Есть счёт `MARKER` — ничего не создаётся, `created: false`.
+    ///
+    /// # Errors
+    /// Те же, что у `create_account`: например, `QuotaExceeded`, если
+    /// демо-счета не помещаются в `MAX_ACCOUNTS`.
+    pub async fn seed_demo(&self) -> Result<SeedResponse, AccountServiceError<R::Error>> {
+        if self.find_demo_account(MARKER).await?.is_some() {
+            return Ok(SeedResponse {
+                created: false,
+                accounts: self.existing_demo_accounts().await?,
+            });
+        }
+
+        let mut accounts = Vec::with_capacity(DEMO_ACCOUNTS.len());
+        for demo in DEMO_ACCOUNTS {
+            let mut account = self
+                .create_account(CreateAccountRequest {
+                    name: demo.name.to_string(),
+                    currency: Some(demo.currency.to_string()),
+                    account_type: demo.account_type,
+                    credit_limit: None,
+                    description: Some(DESCRIPTION.to_string()),
+                })
+                .await?;
+
+            for operation in demo.operations {
+                account = self.apply_demo_operation(&account, operation).await?;
+            }
+            accounts.push(account);
+        }
+
+        Ok(SeedResponse {
+            created: true,
+            accounts,
+        })
+    }
+
+    /// Проводит одну операцию демо-счёта.
+    async fn apply_demo_operation(
+        &self,
+        account: &AccountResponse,
+        operation: &DemoOperation,
+    ) -> Result<AccountResponse, AccountServiceError<R::Error>> {
+        match *operation {
+            DemoOperation::Deposit(amount, note, category) => {
+                let request = DepositRequest {
+                    amount: Money::parse(amount)?,
+                    note: Some(note.to_string()),
+                    category: Some(category.to_string()),
+                };
+                self.deposit(account.id, request, None, false).await
+            }
+            DemoOperation::Withdraw(amount, note, category) => {
+                let request = WithdrawRequest {
+                    amount: WithdrawAmount::Exact(Money::parse(amount)?),
+                    note: Some(note.to_string()),
+                    category: Some(category.to_string()),
+                };
+                self.withdraw(account.id, request, None, false).await
+            }
+        }
+    }
+
+    /// Демо-счета, которые ещё есть (их могли удалить или переименовать).
+    async fn existing_demo_accounts(
+        &self,
+    ) -> Result<Vec<AccountResponse>, AccountServiceError<R::Error>> {
+        let mut accounts = Vec::new();
+        for demo in DEMO_ACCOUNTS {
+            if let Some(account) = self.find_demo_account(demo.name).await? {
+                accounts.push(account);
+            }
+        }
+        Ok(accounts)
+    }
+
+    /// Счёт по имени; `None` вместо `AccountNotFound`.
+    async fn find_demo_account(
+        &self,
+        name: &str,
+    ) -> Result<Option<AccountResponse>, AccountServiceError<R::Error>> {
+        match self.get_account_by_name(name).await {
+            Ok(account) => Ok(Some(account)),
+            Err(AccountServiceError::Domain(DomainError::AccountNotFound(_))) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
@@ -1,3 +1,15 @@
 mod account_service;
+mod backup_service;
+#[cfg(feature = "dev")]
+mod demo_seed;
+mod recurring_service;
+#[cfg(test)]
+mod test_support;
+mod wallet_service;
+mod webhook_service;
 
 pub use account_service::{AccountService, AccountServiceError};
+pub use backup_service::{BackupService, BackupServiceError};
+pub use recurring_service::RecurringService;
+pub use wallet_service::WalletService;
+pub use webhook_service::WebhookService;
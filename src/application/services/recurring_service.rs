@@ -0,0 +1,427 @@
+//! Сервис повторяющихся операций — CRUD правил и их исполнение.
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::application::dto::{
+    AccountResponse, ChangeCurrencyRequest, CreateRecurringRequest, DepositRequest,
+    RecurringResponse, UpdateRecurringRequest, WithdrawAmount, WithdrawRequest,
+};
+use crate::application::ports::{
+    AccountRepository, EventPublisher, ExchangeRateProvider, IdempotencyRepository,
+    RecurringRepository, SnapshotRepository, TransactionRepository,
+};
+use crate::domain::entities::{RecurringKind, RecurringTransaction};
+use crate::domain::errors::DomainError;
+use crate::domain::value_objects::{Currency, Money};
+
+use super::{AccountService, AccountServiceError};
+
+/// Сервис правил повторяющихся операций.
+///
+/// # Почему сервис держит `AccountService`, а не репозиторий счетов?
+/// Правило исполняется обычным `deposit`/`withdraw` — со всеми проверками
+/// (лимиты, минимальный остаток), журналом и историей баланса.
+/// Дублировать это здесь было бы ошибкой, поэтому сервис просто
+/// вызывает use cases `AccountService`.
+#[derive(Clone)]
+pub struct RecurringService<R, T, S, I, X, P, Q>
+where
+    R: AccountRepository,
+    T: TransactionRepository<Error = R::Error>,
+    S: SnapshotRepository<Error = R::Error>,
+    I: IdempotencyRepository<Error = R::Error>,
+    X: ExchangeRateProvider,
+    P: EventPublisher,
+    Q: RecurringRepository<Error = R::Error>,
+{
+    accounts: AccountService<R, T, S, I, X, P>,
+    rules: Q,
+}
+
+impl<R, T, S, I, X, P, Q> RecurringService<R, T, S, I, X, P, Q>
+where
+    R: AccountRepository,
+    T: TransactionRepository<Error = R::Error>,
+    S: SnapshotRepository<Error = R::Error>,
+    I: IdempotencyRepository<Error = R::Error>,
+    X: ExchangeRateProvider,
+    P: EventPublisher,
+    Q: RecurringRepository<Error = R::Error>,
+{
+    /// Создаёт новый экземпляр сервиса.
+    pub fn new(accounts: AccountService<R, T, S, I, X, P>, rules: Q) -> Self {
+        Self { accounts, rules }
+    }
+
+    /// Тот же сервис для счетов пользователя `user_id`
+    /// (см. `AccountService::for_user`).
+    ///
+    /// Правила отдельно не фильтруются: каждый use case сначала ищет
+    /// счёт, а чужой счёт для этого сервиса не существует.
+    pub fn for_user(&self, user_id: &str) -> Self
+    where
+        Self: Clone,
+        AccountService<R, T, S, I, X, P>: Clone,
+    {
+        let mut scoped = self.clone();
+        scoped.accounts = self.accounts.for_user(user_id);
+        scoped
+    }
+
+    /// Текущее время по часам `AccountService`.
+    pub fn now(&self) -> DateTime<Utc> {
+        self.accounts.now()
+    }
+
+    /// Use case: Правила счёта.
+    pub async fn list_rules(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<RecurringResponse>, AccountServiceError<R::Error>> {
+        // Несуществующий счёт — 404, а не пустой список
+        self.accounts.get_account(account_id).await?;
+
+        let rules = self
+            .rules
+            .find_by_account(account_id)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        Ok(rules.into_iter().map(RecurringResponse::from).collect())
+    }
+
+    /// Use case: Создание правила.
+    pub async fn create_rule(
+        &self,
+        account_id: Uuid,
+        request: CreateRecurringRequest,
+    ) -> Result<RecurringResponse, AccountServiceError<R::Error>> {
+        let account = self.accounts.get_account(account_id).await?;
+        // Иначе ошибка всплыла бы только при исполнении, в планировщике.
+        // Округлённая сумма сохраняется в правиле — дальше она точная
+        let (amount, warning) = self
+            .accounts
+            .fit_amount(request.amount, &Currency::new(&account.currency))?;
+
+        let now = self.now();
+        let first_run = request
+            .starts_at
+            .unwrap_or_else(|| request.schedule.next_after(now));
+
+        let rule = RecurringTransaction::new(
+            account_id,
+            request.kind,
+            amount.cents(),
+            request.schedule,
+            first_run,
+            request.note,
+            now,
+        )?;
+
+        self.rules
+            .create(&rule)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        let mut response = RecurringResponse::from(rule);
+        response.warnings.extend(warning);
+        Ok(response)
+    }
+
+    /// Use case: Получение правила.
+    pub async fn get_rule(
+        &self,
+        account_id: Uuid,
+        rule_id: Uuid,
+    ) -> Result<RecurringResponse, AccountServiceError<R::Error>> {
+        Ok(self.find_rule(account_id, rule_id).await?.into())
+    }
+
+    /// Use case: Изменение правила.
+    pub async fn update_rule(
+        &self,
+        account_id: Uuid,
+        rule_id: Uuid,
+        request: UpdateRecurringRequest,
+    ) -> Result<RecurringResponse, AccountServiceError<R::Error>> {
+        let mut rule = self.find_rule(account_id, rule_id).await?;
+
+        let mut warning = None;
+        if let Some(amount) = request.amount {
+            let account = self.accounts.get_account(account_id).await?;
+            let (amount, amount_warning) = self
+                .accounts
+                .fit_amount(amount, &Currency::new(&account.currency))?;
+            warning = amount_warning;
+            rule.set_amount(amount.cents())?;
+        }
+        if let Some(schedule) = request.schedule {
+            rule.schedule = schedule;
+            // Старый срок считался по старому расписанию
+            rule.next_run = schedule.next_after(self.now());
+        }
+        if let Some(next_run) = request.next_run {
+            rule.next_run = next_run;
+        }
+        if let Some(enabled) = request.enabled {
+            rule.enabled = enabled;
+        }
+        if let Some(note) = request.note {
+            rule.note = note;
+        }
+
+        self.rules
+            .update(&rule)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        let mut response = RecurringResponse::from(rule);
+        response.warnings.extend(warning);
+        Ok(response)
+    }
+
+    /// Use case: Удаление правила.
+    pub async fn delete_rule(
+        &self,
+        account_id: Uuid,
+        rule_id: Uuid,
+    ) -> Result<(), AccountServiceError<R::Error>> {
+        self.find_rule(account_id, rule_id).await?;
+
+        self.rules
+            .delete(rule_id)
+            .await
+            .map_err(AccountServiceError::Repository)
+    }
+
+    /// Use case: Смена валюты счёта (см. `AccountService::change_currency`).
+    ///
+    /// # Почему здесь, а не в `AccountService`?
+    /// Суммы правил записаны в валюте счёта: после смены правило
+    /// на 100.00 USD стало бы списывать 100.00 EUR. Правила знает только
+    /// этот сервис, поэтому он и проверяет: есть включённые — ошибка
+    /// `AccountHasRecurring`, если не передан `force`. С `force` правила
+    /// остаются как есть — их суммы исправляют отдельно.
+    pub async fn change_currency(
+        &self,
+        account_id: Uuid,
+        request: ChangeCurrencyRequest,
+        force: bool,
+    ) -> Result<AccountResponse, AccountServiceError<R::Error>> {
+        if !force {
+            let count = self
+                .rules
+                .find_by_account(account_id)
+                .await
+                .map_err(AccountServiceError::Repository)?
+                .iter()
+                .filter(|rule| rule.enabled)
+                .count();
+            if count > 0 {
+                // Несуществующий счёт — 404, а не 409
+                self.accounts.get_account(account_id).await?;
+                return Err(DomainError::AccountHasRecurring { count }.into());
+            }
+        }
+
+        self.accounts.change_currency(account_id, request).await
+    }
+
+    /// Правила, срок которых наступил к `now` (не больше `limit`).
+    pub async fn due_rules(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<RecurringTransaction>, AccountServiceError<R::Error>> {
+        self.rules
+            .find_due(now, limit)
+            .await
+            .map_err(AccountServiceError::Repository)
+    }
+
+    /// Исполняет правило и переносит его на следующий срок.
+    ///
+    /// # Когда правило переносится
+    /// - Операция прошла — переносим и возвращаем новое состояние счёта
+    /// - Доменная ошибка (не хватило средств, лимит, счёт удалён) —
+    ///   переносим и возвращаем ошибку: повтор через минуту не поможет,
+    ///   а правило не должно застревать
+    /// - Ошибка БД — НЕ переносим: следующий проход попробует снова
+    ///
+    /// # Почему ключ идемпотентности?
+    /// Если операция прошла, а перенос не сохранился, следующий проход
+    /// исполнит правило повторно. Ключ `recurring:{id}:{next_run}`
+    /// одинаков для обоих проходов, поэтому деньги не спишутся дважды.
+    ///
+    /// # Чей счёт?
+    /// Планировщик не знает пользователя, поэтому операция идёт от имени
+    /// владельца счёта (`for_account_owner`). Счёт удалён — владельца нет,
+    /// и `deposit`/`withdraw` вернут `AccountNotFound`.
+    pub async fn apply_rule(
+        &self,
+        mut rule: RecurringTransaction,
+        now: DateTime<Utc>,
+    ) -> Result<AccountResponse, AccountServiceError<R::Error>>
+    where
+        AccountService<R, T, S, I, X, P>: Clone,
+    {
+        let accounts = self
+            .accounts
+            .for_account_owner(rule.account_id)
+            .await?
+            .unwrap_or_else(|| self.accounts.clone());
+        let key = format!("recurring:{}:{}", rule.id, rule.next_run.timestamp());
+        let amount = Money::from_cents(rule.amount);
+        let note = rule.note.clone();
+
+        let result = match rule.kind {
+            RecurringKind::Deposit => {
+                let request = DepositRequest {
+                    amount,
+                    note,
+                    category: None,
+                };
+                accounts
+                    .deposit(rule.account_id, request, Some(key), false)
+                    .await
+            }
+            RecurringKind::Withdraw => {
+                let request = WithdrawRequest {
+                    amount: WithdrawAmount::Exact(amount),
+                    note,
+                    category: None,
+                };
+                accounts
+                    .withdraw(rule.account_id, request, Some(key), false)
+                    .await
+            }
+        };
+
+        if matches!(result, Ok(_) | Err(AccountServiceError::Domain(_))) {
+            rule.advance(now);
+            self.rules
+                .update(&rule)
+                .await
+                .map_err(AccountServiceError::Repository)?;
+        }
+
+        result
+    }
+
+    /// Находит правило, принадлежащее счёту.
+    ///
+    /// Правило чужого счёта — тоже `RecurringNotFound`: по URL
+    /// `/accounts/A/recurring/B` нельзя добраться до правила счёта C.
+    /// Сам счёт ищется через `AccountService`, поэтому счёт другого
+    /// пользователя — `AccountNotFound`, даже если ID правила известен.
+    async fn find_rule(
+        &self,
+        account_id: Uuid,
+        rule_id: Uuid,
+    ) -> Result<RecurringTransaction, AccountServiceError<R::Error>> {
+        self.accounts.get_account(account_id).await?;
+
+        self.rules
+            .find_by_id(rule_id)
+            .await
+            .map_err(AccountServiceError::Repository)?
+            .filter(|rule| rule.account_id == account_id)
+            .ok_or_else(|| DomainError::RecurringNotFound(rule_id.to_string()).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use serde_json::json;
+
+    use super::*;
+    use crate::application::services::test_support::{request, Harness};
+    use crate::infrastructure::database::InMemoryRecurringRepository;
+
+    #[tokio::test]
+    async fn due_rule_runs_once_and_moves_to_next_day() {
+        let h = Harness::new();
+        let account = h.open("Wallet", "USD", "0").await;
+        let rules = RecurringService::new(h.service.clone(), InMemoryRecurringRepository::new());
+        let now = h.service.now();
+
+        rules
+            .create_rule(
+                account.id,
+                request(json!({
+                    "kind": "deposit",
+                    "amount": "10.00",
+                    "schedule": "1d",
+                    "starts_at": now,
+                })),
+            )
+            .await
+            .unwrap();
+
+        let due = rules.due_rules(now, 10).await.unwrap();
+        assert_eq!(due.len(), 1);
+        let account = rules.apply_rule(due[0].clone(), now).await.unwrap();
+        assert_eq!(account.balance.cents(), 1000);
+
+        // Перенесено на завтра: сейчас исполнять нечего
+        assert!(rules.due_rules(now, 10).await.unwrap().is_empty());
+        let due = rules.due_rules(now + Duration::days(1), 10).await.unwrap();
+        assert_eq!(due[0].next_run, now + Duration::days(1));
+    }
+
+    #[tokio::test]
+    async fn rule_is_not_found_through_another_account() {
+        let h = Harness::new();
+        let owner = h.open("Wallet", "USD", "0").await;
+        let other = h.open("Savings", "USD", "0").await;
+        let rules = RecurringService::new(h.service.clone(), InMemoryRecurringRepository::new());
+
+        let rule = rules
+            .create_rule(
+                owner.id,
+                request(json!({ "kind": "withdraw", "amount": "5.00", "schedule": "1w" })),
+            )
+            .await
+            .unwrap();
+
+        let err = rules.get_rule(other.id, rule.id).await.unwrap_err();
+        assert!(matches!(
+            err,
+            AccountServiceError::Domain(DomainError::RecurringNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn another_users_rules_are_not_found() {
+        let h = Harness::new();
+        let account = h.open("Wallet", "USD", "0").await;
+        let rules = RecurringService::new(h.service.clone(), InMemoryRecurringRepository::new());
+        let rule = rules
+            .create_rule(
+                account.id,
+                request(json!({ "kind": "deposit", "amount": "5.00", "schedule": "1w" })),
+            )
+            .await
+            .unwrap();
+
+        // Чужой счёт не найден — правила за ним тоже
+        let bob = rules.for_user("bob");
+        let not_found = |err| {
+            matches!(
+                err,
+                AccountServiceError::Domain(DomainError::AccountNotFound(_))
+            )
+        };
+        assert!(not_found(bob.list_rules(account.id).await.unwrap_err()));
+        assert!(not_found(
+            bob.get_rule(account.id, rule.id).await.unwrap_err()
+        ));
+        assert!(not_found(
+            bob.delete_rule(account.id, rule.id).await.unwrap_err()
+        ));
+
+        assert!(rules.get_rule(account.id, rule.id).await.is_ok());
+    }
+}
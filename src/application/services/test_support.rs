@@ -0,0 +1,122 @@
+//! Общее для тестов сервисов: `AccountService` на репозиториях в памяти.
+//!
+//! # Почему отдельный модуль, а не `mod tests` в `account_service.rs`?
+//! `RecurringService` исполняет правила через `AccountService`, и его
+//! тестам нужен тот же сервис с теми же часами и событиями.
+
+use std::sync::{Arc, Mutex};
+
+use chrono::{TimeZone, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::application::dto::AccountResponse;
+use crate::application::ports::{BalanceChanged, EventPublisher};
+use crate::infrastructure::clock::FixedClock;
+use crate::infrastructure::database::{
+    InMemoryAccountRepository, InMemoryIdempotencyRepository, InMemorySnapshotRepository,
+    InMemoryTransactionRepository,
+};
+use crate::infrastructure::exchange_rates::StaticExchangeRateProvider;
+
+use super::AccountService;
+
+/// `AccountService` на репозиториях в памяти.
+pub type TestAccountService = AccountService<
+    InMemoryAccountRepository,
+    InMemoryTransactionRepository,
+    InMemorySnapshotRepository,
+    InMemoryIdempotencyRepository,
+    StaticExchangeRateProvider,
+    RecordingPublisher,
+>;
+
+/// Подписчик, который запоминает события вместо доставки.
+#[derive(Clone, Default)]
+pub struct RecordingPublisher {
+    events: Arc<Mutex<Vec<BalanceChanged>>>,
+}
+
+impl RecordingPublisher {
+    /// Полученные события по порядку.
+    pub fn events(&self) -> Vec<BalanceChanged> {
+        self.events.lock().unwrap().clone()
+    }
+}
+
+impl EventPublisher for RecordingPublisher {
+    fn publish(&self, event: BalanceChanged) {
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+/// Сервис, его хранилище счетов, часы и подписчик.
+///
+/// `accounts` — то же хранилище, что у сервиса: через него тест
+/// записывает состояние, которого API не допускает (как после импорта
+/// резервной копии).
+pub struct Harness {
+    pub service: TestAccountService,
+    pub accounts: InMemoryAccountRepository,
+    pub clock: FixedClock,
+    pub events: RecordingPublisher,
+}
+
+impl Harness {
+    /// Пустое хранилище, часы на 2026-03-10 12:00 UTC,
+    /// единственный курс — `USD/EUR` 0.923.
+    pub fn new() -> Self {
+        Self::with_rates(r#"{"USD/EUR": "0.923"}"#)
+    }
+
+    /// Как `new`, но с курсами из `rates` (JSON, как `EXCHANGE_RATES`).
+    pub fn with_rates(rates: &str) -> Self {
+        let clock = FixedClock::new(Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap());
+        let events = RecordingPublisher::default();
+        let transactions = InMemoryTransactionRepository::new();
+        let accounts = InMemoryAccountRepository::with_ledger(transactions.clone());
+        let service = AccountService::new(
+            accounts.clone(),
+            transactions,
+            InMemorySnapshotRepository::new(),
+            InMemoryIdempotencyRepository::new(),
+            StaticExchangeRateProvider::from_json(rates).unwrap(),
+            events.clone(),
+            Arc::new(clock.clone()),
+        );
+
+        Self {
+            service,
+            accounts,
+            clock,
+            events,
+        }
+    }
+
+    /// Открывает счёт `name` в `currency` и пополняет его на `balance`
+    /// (если не `"0"`).
+    pub async fn open(&self, name: &str, currency: &str, balance: &str) -> AccountResponse {
+        let account = self
+            .service
+            .create_account(request(json!({ "name": name, "currency": currency })))
+            .await
+            .unwrap();
+        if balance == "0" {
+            return account;
+        }
+        self.deposit(account.id, balance).await
+    }
+
+    /// Пополняет счёт без ключа идемпотентности.
+    pub async fn deposit(&self, id: Uuid, amount: &str) -> AccountResponse {
+        self.service
+            .deposit(id, request(json!({ "amount": amount })), None, false)
+            .await
+            .unwrap()
+    }
+}
+
+/// DTO запроса из JSON — так же, как его разберёт handler.
+pub fn request<T: serde::de::DeserializeOwned>(value: serde_json::Value) -> T {
+    serde_json::from_value(value).unwrap()
+}
@@ -0,0 +1,237 @@
+//! Сервис кошельков — создание, пополнение и снятие в разных валютах.
+
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::application::dto::{CreateWalletRequest, WalletOperationRequest, WalletResponse};
+use crate::application::ports::{Clock, WalletRepository};
+use crate::domain::entities::Wallet;
+use crate::domain::errors::DomainError;
+use crate::domain::value_objects::{Currency, PrecisionMode, RoundingMode};
+
+use super::AccountServiceError;
+
+/// Сервис кошельков.
+///
+/// # Почему не часть `AccountService`?
+/// У кошелька нет журнала операций, лимитов, переводов и подписчиков —
+/// общего со счётом только `Money` и `Currency`. Ошибка та же,
+/// `AccountServiceError`, по той же причине, что у `WebhookService`.
+#[derive(Clone)]
+pub struct WalletService<W>
+where
+    W: WalletRepository,
+{
+    wallets: W,
+    clock: Arc<dyn Clock>,
+    precision: PrecisionMode,
+    rounding: RoundingMode,
+}
+
+impl<W> WalletService<W>
+where
+    W: WalletRepository,
+{
+    /// Создаёт новый экземпляр сервиса.
+    pub fn new(wallets: W, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            wallets,
+            clock,
+            precision: PrecisionMode::default(),
+            rounding: RoundingMode::default(),
+        }
+    }
+
+    /// Лишние для валюты знаки суммы: отклонить или округлить по `rounding`
+    /// — как у счетов (`AccountService::with_precision_mode`).
+    pub fn with_precision(mut self, precision: PrecisionMode, rounding: RoundingMode) -> Self {
+        self.precision = precision;
+        self.rounding = rounding;
+        self
+    }
+
+    /// Use case: Все кошельки.
+    pub async fn list_wallets(&self) -> Result<Vec<WalletResponse>, AccountServiceError<W::Error>> {
+        let wallets = self
+            .wallets
+            .find_all()
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        Ok(wallets.into_iter().map(WalletResponse::from).collect())
+    }
+
+    /// Use case: Создание пустого кошелька.
+    pub async fn create_wallet(
+        &self,
+        request: CreateWalletRequest,
+    ) -> Result<WalletResponse, AccountServiceError<W::Error>> {
+        let wallet = Wallet::new(&request.name, self.clock.now())?;
+
+        self.wallets
+            .create(&wallet)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        Ok(wallet.into())
+    }
+
+    /// Use case: Получение кошелька.
+    pub async fn get_wallet(
+        &self,
+        id: Uuid,
+    ) -> Result<WalletResponse, AccountServiceError<W::Error>> {
+        Ok(self.find_wallet(id).await?.into())
+    }
+
+    /// Use case: Пополнение в одной валюте.
+    ///
+    /// Валюта, которой в кошельке ещё не было, появляется в нём.
+    pub async fn deposit(
+        &self,
+        id: Uuid,
+        request: WalletOperationRequest,
+    ) -> Result<WalletResponse, AccountServiceError<W::Error>> {
+        let currency = Currency::parse(&request.currency)?;
+        let (amount, warning) = self
+            .precision
+            .fit(request.amount, &currency, self.rounding)?;
+        let mut wallet = self.find_wallet(id).await?;
+
+        wallet.deposit(currency, amount.cents(), self.clock.now())?;
+        self.save(&mut wallet).await?;
+
+        let mut response = WalletResponse::from(wallet);
+        response.warnings.extend(warning);
+        Ok(response)
+    }
+
+    /// Use case: Снятие в одной валюте.
+    ///
+    /// Не хватает в этой валюте — `InsufficientFunds` с её кодом,
+    /// сколько бы ни лежало в других.
+    pub async fn withdraw(
+        &self,
+        id: Uuid,
+        request: WalletOperationRequest,
+    ) -> Result<WalletResponse, AccountServiceError<W::Error>> {
+        let currency = Currency::parse(&request.currency)?;
+        let (amount, warning) = self
+            .precision
+            .fit(request.amount, &currency, self.rounding)?;
+        let mut wallet = self.find_wallet(id).await?;
+
+        wallet.withdraw(&currency, amount.cents(), self.clock.now())?;
+        self.save(&mut wallet).await?;
+
+        let mut response = WalletResponse::from(wallet);
+        response.warnings.extend(warning);
+        Ok(response)
+    }
+
+    /// Use case: Удаление кошелька вместе с остатками.
+    pub async fn delete_wallet(&self, id: Uuid) -> Result<(), AccountServiceError<W::Error>> {
+        self.find_wallet(id).await?;
+
+        self.wallets
+            .delete(id)
+            .await
+            .map_err(AccountServiceError::Repository)
+    }
+
+    /// Находит кошелёк или возвращает `WalletNotFound`.
+    async fn find_wallet(&self, id: Uuid) -> Result<Wallet, AccountServiceError<W::Error>> {
+        self.wallets
+            .find_by_id(id)
+            .await
+            .map_err(AccountServiceError::Repository)?
+            .ok_or_else(|| DomainError::WalletNotFound(id.to_string()).into())
+    }
+
+    /// Сохраняет кошелёк с проверкой версии (см. `AccountService::save`).
+    async fn save(&self, wallet: &mut Wallet) -> Result<(), AccountServiceError<W::Error>> {
+        let updated = self
+            .wallets
+            .update(wallet)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+        if !updated {
+            return Err(DomainError::WalletModified(wallet.id.to_string()).into());
+        }
+        wallet.version += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    use super::*;
+    use crate::application::services::test_support::request;
+    use crate::infrastructure::clock::FixedClock;
+    use crate::infrastructure::database::InMemoryWalletRepository;
+
+    fn service() -> WalletService<InMemoryWalletRepository> {
+        let clock = FixedClock::new(Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap());
+        WalletService::new(InMemoryWalletRepository::new(), Arc::new(clock))
+    }
+
+    #[tokio::test]
+    async fn currencies_are_kept_apart() {
+        let wallets = service();
+        let wallet = wallets
+            .create_wallet(request(json!({ "name": "Travel" })))
+            .await
+            .unwrap();
+
+        for (currency, amount) in [("USD", "50.00"), ("eur", "20.00")] {
+            wallets
+                .deposit(
+                    wallet.id,
+                    request(json!({ "currency": currency, "amount": amount })),
+                )
+                .await
+                .unwrap();
+        }
+
+        let err = wallets
+            .withdraw(
+                wallet.id,
+                request(json!({ "currency": "EUR", "amount": "30.00" })),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AccountServiceError::Domain(DomainError::InsufficientFunds { .. })
+        ));
+
+        let wallet = wallets.get_wallet(wallet.id).await.unwrap();
+        let balances: Vec<_> = wallet
+            .balances
+            .iter()
+            .map(|b| (b.currency.as_str(), b.balance.cents()))
+            .collect();
+        assert_eq!(balances, [("EUR", 2000), ("USD", 5000)]);
+    }
+
+    #[tokio::test]
+    async fn deleted_wallet_is_not_found() {
+        let wallets = service();
+        let wallet = wallets
+            .create_wallet(request(json!({ "name": "Travel" })))
+            .await
+            .unwrap();
+
+        wallets.delete_wallet(wallet.id).await.unwrap();
+
+        let err = wallets.get_wallet(wallet.id).await.unwrap_err();
+        assert!(matches!(
+            err,
+            AccountServiceError::Domain(DomainError::WalletNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,237 @@
+//! Сервис подписок на события — CRUD webhooks.
+//!
+//! Саму рассылку делает `EventPublisher` (см. `infrastructure::webhooks`),
+//! этот сервис только управляет списком подписок.
+
+use uuid::Uuid;
+
+use crate::application::dto::{CreateWebhookRequest, UpdateWebhookRequest, WebhookResponse};
+use crate::application::ports::WebhookRepository;
+use crate::domain::entities::{Account, Webhook};
+use crate::domain::errors::DomainError;
+
+use super::AccountServiceError;
+
+/// Сервис подписок на события.
+///
+/// # Почему ошибка — `AccountServiceError`?
+/// Варианты те же: доменная ошибка (неверный URL, нет подписки) или
+/// сбой репозитория. Отдельный тип дублировал бы его и маппинг в HTTP.
+///
+/// # Пользователь
+/// Как и `AccountService`, сервис работает с подписками одного
+/// пользователя — `user_id`; handler берёт копию через `for_user`.
+#[derive(Clone)]
+pub struct WebhookService<W>
+where
+    W: WebhookRepository,
+{
+    webhooks: W,
+    user_id: String,
+}
+
+impl<W> WebhookService<W>
+where
+    W: WebhookRepository,
+{
+    /// Создаёт новый экземпляр сервиса.
+    pub fn new(webhooks: W) -> Self {
+        Self {
+            webhooks,
+            user_id: Account::DEFAULT_USER.to_string(),
+        }
+    }
+
+    /// Тот же сервис для подписок пользователя `user_id`.
+    ///
+    /// Чужая подписка по ID — `WebhookNotFound` (404, а не 403,
+    /// как и у счетов), в списке её нет.
+    pub fn for_user(&self, user_id: &str) -> Self
+    where
+        Self: Clone,
+    {
+        Self {
+            user_id: user_id.to_string(),
+            ..self.clone()
+        }
+    }
+
+    /// Use case: Все подписки.
+    pub async fn list_webhooks(
+        &self,
+    ) -> Result<Vec<WebhookResponse>, AccountServiceError<W::Error>> {
+        let webhooks = self
+            .webhooks
+            .find_all(&self.user_id)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        Ok(webhooks.into_iter().map(WebhookResponse::from).collect())
+    }
+
+    /// Use case: Создание подписки.
+    pub async fn create_webhook(
+        &self,
+        request: CreateWebhookRequest,
+    ) -> Result<WebhookResponse, AccountServiceError<W::Error>> {
+        let mut webhook = Webhook::new(&request.url, request.secret)?;
+        webhook.user_id = self.user_id.clone();
+        if let Some(enabled) = request.enabled {
+            webhook.enabled = enabled;
+        }
+
+        self.webhooks
+            .create(&webhook)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        Ok(webhook.into())
+    }
+
+    /// Use case: Получение подписки.
+    pub async fn get_webhook(
+        &self,
+        id: Uuid,
+    ) -> Result<WebhookResponse, AccountServiceError<W::Error>> {
+        Ok(self.find_webhook(id).await?.into())
+    }
+
+    /// Use case: Изменение подписки.
+    ///
+    /// Все поля проверяются до записи: неверный секрет не оставит
+    /// подписку с уже сменённым URL.
+    pub async fn update_webhook(
+        &self,
+        id: Uuid,
+        request: UpdateWebhookRequest,
+    ) -> Result<WebhookResponse, AccountServiceError<W::Error>> {
+        let mut webhook = self.find_webhook(id).await?;
+
+        if let Some(url) = request.url {
+            webhook.url = Webhook::normalize_url(&url)?;
+        }
+        if let Some(secret) = request.secret {
+            Webhook::check_secret(&secret)?;
+            webhook.secret = secret;
+        }
+        if let Some(enabled) = request.enabled {
+            webhook.enabled = enabled;
+        }
+
+        self.webhooks
+            .update(&webhook)
+            .await
+            .map_err(AccountServiceError::Repository)?;
+
+        Ok(webhook.into())
+    }
+
+    /// Use case: Удаление подписки.
+    pub async fn delete_webhook(&self, id: Uuid) -> Result<(), AccountServiceError<W::Error>> {
+        self.find_webhook(id).await?;
+
+        self.webhooks
+            .delete(&self.user_id, id)
+            .await
+            .map_err(AccountServiceError::Repository)
+    }
+
+    /// Находит подписку или возвращает `WebhookNotFound`.
+    async fn find_webhook(&self, id: Uuid) -> Result<Webhook, AccountServiceError<W::Error>> {
+        self.webhooks
+            .find_by_id(&self.user_id, id)
+            .await
+            .map_err(AccountServiceError::Repository)?
+            .ok_or_else(|| DomainError::WebhookNotFound(id.to_string()).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::application::services::test_support::request;
+    use crate::infrastructure::database::InMemoryWebhookRepository;
+
+    const SECRET: &str = "0123456789abcdef";
+
+    #[tokio::test]
+    async fn invalid_update_leaves_webhook_unchanged() {
+        let webhooks = WebhookService::new(InMemoryWebhookRepository::new());
+        let webhook = webhooks
+            .create_webhook(request(
+                json!({ "url": "http://hooks.local/a", "secret": SECRET }),
+            ))
+            .await
+            .unwrap();
+
+        let err = webhooks
+            .update_webhook(
+                webhook.id,
+                request(json!({ "url": "http://hooks.local/b", "secret": "short" })),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AccountServiceError::Domain(DomainError::InvalidWebhook(_))
+        ));
+
+        let webhook = webhooks.get_webhook(webhook.id).await.unwrap();
+        assert_eq!(webhook.url, "http://hooks.local/a");
+    }
+
+    #[tokio::test]
+    async fn deleted_webhook_is_not_found() {
+        let webhooks = WebhookService::new(InMemoryWebhookRepository::new());
+        let webhook = webhooks
+            .create_webhook(request(
+                json!({ "url": "http://hooks.local/a", "secret": SECRET }),
+            ))
+            .await
+            .unwrap();
+
+        webhooks.delete_webhook(webhook.id).await.unwrap();
+
+        assert_eq!(webhooks.list_webhooks().await.unwrap().len(), 0);
+        let err = webhooks.get_webhook(webhook.id).await.unwrap_err();
+        assert!(matches!(
+            err,
+            AccountServiceError::Domain(DomainError::WebhookNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn another_users_webhooks_are_not_found() {
+        let repository = InMemoryWebhookRepository::new();
+        let webhooks = WebhookService::new(repository.clone());
+        let webhook = webhooks
+            .create_webhook(request(
+                json!({ "url": "http://hooks.local/a", "secret": SECRET }),
+            ))
+            .await
+            .unwrap();
+
+        let bob = webhooks.for_user("bob");
+        let not_found = |err| {
+            matches!(
+                err,
+                AccountServiceError::Domain(DomainError::WebhookNotFound(_))
+            )
+        };
+        assert!(bob.list_webhooks().await.unwrap().is_empty());
+        assert!(not_found(bob.get_webhook(webhook.id).await.unwrap_err()));
+        assert!(not_found(
+            bob.update_webhook(webhook.id, request(json!({ "enabled": false })))
+                .await
+                .unwrap_err()
+        ));
+        assert!(not_found(bob.delete_webhook(webhook.id).await.unwrap_err()));
+
+        // События счетов Боба подписке не рассылаются
+        assert!(repository.find_enabled("bob").await.unwrap().is_empty());
+        let webhook = webhooks.get_webhook(webhook.id).await.unwrap();
+        assert!(webhook.enabled);
+    }
+}
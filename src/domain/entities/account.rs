@@ -6,12 +6,71 @@
 //! - Фреймворках
 //!
 //! Только чистая бизнес-логика.
+//!
+//! # Почему методы принимают `now`, а не зовут `Utc::now()`?
+//! Часы — тоже внешний мир. Время передаёт сервис (`Clock`), поэтому
+//! в тестах `created_at`/`updated_at` известны заранее, а домен
+//! не зависит от системных часов.
 
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::domain::errors::DomainError;
+use crate::domain::value_objects::{Currency, Money, RoundingMode};
+
+/// Максимальная длина имени счёта в символах (после обрезки пробелов).
+const MAX_NAME_LENGTH: usize = 100;
+
+/// Максимальная длина описания счёта (в символах).
+pub const MAX_DESCRIPTION_LENGTH: usize = 500;
+
+/// Дней в году для начисления процентов (ставка годовая).
+const DAYS_IN_YEAR: u32 = 365;
+
+/// Самый длинный период начисления процентов — високосный год.
+const MAX_INTEREST_DAYS: u32 = 366;
+
+/// Тип счёта.
+///
+/// Влияет на бизнес-правила: только кредитный счёт может уйти в минус.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountType {
+    #[default]
+    Checking,
+    Savings,
+    Cash,
+    Credit,
+}
+
+impl AccountType {
+    /// Строковое представление — так тип хранится в БД.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccountType::Checking => "checking",
+            AccountType::Savings => "savings",
+            AccountType::Cash => "cash",
+            AccountType::Credit => "credit",
+        }
+    }
+}
+
+/// Разбор типа счёта из строки (обратное к `as_str`).
+impl std::str::FromStr for AccountType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "checking" => Ok(AccountType::Checking),
+            "savings" => Ok(AccountType::Savings),
+            "cash" => Ok(AccountType::Cash),
+            "credit" => Ok(AccountType::Credit),
+            other => Err(format!("Unknown account type: {}", other)),
+        }
+    }
+}
 
 /// Сущность "Счёт" — основной объект предметной области.
 ///
@@ -19,6 +78,20 @@ use crate::domain::errors::DomainError;
 /// - `id` — уникальный идентификатор (UUID v4)
 /// - `balance` — баланс в копейках/центах (i64 вместо f64 для точности)
 /// - `currency` — код валюты (USD, RUB, EUR)
+/// - `version` — номер версии для optimistic locking (растёт при каждом сохранении)
+/// - `deleted_at` — момент мягкого удаления (`None` — счёт активен)
+/// - `withdrawal_limit` — дневной лимит снятия в копейках (`None` — без лимита)
+/// - `account_type` — тип счёта (по умолчанию `Checking`)
+/// - `credit_limit` — насколько кредитный счёт может уйти в минус (`None` — без ограничения)
+/// - `min_balance` — неснижаемый остаток в копейках (`None` — без него)
+/// - `archived` — счёт скрыт из списка счетов (см. `archive`)
+/// - `overdraft_source` — счёт, который покрывает нехватку при снятии
+///   (см. `set_overdraft_source`)
+/// - `low_balance_threshold` — порог предупреждения о низком балансе
+///   (см. `crossed_low_balance`)
+/// - `description` — описание в свободной форме (`None` — нет описания)
+/// - `user_id` — владелец счёта (см. `DEFAULT_USER`); другие пользователи
+///   счёта не видят
 ///
 /// # Почему баланс в i64?
 /// Floating point числа имеют проблемы с точностью:
@@ -34,29 +107,435 @@ pub struct Account {
     pub currency: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub version: i64,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub withdrawal_limit: Option<i64>,
+    #[serde(default)] // В старых резервных копиях поля нет
+    pub account_type: AccountType,
+    pub credit_limit: Option<i64>,
+    pub min_balance: Option<i64>,
+    #[serde(default)] // В старых резервных копиях поля нет
+    pub archived: bool,
+    #[serde(default)] // В старых резервных копиях поля нет
+    pub overdraft_source: Option<Uuid>,
+    #[serde(default)] // В старых резервных копиях поля нет
+    pub low_balance_threshold: Option<i64>,
+    #[serde(default)] // В старых резервных копиях поля нет
+    pub description: Option<String>,
+    #[serde(default = "default_user")] // В старых резервных копиях поля нет
+    pub user_id: String,
+}
+
+/// Владелец счетов из старых резервных копий (см. `Account::DEFAULT_USER`).
+fn default_user() -> String {
+    Account::DEFAULT_USER.to_string()
 }
 
 impl Account {
+    /// Пользователь по умолчанию.
+    ///
+    /// Ему принадлежат счета, созданные до разделения по пользователям,
+    /// и от его имени работает API без ключей (или с ключом без имени
+    /// пользователя, см. `middleware::auth`).
+    pub const DEFAULT_USER: &'static str = "default";
+
     /// Создаёт новый счёт с нулевым балансом.
     ///
+    /// Владелец — `DEFAULT_USER`; счёт другого пользователя сервис
+    /// создаёт так же и затем меняет `user_id`.
+    ///
     /// # Arguments
-    /// * `name` — название счёта ("Кошелёк", "Сбережения")
-    /// * `currency` — код валюты ("RUB", "USD")
+    /// * `name` — название счёта ("Кошелёк", "Сбережения"), см. `normalize_name`
+    /// * `currency` — код валюты ("RUB", "usd"), хранится в верхнем регистре
+    /// * `now` — время создания (`created_at` и `updated_at`)
+    ///
+    /// # Errors
+    /// - `InvalidName` если имя пустое или длиннее `MAX_NAME_LENGTH`
+    /// - `InvalidCurrency` если код валюты — не три латинские буквы
     ///
     /// # Пример
     /// ```text
-    /// let account = Account::new("Wallet".to_string(), "USD".to_string());
+    /// let account = Account::new("  Wallet ".to_string(), "USD".to_string(), clock.now())?;
+    /// assert_eq!(account.name, "Wallet");
     /// assert_eq!(account.balance, 0);
     /// ```
-    pub fn new(name: String, currency: String) -> Self {
-        let now = Utc::now();
-        Self {
+    pub fn new(name: String, currency: String, now: DateTime<Utc>) -> Result<Self, DomainError> {
+        let name = Self::normalize_name(&name)?;
+        let currency = Currency::parse(&currency)?.to_string();
+        Ok(Self {
             id: Uuid::new_v4(), // Генерируем случайный UUID
             name,
             balance: 0,
             currency,
             created_at: now,
             updated_at: now,
+            version: 0,
+            deleted_at: None,
+            withdrawal_limit: None,
+            account_type: AccountType::Checking,
+            credit_limit: None,
+            min_balance: None,
+            archived: false,
+            overdraft_source: None,
+            low_balance_threshold: None,
+            description: None,
+            user_id: Self::DEFAULT_USER.to_string(),
+        })
+    }
+
+    /// Обрезает пробелы по краям имени и проверяет результат.
+    ///
+    /// # Почему имя хранится обрезанным?
+    /// `"Wallet"` и `"Wallet "` выглядят одинаково, но `find_by_name`
+    /// их различает — счёт было бы не найти по имени, которое видишь.
+    /// А имя из одних пробелов и вовсе не на что сослаться.
+    ///
+    /// # Errors
+    /// `InvalidName` если после обрезки имя пустое
+    /// или длиннее `MAX_NAME_LENGTH` символов
+    pub fn normalize_name(name: &str) -> Result<String, DomainError> {
+        let trimmed = name.trim();
+        if trimmed.is_empty() {
+            return Err(DomainError::InvalidName(
+                "Account name must not be empty".into(),
+            ));
+        }
+        // chars(), а не len(): len() считает байты, а "Кошелёк" — 14 байт
+        if trimmed.chars().count() > MAX_NAME_LENGTH {
+            return Err(DomainError::InvalidName(format!(
+                "Account name must be at most {} characters",
+                MAX_NAME_LENGTH
+            )));
+        }
+        Ok(trimmed.to_string())
+    }
+
+    /// Обрезает пробелы по краям описания и проверяет длину.
+    ///
+    /// # Почему пустое описание — `None`?
+    /// `""` и `"   "` ничего не описывают. Храним одно «описания нет»,
+    /// а не три разных, чтобы клиенту не проверять каждое.
+    ///
+    /// # Errors
+    /// `InvalidDescription` если описание длиннее `MAX_DESCRIPTION_LENGTH` символов
+    pub fn normalize_description(description: &str) -> Result<Option<String>, DomainError> {
+        let trimmed = description.trim();
+        if trimmed.chars().count() > MAX_DESCRIPTION_LENGTH {
+            return Err(DomainError::InvalidDescription(format!(
+                "Account description must be at most {} characters",
+                MAX_DESCRIPTION_LENGTH
+            )));
+        }
+        Ok((!trimmed.is_empty()).then(|| trimmed.to_string()))
+    }
+
+    /// Задаёт (или убирает при `None`) описание счёта.
+    ///
+    /// # Errors
+    /// `InvalidDescription` — см. `normalize_description`
+    pub fn set_description(
+        &mut self,
+        description: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        self.description = match description {
+            Some(description) => Self::normalize_description(description)?,
+            None => None,
+        };
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Задаёт тип счёта и кредитный лимит.
+    ///
+    /// # Errors
+    /// `InvalidAmount` если лимит отрицательный или задан не для `Credit`
+    pub fn set_account_type(
+        &mut self,
+        account_type: AccountType,
+        credit_limit: Option<i64>,
+        now: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        Self::check_credit_limit(account_type, credit_limit)?;
+        self.account_type = account_type;
+        self.credit_limit = credit_limit;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Проверяет кредитный лимит для типа счёта, не меняя счёт.
+    ///
+    /// Отдельно от `set_account_type`, чтобы проверить запрос
+    /// на создание ещё до того, как появился сам счёт.
+    ///
+    /// # Errors
+    /// `InvalidAmount` если лимит отрицательный или задан не для `Credit`
+    pub fn check_credit_limit(
+        account_type: AccountType,
+        credit_limit: Option<i64>,
+    ) -> Result<(), DomainError> {
+        if credit_limit.is_some_and(|limit| limit < 0) {
+            return Err(DomainError::InvalidAmount(
+                "Credit limit must not be negative".into(),
+            ));
+        }
+        if credit_limit.is_some() && account_type != AccountType::Credit {
+            return Err(DomainError::InvalidAmount(
+                "Credit limit applies only to credit accounts".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Сколько можно снять с учётом баланса и кредита.
+    ///
+    /// # Возвращает
+    /// - обычный счёт — баланс
+    /// - кредитный — баланс + кредитный лимит
+    /// - `None` — кредитный счёт без лимита, ограничения нет
+    pub fn available_funds(&self) -> Option<i64> {
+        match self.account_type {
+            AccountType::Credit => self
+                .credit_limit
+                .map(|limit| self.balance.saturating_add(limit)),
+            _ => Some(self.balance),
+        }
+    }
+
+    /// Переименовывает счёт.
+    ///
+    /// Уникальность имени здесь НЕ проверяется — для этого нужен
+    /// доступ ко всем счетам, а это забота сервиса.
+    ///
+    /// # Errors
+    /// `InvalidName` — те же правила, что у `new`
+    pub fn rename(&mut self, name: &str, now: DateTime<Utc>) -> Result<(), DomainError> {
+        self.name = Self::normalize_name(name)?;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Устанавливает (или снимает при `None`) дневной лимит снятия.
+    ///
+    /// # Errors
+    /// `InvalidAmount` если лимит отрицательный
+    pub fn set_withdrawal_limit(
+        &mut self,
+        limit: Option<i64>,
+        now: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        if limit.is_some_and(|limit| limit < 0) {
+            return Err(DomainError::InvalidAmount(
+                "Withdrawal limit must not be negative".into(),
+            ));
+        }
+        self.withdrawal_limit = limit;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Устанавливает (или снимает при `None`) неснижаемый остаток.
+    ///
+    /// Отрицательный остаток имеет смысл только для кредитного счёта;
+    /// для остальных баланс и так не опустится ниже нуля.
+    pub fn set_min_balance(&mut self, min_balance: Option<i64>, now: DateTime<Utc>) {
+        self.min_balance = min_balance;
+        self.updated_at = now;
+    }
+
+    /// Устанавливает (или снимает при `None`) порог низкого баланса.
+    ///
+    /// # Чем порог отличается от `min_balance`?
+    /// `min_balance` запрещает снятие. Порог ничего не запрещает:
+    /// операция проходит, а в ответе появляется предупреждение.
+    pub fn set_low_balance_threshold(&mut self, threshold: Option<i64>, now: DateTime<Utc>) {
+        self.low_balance_threshold = threshold;
+        self.updated_at = now;
+    }
+
+    /// Опустился ли баланс ниже порога именно этой операцией.
+    ///
+    /// # Почему только при пересечении?
+    /// Если баланс уже ниже порога, каждое следующее снятие повторяло бы
+    /// то же предупреждение — и его перестали бы читать. Предупреждаем
+    /// один раз: когда до операции баланс был не ниже порога, а после — ниже.
+    ///
+    /// # Arguments
+    /// * `previous_balance` — баланс до операции (в копейках)
+    pub fn crossed_low_balance(&self, previous_balance: i64) -> bool {
+        self.low_balance_threshold
+            .is_some_and(|threshold| previous_balance >= threshold && self.balance < threshold)
+    }
+
+    /// Заменяет баланс значением, посчитанным по журналу операций.
+    ///
+    /// # Почему в обход `deposit`/`withdraw`?
+    /// Это не операция с деньгами, а исправление ошибки хранения:
+    /// лимиты и неснижаемый остаток тут не при чём. Вызывает только
+    /// сверка (`AccountService::reconcile`).
+    pub fn correct_balance(&mut self, balance: i64, now: DateTime<Utc>) {
+        self.balance = balance;
+        self.updated_at = now;
+    }
+
+    /// Переносит счёт в архив или возвращает из него.
+    ///
+    /// # Чем архив отличается от удаления?
+    /// Удалённый счёт пропадает совсем: его не найти по ID, пока не
+    /// восстановишь. Архивный только не показывается в списке счетов —
+    /// по ID он доступен вместе со всей историей. Так убирают с глаз
+    /// закрытую карту, не теряя выписки по ней.
+    pub fn set_archived(&mut self, archived: bool, now: DateTime<Utc>) {
+        self.archived = archived;
+        self.updated_at = now;
+    }
+
+    /// Закрывает счёт: обнуляет баланс и переносит в архив.
+    ///
+    /// Возвращает баланс до закрытия. Куда он делся — перевод на другой
+    /// счёт или списание — решает и записывает в журнал сервис
+    /// (`AccountService::close_account`).
+    ///
+    /// # Почему в обход `withdraw`?
+    /// Закрытие забирает всё, что есть, а не запрошенную сумму:
+    /// дневной лимит и неснижаемый остаток не должны мешать закрыть счёт.
+    pub fn close(&mut self, now: DateTime<Utc>) -> i64 {
+        self.archived = true;
+        self.updated_at = now;
+        std::mem::take(&mut self.balance)
+    }
+
+    /// Счёт закрыт: в архиве и с нулевым балансом.
+    ///
+    /// Отдельного флага нет — счёт, который обнулили и заархивировали
+    /// вручную, тоже считается закрытым.
+    pub fn is_closed(&self) -> bool {
+        self.archived && self.balance == 0
+    }
+
+    /// Переводит счёт в другую валюту по курсу `rate`.
+    ///
+    /// Баланс пересчитывается вместе с лимитами: дневной лимит снятия,
+    /// кредитный лимит, неснижаемый остаток и порог уведомления заданы
+    /// в валюте счёта и без пересчёта потеряли бы смысл. Каждая сумма
+    /// округляется по `rounding` до точности новой валюты: счёт в JPY
+    /// не получит дробных иен. Возвращает баланс до смены; запись
+    /// в журнал делает сервис (`AccountService::change_currency`).
+    ///
+    /// # Почему курс передаётся, а не берётся из `ExchangeRateProvider`?
+    /// Смену валюты обычно делают по курсу, который назначил банк
+    /// (деноминация, перевод вклада), а не по справочному.
+    ///
+    /// # Errors
+    /// - `InvalidCurrency` — счёт уже в этой валюте
+    /// - `InvalidAmount` — курс не положительный или результат
+    ///   не помещается в `i64`
+    pub fn change_currency(
+        &mut self,
+        currency: &Currency,
+        rate: Decimal,
+        rounding: RoundingMode,
+        now: DateTime<Utc>,
+    ) -> Result<i64, DomainError> {
+        if self.currency == currency.to_string() {
+            return Err(DomainError::InvalidCurrency(format!(
+                "Account is already in {}",
+                currency
+            )));
+        }
+        if rate <= Decimal::ZERO {
+            return Err(DomainError::InvalidAmount(format!(
+                "Exchange rate must be positive, got {}",
+                rate
+            )));
+        }
+
+        let convert = |cents: i64| -> Result<i64, DomainError> {
+            Ok(Money::from_cents(cents)
+                .convert_balance(rate, currency, rounding)?
+                .cents())
+        };
+        let convert_limit = |limit: Option<i64>| limit.map(convert).transpose();
+
+        let balance = convert(self.balance)?;
+        let withdrawal_limit = convert_limit(self.withdrawal_limit)?;
+        let credit_limit = convert_limit(self.credit_limit)?;
+        let min_balance = convert_limit(self.min_balance)?;
+        let low_balance_threshold = convert_limit(self.low_balance_threshold)?;
+
+        // Всё посчитано — только теперь меняем счёт, чтобы ошибка
+        // на середине не оставила его наполовину пересчитанным
+        let previous = std::mem::replace(&mut self.balance, balance);
+        self.currency = currency.to_string();
+        self.withdrawal_limit = withdrawal_limit;
+        self.credit_limit = credit_limit;
+        self.min_balance = min_balance;
+        self.low_balance_threshold = low_balance_threshold;
+        self.updated_at = now;
+        Ok(previous)
+    }
+
+    /// Назначает счёт защиты от овердрафта (`None` — снимает защиту).
+    ///
+    /// Если на этом счёте не хватает денег для снятия, недостающее
+    /// переводится с `source` (см. `AccountService::withdraw`).
+    ///
+    /// # Errors
+    /// `InvalidOverdraftSource` — счёт указывает сам на себя.
+    /// Остальные проверки (счёт существует, та же валюта, нет цикла)
+    /// требуют других счетов, поэтому их делает сервис.
+    pub fn set_overdraft_source(
+        &mut self,
+        source: Option<Uuid>,
+        now: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        if source == Some(self.id) {
+            return Err(DomainError::InvalidOverdraftSource(
+                "An account cannot cover its own overdraft".into(),
+            ));
+        }
+        self.overdraft_source = source;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Сколько ещё можно снять сегодня.
+    ///
+    /// # Arguments
+    /// * `withdrawn_today` — сумма снятий за текущий день (в копейках)
+    ///
+    /// # Возвращает
+    /// `None` если лимита нет; иначе остаток, не меньше нуля.
+    pub fn remaining_withdrawal_limit(&self, withdrawn_today: i64) -> Option<i64> {
+        self.withdrawal_limit
+            .map(|limit| (limit - withdrawn_today).max(0))
+    }
+
+    /// Проверяет дневной лимит снятия.
+    ///
+    /// # Почему отдельный метод?
+    /// Сущность не знает, сколько сегодня уже снято — это знает журнал операций.
+    /// Сервис считает сумму и передаёт её сюда, а правило остаётся в домене.
+    ///
+    /// # Errors
+    /// `LimitExceeded` если `amount` больше остатка лимита
+    pub fn check_withdrawal_limit(
+        &self,
+        amount: i64,
+        withdrawn_today: i64,
+    ) -> Result<(), DomainError> {
+        match (
+            self.withdrawal_limit,
+            self.remaining_withdrawal_limit(withdrawn_today),
+        ) {
+            (Some(limit), Some(remaining)) if amount > remaining => {
+                Err(DomainError::LimitExceeded {
+                    limit,
+                    remaining,
+                    requested: amount,
+                })
+            }
+            _ => Ok(()),
         }
     }
 
@@ -70,47 +549,162 @@ impl Account {
     ///
     /// # Пример
     /// ```text
-    /// account.deposit(10050)?;  // Пополнить на 100.50
+    /// account.deposit(10050, clock.now())?;  // Пополнить на 100.50
     /// ```
-    pub fn deposit(&mut self, amount: i64) -> Result<(), DomainError> {
+    pub fn deposit(&mut self, amount: i64, now: DateTime<Utc>) -> Result<(), DomainError> {
         // Бизнес-правило: нельзя вносить отрицательную или нулевую сумму
         if amount <= 0 {
             return Err(DomainError::InvalidAmount("Amount must be positive".into()));
         }
-        self.balance += amount;
-        self.updated_at = Utc::now(); // Обновляем timestamp
+        // checked_add — None вместо переполнения i64 (паника в debug, мусор в release)
+        self.balance = self
+            .balance
+            .checked_add(amount)
+            .ok_or_else(|| DomainError::InvalidAmount("Resulting balance is too large".into()))?;
+        self.updated_at = now; // Обновляем timestamp
         Ok(())
     }
 
     /// Снимает деньги со счёта.
     ///
+    /// # Arguments
+    /// * `amount` — сумма в копейках
+    /// * `withdrawn_today` — сколько уже снято сегодня (для дневного лимита)
+    ///
     /// # Errors
     /// - `InvalidAmount` — если сумма <= 0
-    /// - `InsufficientFunds` — если недостаточно средств
-    pub fn withdraw(&mut self, amount: i64) -> Result<(), DomainError> {
+    /// - `LimitExceeded` — если превышен дневной лимит
+    /// - `InsufficientFunds` — если недостаточно средств (с учётом кредита)
+    /// - `BelowMinimumBalance` — если баланс опустится ниже `min_balance`
+    ///
+    /// Все проверки идут ДО изменения баланса: отклонённое снятие
+    /// оставляет счёт нетронутым.
+    pub fn withdraw(
+        &mut self,
+        amount: i64,
+        withdrawn_today: i64,
+        now: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
         if amount <= 0 {
             return Err(DomainError::InvalidAmount("Amount must be positive".into()));
         }
-        // Бизнес-правило: нельзя уйти в минус
-        if self.balance < amount {
-            return Err(DomainError::InsufficientFunds {
-                available: self.balance,
-                requested: amount,
-            });
+        self.check_withdrawal_limit(amount, withdrawn_today)?;
+        self.debit(amount, now)
+    }
+
+    /// Списывает сумму отменённого пополнения.
+    ///
+    /// # Почему без дневного лимита?
+    /// Лимит ограничивает, сколько владелец снимает за день. Отмена
+    /// пополнения не снятие: она убирает деньги, которых на счёте быть
+    /// не должно, и не может упереться в лимит, исчерпанный настоящими
+    /// снятиями. Остальные проверки — как у `withdraw`.
+    pub fn reverse_deposit(&mut self, amount: i64, now: DateTime<Utc>) -> Result<(), DomainError> {
+        if amount <= 0 {
+            return Err(DomainError::InvalidAmount("Amount must be positive".into()));
+        }
+        self.debit(amount, now)
+    }
+
+    /// Списание после проверки лимита: кредит, переполнение, неснижаемый остаток.
+    fn debit(&mut self, amount: i64, now: DateTime<Utc>) -> Result<(), DomainError> {
+        // Бизнес-правило: в минус можно уйти только в пределах кредита
+        if let Some(available) = self.available_funds() {
+            if available < amount {
+                return Err(DomainError::InsufficientFunds {
+                    available,
+                    requested: amount,
+                    currency: None,
+                });
+            }
+        }
+        // checked_sub — кредитный счёт без лимита не должен переполнить i64
+        let resulting = self
+            .balance
+            .checked_sub(amount)
+            .ok_or_else(|| DomainError::InvalidAmount("Resulting balance is too small".into()))?;
+        if let Some(minimum) = self.min_balance {
+            if resulting < minimum {
+                return Err(DomainError::BelowMinimumBalance { minimum, resulting });
+            }
         }
-        self.balance -= amount;
-        self.updated_at = Utc::now();
+        self.balance = resulting;
+        self.updated_at = now;
         Ok(())
     }
 
-    /// Конвертирует баланс из копеек в рубли/доллары для отображения.
+    /// Снимает весь баланс; возвращает снятую сумму.
     ///
-    /// # Пример
-    /// ```text
-    /// account.balance = 10050;
-    /// assert_eq!(account.balance_as_f64(), 100.50);
-    /// ```
-    pub fn balance_as_f64(&self) -> f64 {
-        self.balance as f64 / 100.0
+    /// Сумма — ровно `balance` в копейках, без пересчёта из строки,
+    /// поэтому после снятия баланс ровно 0. Кредит не используется:
+    /// "снять всё" — это свои деньги, а не заёмные.
+    ///
+    /// # Errors
+    /// - `InvalidAmount` — баланс не положительный, снимать нечего
+    /// - остальные — как у `withdraw` (дневной лимит, неснижаемый остаток)
+    pub fn withdraw_all(
+        &mut self,
+        withdrawn_today: i64,
+        now: DateTime<Utc>,
+    ) -> Result<i64, DomainError> {
+        let amount = self.balance;
+        if amount <= 0 {
+            return Err(DomainError::InvalidAmount(format!(
+                "Nothing to withdraw: balance is {}",
+                Money::from_cents(amount)
+            )));
+        }
+        self.withdraw(amount, withdrawn_today, now)?;
+        Ok(amount)
+    }
+
+    /// Проценты за `days` дней по годовой ставке `annual_rate` (в процентах).
+    ///
+    /// `balance * annual_rate / 100 * days / 365`, округлённое до целой
+    /// минимальной единицы валюты счёта по правилу `rounding`
+    /// (для JPY — до иены, см. `Money::round_cents`).
+    /// Баланс не меняется — начислить сумму должен вызывающий (`deposit`).
+    ///
+    /// # Почему `Decimal`, а не `f64`?
+    /// `0.1 + 0.2` в `f64` — `0.30000000000000004`. На больших балансах
+    /// такая ошибка доходит до копеек. `Decimal` считает от целых копеек
+    /// точно, округляется только результат — один раз и явно.
+    ///
+    /// # Почему 0 при нулевом и отрицательном балансе?
+    /// Начислять не на что, и это не ошибка. Долг по кредиту проценты
+    /// увеличивают, но это списание, а не начисление — здесь его нет.
+    ///
+    /// # Errors
+    /// `InvalidAmount` — ставка не в `(0, 100]`, период не в `1..=366` дней
+    /// или результат не помещается в `i64`.
+    pub fn interest(
+        &self,
+        annual_rate: Decimal,
+        days: u32,
+        rounding: RoundingMode,
+    ) -> Result<i64, DomainError> {
+        if annual_rate <= Decimal::ZERO || annual_rate > Decimal::ONE_HUNDRED {
+            return Err(DomainError::InvalidAmount(format!(
+                "Annual rate must be above 0 and at most 100 percent, got {}",
+                annual_rate
+            )));
+        }
+        if days == 0 || days > MAX_INTEREST_DAYS {
+            return Err(DomainError::InvalidAmount(format!(
+                "Interest period must be 1 to {} days, got {}",
+                MAX_INTEREST_DAYS, days
+            )));
+        }
+        if self.balance <= 0 {
+            return Ok(0);
+        }
+
+        Decimal::from(self.balance)
+            .checked_mul(annual_rate)
+            .and_then(|d| d.checked_mul(Decimal::from(days)))
+            .and_then(|d| d.checked_div(Decimal::from(100 * DAYS_IN_YEAR)))
+            .and_then(|cents| Money::round_cents(cents, &Currency::new(&self.currency), rounding))
+            .map(|interest| interest.cents())
+            .ok_or_else(|| DomainError::InvalidAmount("Interest is too large".into()))
     }
 }
@@ -0,0 +1,219 @@
+//! Доменная сущность AuditEntry (запись журнала изменений счёта).
+//!
+//! Журнал операций (`Transaction`) отвечает на вопрос, как менялся
+//! БАЛАНС. Журнал изменений — на вопрос, что и когда меняли в самом счёте:
+//! переименование, лимиты, архив, удаление.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::entities::Account;
+use crate::domain::value_objects::Money;
+
+/// Что произошло со счётом.
+///
+/// # Почему нет пополнений и переводов?
+/// Они и так записаны в журнал операций, с суммой и заметкой.
+/// Дублировать их здесь — получить два журнала, которые могут разойтись.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Created,
+    Renamed,
+    DescriptionChanged,
+    WithdrawalLimitChanged,
+    CreditLimitChanged,
+    MinBalanceChanged,
+    LowBalanceThresholdChanged,
+    OverdraftSourceChanged,
+    CurrencyChanged,
+    Archived,
+    Unarchived,
+    Closed,
+    Deleted,
+    Restored,
+}
+
+impl AuditAction {
+    /// Строковое представление — так действие хранится в БД.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Created => "created",
+            AuditAction::Renamed => "renamed",
+            AuditAction::DescriptionChanged => "description_changed",
+            AuditAction::WithdrawalLimitChanged => "withdrawal_limit_changed",
+            AuditAction::CreditLimitChanged => "credit_limit_changed",
+            AuditAction::MinBalanceChanged => "min_balance_changed",
+            AuditAction::LowBalanceThresholdChanged => "low_balance_threshold_changed",
+            AuditAction::OverdraftSourceChanged => "overdraft_source_changed",
+            AuditAction::CurrencyChanged => "currency_changed",
+            AuditAction::Archived => "archived",
+            AuditAction::Unarchived => "unarchived",
+            AuditAction::Closed => "closed",
+            AuditAction::Deleted => "deleted",
+            AuditAction::Restored => "restored",
+        }
+    }
+}
+
+/// Разбор действия из строки (обратное к `as_str`).
+impl std::str::FromStr for AuditAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "created" => Ok(AuditAction::Created),
+            "renamed" => Ok(AuditAction::Renamed),
+            "description_changed" => Ok(AuditAction::DescriptionChanged),
+            "withdrawal_limit_changed" => Ok(AuditAction::WithdrawalLimitChanged),
+            "credit_limit_changed" => Ok(AuditAction::CreditLimitChanged),
+            "min_balance_changed" => Ok(AuditAction::MinBalanceChanged),
+            "low_balance_threshold_changed" => Ok(AuditAction::LowBalanceThresholdChanged),
+            "overdraft_source_changed" => Ok(AuditAction::OverdraftSourceChanged),
+            "currency_changed" => Ok(AuditAction::CurrencyChanged),
+            "archived" => Ok(AuditAction::Archived),
+            "unarchived" => Ok(AuditAction::Unarchived),
+            "closed" => Ok(AuditAction::Closed),
+            "deleted" => Ok(AuditAction::Deleted),
+            "restored" => Ok(AuditAction::Restored),
+            other => Err(format!("Unknown audit action: {}", other)),
+        }
+    }
+}
+
+/// Запись журнала изменений.
+///
+/// # Поля
+/// - `old_value`, `new_value` — значение поля до и после, строкой
+///   (суммы — как в API: `"500.00"`);
+///   `None` — значения не было (лимит не задан) или оно не пишется
+///
+/// # Какие значения не пишутся?
+/// Описание — свободный текст: туда попадают номера карт, договоров,
+/// имена. Журнал хранится вечно и не удаляется вместе со счётом,
+/// поэтому для описания пишется только факт изменения. Владелец
+/// счёта (`user_id`) не пишется никогда.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub account_id: Uuid,
+    pub action: AuditAction,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub at: DateTime<Utc>,
+}
+
+impl AuditEntry {
+    /// Создаёт запись без значений (удаление, закрытие...).
+    pub fn new(account_id: Uuid, action: AuditAction, at: DateTime<Utc>) -> Self {
+        Self {
+            account_id,
+            action,
+            old_value: None,
+            new_value: None,
+            at,
+        }
+    }
+
+    /// Создаёт запись о смене значения поля.
+    pub fn changed(
+        account_id: Uuid,
+        action: AuditAction,
+        old_value: Option<String>,
+        new_value: Option<String>,
+        at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            account_id,
+            action,
+            old_value,
+            new_value,
+            at,
+        }
+    }
+
+    /// Запись о создании счёта: новое значение — имя.
+    pub fn created(account: &Account) -> Self {
+        Self::changed(
+            account.id,
+            AuditAction::Created,
+            None,
+            Some(account.name.clone()),
+            account.created_at,
+        )
+    }
+
+    /// Записи обо всех полях, которыми `after` отличается от `before`.
+    ///
+    /// Баланс не сравнивается — его изменения в журнале операций.
+    /// Сравнение, а не запись в каждом use case по отдельности:
+    /// новое поле в `update_account` не потеряется в журнале, если его
+    /// добавят сюда, а use case, поменявший несколько полей сразу
+    /// (`change_currency` пересчитывает и лимиты), запишет их все.
+    pub fn diff(before: &Account, after: &Account, at: DateTime<Utc>) -> Vec<Self> {
+        let id = after.id;
+        let mut entries = Vec::new();
+        let mut field = |action, old: Option<String>, new: Option<String>| {
+            if old != new {
+                entries.push(Self::changed(id, action, old, new, at));
+            }
+        };
+
+        field(
+            AuditAction::Renamed,
+            Some(before.name.clone()),
+            Some(after.name.clone()),
+        );
+        field(
+            AuditAction::CurrencyChanged,
+            Some(before.currency.clone()),
+            Some(after.currency.clone()),
+        );
+        field(
+            AuditAction::WithdrawalLimitChanged,
+            money(before.withdrawal_limit),
+            money(after.withdrawal_limit),
+        );
+        field(
+            AuditAction::CreditLimitChanged,
+            money(before.credit_limit),
+            money(after.credit_limit),
+        );
+        field(
+            AuditAction::MinBalanceChanged,
+            money(before.min_balance),
+            money(after.min_balance),
+        );
+        field(
+            AuditAction::LowBalanceThresholdChanged,
+            money(before.low_balance_threshold),
+            money(after.low_balance_threshold),
+        );
+        field(
+            AuditAction::OverdraftSourceChanged,
+            before.overdraft_source.map(|id| id.to_string()),
+            after.overdraft_source.map(|id| id.to_string()),
+        );
+
+        // Значение описания не пишется (см. `AuditEntry`)
+        if before.description != after.description {
+            entries.push(Self::new(id, AuditAction::DescriptionChanged, at));
+        }
+
+        if before.archived != after.archived {
+            let action = if after.archived {
+                AuditAction::Archived
+            } else {
+                AuditAction::Unarchived
+            };
+            entries.push(Self::new(id, action, at));
+        }
+
+        entries
+    }
+}
+
+/// Сумма в копейках строкой — в том же формате, что суммы в API.
+fn money(cents: Option<i64>) -> Option<String> {
+    cents.map(|cents| Money::from_cents(cents).to_string())
+}
@@ -0,0 +1,31 @@
+//! Доменная сущность BalanceSnapshot (снимок баланса).
+//!
+//! Снимок фиксирует баланс счёта в момент времени.
+//! Последовательность снимков — это график баланса.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Снимок баланса счёта.
+///
+/// # Чем отличается от Transaction?
+/// Операция хранит ИЗМЕНЕНИЕ (+100), снимок — РЕЗУЛЬТАТ (баланс 350).
+/// Для графика удобнее готовые значения, чем пересчёт суммы операций.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceSnapshot {
+    pub account_id: Uuid,
+    pub balance: i64,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl BalanceSnapshot {
+    /// Создаёт снимок баланса на момент `recorded_at`.
+    pub fn new(account_id: Uuid, balance: i64, recorded_at: DateTime<Utc>) -> Self {
+        Self {
+            account_id,
+            balance,
+            recorded_at,
+        }
+    }
+}
@@ -1,3 +1,15 @@
 mod account;
+mod audit_entry;
+mod balance_snapshot;
+mod recurring_transaction;
+mod transaction;
+mod wallet;
+mod webhook;
 
-pub use account::Account;
+pub use account::{Account, AccountType};
+pub use audit_entry::{AuditAction, AuditEntry};
+pub use balance_snapshot::BalanceSnapshot;
+pub use recurring_transaction::{RecurringKind, RecurringTransaction};
+pub use transaction::{Transaction, TransactionKind};
+pub use wallet::Wallet;
+pub use webhook::Webhook;
@@ -0,0 +1,126 @@
+//! Доменная сущность RecurringTransaction (повторяющаяся операция).
+//!
+//! Правило вида "пополнять счёт на 1000.00 первого числа каждого месяца".
+//! Само правило деньги не двигает — его исполняет планировщик,
+//! вызывая обычные deposit/withdraw.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::errors::DomainError;
+use crate::domain::value_objects::Schedule;
+
+/// Что делает правило.
+///
+/// # Почему не `TransactionKind`?
+/// У `TransactionKind` есть половинки переводов, а переводы по расписанию
+/// не поддерживаются. Отдельный enum делает такое правило непредставимым.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecurringKind {
+    Deposit,
+    Withdraw,
+}
+
+impl RecurringKind {
+    /// Строковое представление — так тип хранится в БД.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RecurringKind::Deposit => "deposit",
+            RecurringKind::Withdraw => "withdraw",
+        }
+    }
+}
+
+/// Разбор из строки (обратное к `as_str`).
+impl std::str::FromStr for RecurringKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "deposit" => Ok(RecurringKind::Deposit),
+            "withdraw" => Ok(RecurringKind::Withdraw),
+            other => Err(format!("Unknown recurring kind: {}", other)),
+        }
+    }
+}
+
+/// Правило повторяющейся операции.
+///
+/// # Поля
+/// - `amount` — сумма в копейках (всегда положительная)
+/// - `schedule` — как часто выполнять
+/// - `next_run` — когда выполнить в следующий раз
+/// - `enabled` — выключенное правило планировщик пропускает
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecurringTransaction {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub kind: RecurringKind,
+    pub amount: i64,
+    pub schedule: Schedule,
+    pub next_run: DateTime<Utc>,
+    pub enabled: bool,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RecurringTransaction {
+    /// Создаёт включённое правило с первым запуском в `first_run`;
+    /// `now` — время создания.
+    ///
+    /// # Errors
+    /// `InvalidAmount` если сумма <= 0
+    pub fn new(
+        account_id: Uuid,
+        kind: RecurringKind,
+        amount: i64,
+        schedule: Schedule,
+        first_run: DateTime<Utc>,
+        note: Option<String>,
+        now: DateTime<Utc>,
+    ) -> Result<Self, DomainError> {
+        Self::check_amount(amount)?;
+        Ok(Self {
+            id: Uuid::new_v4(),
+            account_id,
+            kind,
+            amount,
+            schedule,
+            next_run: first_run,
+            enabled: true,
+            note,
+            created_at: now,
+        })
+    }
+
+    /// Меняет сумму.
+    ///
+    /// # Errors
+    /// `InvalidAmount` если сумма <= 0
+    pub fn set_amount(&mut self, amount: i64) -> Result<(), DomainError> {
+        Self::check_amount(amount)?;
+        self.amount = amount;
+        Ok(())
+    }
+
+    /// Переносит `next_run` на первый запуск позже `now`.
+    ///
+    /// # Почему не просто на один шаг?
+    /// Если сервис не работал неделю, ежедневное правило пропустило
+    /// семь запусков. Догонять их пачкой (семь зарплат сразу) хуже,
+    /// чем пропустить: правило выполняется один раз и ждёт следующего срока.
+    pub fn advance(&mut self, now: DateTime<Utc>) {
+        while self.next_run <= now {
+            self.next_run = self.schedule.next_after(self.next_run);
+        }
+    }
+
+    fn check_amount(amount: i64) -> Result<(), DomainError> {
+        if amount <= 0 {
+            return Err(DomainError::InvalidAmount("Amount must be positive".into()));
+        }
+        Ok(())
+    }
+}
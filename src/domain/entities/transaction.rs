@@ -0,0 +1,292 @@
+//! Доменная сущность Transaction (операция по счёту).
+//!
+//! Каждое изменение баланса записывается как отдельная операция.
+//! Это "журнал" (ledger): по нему можно восстановить, КАК счёт
+//! пришёл к текущему балансу.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::errors::DomainError;
+
+/// Максимальная длина категории (совпадает с колонкой `VARCHAR(50)`).
+const MAX_CATEGORY_LENGTH: usize = 50;
+
+/// Тип операции.
+///
+/// # Почему enum, а не строка?
+/// Компилятор проверяет, что мы обработали все варианты в `match`.
+/// Опечатка вроде "depsoit" просто не скомпилируется.
+///
+/// # Почему у перевода два варианта?
+/// Перевод пишется в журнал двумя записями — по одной на каждый счёт.
+/// Направление нужно различать: исходящий перевод расходует дневной
+/// лимит снятия, входящий — нет.
+///
+/// # Почему проценты — не `Deposit`?
+/// Их начисляет сервер, а не пользователь. Отдельный тип отличает
+/// доход от процентов в выписке и в журнале от обычных пополнений.
+///
+/// # Почему `Reconciliation` не меняет баланс по журналу?
+/// Сверка исправляет сохранённый баланс так, чтобы он совпал с суммой
+/// журнала (см. `AccountService::reconcile`). Запись нужна, чтобы
+/// исправление было видно в истории; войди она в сумму журнала —
+/// следующая сверка снова нашла бы расхождение.
+///
+/// # Почему у `CurrencyChange` сумма со знаком?
+/// Смена валюты счёта (`Account::change_currency`) пересчитывает баланс
+/// по курсу, и он может как вырасти, так и уменьшиться. Запись хранит
+/// это изменение — новый баланс минус старый, — чтобы сумма журнала
+/// по-прежнему давала баланс счёта.
+///
+/// # Откуда `OpeningBalance`?
+/// Журнал появился позже счетов: у счёта, на котором уже были деньги,
+/// сумма журнала начиналась с нуля. Миграция
+/// `20240126000000_backfill_opening_balances` записала таким счетам
+/// остаток на момент появления журнала, датой создания счёта. Сервер
+/// сам таких записей не создаёт. Сумма со знаком, как у `CurrencyChange`:
+/// у кредитного счёта остаток бывает отрицательным. Только у этих двух
+/// типов `amount` бывает меньше нуля.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionKind {
+    Deposit,
+    Withdraw,
+    TransferIn,
+    TransferOut,
+    Interest,
+    Reconciliation,
+    CurrencyChange,
+    OpeningBalance,
+}
+
+impl TransactionKind {
+    /// Увеличивает ли операция баланс.
+    pub fn is_credit(&self) -> bool {
+        matches!(
+            self,
+            TransactionKind::Deposit | TransactionKind::TransferIn | TransactionKind::Interest
+        )
+    }
+
+    /// Уменьшает ли операция баланс.
+    ///
+    /// Не то же, что `!is_credit()`: `Reconciliation`, `CurrencyChange`
+    /// и `OpeningBalance` — ни то, ни другое.
+    pub fn is_debit(&self) -> bool {
+        matches!(
+            self,
+            TransactionKind::Withdraw | TransactionKind::TransferOut
+        )
+    }
+
+    /// Изменение баланса от операции: `amount` для поступлений, `-amount` для списаний,
+    /// 0 для `Reconciliation`, `amount` как есть (со знаком) для `CurrencyChange`
+    /// и `OpeningBalance`.
+    pub fn signed(&self, amount: i64) -> i64 {
+        if self.is_credit()
+            || matches!(
+                self,
+                TransactionKind::CurrencyChange | TransactionKind::OpeningBalance
+            )
+        {
+            amount
+        } else if self.is_debit() {
+            -amount
+        } else {
+            0
+        }
+    }
+
+    /// Строковое представление — так тип хранится в БД.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransactionKind::Deposit => "deposit",
+            TransactionKind::Withdraw => "withdraw",
+            TransactionKind::TransferIn => "transfer_in",
+            TransactionKind::TransferOut => "transfer_out",
+            TransactionKind::Interest => "interest",
+            TransactionKind::Reconciliation => "reconciliation",
+            TransactionKind::CurrencyChange => "currency_change",
+            TransactionKind::OpeningBalance => "opening_balance",
+        }
+    }
+}
+
+/// Разбор типа операции из строки (обратное к `as_str`).
+///
+/// # Trait `FromStr`
+/// Позволяет писать `"deposit".parse::<TransactionKind>()`.
+impl std::str::FromStr for TransactionKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "deposit" => Ok(TransactionKind::Deposit),
+            "withdraw" => Ok(TransactionKind::Withdraw),
+            "transfer_in" => Ok(TransactionKind::TransferIn),
+            "transfer_out" => Ok(TransactionKind::TransferOut),
+            "interest" => Ok(TransactionKind::Interest),
+            "reconciliation" => Ok(TransactionKind::Reconciliation),
+            "currency_change" => Ok(TransactionKind::CurrencyChange),
+            "opening_balance" => Ok(TransactionKind::OpeningBalance),
+            other => Err(format!("Unknown transaction kind: {}", other)),
+        }
+    }
+}
+
+/// Сущность "Операция" — одна запись в журнале счёта.
+///
+/// # Поля
+/// - `account_id` — счёт, к которому относится операция
+/// - `amount` — сумма в копейках/центах (положительная; со знаком
+///   только у `CurrencyChange` и `OpeningBalance`, см. `TransactionKind`)
+/// - `timestamp` — момент выполнения операции
+/// - `note` — необязательный комментарий ("Зарплата", "Кофе")
+/// - `counterparty_id` — для перевода: счёт на другой стороне
+/// - `counter_amount` — для перевода: сумма на другой стороне, в её валюте.
+///   Отличается от `amount`, только если перевод был с конвертацией
+/// - `category` — категория для бюджета ("groceries", "salary"), см. `normalize_category`
+/// - `reversed_at` — когда операцию отменили (`None` — не отменена), см. `reverse`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transaction {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub kind: TransactionKind,
+    pub amount: i64,
+    pub timestamp: DateTime<Utc>,
+    pub note: Option<String>,
+    #[serde(default)] // В копиях, снятых до появления переводов, полей нет
+    pub counterparty_id: Option<Uuid>,
+    #[serde(default)]
+    pub counter_amount: Option<i64>,
+    #[serde(default)] // В копиях, снятых до появления категорий, поля нет
+    pub category: Option<String>,
+    #[serde(default)] // В копиях, снятых до появления отмены, поля нет
+    pub reversed_at: Option<DateTime<Utc>>,
+}
+
+impl Transaction {
+    /// Название группы операций без категории в отчётах.
+    ///
+    /// Такую категорию нельзя задать явно — иначе она смешалась бы
+    /// с операциями, у которых категории нет.
+    pub const UNCATEGORIZED: &'static str = "(uncategorized)";
+
+    /// Создаёт новую запись журнала со временем `timestamp`.
+    ///
+    /// # Пример
+    /// ```text
+    /// let tx = Transaction::new(account.id, TransactionKind::Deposit, 10050, None, clock.now());
+    /// ```
+    pub fn new(
+        account_id: Uuid,
+        kind: TransactionKind,
+        amount: i64,
+        note: Option<String>,
+        timestamp: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            account_id,
+            kind,
+            amount,
+            timestamp,
+            note,
+            counterparty_id: None,
+            counter_amount: None,
+            category: None,
+            reversed_at: None,
+        }
+    }
+
+    /// Приводит категорию к виду, в котором она хранится.
+    ///
+    /// # Почему нижний регистр?
+    /// Категория нужна для группировки: "Groceries" и "groceries " —
+    /// одна и та же статья бюджета, и в отчёте должны быть одной строкой.
+    ///
+    /// # Errors
+    /// `InvalidCategory` если после обрезки пробелов категория пустая,
+    /// длиннее `MAX_CATEGORY_LENGTH` символов или равна `UNCATEGORIZED`.
+    pub fn normalize_category(category: &str) -> Result<String, DomainError> {
+        let normalized = category.trim().to_lowercase();
+        if normalized.is_empty() {
+            return Err(DomainError::InvalidCategory(
+                "Category must not be empty".into(),
+            ));
+        }
+        if normalized.chars().count() > MAX_CATEGORY_LENGTH {
+            return Err(DomainError::InvalidCategory(format!(
+                "Category must be at most {} characters",
+                MAX_CATEGORY_LENGTH
+            )));
+        }
+        if normalized == Self::UNCATEGORIZED {
+            return Err(DomainError::InvalidCategory(format!(
+                "'{}' is reserved",
+                Self::UNCATEGORIZED
+            )));
+        }
+        Ok(normalized)
+    }
+
+    /// Задаёт категорию (уже приведённую `normalize_category`).
+    pub fn with_category(mut self, category: Option<String>) -> Self {
+        self.category = category;
+        self
+    }
+
+    /// Отменяет операцию: помечает её отменённой и возвращает обратную запись.
+    ///
+    /// # Почему обратная запись, а не удаление?
+    /// Сумма журнала должна совпадать с балансом (см. `AccountService::reconcile`).
+    /// Удалённая операция пропала бы из суммы, а изменённый ею баланс — нет.
+    /// Обратная запись возвращает баланс, обе операции остаются в истории,
+    /// а `reversed_at` показывает, какая из них отменена.
+    ///
+    /// # Errors
+    /// `TransactionNotReversible` если операция уже отменена или это не
+    /// пополнение и не снятие: у перевода вторая половина на другом счёте,
+    /// а проценты, сверку, смену валюты и начальный остаток записывает
+    /// не клиент.
+    pub fn reverse(&mut self, now: DateTime<Utc>) -> Result<Transaction, DomainError> {
+        if self.reversed_at.is_some() {
+            return Err(DomainError::TransactionNotReversible(format!(
+                "Transaction {} is already reversed",
+                self.id
+            )));
+        }
+        let kind = match self.kind {
+            TransactionKind::Deposit => TransactionKind::Withdraw,
+            TransactionKind::Withdraw => TransactionKind::Deposit,
+            other => {
+                return Err(DomainError::TransactionNotReversible(format!(
+                    "Only deposits and withdrawals can be reversed, not {}",
+                    other.as_str()
+                )))
+            }
+        };
+
+        self.reversed_at = Some(now);
+        let note = Some(format!("Reversal of {}", self.id));
+        Ok(
+            Transaction::new(self.account_id, kind, self.amount, note, now)
+                .with_category(self.category.clone()),
+        )
+    }
+
+    /// Помечает запись как половину перевода.
+    ///
+    /// # Пример
+    /// ```text
+    /// let tx = Transaction::new(from.id, TransactionKind::TransferOut, 1000, None, now)
+    ///     .with_counterparty(to.id, 923);
+    /// ```
+    pub fn with_counterparty(mut self, counterparty_id: Uuid, counter_amount: i64) -> Self {
+        self.counterparty_id = Some(counterparty_id);
+        self.counter_amount = Some(counter_amount);
+        self
+    }
+}
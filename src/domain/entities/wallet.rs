@@ -0,0 +1,138 @@
+//! Доменная сущность Wallet (кошелёк с несколькими валютами).
+//!
+//! У счёта одна валюта. Кошелёк держит остатки сразу в нескольких:
+//! «наличные в поездке» — доллары, евро и немного местной валюты.
+//! Пополнение и снятие всегда в конкретной валюте; валюты между собой
+//! не смешиваются и не пересчитываются.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::errors::DomainError;
+use crate::domain::value_objects::Currency;
+
+/// Максимальная длина имени (совпадает с колонкой `VARCHAR(100)`).
+const MAX_NAME_LENGTH: usize = 100;
+
+/// Кошелёк с остатками в нескольких валютах.
+///
+/// # Поля
+/// - `balances` — остаток в минимальных единицах для каждой валюты.
+///   Валюты с нулевым остатком в нём нет: снятие до нуля её убирает
+/// - `version` — для optimistic locking, как у `Account`
+#[derive(Debug, Clone)]
+pub struct Wallet {
+    pub id: Uuid,
+    pub name: String,
+    pub balances: HashMap<Currency, i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub version: i64,
+}
+
+impl Wallet {
+    /// Создаёт пустой кошелёк.
+    ///
+    /// # Errors
+    /// `InvalidName` если имя пустое или длиннее `MAX_NAME_LENGTH`.
+    pub fn new(name: &str, now: DateTime<Utc>) -> Result<Self, DomainError> {
+        Ok(Self {
+            id: Uuid::new_v4(),
+            name: Self::normalize_name(name)?,
+            balances: HashMap::new(),
+            created_at: now,
+            updated_at: now,
+            version: 1,
+        })
+    }
+
+    /// Обрезает пробелы по краям имени и проверяет его
+    /// (те же правила, что у `Account::normalize_name`).
+    ///
+    /// # Errors
+    /// `InvalidName` если после обрезки имя пустое
+    /// или длиннее `MAX_NAME_LENGTH` символов.
+    pub fn normalize_name(name: &str) -> Result<String, DomainError> {
+        let trimmed = name.trim();
+        if trimmed.is_empty() {
+            return Err(DomainError::InvalidName(
+                "Wallet name must not be empty".into(),
+            ));
+        }
+        if trimmed.chars().count() > MAX_NAME_LENGTH {
+            return Err(DomainError::InvalidName(format!(
+                "Wallet name must be at most {} characters",
+                MAX_NAME_LENGTH
+            )));
+        }
+        Ok(trimmed.to_string())
+    }
+
+    /// Остаток в валюте `currency`; 0, если её в кошельке нет.
+    pub fn balance(&self, currency: &Currency) -> i64 {
+        self.balances.get(currency).copied().unwrap_or(0)
+    }
+
+    /// Пополняет кошелёк в валюте `currency`.
+    ///
+    /// # Errors
+    /// `InvalidAmount` если сумма <= 0 или остаток переполнит `i64`.
+    pub fn deposit(
+        &mut self,
+        currency: Currency,
+        amount: i64,
+        now: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        if amount <= 0 {
+            return Err(DomainError::InvalidAmount("Amount must be positive".into()));
+        }
+        let balance = self
+            .balance(&currency)
+            .checked_add(amount)
+            .ok_or_else(|| DomainError::InvalidAmount("Resulting balance is too large".into()))?;
+        self.balances.insert(currency, balance);
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Снимает деньги в валюте `currency`.
+    ///
+    /// # Почему нет кредита и лимитов, как у счёта?
+    /// Кошелёк — это наличные: в минус они не уходят. Остатка в одной
+    /// валюте не покрывает остаток в другой — пересчёт по курсу
+    /// здесь не делается.
+    ///
+    /// # Errors
+    /// - `InvalidAmount` если сумма <= 0
+    /// - `InsufficientFunds` с `currency`, если в этой валюте не хватает
+    ///   (в том числе если её в кошельке нет совсем)
+    pub fn withdraw(
+        &mut self,
+        currency: &Currency,
+        amount: i64,
+        now: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        if amount <= 0 {
+            return Err(DomainError::InvalidAmount("Amount must be positive".into()));
+        }
+        let available = self.balance(currency);
+        if available < amount {
+            return Err(DomainError::InsufficientFunds {
+                available,
+                requested: amount,
+                currency: Some(currency.to_string()),
+            });
+        }
+
+        let remaining = available - amount;
+        if remaining == 0 {
+            self.balances.remove(currency);
+        } else {
+            self.balances.insert(currency.clone(), remaining);
+        }
+        self.updated_at = now;
+        Ok(())
+    }
+}
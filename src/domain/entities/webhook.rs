@@ -0,0 +1,110 @@
+//! Доменная сущность Webhook (подписка на изменения баланса).
+//!
+//! После каждой операции, изменившей баланс, сервер отправляет POST
+//! на `url` каждой включённой подписки. Тело подписывается `secret`,
+//! чтобы получатель мог проверить, что запрос пришёл от нас.
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::Account;
+use crate::domain::errors::DomainError;
+
+/// Максимальная длина URL (совпадает с колонкой `VARCHAR(2048)`).
+const MAX_URL_LENGTH: usize = 2048;
+
+/// Минимальная длина секрета: короткий секрет подбирается перебором.
+const MIN_SECRET_LENGTH: usize = 16;
+
+/// Максимальная длина секрета (совпадает с колонкой `VARCHAR(256)`).
+const MAX_SECRET_LENGTH: usize = 256;
+
+/// Подписка на события изменения баланса.
+///
+/// `user_id` — владелец подписки: ей приходят события только его счетов,
+/// а другие пользователи её не видят (как счёт, см. `Account::user_id`).
+///
+/// # Почему без `Serialize`?
+/// В сущности лежит `secret`. Наружу она уходит только через
+/// `WebhookResponse`, где секрета нет, — случайно отдать его клиенту
+/// в JSON не получится.
+#[derive(Debug, Clone)]
+pub struct Webhook {
+    pub id: Uuid,
+    pub url: String,
+    pub secret: String,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub user_id: String,
+}
+
+impl Webhook {
+    /// Создаёт включённую подписку.
+    ///
+    /// Владелец — `Account::DEFAULT_USER`; подписку другого пользователя
+    /// сервис создаёт так же и затем меняет `user_id`.
+    ///
+    /// # Errors
+    /// `InvalidWebhook` если URL или секрет не проходят проверку
+    /// (см. `normalize_url`, `check_secret`).
+    pub fn new(url: &str, secret: String) -> Result<Self, DomainError> {
+        let url = Self::normalize_url(url)?;
+        Self::check_secret(&secret)?;
+        Ok(Self {
+            id: Uuid::new_v4(),
+            url,
+            secret,
+            enabled: true,
+            created_at: Utc::now(),
+            user_id: Account::DEFAULT_USER.to_string(),
+        })
+    }
+
+    /// Обрезает пробелы по краям URL и проверяет его.
+    ///
+    /// # Почему только `http://`?
+    /// В сервере нет TLS-клиента. До получателя с `https://` нужно
+    /// ставить прокси, который сам устанавливает TLS-соединение.
+    /// Отклонить такой URL сразу честнее, чем принять подписку,
+    /// которая никогда не доставит ни одного события.
+    ///
+    /// # Errors
+    /// `InvalidWebhook` если URL не начинается с `http://`, в нём нет
+    /// адреса хоста или он длиннее `MAX_URL_LENGTH`.
+    pub fn normalize_url(url: &str) -> Result<String, DomainError> {
+        let url = url.trim();
+        let Some(rest) = url.strip_prefix("http://") else {
+            return Err(DomainError::InvalidWebhook(
+                "Webhook URL must start with http://".into(),
+            ));
+        };
+        if rest.is_empty() || rest.starts_with('/') {
+            return Err(DomainError::InvalidWebhook(
+                "Webhook URL must include a host".into(),
+            ));
+        }
+        if url.len() > MAX_URL_LENGTH {
+            return Err(DomainError::InvalidWebhook(format!(
+                "Webhook URL must be at most {} characters",
+                MAX_URL_LENGTH
+            )));
+        }
+        Ok(url.to_string())
+    }
+
+    /// Проверяет длину секрета.
+    ///
+    /// # Errors
+    /// `InvalidWebhook` если секрет короче `MIN_SECRET_LENGTH`
+    /// или длиннее `MAX_SECRET_LENGTH` символов.
+    pub fn check_secret(secret: &str) -> Result<(), DomainError> {
+        let length = secret.chars().count();
+        if !(MIN_SECRET_LENGTH..=MAX_SECRET_LENGTH).contains(&length) {
+            return Err(DomainError::InvalidWebhook(format!(
+                "Webhook secret must be {} to {} characters",
+                MIN_SECRET_LENGTH, MAX_SECRET_LENGTH
+            )));
+        }
+        Ok(())
+    }
+}
@@ -3,6 +3,8 @@
 //! Эти ошибки не знают об HTTP кодах или базе данных.
 //! Они описывают что пошло не так с точки зрения бизнеса.
 
+use std::collections::BTreeMap;
+
 use thiserror::Error;
 
 /// Перечисление всех возможных доменных ошибок.
@@ -16,16 +18,167 @@ pub enum DomainError {
     #[error("Invalid amount: {0}")]
     InvalidAmount(String),
 
+    /// В сумме больше знаков после запятой, чем допускает валюта:
+    /// `"10.5"` для JPY. Округлять молча нельзя — клиент думает,
+    /// что перевёл одну сумму, а проведена другая.
+    #[error("{currency} accepts at most {max_decimals} decimal places")]
+    PrecisionExceeded { currency: String, max_decimals: u32 },
+
     /// Недостаточно средств на счёте.
     /// Используем named fields для удобства форматирования.
+    ///
+    /// `currency` — в какой валюте не хватило, если их несколько
+    /// (кошелёк). У счёта валюта одна, там `None`.
     #[error("Insufficient funds: available {available}, requested {requested}")]
-    InsufficientFunds { available: i64, requested: i64 },
+    InsufficientFunds {
+        available: i64,
+        requested: i64,
+        currency: Option<String>,
+    },
+
+    /// Превышен дневной лимит снятия.
+    /// `remaining` — сколько ещё можно снять сегодня.
+    #[error("Daily withdrawal limit exceeded: limit {limit}, remaining {remaining}, requested {requested}")]
+    LimitExceeded {
+        limit: i64,
+        remaining: i64,
+        requested: i64,
+    },
+
+    /// Снятие опустило бы баланс ниже неснижаемого остатка счёта
+    #[error("Balance would fall below minimum: minimum {minimum}, resulting {resulting}")]
+    BelowMinimumBalance { minimum: i64, resulting: i64 },
+
+    /// Нет курса для пары валют
+    #[error("Exchange rate unavailable: {from} -> {to}")]
+    RateUnavailable { from: String, to: String },
+
+    /// Перевод со счёта на тот же самый счёт
+    #[error("Cannot transfer to the same account: {0}")]
+    SameAccountTransfer(String),
+
+    /// Некорректное имя счёта (пустое или слишком длинное)
+    #[error("Invalid account name: {0}")]
+    InvalidName(String),
+
+    /// Слишком длинное описание счёта
+    #[error("Invalid account description: {0}")]
+    InvalidDescription(String),
+
+    /// Некорректный код валюты (не три латинские буквы)
+    #[error("Invalid currency: {0}")]
+    InvalidCurrency(String),
+
+    /// Некорректная категория операции (пустая, слишком длинная или зарезервированная)
+    #[error("Invalid category: {0}")]
+    InvalidCategory(String),
+
+    /// Некорректное расписание повторяющейся операции
+    #[error("Invalid schedule: {0}")]
+    InvalidSchedule(String),
+
+    /// Недопустимый счёт защиты от овердрафта (сам счёт, другая валюта, цикл)
+    #[error("Invalid overdraft source: {0}")]
+    InvalidOverdraftSource(String),
+
+    /// Некорректная подписка на события (URL или секрет)
+    #[error("Invalid webhook: {0}")]
+    InvalidWebhook(String),
+
+    /// Подписка на события не найдена
+    #[error("Webhook not found: {0}")]
+    WebhookNotFound(String),
+
+    /// Кошелёк не найден
+    #[error("Wallet not found: {0}")]
+    WalletNotFound(String),
+
+    /// Кошелёк изменили параллельно (как `ConcurrentModification` у счёта)
+    #[error("Wallet was modified concurrently: {0}")]
+    WalletModified(String),
+
+    /// Правило повторяющейся операции не найдено
+    #[error("Recurring transaction not found: {0}")]
+    RecurringNotFound(String),
+
+    /// Операция не найдена (или относится к другому счёту)
+    #[error("Transaction not found: {0}")]
+    TransactionNotFound(String),
+
+    /// Операцию нельзя отменить: уже отменена или её тип не отменяется
+    #[error("Transaction cannot be reversed: {0}")]
+    TransactionNotReversible(String),
 
     /// Счёт не найден
     #[error("Account not found: {0}")]
     AccountNotFound(String),
 
+    /// Удаление счёта, на котором остались деньги (или долг)
+    #[error("Account is not empty: balance {balance}")]
+    AccountNotEmpty { balance: i64 },
+
+    /// Зачисление остатка на закрытый счёт (см. `Account::is_closed`)
+    #[error("Account is closed: {0}")]
+    AccountClosed(String),
+
+    /// Смена валюты счёта, у которого есть активные регулярные операции
+    #[error("Account has {count} active recurring transactions")]
+    AccountHasRecurring { count: usize },
+
     /// Счёт с таким именем уже существует
     #[error("Account already exists: {0}")]
     AccountAlreadyExists(String),
+
+    /// Достигнут лимит числа счетов (`MAX_ACCOUNTS`)
+    #[error("Account quota exceeded: limit {limit}")]
+    QuotaExceeded { limit: u64 },
+
+    /// Счёт изменили параллельно: версия в хранилище не совпала с прочитанной
+    #[error("Account was modified concurrently: {0}")]
+    ConcurrentModification(String),
+
+    /// Некорректны одно или несколько полей запроса: поле → причина.
+    ///
+    /// # Зачем, если есть `InvalidName`, `InvalidCurrency`...?
+    /// Те останавливают проверку на первой ошибке. Форме в UI нужно
+    /// подсветить все неверные поля сразу, поэтому сервис собирает
+    /// причины по полям и возвращает их вместе.
+    /// `BTreeMap` — поля в сообщении всегда в одном порядке.
+    #[error("{}", join_fields(.0))]
+    InvalidFields(BTreeMap<String, String>),
+}
+
+impl DomainError {
+    /// Причина без префикса варианта: `"Account name must not be empty"`
+    /// вместо `"Invalid account name: Account name must not be empty"`.
+    ///
+    /// Для ошибок проверки одного значения — их текст как есть,
+    /// для остальных — обычный `Display`.
+    pub fn reason(&self) -> String {
+        match self {
+            DomainError::InvalidAmount(msg)
+            | DomainError::InvalidName(msg)
+            | DomainError::InvalidDescription(msg)
+            | DomainError::InvalidCurrency(msg)
+            | DomainError::InvalidCategory(msg)
+            | DomainError::InvalidSchedule(msg)
+            | DomainError::InvalidOverdraftSource(msg)
+            | DomainError::InvalidWebhook(msg) => msg.clone(),
+            other => other.to_string(),
+        }
+    }
+
+    /// `InvalidFields` из одной ошибки поля `field`.
+    pub fn field(field: &str, err: DomainError) -> DomainError {
+        DomainError::InvalidFields(BTreeMap::from([(field.to_string(), err.reason())]))
+    }
+}
+
+/// `"currency: ...; name: ..."` — для `Display` у `InvalidFields`.
+fn join_fields(fields: &BTreeMap<String, String>) -> String {
+    fields
+        .iter()
+        .map(|(field, reason)| format!("{}: {}", field, reason))
+        .collect::<Vec<_>>()
+        .join("; ")
 }
@@ -0,0 +1,64 @@
+//! Value Object Currency — код валюты.
+
+use std::fmt;
+
+use crate::domain::errors::DomainError;
+
+/// Код валюты в верхнем регистре: `USD`, `EUR`, `RUB`.
+///
+/// # Почему не просто `String`?
+/// `"usd"` и `"USD"` — одна валюта. Конструктор приводит код к верхнему
+/// регистру, поэтому сравнение и поиск курса не зависят от того,
+/// как валюту записали при создании счёта.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Currency(String);
+
+impl Currency {
+    /// Создаёт код валюты (пробелы по краям отбрасываются).
+    pub fn new(code: &str) -> Self {
+        Self(code.trim().to_uppercase())
+    }
+
+    /// Проверяет код валюты из запроса клиента и создаёт `Currency`.
+    ///
+    /// # Почему только формат, а не список ISO 4217?
+    /// Список меняется (новые валюты, деноминации), а сервер с ним
+    /// не обновится. Три латинские буквы отсекают опечатки вроде
+    /// `"US"` или `"доллар"`, а курс для неизвестной валюты просто
+    /// не найдётся при конвертации.
+    ///
+    /// `new` остаётся без проверки: он разбирает коды, уже лежащие
+    /// в хранилище, и коды из настроек курсов.
+    ///
+    /// # Errors
+    /// `InvalidCurrency` если код — не три латинские буквы.
+    pub fn parse(code: &str) -> Result<Self, DomainError> {
+        let currency = Self::new(code);
+        if currency.0.len() != 3 || !currency.0.bytes().all(|b| b.is_ascii_uppercase()) {
+            return Err(DomainError::InvalidCurrency(format!(
+                "Currency must be a 3-letter ISO 4217 code, got '{}'",
+                code.trim()
+            )));
+        }
+        Ok(currency)
+    }
+
+    /// Число знаков после запятой в минимальной единице (ISO 4217).
+    ///
+    /// `JPY` — 0 (у иены нет «копеек»), `BHD` — 3 (филсы),
+    /// большинство валют — 2. Неизвестный код считаем двухзначным.
+    pub fn decimal_places(&self) -> u32 {
+        match self.0.as_str() {
+            "BIF" | "CLP" | "DJF" | "GNF" | "ISK" | "JPY" | "KMF" | "KRW" | "PYG" | "RWF"
+            | "UGX" | "UYI" | "VND" | "VUV" | "XAF" | "XOF" | "XPF" => 0,
+            "BHD" | "IQD" | "JOD" | "KWD" | "LYD" | "OMR" | "TND" => 3,
+            _ => 2,
+        }
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
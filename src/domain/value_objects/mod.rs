@@ -0,0 +1,11 @@
+mod currency;
+mod money;
+mod precision;
+mod rounding;
+mod schedule;
+
+pub use currency::Currency;
+pub use money::Money;
+pub use precision::PrecisionMode;
+pub use rounding::RoundingMode;
+pub use schedule::Schedule;
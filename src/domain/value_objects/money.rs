@@ -0,0 +1,442 @@
+//! Value Object Money — денежная сумма в минимальных единицах.
+//!
+//! # Что такое Value Object?
+//! Объект без identity: две суммы по 10.50 — это одно и то же значение.
+//! Value Object неизменяем и сам следит за своей корректностью —
+//! невалидный `Money` просто нельзя создать.
+
+use std::fmt;
+use std::str::FromStr;
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::domain::errors::DomainError;
+use crate::domain::value_objects::{Currency, RoundingMode};
+
+/// Количество знаков после запятой (копейки/центы).
+const DECIMAL_PLACES: u32 = 2;
+
+/// Наибольшая сумма в запросе по модулю — `999999999999.99`.
+///
+/// # Почему не `i64::MAX`?
+/// Сумма, которая едва помещается в `i64`, ломает всё, что складывает
+/// суммы: баланс после пополнения, итоги отчётов, капитал. Триллион
+/// с лишним — больше любой реальной операции, а запас до `i64::MAX`
+/// позволяет сложить миллионы таких сумм без переполнения.
+const MAX_AMOUNT_CENTS: i64 = 99_999_999_999_999;
+
+/// Шаг точности `currency` в копейках: 1 для USD и BHD, 100 для JPY.
+///
+/// Третьего знака BHD `Money` не хранит (см. `check_precision`),
+/// поэтому шаг не бывает меньше копейки.
+fn step(currency: &Currency) -> i64 {
+    let max_decimals = currency.decimal_places().min(DECIMAL_PLACES);
+    10_i64.pow(DECIMAL_PLACES - max_decimals)
+}
+
+/// Денежная сумма в копейках/центах.
+///
+/// # Сотые доли для любой валюты
+/// Хранятся всегда сотые доли единицы валюты, а не её минимальная
+/// единица по ISO 4217: 1050 иен — `Money(105000)`, а не `Money(1050)`.
+/// Суммы разбираются до того, как известна валюта (`parse`), и складываются
+/// между валютами при пересчёте — общий масштаб избавляет от пересчёта
+/// знаков. Число знаков валюты учитывается при проверке (`check_precision`)
+/// и при показе (`format_in`).
+///
+/// # Почему newtype над `i64`?
+/// `Money(1050)` нельзя случайно сложить с количеством дней или ID —
+/// компилятор различает типы. При этом в памяти это тот же `i64`.
+///
+/// # Парсинг
+/// Суммы приходят строками (`"100.50"`) и разбираются через `rust_decimal`,
+/// а не через `f64`. Поэтому `"100.005"` отклоняется, а не округляется молча.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Money(i64);
+
+impl Money {
+    /// Создаёт сумму из копеек/центов.
+    pub fn from_cents(cents: i64) -> Self {
+        Self(cents)
+    }
+
+    /// Возвращает сумму в копейках/центах.
+    pub fn cents(&self) -> i64 {
+        self.0
+    }
+
+    /// Разбирает десятичную строку вида `"100.50"`.
+    ///
+    /// # Errors
+    /// `DomainError::InvalidAmount` если:
+    /// - строка не является числом
+    /// - это отрицательный ноль (`"-0"`, `"-0.00"`)
+    /// - больше двух знаков после запятой (`"100.005"`)
+    /// - сумма по модулю больше `MAX_AMOUNT_CENTS`
+    ///
+    /// `NaN`, `Infinity` и `"1e308"` (больше, чем вмещает `Decimal`) — не
+    /// числа для `rust_decimal`, так что отклоняются первым пунктом.
+    pub fn parse(input: &str) -> Result<Self, DomainError> {
+        let trimmed = input.trim();
+        let decimal = Decimal::from_str(trimmed)
+            .map_err(|_| DomainError::InvalidAmount(format!("'{}' is not a number", input)))?;
+
+        // "-0" формально число, но как сумма бессмысленно.
+        // Смотрим на исходную строку: Decimal теряет знак у нуля.
+        if decimal.is_zero() && trimmed.starts_with('-') {
+            return Err(DomainError::InvalidAmount(
+                "Negative zero is not allowed".into(),
+            ));
+        }
+
+        // normalize() убирает хвостовые нули: "100.500" → "100.5"
+        if decimal.normalize().scale() > DECIMAL_PLACES {
+            return Err(DomainError::InvalidAmount(format!(
+                "At most {} decimal places are allowed",
+                DECIMAL_PLACES
+            )));
+        }
+
+        // checked_mul + to_i64 — обе операции возвращают None при переполнении
+        let cents = decimal
+            .checked_mul(Decimal::from(10_i64.pow(DECIMAL_PLACES)))
+            .and_then(|d| d.to_i64())
+            .filter(|cents| cents.abs() <= MAX_AMOUNT_CENTS)
+            .ok_or_else(|| {
+                DomainError::InvalidAmount(format!(
+                    "Amount must be at most {} in absolute value",
+                    Self(MAX_AMOUNT_CENTS)
+                ))
+            })?;
+
+        Ok(Self(cents))
+    }
+
+    /// Проверяет, что знаков после запятой не больше, чем у `currency`.
+    ///
+    /// `parse` знает только общий предел — два знака. Валюта суммы
+    /// становится известна позже, у счёта или кошелька: для JPY
+    /// `"10.5"` разобран, но иены не делятся. Валюты с тремя знаками
+    /// (BHD) здесь не ограничены — третьего знака `Money` не хранит.
+    ///
+    /// # Errors
+    /// `DomainError::PrecisionExceeded` с кодом валюты и числом знаков.
+    pub fn check_precision(&self, currency: &Currency) -> Result<(), DomainError> {
+        // Для JPY лишние знаки — всё, что не кратно 100 копейкам
+        if self.0 % step(currency) != 0 {
+            return Err(DomainError::PrecisionExceeded {
+                currency: currency.to_string(),
+                max_decimals: currency.decimal_places().min(DECIMAL_PLACES),
+            });
+        }
+        Ok(())
+    }
+
+    /// Округляет сумму до точности `currency` по правилу `rounding`.
+    ///
+    /// Для JPY `"10.50"` → `"10.00"` при `HalfEven`, `"11.00"` при `HalfUp`.
+    /// Сумма уже в пределах точности не меняется (см. `check_precision`).
+    pub fn round_to(&self, currency: &Currency, rounding: RoundingMode) -> Money {
+        // У самого края i64 округление вверх переполнило бы — тогда
+        // сумма остаётся как есть
+        Self::round_cents(Decimal::from(self.0), currency, rounding).unwrap_or(*self)
+    }
+
+    /// Округляет дробное число копеек до точности `currency` по правилу
+    /// `rounding`. `None` — результат не помещается в `i64`.
+    ///
+    /// # Почему одно округление, а не до копеек и потом `round_to`?
+    /// Двойное округление сдвигает половинки: 150.495 иены при `HalfUp`
+    /// сначала станут 150.50, а потом 151 — хотя до 151 не хватает
+    /// почти половины иены. Пересчёт и проценты считаются в `Decimal`
+    /// и округляются сразу до единиц валюты.
+    pub fn round_cents(
+        cents: Decimal,
+        currency: &Currency,
+        rounding: RoundingMode,
+    ) -> Option<Money> {
+        let step = step(currency);
+        cents
+            .checked_div(Decimal::from(step))
+            .map(|units| rounding.round(units))
+            .and_then(|units| units.to_i64())
+            .and_then(|units| units.checked_mul(step))
+            .map(Self)
+    }
+
+    /// Пересчитывает сумму в валюту `currency` по курсу `rate`.
+    ///
+    /// Результат округляется до точности `currency` по правилу `rounding`:
+    /// при `HalfEven` `10.005 → 10.00`, `10.015 → 10.02`, а в иенах —
+    /// до целых (1.01 USD по курсу 150 — 152 иены, а не 151.50).
+    ///
+    /// # Errors
+    /// `DomainError::InvalidAmount` если результат переполняет `i64`
+    /// или округляется до нуля (сумма слишком мала для такого курса).
+    pub fn convert(
+        &self,
+        rate: Decimal,
+        currency: &Currency,
+        rounding: RoundingMode,
+    ) -> Result<Money, DomainError> {
+        let Self(cents) = self.convert_balance(rate, currency, rounding)?;
+
+        if cents <= 0 {
+            return Err(DomainError::InvalidAmount(format!(
+                "{} converts to zero at rate {}",
+                self, rate
+            )));
+        }
+
+        Ok(Self(cents))
+    }
+
+    /// Пересчитывает остаток счёта в другую валюту по курсу `rate`.
+    ///
+    /// Округление то же, что у `convert`, но ноль и минус допустимы:
+    /// пустой счёт или долг по кредиту — тоже остаток.
+    ///
+    /// # Errors
+    /// `DomainError::InvalidAmount` если результат переполняет `i64`.
+    pub fn convert_balance(
+        &self,
+        rate: Decimal,
+        currency: &Currency,
+        rounding: RoundingMode,
+    ) -> Result<Money, DomainError> {
+        Decimal::from(self.0)
+            .checked_mul(rate)
+            .and_then(|cents| Self::round_cents(cents, currency, rounding))
+            .ok_or_else(|| DomainError::InvalidAmount("Converted amount is too large".into()))
+    }
+
+    /// Форматирует сумму с числом знаков валюты (`Currency::decimal_places`).
+    ///
+    /// `Money` всегда хранит сотые доли, какой бы ни была валюта: 10 иен —
+    /// это `Money(1000)`. Поэтому значение не делится на `10^decimal_places`,
+    /// а пересчитывается из двух знаков в знаки валюты:
+    /// `Money(105000)` → `"1050"` для JPY, `Money(1050)` → `"10.50"` для USD,
+    /// `Money(105)` → `"1.050"` для BHD.
+    ///
+    /// # Почему у BHD третий знак всегда ноль?
+    /// Двух знаков хранения хватает всем валютам, кроме трёхзначных:
+    /// для них `check_precision` ограничивает ввод двумя знаками, а третий
+    /// дописывается только при показе. Лишние знаки у валют без дробной
+    /// части (JPY) округляются по-банковски — при показе, не в хранилище.
+    ///
+    /// # Отличие от `Display`
+    /// `Display` всегда пишет два знака — это формат API для сумм
+    /// в запросах. Здесь — то, как сумму принято показывать человеку.
+    pub fn format_in(&self, currency: &Currency) -> String {
+        let places = currency.decimal_places();
+        let mut amount = Decimal::new(self.0, DECIMAL_PLACES).round_dp(places);
+        // round_dp не дописывает нули: 1.05 для BHD должно стать 1.050
+        amount.rescale(places);
+        amount.to_string()
+    }
+}
+
+/// Форматирование: `Money(1050)` → `"10.50"`, `Money(-5)` → `"-0.05"`.
+///
+/// # Почему через `Decimal`?
+/// `Decimal::new(cents, 2)` — точное значение с двумя знаками,
+/// без артефактов вроде `10.500000000000002`.
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Decimal::new(self.0, DECIMAL_PLACES))
+    }
+}
+
+/// Позволяет писать `"100.50".parse::<Money>()`.
+impl FromStr for Money {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// В JSON сумма пишется строкой: `"balance": "10.50"`.
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Из JSON сумма читается строкой: `"amount": "10.50"`.
+///
+/// # Почему не число?
+/// JSON-число serde разбирает в `f64` — точность теряется ещё до нас.
+/// Строка доходит до `rust_decimal` без искажений. На число в JSON
+/// ответ подсказывает формат (см. `MoneyVisitor::expecting`).
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(MoneyVisitor)
+    }
+}
+
+/// Visitor для `Money`: принимает только строку.
+///
+/// # Зачем свой visitor вместо `String::deserialize`?
+/// Ради текста ошибки. `String` на `10.5` отвечает «expected a string»,
+/// а клиенту, который шлёт числа, нужно знать, какая строка ожидается.
+struct MoneyVisitor;
+
+impl<'de> serde::de::Visitor<'de> for MoneyVisitor {
+    type Value = Money;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a decimal amount as a string, like \"10.50\"")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, raw: &str) -> Result<Money, E> {
+        // Только причина, без "Invalid amount:": имя поля к ней
+        // добавит тот, кто показывает ошибку ("invalid amount: ...")
+        raw.parse().map_err(|err| match err {
+            DomainError::InvalidAmount(reason) => E::custom(reason),
+            other => E::custom(other),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn money(raw: &str) -> Money {
+        raw.parse().unwrap()
+    }
+
+    #[test]
+    fn format_in_writes_zero_with_currency_places() {
+        assert_eq!(
+            Money::from_cents(0).format_in(&Currency::new("USD")),
+            "0.00"
+        );
+        assert_eq!(Money::from_cents(0).format_in(&Currency::new("JPY")), "0");
+    }
+
+    #[test]
+    fn format_in_keeps_the_sign_and_no_grouping() {
+        let usd = Currency::new("USD");
+
+        assert_eq!(Money::from_cents(-5).format_in(&usd), "-0.05");
+        // Без разделителей тысяч: строку разбирают клиенты
+        assert_eq!(
+            Money::from_cents(-123_456_789).format_in(&usd),
+            "-1234567.89"
+        );
+    }
+
+    #[test]
+    fn format_in_writes_jpy_without_decimals() {
+        // 1050 иен хранятся как 105000 сотых
+        assert_eq!(
+            Money::from_cents(105_000).format_in(&Currency::new("JPY")),
+            "1050"
+        );
+    }
+
+    #[test]
+    fn jpy_rejects_any_decimals() {
+        let jpy = Currency::new("JPY");
+
+        for raw in ["10.5", "10.05", "0.01"] {
+            let err = money(raw).check_precision(&jpy).unwrap_err();
+            assert!(matches!(
+                &err,
+                DomainError::PrecisionExceeded { currency, max_decimals: 0 } if currency == "JPY"
+            ));
+            assert_eq!(err.to_string(), "JPY accepts at most 0 decimal places");
+        }
+    }
+
+    #[test]
+    fn jpy_accepts_whole_amounts() {
+        let jpy = Currency::new("JPY");
+
+        assert!(money("10").check_precision(&jpy).is_ok());
+        assert!(money("10.00").check_precision(&jpy).is_ok());
+    }
+
+    #[test]
+    fn format_in_rescales_to_currency_places() {
+        assert_eq!(money("10").format_in(&Currency::new("JPY")), "10");
+        assert_eq!(money("10").format_in(&Currency::new("USD")), "10.00");
+        assert_eq!(money("1.05").format_in(&Currency::new("BHD")), "1.050");
+        assert_eq!(money("-3.20").format_in(&Currency::new("BHD")), "-3.200");
+    }
+
+    #[test]
+    fn rejects_non_finite_and_out_of_range_amounts() {
+        for raw in ["NaN", "nan", "Infinity", "-Infinity", "inf", "1e308"] {
+            let err = Money::parse(raw).unwrap_err();
+            assert!(matches!(err, DomainError::InvalidAmount(_)), "{raw}");
+        }
+        assert!(Money::parse("1000000000000.00").is_err());
+        assert!(Money::parse("-0.00").is_err());
+        assert!(Money::parse("0.001").is_err());
+    }
+
+    #[test]
+    fn deserialize_accepts_only_decimal_strings() {
+        let parse = |value| serde_json::from_value::<Money>(value);
+
+        assert_eq!(parse(serde_json::json!("10.50")).unwrap().cents(), 1050);
+        let err = parse(serde_json::json!("NaN")).unwrap_err();
+        assert_eq!(err.to_string(), "'NaN' is not a number");
+        // Число в JSON — не сумма, даже конечное: f64 уже потерял точность
+        let err = parse(serde_json::json!(1e308)).unwrap_err();
+        assert!(err.to_string().contains("a decimal amount as a string"));
+    }
+
+    #[test]
+    fn half_cent_rounds_to_even_or_up_by_mode() {
+        // 1.00 по курсу 2.125 — ровно 212.5 копейки
+        let rate = Decimal::from_str("2.125").unwrap();
+        let convert = |rounding| {
+            money("1.00")
+                .convert(rate, &Currency::new("EUR"), rounding)
+                .unwrap()
+                .cents()
+        };
+
+        assert_eq!(convert(RoundingMode::HalfEven), 212);
+        assert_eq!(convert(RoundingMode::HalfUp), 213);
+        assert_eq!(convert(RoundingMode::Down), 212);
+        assert_eq!(RoundingMode::default(), RoundingMode::HalfEven);
+    }
+
+    #[test]
+    fn conversion_rounds_once_to_the_target_currency() {
+        let jpy = Currency::new("JPY");
+        // 100.33 × 1.5 = 150.495 иены: ближе к 150, чем к 151
+        let rate = Decimal::from_str("1.5").unwrap();
+        let convert = |rounding| money("100.33").convert(rate, &jpy, rounding).unwrap();
+
+        assert_eq!(convert(RoundingMode::HalfUp).cents(), 15000);
+        assert_eq!(convert(RoundingMode::HalfEven).cents(), 15000);
+        // Через копейки было бы 150.50 → 151
+        assert_eq!(
+            money("150.50").round_to(&jpy, RoundingMode::HalfUp).cents(),
+            15100
+        );
+    }
+
+    #[test]
+    fn half_cent_below_zero_rounds_away_from_zero_in_half_up() {
+        let rate = Decimal::from_str("2.125").unwrap();
+        let convert = |rounding| {
+            money("-1.00")
+                .convert_balance(rate, &Currency::new("EUR"), rounding)
+                .unwrap()
+                .cents()
+        };
+
+        assert_eq!(convert(RoundingMode::HalfEven), -212);
+        assert_eq!(convert(RoundingMode::HalfUp), -213);
+        assert_eq!(convert(RoundingMode::Down), -212);
+    }
+}
@@ -0,0 +1,89 @@
+//! Value Object PrecisionMode — что делать с лишними знаками суммы.
+//!
+//! `Money::parse` принимает два знака после запятой, а валюта счёта
+//! может допускать меньше (JPY — ни одного). Такие суммы либо
+//! отклоняются, либо округляются — в зависимости от `PRECISION_MODE`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use super::{Currency, Money, RoundingMode};
+use crate::domain::errors::DomainError;
+
+/// Режим проверки точности суммы из запроса.
+///
+/// - `Strict` — `DomainError::PrecisionExceeded` (400)
+/// - `Round` — сумма округляется по `RoundingMode`, операция проходит,
+///   а в ответ добавляется предупреждение
+///
+/// # Почему по умолчанию `Strict`?
+/// Округление меняет сумму, которую прислал клиент: `"10.50"` JPY
+/// станет `"10.00"` при `HalfEven`. Молча списать не ту сумму хуже,
+/// чем отказать, поэтому округление нужно включить явно.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PrecisionMode {
+    #[default]
+    Strict,
+    Round,
+}
+
+impl PrecisionMode {
+    /// Строковое представление — то же, что принимает `FromStr`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Strict => "strict",
+            Self::Round => "round",
+        }
+    }
+
+    /// Приводит `amount` к точности `currency`.
+    ///
+    /// Возвращает сумму и предупреждение, если она округлена.
+    /// Сумма, округлившаяся до нуля, не отклоняется здесь — её отклонит
+    /// операция, как любую неположительную сумму.
+    ///
+    /// # Errors
+    /// `DomainError::PrecisionExceeded` в режиме `Strict`.
+    pub fn fit(
+        &self,
+        amount: Money,
+        currency: &Currency,
+        rounding: RoundingMode,
+    ) -> Result<(Money, Option<String>), DomainError> {
+        let err = match amount.check_precision(currency) {
+            Ok(()) => return Ok((amount, None)),
+            Err(err) => err,
+        };
+        if *self == Self::Strict {
+            return Err(err);
+        }
+
+        let rounded = amount.round_to(currency, rounding);
+        Ok((
+            rounded,
+            Some(format!("amount {} rounded to {}: {}", amount, rounded, err)),
+        ))
+    }
+}
+
+impl fmt::Display for PrecisionMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// `"strict"`, `"ROUND"`, `" round "` — регистр и пробелы по краям не важны.
+impl FromStr for PrecisionMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "strict" => Ok(Self::Strict),
+            "round" => Ok(Self::Round),
+            other => Err(format!(
+                "Unknown precision mode '{}', expected strict or round",
+                other
+            )),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+//! Value Object RoundingMode — правило округления до целой минимальной единицы.
+//!
+//! Округлять приходится там, где сумма получается вычислением:
+//! пересчёт по курсу (`Money::convert`) и начисление процентов
+//! (`Account::interest`). Суммы из запросов округляются, только если
+//! у валюты меньше двух знаков и `PRECISION_MODE=round` (см.
+//! `PrecisionMode`); больше двух знаков отклоняет `Money::parse`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use rust_decimal::{Decimal, RoundingStrategy};
+
+/// Как округлять дробную часть минимальной единицы (копейки).
+///
+/// | Режим      | `212.5` | `213.5` | `212.9` | `-212.5` |
+/// |------------|---------|---------|---------|----------|
+/// | `HalfEven` | 212     | 214     | 213     | -212     |
+/// | `HalfUp`   | 213     | 214     | 213     | -213     |
+/// | `Down`     | 212     | 213     | 212     | -212     |
+///
+/// # Почему по умолчанию `HalfEven`?
+/// При округлении половины вверх на множестве операций ошибка
+/// копится в одну сторону. Банковское округление (половина — к чётному)
+/// уводит половины то вверх, то вниз, и в среднем ошибка нулевая.
+///
+/// # Почему `HalfUp` округляет `-212.5` до `-213`?
+/// «Вверх» здесь — от нуля по модулю: так считают вручную,
+/// и у суммы с минусом та же копейка, что у суммы без него.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RoundingMode {
+    HalfUp,
+    #[default]
+    HalfEven,
+    Down,
+}
+
+impl RoundingMode {
+    /// Строковое представление — то же, что принимает `FromStr`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::HalfUp => "half_up",
+            Self::HalfEven => "half_even",
+            Self::Down => "down",
+        }
+    }
+
+    /// Округляет до целого числа минимальных единиц.
+    ///
+    /// `Down` отбрасывает дробную часть (к нулю), а не округляет к минус
+    /// бесконечности: `-212.9 → -212`.
+    pub fn round(&self, value: Decimal) -> Decimal {
+        let strategy = match self {
+            Self::HalfUp => RoundingStrategy::MidpointAwayFromZero,
+            Self::HalfEven => RoundingStrategy::MidpointNearestEven,
+            Self::Down => RoundingStrategy::ToZero,
+        };
+        value.round_dp_with_strategy(0, strategy)
+    }
+}
+
+impl fmt::Display for RoundingMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// `"half_even"`, `"HALF_UP"`, `" down "` — регистр и пробелы по краям не важны.
+impl FromStr for RoundingMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "half_up" => Ok(Self::HalfUp),
+            "half_even" => Ok(Self::HalfEven),
+            "down" => Ok(Self::Down),
+            other => Err(format!(
+                "Unknown rounding mode '{}', expected half_up, half_even or down",
+                other
+            )),
+        }
+    }
+}
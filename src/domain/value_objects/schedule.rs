@@ -0,0 +1,135 @@
+//! Value Object Schedule — расписание повторяющейся операции.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Duration, Months, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::domain::errors::DomainError;
+
+/// Последний день месяца, который можно указать в `monthly:N`.
+///
+/// # Почему 28?
+/// 28-е есть в любом месяце. С 31-м пришлось бы решать, что делать
+/// в феврале и апреле — проще запретить.
+const MAX_MONTH_DAY: u32 = 28;
+
+/// Расписание повторяющейся операции.
+///
+/// # Формат строки
+/// - `30m`, `12h`, `1d`, `2w` — каждые N минут/часов/дней/недель
+/// - `monthly:1` — каждый месяц в указанный день (1–28), в то же время суток
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Schedule {
+    Every(Duration),
+    Monthly { day: u32 },
+}
+
+impl Schedule {
+    /// Следующий запуск после `previous`.
+    ///
+    /// Для `Monthly` время суток берётся из `previous`.
+    pub fn next_after(&self, previous: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Schedule::Every(interval) => previous + *interval,
+            Schedule::Monthly { day } => {
+                // Сначала переносим на нужное число текущего месяца;
+                // если оно уже прошло — берём следующий месяц.
+                // with_day не сработает только для дня > 28, а такие отклоняет parse
+                let this_month = previous
+                    .with_day(*day)
+                    .expect("day is validated to be at most 28");
+                if this_month > previous {
+                    this_month
+                } else {
+                    this_month + Months::new(1)
+                }
+            }
+        }
+    }
+}
+
+/// Разбор расписания из строки.
+///
+/// # Errors
+/// `DomainError::InvalidSchedule` с описанием допустимого формата.
+impl FromStr for Schedule {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            DomainError::InvalidSchedule(format!(
+                "'{}': expected an interval like 30m, 12h, 1d, 2w or monthly:<1-{}>",
+                s, MAX_MONTH_DAY
+            ))
+        };
+
+        if let Some(day) = s.strip_prefix("monthly:") {
+            let day: u32 = day.parse().map_err(|_| invalid())?;
+            if !(1..=MAX_MONTH_DAY).contains(&day) {
+                return Err(invalid());
+            }
+            return Ok(Schedule::Monthly { day });
+        }
+
+        // Последний символ — единица, остальное — число.
+        // Длину символа учитываем: split_at посреди UTF-8 символа паникует
+        let unit_len = s.chars().last().map_or(0, char::len_utf8);
+        let (count, unit) = s.split_at(s.len() - unit_len);
+        let count: i64 = count.parse().map_err(|_| invalid())?;
+        if count <= 0 {
+            return Err(invalid());
+        }
+
+        let interval = match unit {
+            "m" => Duration::try_minutes(count),
+            "h" => Duration::try_hours(count),
+            "d" => Duration::try_days(count),
+            "w" => Duration::try_weeks(count),
+            _ => None,
+        }
+        .ok_or_else(invalid)?;
+
+        Ok(Schedule::Every(interval))
+    }
+}
+
+/// Обратное к `FromStr`: интервал выводится в самой крупной целой единице.
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Schedule::Monthly { day } => write!(f, "monthly:{}", day),
+            Schedule::Every(interval) => {
+                let minutes = interval.num_minutes();
+                if minutes % (60 * 24 * 7) == 0 {
+                    write!(f, "{}w", minutes / (60 * 24 * 7))
+                } else if minutes % (60 * 24) == 0 {
+                    write!(f, "{}d", minutes / (60 * 24))
+                } else if minutes % 60 == 0 {
+                    write!(f, "{}h", minutes / 60)
+                } else {
+                    write!(f, "{}m", minutes)
+                }
+            }
+        }
+    }
+}
+
+/// В JSON расписание — строка, как в query/БД.
+impl Serialize for Schedule {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Schedule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        // Только причина — см. `impl Deserialize for Money`
+        raw.parse().map_err(|err| match err {
+            DomainError::InvalidSchedule(reason) => serde::de::Error::custom(reason),
+            other => serde::de::Error::custom(other),
+        })
+    }
+}
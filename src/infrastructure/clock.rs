@@ -0,0 +1,65 @@
+//! Реализации `Clock`: настоящие часы и остановленные (для тестов).
+
+#[cfg(test)]
+use std::sync::{Arc, Mutex};
+
+#[cfg(test)]
+use chrono::Duration;
+use chrono::{DateTime, Utc};
+
+use crate::application::ports::Clock;
+
+/// Системные часы — `Utc::now()`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Часы, которые идут только вручную.
+///
+/// # Почему `Arc<Mutex<...>>`?
+/// Тест отдаёт копию часов сервису, а сам сдвигает время через свою:
+/// ```text
+/// let clock = FixedClock::new(start);
+/// let service = AccountService::new(..., Arc::new(clock.clone()));
+/// clock.advance(Duration::days(1)); // сервис видит следующий день
+/// ```
+#[cfg(test)]
+#[derive(Debug, Clone)]
+pub struct FixedClock {
+    now: Arc<Mutex<DateTime<Utc>>>,
+}
+
+#[cfg(test)]
+impl FixedClock {
+    /// Часы, остановленные на `now`.
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Arc::new(Mutex::new(now)),
+        }
+    }
+
+    /// Сдвигает часы вперёд на `step`.
+    pub fn advance(&self, step: Duration) {
+        *self.lock() += step;
+    }
+
+    /// Блокировка, пережившая панику другого потока: время внутри
+    /// всегда целое, поэтому "отравленный" `Mutex` безопасно читать.
+    fn lock(&self) -> std::sync::MutexGuard<'_, DateTime<Utc>> {
+        self.now
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.lock()
+    }
+}
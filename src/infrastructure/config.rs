@@ -3,6 +3,150 @@
 //! Загружает настройки из переменных окружения.
 
 use std::env;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::domain::value_objects::{PrecisionMode, RoundingMode};
+
+/// Аргументы командной строки сервера.
+///
+/// # Почему без clap?
+/// Флагов единицы, а всё остальное настраивается через окружение.
+/// Разбор в десяток строк дешевле ещё одной зависимости.
+#[derive(Debug, Default)]
+pub struct Args {
+    /// `--config <path>` — `.env` файл вместо поиска по каталогам
+    pub config_path: Option<PathBuf>,
+    /// `--skip-migrations` — не применять миграции при старте
+    /// (read-only реплика, схему обновляет основной экземпляр)
+    pub skip_migrations: bool,
+    /// `--read-only` — то же, что `READ_ONLY=true`
+    pub read_only: bool,
+}
+
+impl Args {
+    /// Разбирает аргументы (без имени программы): `--config <path>`
+    /// или `--config=<path>`, `--skip-migrations` и `--read-only`.
+    ///
+    /// # Errors
+    /// Текст для пользователя: неизвестный аргумент или `--config` без пути.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            if arg == "--config" {
+                let path = args.next().ok_or("--config requires a path")?;
+                parsed.config_path = Some(path.into());
+            } else if let Some(path) = arg.strip_prefix("--config=") {
+                parsed.config_path = Some(path.into());
+            } else if arg == "--skip-migrations" {
+                parsed.skip_migrations = true;
+            } else if arg == "--read-only" {
+                parsed.read_only = true;
+            } else {
+                return Err(format!("Unknown argument '{}'", arg));
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// Формат строк лога.
+///
+/// - `Pretty` — для человека в терминале (по умолчанию)
+/// - `Json` — объект на строку для сборщиков логов (Loki, ELK):
+///   `timestamp`, `level`, `target`, `message` и поля span'а запроса,
+///   в том числе `request_id`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    #[default]
+    Pretty,
+    Json,
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "pretty" => Ok(Self::Pretty),
+            "json" => Ok(Self::Json),
+            other => Err(format!(
+                "Unknown log format '{}', expected pretty or json",
+                other
+            )),
+        }
+    }
+}
+
+/// Настройки логирования.
+///
+/// # Переменные окружения
+/// - `LOG_FORMAT` — `pretty` или `json` (по умолчанию `pretty`)
+/// - `LOG_LEVEL` — уровень или директивы `EnvFilter`: `debug`,
+///   `info,finance_tracker=debug` (по умолчанию `info,sqlx=warn`)
+/// - `RUST_LOG` — если задан, важнее `LOG_LEVEL` (как принято в экосистеме)
+///
+/// # Почему не поле `Config`?
+/// Логирование включается раньше `Config::from_env`, иначе его
+/// предупреждения (см. `positive_var`) некуда было бы писать.
+#[derive(Debug, Clone)]
+pub struct LogConfig {
+    pub format: LogFormat,
+    pub filter: String,
+    /// Нераспознанный `LOG_FORMAT`: вместо него взят `pretty`.
+    /// Предупредить о нём можно только после включения логирования.
+    pub invalid_format: Option<String>,
+}
+
+impl LogConfig {
+    /// Читает настройки логирования из окружения.
+    pub fn from_env() -> Self {
+        let (format, invalid_format) = match env::var("LOG_FORMAT") {
+            Ok(raw) => match raw.parse() {
+                Ok(format) => (format, None),
+                Err(_) => (LogFormat::default(), Some(raw)),
+            },
+            Err(_) => (LogFormat::default(), None),
+        };
+
+        let filter = env::var("RUST_LOG")
+            .or_else(|_| env::var("LOG_LEVEL"))
+            .unwrap_or_else(|_| "info,sqlx=warn".into());
+
+        Self {
+            format,
+            filter,
+            invalid_format,
+        }
+    }
+}
+
+/// Загружает переменные из `.env` файла в окружение процесса.
+///
+/// - `path` задан — читается именно он; если файла нет, это ошибка
+/// - `path` не задан — `.env` ищется в текущем каталоге и выше;
+///   не нашёлся — не ошибка, возвращается `None`
+///
+/// Переменные, которые уже есть в окружении, файл НЕ перезаписывает:
+/// `DATABASE_URL=... cargo run` важнее строки в `.env`.
+///
+/// Вызывается до `Config::from_env`.
+///
+/// # Errors
+/// Файл не читается или в нём синтаксическая ошибка.
+pub fn load_env_file(path: Option<&Path>) -> Result<Option<PathBuf>, dotenvy::Error> {
+    match path {
+        Some(path) => dotenvy::from_path(path).map(|()| Some(path.to_path_buf())),
+        None => match dotenvy::dotenv() {
+            Ok(found) => Ok(Some(found)),
+            Err(err) if err.not_found() => Ok(None),
+            Err(err) => Err(err),
+        },
+    }
+}
 
 /// Конфигурация приложения.
 ///
@@ -10,11 +154,79 @@ use std::env;
 /// - `DATABASE_URL` — строка подключения к PostgreSQL (обязательно)
 /// - `SERVER_HOST` — хост сервера (по умолчанию 127.0.0.1)
 /// - `SERVER_PORT` — порт сервера (по умолчанию 3000)
+/// - `SHUTDOWN_TIMEOUT_SECS` — сколько ждать активные запросы при остановке (по умолчанию 30)
+/// - `CORS_ALLOWED_ORIGINS` — origin через запятую, `*` — любой (по умолчанию никто)
+/// - `DB_MAX_CONNECTIONS` — максимум соединений в пуле (по умолчанию 5)
+/// - `DB_MIN_CONNECTIONS` — сколько соединений держать открытыми всегда (по умолчанию 1)
+/// - `DB_ACQUIRE_TIMEOUT_SECS` — сколько ждать свободное соединение (по умолчанию 30)
+/// - `DB_IDLE_TIMEOUT_SECS` — через сколько закрывать простаивающее соединение (по умолчанию 600)
+/// - `DB_MAX_RETRIES` — сколько раз повторять чтение счетов при обрыве соединения (по умолчанию 3)
+/// - `DB_CONNECT_RETRIES` — сколько раз повторять подключение к БД при старте (по умолчанию 5;
+///   `0` — одна попытка без повторов)
+/// - `DB_CONNECT_RETRY_DELAY_SECS` — пауза между попытками подключения (по умолчанию 2)
+/// - `SCHEDULER_INTERVAL_SECS` — как часто проверять повторяющиеся операции (по умолчанию 60)
+/// - `EXCHANGE_RATES` — курсы валют JSON-объектом `{"USD/EUR": "0.923"}` (по умолчанию нет)
+/// - `MAX_REQUEST_BODY_BYTES` — максимальный размер тела запроса (по умолчанию 64 КБ;
+///   на `/api/import` свой лимит)
+/// - `RATE_LIMIT_PER_MINUTE` — запросов к API в минуту с одного IP (по умолчанию 600)
+/// - `REQUEST_TIMEOUT_SECS` — сколько обрабатывать запрос к API, прежде чем
+///   ответить 504 (по умолчанию 30)
+/// - `RESPONSE_COMPRESSION` — `false`, чтобы не сжимать ответы, если это уже
+///   делает прокси перед сервером (по умолчанию сжатие включено)
+/// - `READ_ONLY` — `true`, чтобы API отклонял запись с 503, а планировщик
+///   не запускался (на время обслуживания; по умолчанию `false`)
+/// - `TRUST_FORWARDED_FOR` — `true`, если сервер за прокси: IP клиента
+///   берётся из `X-Forwarded-For` (по умолчанию `false`)
+/// - `MAX_ACCOUNTS` — максимум активных счетов пользователя (по умолчанию без ограничения;
+///   нечисловое или нулевое значение — тоже без ограничения)
+/// - `ROUNDING_MODE` — округление вычисленных сумм (курс, проценты):
+///   `half_even`, `half_up` или `down` (по умолчанию `half_even`;
+///   нераспознанное значение — тоже `half_even` с предупреждением)
+/// - `PRECISION_MODE` — сумма с лишними для валюты знаками: `strict`
+///   отклоняет, `round` округляет по `ROUNDING_MODE` с предупреждением
+///   в ответе (по умолчанию `strict`; нераспознанное значение — тоже
+///   `strict` с предупреждением)
+/// - `API_BASE_PATH` — префикс маршрутов API, например `/finance`
+///   (по умолчанию нет; неверный префикс — тоже нет, с предупреждением)
+/// - `DEFAULT_CURRENCY` — валюта счёта, если в запросе на создание её нет
+///   (по умолчанию `USD`; неверный код останавливает запуск)
+/// - `API_KEYS` — ключи API через запятую: `user:hash` или `hash`
+///   (SHA-256 ключа в hex; без пользователя — `default`). С ними каждый
+///   запрос к `/api` требует `Authorization: Bearer <key>`, и пользователь
+///   видит только свои счета (по умолчанию нет — API открыт; неверная
+///   запись останавливает запуск)
+///
+/// Нечисловые и нулевые значения `DB_*`, `SCHEDULER_INTERVAL_SECS`, `MAX_REQUEST_BODY_BYTES`
+/// `RATE_LIMIT_PER_MINUTE` и `REQUEST_TIMEOUT_SECS` заменяются значениями по умолчанию
+/// (кроме `DB_CONNECT_RETRIES`: там ноль допустим).
 #[derive(Clone)]
 pub struct Config {
     pub database_url: String,
     pub server_host: String,
     pub server_port: u16,
+    pub shutdown_timeout_secs: u64,
+    pub cors_allowed_origins: Vec<String>,
+    pub db_max_connections: u32,
+    pub db_min_connections: u32,
+    pub db_acquire_timeout_secs: u64,
+    pub db_idle_timeout_secs: u64,
+    pub db_max_retries: u32,
+    pub db_connect_retries: u32,
+    pub db_connect_retry_delay_secs: u64,
+    pub scheduler_interval_secs: u64,
+    pub exchange_rates: Option<String>,
+    pub max_request_body_bytes: usize,
+    pub rate_limit_per_minute: u32,
+    pub request_timeout_secs: u64,
+    pub trust_forwarded_for: bool,
+    pub read_only: bool,
+    pub response_compression: bool,
+    pub max_accounts: Option<u64>,
+    pub rounding_mode: RoundingMode,
+    pub precision_mode: PrecisionMode,
+    pub api_base_path: String,
+    pub default_currency: String,
+    pub api_keys: Vec<String>,
 }
 
 impl Config {
@@ -35,6 +247,101 @@ impl Config {
                 .unwrap_or_else(|_| "3000".into())
                 .parse() // Парсим строку в число
                 .unwrap_or(3000), // Если не удалось — 3000
+
+            shutdown_timeout_secs: env::var("SHUTDOWN_TIMEOUT_SECS")
+                .ok() // Result → Option: нет переменной — None
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+
+            // "https://a.com, https://b.com" → ["https://a.com", "https://b.com"]
+            cors_allowed_origins: env::var("CORS_ALLOWED_ORIGINS")
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|origin| !origin.is_empty())
+                .map(String::from)
+                .collect(),
+
+            db_max_connections: positive_var("DB_MAX_CONNECTIONS", 5),
+            db_min_connections: positive_var("DB_MIN_CONNECTIONS", 1),
+            db_acquire_timeout_secs: positive_var("DB_ACQUIRE_TIMEOUT_SECS", 30),
+            db_idle_timeout_secs: positive_var("DB_IDLE_TIMEOUT_SECS", 600),
+            db_max_retries: positive_var("DB_MAX_RETRIES", 3),
+
+            // Не positive_var: ноль здесь осмысленный — не ждать БД вовсе
+            db_connect_retries: match env::var("DB_CONNECT_RETRIES") {
+                Ok(raw) => raw.trim().parse().unwrap_or_else(|_| {
+                    tracing::warn!("Invalid DB_CONNECT_RETRIES='{}', using default 5", raw);
+                    5
+                }),
+                Err(_) => 5,
+            },
+            db_connect_retry_delay_secs: positive_var("DB_CONNECT_RETRY_DELAY_SECS", 2),
+            scheduler_interval_secs: positive_var("SCHEDULER_INTERVAL_SECS", 60),
+
+            // Разбирается в main: кривой JSON должен остановить запуск
+            exchange_rates: env::var("EXCHANGE_RATES").ok(),
+
+            max_request_body_bytes: positive_var("MAX_REQUEST_BODY_BYTES", 64 * 1024),
+            rate_limit_per_minute: positive_var("RATE_LIMIT_PER_MINUTE", 600),
+            request_timeout_secs: positive_var("REQUEST_TIMEOUT_SECS", 30),
+
+            // Только явное "true": без прокси заголовок подделывается
+            trust_forwarded_for: env::var("TRUST_FORWARDED_FOR")
+                .map(|v| v.trim().eq_ignore_ascii_case("true"))
+                .unwrap_or(false),
+
+            // Только явное "true", как у флага --read-only
+            read_only: env::var("READ_ONLY")
+                .map(|v| v.trim().eq_ignore_ascii_case("true"))
+                .unwrap_or(false),
+
+            // Выключается только явным "false"
+            response_compression: env::var("RESPONSE_COMPRESSION")
+                .map(|v| !v.trim().eq_ignore_ascii_case("false"))
+                .unwrap_or(true),
+
+            max_accounts: env::var("MAX_ACCOUNTS")
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .filter(|&max| max > 0),
+
+            rounding_mode: match env::var("ROUNDING_MODE") {
+                Ok(raw) => raw.parse().unwrap_or_else(|err| {
+                    tracing::warn!("{}, using {}", err, RoundingMode::default());
+                    RoundingMode::default()
+                }),
+                Err(_) => RoundingMode::default(),
+            },
+
+            precision_mode: match env::var("PRECISION_MODE") {
+                Ok(raw) => raw.parse().unwrap_or_else(|err| {
+                    tracing::warn!("{}, using {}", err, PrecisionMode::default());
+                    PrecisionMode::default()
+                }),
+                Err(_) => PrecisionMode::default(),
+            },
+
+            api_base_path: match env::var("API_BASE_PATH") {
+                Ok(raw) => normalize_base_path(&raw).unwrap_or_else(|err| {
+                    tracing::warn!("{}, serving the API without a prefix", err);
+                    String::new()
+                }),
+                Err(_) => String::new(),
+            },
+
+            // Разбирается в main, как EXCHANGE_RATES: с неверным кодом
+            // каждый счёт без валюты получал бы 400, а не только этот запуск
+            default_currency: env::var("DEFAULT_CURRENCY").unwrap_or_else(|_| "USD".into()),
+
+            // Разбираются в main (`ApiKeys::from_hashes`), как EXCHANGE_RATES
+            api_keys: env::var("API_KEYS")
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|hash| !hash.is_empty())
+                .map(String::from)
+                .collect(),
         })
     }
 
@@ -42,4 +349,129 @@ impl Config {
     pub fn server_addr(&self) -> String {
         format!("{}:{}", self.server_host, self.server_port)
     }
+
+    /// `DATABASE_URL` без секретов — для логов.
+    ///
+    /// `postgres://app:s3cr%40t@db:5432/finance?sslmode=require`
+    /// → `postgres://app:***@db:5432/finance`
+    pub fn redacted_database_url(&self) -> String {
+        redact_url(&self.database_url)
+    }
+
+    /// Минимум соединений, не больше максимума.
+    ///
+    /// `PgPoolOptions` не проверяет, что min <= max, поэтому ограничиваем здесь.
+    pub fn db_min_connections(&self) -> u32 {
+        self.db_min_connections.min(self.db_max_connections)
+    }
+
+    /// Таймаут ожидания соединения из пула как `Duration`.
+    pub fn db_acquire_timeout(&self) -> Duration {
+        Duration::from_secs(self.db_acquire_timeout_secs)
+    }
+
+    /// Таймаут простоя соединения как `Duration`.
+    pub fn db_idle_timeout(&self) -> Duration {
+        Duration::from_secs(self.db_idle_timeout_secs)
+    }
+
+    /// Пауза между попытками подключения к БД при старте как `Duration`.
+    pub fn db_connect_retry_delay(&self) -> Duration {
+        Duration::from_secs(self.db_connect_retry_delay_secs)
+    }
+
+    /// Период планировщика повторяющихся операций как `Duration`.
+    pub fn scheduler_interval(&self) -> Duration {
+        Duration::from_secs(self.scheduler_interval_secs)
+    }
+
+    /// Таймаут обработки запроса к API как `Duration`.
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.request_timeout_secs)
+    }
+
+    /// Таймаут graceful shutdown как `Duration`.
+    pub fn shutdown_timeout(&self) -> Duration {
+        Duration::from_secs(self.shutdown_timeout_secs)
+    }
+}
+
+/// Читает положительное число из переменной окружения.
+///
+/// # Почему не паника?
+/// Опечатка в `DB_MAX_CONNECTIONS=1O` не должна ронять сервис при старте.
+/// Нет переменной, не число или ноль — берём `default` и предупреждаем в лог.
+///
+/// # Generic `T`
+/// Одна функция и для `u32`, и для `u64`, и для `usize`: `FromStr` — чтобы `.parse()`,
+/// `Default + PartialEq` — чтобы сравнить с нулём.
+fn positive_var<T>(name: &str, default: T) -> T
+where
+    T: std::str::FromStr + Default + PartialEq + std::fmt::Display + Copy,
+{
+    let Ok(raw) = env::var(name) else {
+        return default;
+    };
+
+    match raw.trim().parse::<T>() {
+        Ok(value) if value != T::default() => value,
+        _ => {
+            tracing::warn!("Invalid {}='{}', using default {}", name, raw, default);
+            default
+        }
+    }
+}
+
+/// Приводит префикс маршрутов к виду `/finance` или пустой строке.
+///
+/// `finance`, `/finance/` и ` /finance ` дают `/finance`; пустая
+/// строка и `/` — отсутствие префикса. Сегменты — только буквы, цифры
+/// и `-._~`: `:id` и `*rest` в префиксе axum понял бы как параметры пути.
+///
+/// # Errors
+/// Строка с описанием, если в префиксе пустой сегмент (`/a//b`)
+/// или недопустимый символ.
+fn normalize_base_path(raw: &str) -> Result<String, String> {
+    let path = raw.trim().trim_matches('/');
+    if path.is_empty() {
+        return Ok(String::new());
+    }
+
+    let valid = path.split('/').all(|segment| {
+        !segment.is_empty()
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-._~".contains(c))
+    });
+    if !valid {
+        return Err(format!("Invalid API_BASE_PATH='{}'", raw));
+    }
+
+    Ok(format!("/{}", path))
+}
+
+/// Заменяет пароль в URL на `***` и отбрасывает параметры.
+///
+/// # Почему с конца, а не по правилам URL?
+/// В пароле должны быть закодированы `@`, `:` и `/` (`%40`), но часто
+/// их вставляют как есть. Граница логина — последний `@`: в хосте и имени
+/// базы его не бывает, а всё, что до него, — логин и пароль, какие бы
+/// символы в них ни были. Параметры отбрасываются целиком: в них тоже
+/// может быть `password=...`.
+fn redact_url(url: &str) -> String {
+    let Some((scheme, rest)) = url.split_once("://") else {
+        // sqlite:finance.db — логина и пароля нет
+        return url.to_string();
+    };
+
+    let (user, location) = match rest.rsplit_once('@') {
+        Some((credentials, location)) => match credentials.split_once(':') {
+            Some((user, _password)) => (format!("{}:***@", user), location),
+            None => (format!("{}@", credentials), location),
+        },
+        None => (String::new(), rest),
+    };
+    let location = location.split(['?', '#']).next().unwrap_or_default();
+
+    format!("{}://{}{}", scheme, user, location)
 }
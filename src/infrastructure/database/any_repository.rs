@@ -0,0 +1,442 @@
+//! Репозитории над пулом, выбранным во время выполнения (см. `DatabasePool`).
+//!
+//! # Почему enum, а не `Box<dyn ...>`?
+//! Async-методы портов (через `trait_variant`) не object-safe — `dyn`
+//! с ними не работает. Enum с делегированием — статическая диспетчеризация
+//! без этого ограничения.
+//!
+//! # Почему `Error = sqlx::Error`?
+//! Обе реализации каждого порта построены на sqlx, ошибка у них общая —
+//! и классифицируется одинаково (см. `DbErrorKind`).
+
+use chrono::{DateTime, Utc};
+use futures_util::stream::BoxStream;
+use uuid::Uuid;
+
+use super::{
+    DatabasePool, PostgresAccountRepository, PostgresBackupRepository,
+    PostgresIdempotencyRepository, PostgresRecurringRepository, PostgresSnapshotRepository,
+    PostgresTransactionRepository, PostgresWalletRepository, PostgresWebhookRepository,
+    SqliteAccountRepository, SqliteBackupRepository, SqliteIdempotencyRepository,
+    SqliteRecurringRepository, SqliteSnapshotRepository, SqliteTransactionRepository,
+    SqliteWalletRepository, SqliteWebhookRepository,
+};
+use crate::application::dto::AccountResponse;
+use crate::application::ports::{
+    AccountFilter, AccountRepository, ActivityCursor, BackupData, BackupRepository, CategoryTotal,
+    CurrencyStats, IdempotencyRecord, IdempotencyRepository, KindTotal, RecurringRepository,
+    SnapshotRepository, TransactionFilter, TransactionRepository, WalletRepository,
+    WebhookRepository,
+};
+use crate::domain::entities::{
+    Account, AuditEntry, BalanceSnapshot, RecurringTransaction, Transaction, TransactionKind,
+    Wallet, Webhook,
+};
+
+/// Enum из реализаций порта для PostgreSQL и SQLite и `new` над `DatabasePool`.
+macro_rules! any_repository {
+    ($(#[$doc:meta])* $name:ident($postgres:ident, $sqlite:ident)) => {
+        $(#[$doc])*
+        #[derive(Clone)]
+        pub enum $name {
+            Postgres($postgres),
+            Sqlite($sqlite),
+        }
+
+        impl $name {
+            /// Репозиторий над пулом `pool`.
+            pub fn new(pool: &DatabasePool) -> Self {
+                match pool {
+                    DatabasePool::Postgres(pool) => Self::Postgres($postgres::new(pool.clone())),
+                    DatabasePool::Sqlite(pool) => Self::Sqlite($sqlite::new(pool.clone())),
+                }
+            }
+        }
+    };
+}
+
+/// Вызывает одноимённый метод у выбранной реализации.
+macro_rules! delegate {
+    ($self:ident.$method:ident($($arg:expr),*)) => {
+        match $self {
+            Self::Postgres(repository) => repository.$method($($arg),*).await,
+            Self::Sqlite(repository) => repository.$method($($arg),*).await,
+        }
+    };
+}
+
+any_repository!(
+    /// Репозиторий счетов.
+    AnyAccountRepository(PostgresAccountRepository, SqliteAccountRepository)
+);
+any_repository!(
+    /// Журнал операций.
+    AnyTransactionRepository(PostgresTransactionRepository, SqliteTransactionRepository)
+);
+any_repository!(
+    /// История баланса.
+    AnySnapshotRepository(PostgresSnapshotRepository, SqliteSnapshotRepository)
+);
+any_repository!(
+    /// Ключи идемпотентности.
+    AnyIdempotencyRepository(PostgresIdempotencyRepository, SqliteIdempotencyRepository)
+);
+any_repository!(
+    /// Правила повторяющихся операций.
+    AnyRecurringRepository(PostgresRecurringRepository, SqliteRecurringRepository)
+);
+any_repository!(
+    /// Подписки на события.
+    AnyWebhookRepository(PostgresWebhookRepository, SqliteWebhookRepository)
+);
+any_repository!(
+    /// Кошельки.
+    AnyWalletRepository(PostgresWalletRepository, SqliteWalletRepository)
+);
+any_repository!(
+    /// Резервные копии.
+    AnyBackupRepository(PostgresBackupRepository, SqliteBackupRepository)
+);
+
+impl AccountRepository for AnyAccountRepository {
+    type Error = sqlx::Error;
+
+    async fn create(&self, account: &Account, audit: &[AuditEntry]) -> Result<(), Self::Error> {
+        delegate!(self.create(account, audit))
+    }
+
+    async fn create_many(
+        &self,
+        accounts: &[Account],
+        audit: &[AuditEntry],
+    ) -> Result<(), Self::Error> {
+        delegate!(self.create_many(accounts, audit))
+    }
+
+    async fn find_by_id(&self, user_id: &str, id: Uuid) -> Result<Option<Account>, Self::Error> {
+        delegate!(self.find_by_id(user_id, id))
+    }
+
+    async fn find_by_name(
+        &self,
+        user_id: &str,
+        name: &str,
+    ) -> Result<Option<Account>, Self::Error> {
+        delegate!(self.find_by_name(user_id, name))
+    }
+
+    async fn find_owner(&self, id: Uuid) -> Result<Option<String>, Self::Error> {
+        delegate!(self.find_owner(id))
+    }
+
+    async fn find_all(&self, user_id: &str) -> Result<Vec<Account>, Self::Error> {
+        delegate!(self.find_all(user_id))
+    }
+
+    async fn find_all_paginated(
+        &self,
+        user_id: &str,
+        filter: &AccountFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Account>, Self::Error> {
+        delegate!(self.find_all_paginated(user_id, filter, limit, offset))
+    }
+
+    async fn count(&self, user_id: &str, filter: &AccountFilter) -> Result<i64, Self::Error> {
+        delegate!(self.count(user_id, filter))
+    }
+
+    async fn stats_by_currency(&self, user_id: &str) -> Result<Vec<CurrencyStats>, Self::Error> {
+        delegate!(self.stats_by_currency(user_id))
+    }
+
+    async fn update(
+        &self,
+        account: &Account,
+        audit: &[AuditEntry],
+        ledger: &[Transaction],
+    ) -> Result<bool, Self::Error> {
+        delegate!(self.update(account, audit, ledger))
+    }
+
+    async fn update_pair(
+        &self,
+        first: &Account,
+        second: &Account,
+        audit: &[AuditEntry],
+        ledger: &[Transaction],
+    ) -> Result<bool, Self::Error> {
+        delegate!(self.update_pair(first, second, audit, ledger))
+    }
+
+    async fn delete(
+        &self,
+        user_id: &str,
+        id: Uuid,
+        now: DateTime<Utc>,
+        audit: &[AuditEntry],
+    ) -> Result<(), Self::Error> {
+        delegate!(self.delete(user_id, id, now, audit))
+    }
+
+    async fn find_deleted_by_id(
+        &self,
+        user_id: &str,
+        id: Uuid,
+    ) -> Result<Option<Account>, Self::Error> {
+        delegate!(self.find_deleted_by_id(user_id, id))
+    }
+
+    async fn restore(
+        &self,
+        user_id: &str,
+        id: Uuid,
+        now: DateTime<Utc>,
+        audit: &[AuditEntry],
+    ) -> Result<bool, Self::Error> {
+        delegate!(self.restore(user_id, id, now, audit))
+    }
+
+    async fn find_audit(
+        &self,
+        user_id: &str,
+        account_id: Uuid,
+    ) -> Result<Vec<AuditEntry>, Self::Error> {
+        delegate!(self.find_audit(user_id, account_id))
+    }
+}
+
+impl TransactionRepository for AnyTransactionRepository {
+    type Error = sqlx::Error;
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Transaction>, Self::Error> {
+        delegate!(self.find_by_id(id))
+    }
+
+    async fn update_details(&self, transaction: &Transaction) -> Result<bool, Self::Error> {
+        delegate!(self.update_details(transaction))
+    }
+
+    async fn find_by_account(
+        &self,
+        account_id: Uuid,
+        filter: &TransactionFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Transaction>, Self::Error> {
+        delegate!(self.find_by_account(account_id, filter, limit, offset))
+    }
+
+    async fn count_by_account(
+        &self,
+        account_id: Uuid,
+        filter: &TransactionFilter,
+    ) -> Result<i64, Self::Error> {
+        delegate!(self.count_by_account(account_id, filter))
+    }
+
+    /// Не async: поток возвращается сразу, `delegate!` здесь не подходит.
+    fn stream_by_account(
+        &self,
+        account_id: Uuid,
+    ) -> BoxStream<'static, Result<Transaction, Self::Error>> {
+        match self {
+            Self::Postgres(repository) => repository.stream_by_account(account_id),
+            Self::Sqlite(repository) => repository.stream_by_account(account_id),
+        }
+    }
+
+    async fn find_in_range(
+        &self,
+        account_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Transaction>, Self::Error> {
+        delegate!(self.find_in_range(account_id, from, to))
+    }
+
+    async fn totals_before(
+        &self,
+        account_id: Uuid,
+        before: DateTime<Utc>,
+    ) -> Result<Vec<KindTotal>, Self::Error> {
+        delegate!(self.totals_before(account_id, before))
+    }
+
+    async fn totals_by_category(
+        &self,
+        account_ids: &[Uuid],
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<CategoryTotal>, Self::Error> {
+        delegate!(self.totals_by_category(account_ids, from, to))
+    }
+
+    async fn find_recent(
+        &self,
+        account_ids: &[Uuid],
+        before: Option<ActivityCursor>,
+        limit: i64,
+    ) -> Result<Vec<Transaction>, Self::Error> {
+        delegate!(self.find_recent(account_ids, before, limit))
+    }
+
+    async fn sum_since(
+        &self,
+        account_id: Uuid,
+        kind: TransactionKind,
+        since: DateTime<Utc>,
+    ) -> Result<i64, Self::Error> {
+        delegate!(self.sum_since(account_id, kind, since))
+    }
+
+    async fn sum_reversed_since(
+        &self,
+        account_id: Uuid,
+        kind: TransactionKind,
+        since: DateTime<Utc>,
+    ) -> Result<i64, Self::Error> {
+        delegate!(self.sum_reversed_since(account_id, kind, since))
+    }
+}
+
+impl SnapshotRepository for AnySnapshotRepository {
+    type Error = sqlx::Error;
+
+    async fn create(&self, snapshot: &BalanceSnapshot) -> Result<(), Self::Error> {
+        delegate!(self.create(snapshot))
+    }
+
+    async fn find_latest(&self, account_id: Uuid) -> Result<Option<BalanceSnapshot>, Self::Error> {
+        delegate!(self.find_latest(account_id))
+    }
+
+    async fn find_in_range(
+        &self,
+        account_id: Uuid,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<BalanceSnapshot>, Self::Error> {
+        delegate!(self.find_in_range(account_id, from, to))
+    }
+}
+
+impl IdempotencyRepository for AnyIdempotencyRepository {
+    type Error = sqlx::Error;
+
+    async fn reserve(
+        &self,
+        record: &IdempotencyRecord,
+        expired_before: DateTime<Utc>,
+    ) -> Result<Option<IdempotencyRecord>, Self::Error> {
+        delegate!(self.reserve(record, expired_before))
+    }
+
+    async fn complete(&self, key: &str, response: &AccountResponse) -> Result<(), Self::Error> {
+        delegate!(self.complete(key, response))
+    }
+
+    async fn release(&self, key: &str) -> Result<(), Self::Error> {
+        delegate!(self.release(key))
+    }
+}
+
+impl RecurringRepository for AnyRecurringRepository {
+    type Error = sqlx::Error;
+
+    async fn create(&self, rule: &RecurringTransaction) -> Result<(), Self::Error> {
+        delegate!(self.create(rule))
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RecurringTransaction>, Self::Error> {
+        delegate!(self.find_by_id(id))
+    }
+
+    async fn find_by_account(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<RecurringTransaction>, Self::Error> {
+        delegate!(self.find_by_account(account_id))
+    }
+
+    async fn find_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<RecurringTransaction>, Self::Error> {
+        delegate!(self.find_due(now, limit))
+    }
+
+    async fn update(&self, rule: &RecurringTransaction) -> Result<(), Self::Error> {
+        delegate!(self.update(rule))
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), Self::Error> {
+        delegate!(self.delete(id))
+    }
+}
+
+impl WebhookRepository for AnyWebhookRepository {
+    type Error = sqlx::Error;
+
+    async fn create(&self, webhook: &Webhook) -> Result<(), Self::Error> {
+        delegate!(self.create(webhook))
+    }
+
+    async fn find_by_id(&self, user_id: &str, id: Uuid) -> Result<Option<Webhook>, Self::Error> {
+        delegate!(self.find_by_id(user_id, id))
+    }
+
+    async fn find_all(&self, user_id: &str) -> Result<Vec<Webhook>, Self::Error> {
+        delegate!(self.find_all(user_id))
+    }
+
+    async fn find_enabled(&self, user_id: &str) -> Result<Vec<Webhook>, Self::Error> {
+        delegate!(self.find_enabled(user_id))
+    }
+
+    async fn update(&self, webhook: &Webhook) -> Result<(), Self::Error> {
+        delegate!(self.update(webhook))
+    }
+
+    async fn delete(&self, user_id: &str, id: Uuid) -> Result<(), Self::Error> {
+        delegate!(self.delete(user_id, id))
+    }
+}
+
+impl WalletRepository for AnyWalletRepository {
+    type Error = sqlx::Error;
+
+    async fn create(&self, wallet: &Wallet) -> Result<(), Self::Error> {
+        delegate!(self.create(wallet))
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Wallet>, Self::Error> {
+        delegate!(self.find_by_id(id))
+    }
+
+    async fn find_all(&self) -> Result<Vec<Wallet>, Self::Error> {
+        delegate!(self.find_all())
+    }
+
+    async fn update(&self, wallet: &Wallet) -> Result<bool, Self::Error> {
+        delegate!(self.update(wallet))
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), Self::Error> {
+        delegate!(self.delete(id))
+    }
+}
+
+impl BackupRepository for AnyBackupRepository {
+    type Error = sqlx::Error;
+
+    async fn export_all(&self) -> Result<BackupData, Self::Error> {
+        delegate!(self.export_all())
+    }
+
+    async fn import_all(&self, data: &BackupData) -> Result<bool, Self::Error> {
+        delegate!(self.import_all(data))
+    }
+}
@@ -0,0 +1,71 @@
+//! Подключение к PostgreSQL при старте — с повторами.
+//!
+//! БД и сервер часто поднимаются одновременно (docker compose,
+//! перезапуск узла), и первые секунды БД ещё не принимает соединения.
+//! Вместо падения на первой попытке сервер ждёт её ограниченное время.
+
+use std::time::Duration;
+
+use sqlx::{Connection, PgConnection};
+
+use super::TransientError;
+
+/// БД недоступна при старте.
+///
+/// `attempts` — сколько попыток сделано: все, если ошибка временная,
+/// и одна, если повтор не поможет (неверный пароль, нет такой базы).
+#[derive(Debug, thiserror::Error)]
+#[error("Could not connect to the database after {attempts} attempt(s): {source}")]
+pub struct DatabaseUnavailable {
+    pub attempts: u32,
+    pub source: sqlx::Error,
+}
+
+/// Проверяет, что БД принимает соединения: до `retries + 1` попыток
+/// с паузой `delay` между ними. Каждая неудача — предупреждение в лог.
+///
+/// # Почему отдельное соединение, а не сразу пул?
+/// `PgPoolOptions::connect` при недоступной БД ждёт весь
+/// `DB_ACQUIRE_TIMEOUT_SECS` и возвращает `PoolTimedOut` — без причины.
+/// Одиночное соединение падает сразу и с настоящей ошибкой
+/// («connection refused», «password authentication failed»).
+///
+/// # Errors
+/// `DatabaseUnavailable` с последней ошибкой — после всех попыток
+/// или сразу, если ошибка не временная (см. `TransientError`).
+pub async fn wait_for_database(
+    url: &str,
+    retries: u32,
+    delay: Duration,
+) -> Result<(), DatabaseUnavailable> {
+    let attempts = retries.saturating_add(1);
+    let mut attempt = 1;
+
+    loop {
+        let error = match PgConnection::connect(url).await {
+            Ok(connection) => {
+                // Ошибка закрытия не важна: соединение было, БД доступна
+                let _ = connection.close().await;
+                return Ok(());
+            }
+            Err(error) => error,
+        };
+
+        if !error.is_transient() || attempt >= attempts {
+            return Err(DatabaseUnavailable {
+                attempts: attempt,
+                source: error,
+            });
+        }
+
+        tracing::warn!(
+            "Database connection attempt {}/{} failed: {}; retrying in {:?}",
+            attempt,
+            attempts,
+            error,
+            delay
+        );
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
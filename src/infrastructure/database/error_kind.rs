@@ -0,0 +1,95 @@
+//! Классификация ошибок sqlx для ответа клиенту.
+//!
+//! Сервисы возвращают ошибку хранилища как есть (`AccountServiceError::Repository`),
+//! а presentation решает, какой статус отдать. Что за ошибка — знает
+//! только тот, кто знает её тип, поэтому классификация здесь, рядом
+//! с реализациями на sqlx.
+
+use super::TransientError;
+
+/// Категория ошибки хранилища.
+///
+/// | Категория             | Пример                              | HTTP |
+/// |-----------------------|-------------------------------------|------|
+/// | `NotFound`            | `fetch_one` без строк               | 404  |
+/// | `UniqueViolation`     | SQLSTATE `23505`                    | 409  |
+/// | `ForeignKeyViolation` | SQLSTATE `23503`                    | 409  |
+/// | `Transient`           | обрыв соединения, пул исчерпан      | 503  |
+/// | `Other`               | ошибка в SQL, несовпадение типов... | 500  |
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DbErrorKind {
+    NotFound,
+    UniqueViolation,
+    ForeignKeyViolation,
+    Transient,
+    Other,
+}
+
+impl DbErrorKind {
+    /// Категория ошибки sqlx.
+    ///
+    /// Нарушения ограничений различает `DatabaseError::kind()`: sqlx
+    /// сводит к нему SQLSTATE PostgreSQL (`23505`, `23503`) и расширенные
+    /// коды SQLite (`SQLITE_CONSTRAINT_UNIQUE`, `_FOREIGNKEY`) — на SQLite
+    /// классификация проверяется в тестах (см. `test_support`).
+    /// Временные — те же, что повторяет `RetryingAccountRepository`
+    /// (см. `TransientError`): один список на оба решения.
+    pub fn of(err: &sqlx::Error) -> Self {
+        if err.is_transient() {
+            return Self::Transient;
+        }
+        match err {
+            sqlx::Error::RowNotFound => Self::NotFound,
+            // Пул закрыт — сервер останавливается, запрос стоит повторить
+            // на другом экземпляре
+            sqlx::Error::PoolClosed => Self::Transient,
+            sqlx::Error::Database(db_err) => match db_err.kind() {
+                sqlx::error::ErrorKind::UniqueViolation => Self::UniqueViolation,
+                sqlx::error::ErrorKind::ForeignKeyViolation => Self::ForeignKeyViolation,
+                _ => Self::Other,
+            },
+            _ => Self::Other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::test_support::{
+        closed_pool, foreign_key_violation, syntax_error, unique_violation,
+    };
+
+    #[tokio::test]
+    async fn constraint_violations_are_told_apart() {
+        assert_eq!(
+            DbErrorKind::of(&unique_violation().await),
+            DbErrorKind::UniqueViolation
+        );
+        assert_eq!(
+            DbErrorKind::of(&foreign_key_violation().await),
+            DbErrorKind::ForeignKeyViolation
+        );
+    }
+
+    #[tokio::test]
+    async fn unavailable_database_is_transient() {
+        assert_eq!(
+            DbErrorKind::of(&closed_pool().await),
+            DbErrorKind::Transient
+        );
+        assert_eq!(
+            DbErrorKind::of(&sqlx::Error::PoolTimedOut),
+            DbErrorKind::Transient
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_rows_and_broken_queries() {
+        assert_eq!(
+            DbErrorKind::of(&sqlx::Error::RowNotFound),
+            DbErrorKind::NotFound
+        );
+        assert_eq!(DbErrorKind::of(&syntax_error().await), DbErrorKind::Other);
+    }
+}
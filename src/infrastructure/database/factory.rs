@@ -0,0 +1,127 @@
+//! Выбор БД по `DATABASE_URL`.
+//!
+//! `postgres://...` → PostgreSQL, `sqlite:...` → SQLite. Репозитории над
+//! выбранным пулом — в `any_repository`.
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use sqlx::pool::PoolOptions;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{PgPool, SqlitePool};
+
+/// Вид базы данных, определённый по схеме URL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatabaseKind {
+    Postgres,
+    Sqlite,
+}
+
+impl DatabaseKind {
+    /// Определяет вид БД по схеме URL.
+    ///
+    /// `postgres://` и `postgresql://` — оба допустимы для PostgreSQL.
+    /// `None` — схема не поддерживается.
+    pub fn from_url(url: &str) -> Option<Self> {
+        if url.starts_with("postgres://") || url.starts_with("postgresql://") {
+            Some(Self::Postgres)
+        } else if url.starts_with("sqlite:") {
+            Some(Self::Sqlite)
+        } else {
+            None
+        }
+    }
+}
+
+/// Настройки пула, общие для обеих БД (`DB_*` из конфигурации).
+#[derive(Debug, Clone, Copy)]
+pub struct PoolSettings {
+    pub max_connections: u32,
+    pub min_connections: u32,
+    /// Сколько ждать свободное соединение, прежде чем вернуть ошибку
+    pub acquire_timeout: Duration,
+    /// Простаивающие соединения сверх `min_connections` закрываются
+    pub idle_timeout: Duration,
+}
+
+impl PoolSettings {
+    fn options<DB: sqlx::Database>(&self) -> PoolOptions<DB> {
+        PoolOptions::new()
+            .max_connections(self.max_connections)
+            .min_connections(self.min_connections)
+            .acquire_timeout(self.acquire_timeout)
+            .idle_timeout(self.idle_timeout)
+    }
+}
+
+/// Пул соединений выбранной БД.
+///
+/// Как и сами пулы, клонируется дёшево: внутри `Arc`.
+#[derive(Clone)]
+pub enum DatabasePool {
+    Postgres(PgPool),
+    Sqlite(SqlitePool),
+}
+
+impl DatabasePool {
+    /// Подключается к БД из `url`.
+    ///
+    /// Для SQLite файл базы создаётся, если его ещё нет.
+    ///
+    /// # Errors
+    /// `sqlx::Error::Configuration` — схема URL не поддерживается;
+    /// остальные — ошибки подключения.
+    pub async fn connect(url: &str, settings: &PoolSettings) -> Result<Self, sqlx::Error> {
+        match DatabaseKind::from_url(url) {
+            Some(DatabaseKind::Postgres) => {
+                Ok(Self::Postgres(settings.options().connect(url).await?))
+            }
+            Some(DatabaseKind::Sqlite) => {
+                let options = SqliteConnectOptions::from_str(url)?.create_if_missing(true);
+                Ok(Self::Sqlite(
+                    settings.options().connect_with(options).await?,
+                ))
+            }
+            None => Err(sqlx::Error::Configuration(
+                format!("Unsupported DATABASE_URL scheme: {}", url).into(),
+            )),
+        }
+    }
+
+    /// `SELECT 1` — БД отвечает на запросы.
+    pub async fn ping(&self) -> Result<(), sqlx::Error> {
+        match self {
+            Self::Postgres(pool) => {
+                sqlx::query("SELECT 1").execute(pool).await?;
+            }
+            Self::Sqlite(pool) => {
+                sqlx::query("SELECT 1").execute(pool).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Сколько соединений открыто сейчас.
+    pub fn size(&self) -> u32 {
+        match self {
+            Self::Postgres(pool) => pool.size(),
+            Self::Sqlite(pool) => pool.size(),
+        }
+    }
+
+    /// Сколько из них простаивает.
+    pub fn num_idle(&self) -> usize {
+        match self {
+            Self::Postgres(pool) => pool.num_idle(),
+            Self::Sqlite(pool) => pool.num_idle(),
+        }
+    }
+
+    /// Закрывает пул, дождавшись возврата соединений.
+    pub async fn close(&self) {
+        match self {
+            Self::Postgres(pool) => pool.close().await,
+            Self::Sqlite(pool) => pool.close().await,
+        }
+    }
+}
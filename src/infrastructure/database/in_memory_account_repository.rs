@@ -0,0 +1,373 @@
+//! Реализация репозитория счетов в памяти.
+//!
+//! Нужна тестам сервисов, где поднимать PostgreSQL избыточно.
+//! Как и остальные `InMemory*`, собирается только в тестах (`#[cfg(test)]`
+//! в `database/mod.rs`): сервер работает только с PostgreSQL.
+
+use std::collections::{BTreeMap, HashMap};
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::application::ports::{
+    AccountFilter, AccountRepository, AccountRepositoryError, AccountSortField, CurrencyStats,
+};
+use crate::domain::entities::{Account, AuditEntry, Transaction};
+use crate::domain::value_objects::Currency;
+
+use super::InMemoryTransactionRepository;
+
+/// Хранилище счетов в `HashMap` под мьютексом.
+///
+/// # Почему `Arc<Mutex<...>>`?
+/// - `Mutex` — async методы трейта берут `&self`, а менять map нужно через `&mut`
+/// - `Arc` — клоны репозитория (сервис клонируется Axum'ом) видят ОДНИ данные
+///
+/// # Почему `std::sync::Mutex`, а не `tokio::sync::Mutex`?
+/// Блокировка держится доли микросекунды и никогда не живёт через `.await`,
+/// поэтому обычного мьютекса достаточно (и он быстрее).
+///
+/// Журнал изменений и операции (`ledger` в `update`) дописываются, пока
+/// захвачен мьютекс счетов: другой поток не увидит счёт без его записей —
+/// аналог транзакции.
+#[derive(Clone, Default)]
+pub struct InMemoryAccountRepository {
+    accounts: Arc<Mutex<HashMap<Uuid, Account>>>,
+    audit: Arc<Mutex<Vec<AuditEntry>>>,
+    ledger: InMemoryTransactionRepository,
+}
+
+impl InMemoryAccountRepository {
+    /// Создаёт пустой репозиторий, который пишет операции в `ledger`.
+    ///
+    /// Сервису нужен тот же журнал, что и здесь: иначе операции,
+    /// записанные вместе со счётом, не найдёт `TransactionRepository`.
+    pub fn with_ledger(ledger: InMemoryTransactionRepository) -> Self {
+        Self {
+            ledger,
+            ..Self::default()
+        }
+    }
+
+    /// Снимок активных счетов пользователя, новые — первыми
+    /// (как `WHERE user_id = $1 AND deleted_at IS NULL ORDER BY created_at DESC`).
+    fn sorted(&self, user_id: &str) -> Vec<Account> {
+        let mut accounts: Vec<Account> = self
+            .accounts
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|account| account.deleted_at.is_none() && account.user_id == user_id)
+            .cloned()
+            .collect();
+        // Reverse — сортировка по убыванию
+        accounts.sort_by_key(|account| std::cmp::Reverse(account.created_at));
+        accounts
+    }
+
+    /// Активные счета под фильтром в порядке `filter.sort` —
+    /// те же правила, что в SQL у PostgreSQL-реализации.
+    fn filtered(&self, user_id: &str, filter: &AccountFilter) -> Vec<Account> {
+        let name_contains = filter.name_contains.as_ref().map(|s| s.to_lowercase());
+
+        let mut accounts: Vec<Account> = self
+            .sorted(user_id)
+            .into_iter()
+            // Option::iter().all() — true, если фильтр не задан (None)
+            .filter(|account| {
+                filter
+                    .currency
+                    .iter()
+                    .all(|currency| Currency::new(&account.currency).to_string() == *currency)
+            })
+            .filter(|account| {
+                name_contains
+                    .iter()
+                    .all(|part| account.name.to_lowercase().contains(part))
+            })
+            .filter(|account| filter.include_archived || !account.archived)
+            .collect();
+
+        // Ordering::then — второй ключ (id), если первый равен
+        accounts.sort_by(|a, b| {
+            let ordering = match filter.sort.field {
+                AccountSortField::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                AccountSortField::Balance => a.balance.cmp(&b.balance),
+                AccountSortField::CreatedAt => a.created_at.cmp(&b.created_at),
+            }
+            .then(a.id.cmp(&b.id));
+
+            if filter.sort.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        accounts
+    }
+
+    /// Дописывает записи в журнал изменений.
+    fn log(&self, audit: &[AuditEntry]) {
+        self.audit.lock().unwrap().extend_from_slice(audit);
+    }
+}
+
+/// Ошибок нет — нет и нарушений уникальности.
+///
+/// Уникального индекса здесь нет: имя проверяет только сервис,
+/// поэтому гонку двух одинаковых имён эта реализация не ловит.
+impl AccountRepositoryError for Infallible {
+    fn is_unique_violation(&self) -> bool {
+        match *self {}
+    }
+}
+
+/// Реализация порта AccountRepository в памяти.
+///
+/// # `type Error = Infallible`
+/// `Infallible` — тип без значений: ошибка в принципе невозможна.
+/// Компилятор это знает, и `match` по такой ошибке можно не писать.
+impl AccountRepository for InMemoryAccountRepository {
+    type Error = Infallible;
+
+    async fn create(&self, account: &Account, audit: &[AuditEntry]) -> Result<(), Self::Error> {
+        // .unwrap() на lock() паникует, только если другой поток
+        // запаниковал с захваченным мьютексом — это уже баг
+        let mut accounts = self.accounts.lock().unwrap();
+        accounts.insert(account.id, account.clone());
+        self.log(audit);
+        Ok(())
+    }
+
+    async fn create_many(
+        &self,
+        accounts: &[Account],
+        audit: &[AuditEntry],
+    ) -> Result<(), Self::Error> {
+        let mut stored = self.accounts.lock().unwrap();
+        for account in accounts {
+            stored.insert(account.id, account.clone());
+        }
+        self.log(audit);
+        Ok(())
+    }
+
+    async fn find_by_id(&self, user_id: &str, id: Uuid) -> Result<Option<Account>, Self::Error> {
+        Ok(self
+            .accounts
+            .lock()
+            .unwrap()
+            .get(&id)
+            .filter(|account| account.deleted_at.is_none() && account.user_id == user_id)
+            .cloned())
+    }
+
+    /// Поиск без учёта регистра — как `LOWER(name) = LOWER($1)` в PostgreSQL.
+    async fn find_by_name(
+        &self,
+        user_id: &str,
+        name: &str,
+    ) -> Result<Option<Account>, Self::Error> {
+        let name = name.to_lowercase();
+        Ok(self
+            .sorted(user_id)
+            .into_iter()
+            .find(|account| account.name.to_lowercase() == name))
+    }
+
+    async fn find_owner(&self, id: Uuid) -> Result<Option<String>, Self::Error> {
+        Ok(self
+            .accounts
+            .lock()
+            .unwrap()
+            .get(&id)
+            .filter(|account| account.deleted_at.is_none())
+            .map(|account| account.user_id.clone()))
+    }
+
+    async fn find_all(&self, user_id: &str) -> Result<Vec<Account>, Self::Error> {
+        Ok(self.sorted(user_id))
+    }
+
+    async fn find_all_paginated(
+        &self,
+        user_id: &str,
+        filter: &AccountFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Account>, Self::Error> {
+        Ok(self
+            .filtered(user_id, filter)
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn count(&self, user_id: &str, filter: &AccountFilter) -> Result<i64, Self::Error> {
+        Ok(self.filtered(user_id, filter).len() as i64)
+    }
+
+    /// `BTreeMap` — строки сразу в порядке кода валюты, как `ORDER BY code`.
+    async fn stats_by_currency(&self, user_id: &str) -> Result<Vec<CurrencyStats>, Self::Error> {
+        let mut stats: BTreeMap<String, CurrencyStats> = BTreeMap::new();
+        for account in self
+            .sorted(user_id)
+            .into_iter()
+            .filter(|account| !account.archived)
+        {
+            let currency = Currency::new(&account.currency).to_string();
+            let entry = stats
+                .entry(currency.clone())
+                .or_insert_with(|| CurrencyStats {
+                    currency,
+                    count: 0,
+                    balance: 0,
+                    oldest_created_at: account.created_at,
+                    newest_created_at: account.created_at,
+                });
+            entry.count += 1;
+            entry.balance += account.balance;
+            entry.oldest_created_at = entry.oldest_created_at.min(account.created_at);
+            entry.newest_created_at = entry.newest_created_at.max(account.created_at);
+        }
+        Ok(stats.into_values().collect())
+    }
+
+    /// Та же семантика, что `UPDATE ... WHERE id = $1 AND version = $6 AND user_id = $14`.
+    async fn update(
+        &self,
+        account: &Account,
+        audit: &[AuditEntry],
+        ledger: &[Transaction],
+    ) -> Result<bool, Self::Error> {
+        let mut accounts = self.accounts.lock().unwrap();
+        match accounts.get_mut(&account.id) {
+            Some(stored)
+                if stored.version == account.version && stored.user_id == account.user_id =>
+            {
+                *stored = Account {
+                    version: account.version + 1,
+                    ..account.clone()
+                };
+                self.log(audit);
+                self.ledger.append(ledger);
+                Ok(true)
+            }
+            // Нет счёта или версия устарела — ничего не обновили
+            _ => Ok(false),
+        }
+    }
+
+    /// Обе версии проверяются под одной блокировкой, до любой записи.
+    async fn update_pair(
+        &self,
+        first: &Account,
+        second: &Account,
+        audit: &[AuditEntry],
+        ledger: &[Transaction],
+    ) -> Result<bool, Self::Error> {
+        let mut accounts = self.accounts.lock().unwrap();
+
+        let current = |account: &Account| {
+            accounts.get(&account.id).is_some_and(|stored| {
+                stored.version == account.version && stored.user_id == account.user_id
+            })
+        };
+        if !current(first) || !current(second) {
+            return Ok(false);
+        }
+
+        for account in [first, second] {
+            accounts.insert(
+                account.id,
+                Account {
+                    version: account.version + 1,
+                    ..account.clone()
+                },
+            );
+        }
+        self.log(audit);
+        self.ledger.append(ledger);
+        Ok(true)
+    }
+
+    /// Мягкое удаление — как в PostgreSQL, запись остаётся.
+    async fn delete(
+        &self,
+        user_id: &str,
+        id: Uuid,
+        now: DateTime<Utc>,
+        audit: &[AuditEntry],
+    ) -> Result<(), Self::Error> {
+        if let Some(account) = self.accounts.lock().unwrap().get_mut(&id) {
+            if account.deleted_at.is_none() && account.user_id == user_id {
+                account.deleted_at = Some(now);
+                account.version += 1;
+                self.log(audit);
+            }
+        }
+        Ok(())
+    }
+
+    async fn find_deleted_by_id(
+        &self,
+        user_id: &str,
+        id: Uuid,
+    ) -> Result<Option<Account>, Self::Error> {
+        Ok(self
+            .accounts
+            .lock()
+            .unwrap()
+            .get(&id)
+            .filter(|account| account.deleted_at.is_some() && account.user_id == user_id)
+            .cloned())
+    }
+
+    async fn restore(
+        &self,
+        user_id: &str,
+        id: Uuid,
+        now: DateTime<Utc>,
+        audit: &[AuditEntry],
+    ) -> Result<bool, Self::Error> {
+        match self.accounts.lock().unwrap().get_mut(&id) {
+            Some(account) if account.deleted_at.is_some() && account.user_id == user_id => {
+                account.deleted_at = None;
+                account.version += 1;
+                account.updated_at = now;
+                self.log(audit);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn find_audit(
+        &self,
+        user_id: &str,
+        account_id: Uuid,
+    ) -> Result<Vec<AuditEntry>, Self::Error> {
+        let owned = self
+            .accounts
+            .lock()
+            .unwrap()
+            .get(&account_id)
+            .is_some_and(|account| account.user_id == user_id);
+        if !owned {
+            return Ok(Vec::new());
+        }
+
+        // Журнал дописывается по порядку — он уже хронологический
+        Ok(self
+            .audit
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|entry| entry.account_id == account_id)
+            .cloned()
+            .collect())
+    }
+}
@@ -0,0 +1,63 @@
+//! Реализация ключей идемпотентности в памяти.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+
+use crate::application::dto::AccountResponse;
+use crate::application::ports::{IdempotencyRecord, IdempotencyRepository};
+
+/// Ключи идемпотентности в `HashMap` под мьютексом.
+#[derive(Clone, Default)]
+pub struct InMemoryIdempotencyRepository {
+    records: Arc<Mutex<HashMap<String, IdempotencyRecord>>>,
+}
+
+impl InMemoryIdempotencyRepository {
+    /// Создаёт пустое хранилище.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl IdempotencyRepository for InMemoryIdempotencyRepository {
+    type Error = Infallible;
+
+    /// Проверка и вставка под одной блокировкой — атомарно,
+    /// как `ON CONFLICT DO NOTHING` в PostgreSQL.
+    async fn reserve(
+        &self,
+        record: &IdempotencyRecord,
+        expired_before: DateTime<Utc>,
+    ) -> Result<Option<IdempotencyRecord>, Self::Error> {
+        let mut records = self.records.lock().unwrap();
+        records.retain(|_, existing| existing.created_at >= expired_before);
+
+        if let Some(existing) = records.get(&record.key) {
+            return Ok(Some(existing.clone()));
+        }
+
+        records.insert(record.key.clone(), record.clone());
+        Ok(None)
+    }
+
+    async fn complete(&self, key: &str, response: &AccountResponse) -> Result<(), Self::Error> {
+        if let Some(record) = self.records.lock().unwrap().get_mut(key) {
+            record.response = Some(response.clone());
+        }
+        Ok(())
+    }
+
+    async fn release(&self, key: &str) -> Result<(), Self::Error> {
+        let mut records = self.records.lock().unwrap();
+        if records
+            .get(key)
+            .is_some_and(|record| record.response.is_none())
+        {
+            records.remove(key);
+        }
+        Ok(())
+    }
+}
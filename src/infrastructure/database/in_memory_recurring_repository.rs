@@ -0,0 +1,84 @@
+//! Реализация правил повторяющихся операций в памяти.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::application::ports::RecurringRepository;
+use crate::domain::entities::RecurringTransaction;
+
+/// Правила в `HashMap` под мьютексом.
+#[derive(Clone, Default)]
+pub struct InMemoryRecurringRepository {
+    rules: Arc<Mutex<HashMap<Uuid, RecurringTransaction>>>,
+}
+
+impl InMemoryRecurringRepository {
+    /// Создаёт пустое хранилище.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl RecurringRepository for InMemoryRecurringRepository {
+    type Error = Infallible;
+
+    async fn create(&self, rule: &RecurringTransaction) -> Result<(), Self::Error> {
+        self.rules.lock().unwrap().insert(rule.id, rule.clone());
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RecurringTransaction>, Self::Error> {
+        Ok(self.rules.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn find_by_account(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<RecurringTransaction>, Self::Error> {
+        let mut rules: Vec<RecurringTransaction> = self
+            .rules
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|rule| rule.account_id == account_id)
+            .cloned()
+            .collect();
+        rules.sort_by_key(|rule| rule.created_at);
+        Ok(rules)
+    }
+
+    async fn find_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<RecurringTransaction>, Self::Error> {
+        let mut rules: Vec<RecurringTransaction> = self
+            .rules
+            .lock()
+            .unwrap()
+            .values()
+            // Как `WHERE enabled AND next_run <= $1`
+            .filter(|rule| rule.enabled && rule.next_run <= now)
+            .cloned()
+            .collect();
+        rules.sort_by_key(|rule| rule.next_run);
+        rules.truncate(limit as usize);
+        Ok(rules)
+    }
+
+    async fn update(&self, rule: &RecurringTransaction) -> Result<(), Self::Error> {
+        if let Some(stored) = self.rules.lock().unwrap().get_mut(&rule.id) {
+            *stored = rule.clone();
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), Self::Error> {
+        self.rules.lock().unwrap().remove(&id);
+        Ok(())
+    }
+}
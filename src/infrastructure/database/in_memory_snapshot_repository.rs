@@ -0,0 +1,66 @@
+//! Реализация истории баланса в памяти.
+
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::application::ports::SnapshotRepository;
+use crate::domain::entities::BalanceSnapshot;
+
+/// Снимки баланса в `Vec` под мьютексом (в порядке записи).
+#[derive(Clone, Default)]
+pub struct InMemorySnapshotRepository {
+    snapshots: Arc<Mutex<Vec<BalanceSnapshot>>>,
+}
+
+impl InMemorySnapshotRepository {
+    /// Создаёт пустое хранилище.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SnapshotRepository for InMemorySnapshotRepository {
+    type Error = Infallible;
+
+    async fn create(&self, snapshot: &BalanceSnapshot) -> Result<(), Self::Error> {
+        self.snapshots.lock().unwrap().push(snapshot.clone());
+        Ok(())
+    }
+
+    async fn find_latest(&self, account_id: Uuid) -> Result<Option<BalanceSnapshot>, Self::Error> {
+        Ok(self
+            .snapshots
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|snapshot| snapshot.account_id == account_id)
+            .max_by_key(|snapshot| snapshot.recorded_at)
+            .cloned())
+    }
+
+    async fn find_in_range(
+        &self,
+        account_id: Uuid,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<BalanceSnapshot>, Self::Error> {
+        // Граница не задана → берём минимально/максимально возможную дату
+        let from = from.unwrap_or(DateTime::<Utc>::MIN_UTC);
+        let to = to.unwrap_or(DateTime::<Utc>::MAX_UTC);
+
+        let mut snapshots: Vec<BalanceSnapshot> = self
+            .snapshots
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|snapshot| snapshot.account_id == account_id)
+            .filter(|snapshot| (from..=to).contains(&snapshot.recorded_at))
+            .cloned()
+            .collect();
+        snapshots.sort_by_key(|snapshot| snapshot.recorded_at);
+        Ok(snapshots)
+    }
+}
@@ -0,0 +1,285 @@
+//! Реализация журнала операций в памяти.
+//!
+//! Пара к `InMemoryAccountRepository`: сервису нужны оба репозитория,
+//! причём с одинаковым типом ошибки.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use futures_util::stream::{self, BoxStream, StreamExt};
+use uuid::Uuid;
+
+use crate::application::ports::{
+    ActivityCursor, CategoryTotal, KindTotal, TransactionFilter, TransactionRepository,
+};
+use crate::domain::entities::{Transaction, TransactionKind};
+
+/// Журнал операций в `Vec` под мьютексом.
+///
+/// Операции только добавляются (меняются лишь их детали), поэтому `Vec` хватает —
+/// поиск по ID линейный, но для тестов это не важно.
+#[derive(Clone, Default)]
+pub struct InMemoryTransactionRepository {
+    transactions: Arc<Mutex<Vec<Transaction>>>,
+}
+
+impl InMemoryTransactionRepository {
+    /// Создаёт пустой журнал.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Дописывает операции — для `InMemoryAccountRepository`, который
+    /// пишет их вместе со счётом.
+    ///
+    /// Уже записанной операции обновляются детали, как в `update_details`
+    /// (так отмена ставит `reversed_at` вместе с обратной записью).
+    pub(super) fn append(&self, transactions: &[Transaction]) {
+        let mut stored = self.transactions.lock().unwrap();
+        for transaction in transactions {
+            match stored.iter_mut().find(|stored| stored.id == transaction.id) {
+                Some(existing) => Self::update(existing, transaction),
+                None => stored.push(transaction.clone()),
+            }
+        }
+    }
+
+    /// Переносит в `stored` детали из `transaction` — те же правила,
+    /// что `UPDATE ... SET reversed_at = COALESCE(reversed_at, $4)`.
+    fn update(stored: &mut Transaction, transaction: &Transaction) {
+        stored.note = transaction.note.clone();
+        stored.category = transaction.category.clone();
+        stored.reversed_at = stored.reversed_at.or(transaction.reversed_at);
+    }
+}
+
+impl TransactionRepository for InMemoryTransactionRepository {
+    type Error = Infallible;
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Transaction>, Self::Error> {
+        Ok(self
+            .transactions
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|transaction| transaction.id == id)
+            .cloned())
+    }
+
+    async fn update_details(&self, transaction: &Transaction) -> Result<bool, Self::Error> {
+        let mut transactions = self.transactions.lock().unwrap();
+        match transactions
+            .iter_mut()
+            .find(|stored| stored.id == transaction.id)
+        {
+            Some(stored) => {
+                Self::update(stored, transaction);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Страница операций счёта, новые — первыми
+    /// (как `ORDER BY timestamp DESC, id DESC`).
+    async fn find_by_account(
+        &self,
+        account_id: Uuid,
+        filter: &TransactionFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Transaction>, Self::Error> {
+        let mut transactions: Vec<Transaction> = self
+            .transactions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|transaction| matches_filter(transaction, account_id, filter))
+            .cloned()
+            .collect();
+        transactions
+            .sort_by_key(|transaction| std::cmp::Reverse((transaction.timestamp, transaction.id)));
+        Ok(transactions
+            .into_iter()
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
+            .collect())
+    }
+
+    async fn count_by_account(
+        &self,
+        account_id: Uuid,
+        filter: &TransactionFilter,
+    ) -> Result<i64, Self::Error> {
+        Ok(self
+            .transactions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|transaction| matches_filter(transaction, account_id, filter))
+            .count() as i64)
+    }
+
+    /// Снимок журнала счёта на момент вызова, старые — первыми.
+    fn stream_by_account(
+        &self,
+        account_id: Uuid,
+    ) -> BoxStream<'static, Result<Transaction, Self::Error>> {
+        let mut transactions: Vec<Transaction> = self
+            .transactions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|transaction| transaction.account_id == account_id)
+            .cloned()
+            .collect();
+        transactions.sort_by_key(|transaction| transaction.timestamp);
+        stream::iter(transactions.into_iter().map(Ok)).boxed()
+    }
+
+    async fn find_in_range(
+        &self,
+        account_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Transaction>, Self::Error> {
+        let mut transactions: Vec<Transaction> = self
+            .transactions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|t| t.account_id == account_id && t.timestamp >= from && t.timestamp < to)
+            .cloned()
+            .collect();
+        transactions.sort_by_key(|transaction| transaction.timestamp);
+        Ok(transactions)
+    }
+
+    /// Группировка как `GROUP BY account_id, category, kind`.
+    async fn totals_before(
+        &self,
+        account_id: Uuid,
+        before: DateTime<Utc>,
+    ) -> Result<Vec<KindTotal>, Self::Error> {
+        let mut groups: HashMap<TransactionKind, (i64, i64)> = HashMap::new();
+
+        for t in self.transactions.lock().unwrap().iter() {
+            if t.account_id == account_id && t.timestamp < before {
+                let group = groups.entry(t.kind).or_default();
+                group.0 += t.amount;
+                group.1 += 1;
+            }
+        }
+
+        Ok(groups
+            .into_iter()
+            .map(|(kind, (total, count))| KindTotal { kind, total, count })
+            .collect())
+    }
+
+    async fn totals_by_category(
+        &self,
+        account_ids: &[Uuid],
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<CategoryTotal>, Self::Error> {
+        let mut groups: HashMap<(Uuid, Option<String>, TransactionKind), (i64, i64)> =
+            HashMap::new();
+
+        for t in self.transactions.lock().unwrap().iter() {
+            let in_range = from.into_iter().all(|from| t.timestamp >= from)
+                && to.into_iter().all(|to| t.timestamp <= to);
+            if in_range && account_ids.contains(&t.account_id) {
+                let group = groups
+                    .entry((t.account_id, t.category.clone(), t.kind))
+                    .or_default();
+                group.0 += t.amount;
+                group.1 += 1;
+            }
+        }
+
+        Ok(groups
+            .into_iter()
+            .map(
+                |((account_id, category, kind), (total, count))| CategoryTotal {
+                    account_id,
+                    category,
+                    kind,
+                    total,
+                    count,
+                },
+            )
+            .collect())
+    }
+
+    async fn find_recent(
+        &self,
+        account_ids: &[Uuid],
+        before: Option<ActivityCursor>,
+        limit: i64,
+    ) -> Result<Vec<Transaction>, Self::Error> {
+        let mut transactions: Vec<Transaction> = self
+            .transactions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|t| account_ids.contains(&t.account_id))
+            .filter(|t| match before {
+                Some(cursor) => (t.timestamp, t.id) < (cursor.timestamp, cursor.id),
+                None => true,
+            })
+            .cloned()
+            .collect();
+        transactions
+            .sort_by_key(|transaction| std::cmp::Reverse((transaction.timestamp, transaction.id)));
+        transactions.truncate(limit.max(0) as usize);
+        Ok(transactions)
+    }
+
+    async fn sum_since(
+        &self,
+        account_id: Uuid,
+        kind: TransactionKind,
+        since: DateTime<Utc>,
+    ) -> Result<i64, Self::Error> {
+        Ok(self
+            .transactions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|t| t.account_id == account_id && t.kind == kind && t.timestamp >= since)
+            .map(|t| t.amount)
+            .sum())
+    }
+
+    async fn sum_reversed_since(
+        &self,
+        account_id: Uuid,
+        kind: TransactionKind,
+        since: DateTime<Utc>,
+    ) -> Result<i64, Self::Error> {
+        Ok(self
+            .transactions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|t| t.account_id == account_id && t.kind == kind)
+            .filter(|t| t.reversed_at.is_some_and(|at| at >= since))
+            .map(|t| t.amount)
+            .sum())
+    }
+}
+
+/// Те же условия, что `FILTER_CONDITIONS` в `PostgresTransactionRepository`.
+fn matches_filter(transaction: &Transaction, account_id: Uuid, filter: &TransactionFilter) -> bool {
+    transaction.account_id == account_id
+        && (filter.category.is_none() || transaction.category == filter.category)
+        && (filter.kind.is_none() || filter.kind == Some(transaction.kind))
+        && filter
+            .from
+            .iter()
+            .all(|&from| transaction.timestamp >= from)
+        && filter.to.iter().all(|&to| transaction.timestamp <= to)
+}
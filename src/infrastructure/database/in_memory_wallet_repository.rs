@@ -0,0 +1,62 @@
+//! Реализация кошельков в памяти.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+
+use uuid::Uuid;
+
+use crate::application::ports::WalletRepository;
+use crate::domain::entities::Wallet;
+
+/// Кошельки в `HashMap` под мьютексом.
+#[derive(Clone, Default)]
+pub struct InMemoryWalletRepository {
+    wallets: Arc<Mutex<HashMap<Uuid, Wallet>>>,
+}
+
+impl InMemoryWalletRepository {
+    /// Создаёт пустое хранилище.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl WalletRepository for InMemoryWalletRepository {
+    type Error = Infallible;
+
+    async fn create(&self, wallet: &Wallet) -> Result<(), Self::Error> {
+        self.wallets
+            .lock()
+            .unwrap()
+            .insert(wallet.id, wallet.clone());
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Wallet>, Self::Error> {
+        Ok(self.wallets.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn find_all(&self) -> Result<Vec<Wallet>, Self::Error> {
+        let mut wallets: Vec<Wallet> = self.wallets.lock().unwrap().values().cloned().collect();
+        wallets.sort_by_key(|wallet| wallet.created_at);
+        Ok(wallets)
+    }
+
+    async fn update(&self, wallet: &Wallet) -> Result<bool, Self::Error> {
+        let mut wallets = self.wallets.lock().unwrap();
+        match wallets.get_mut(&wallet.id) {
+            Some(stored) if stored.version == wallet.version => {
+                *stored = wallet.clone();
+                stored.version += 1;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), Self::Error> {
+        self.wallets.lock().unwrap().remove(&id);
+        Ok(())
+    }
+}
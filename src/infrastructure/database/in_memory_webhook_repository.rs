@@ -0,0 +1,91 @@
+//! Реализация подписок на события в памяти.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+
+use uuid::Uuid;
+
+use crate::application::ports::WebhookRepository;
+use crate::domain::entities::Webhook;
+
+/// Подписки в `HashMap` под мьютексом.
+#[derive(Clone, Default)]
+pub struct InMemoryWebhookRepository {
+    webhooks: Arc<Mutex<HashMap<Uuid, Webhook>>>,
+}
+
+impl InMemoryWebhookRepository {
+    /// Создаёт пустое хранилище.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Подписки, подходящие под `filter`, в порядке создания.
+    fn sorted(&self, filter: impl Fn(&Webhook) -> bool) -> Vec<Webhook> {
+        let mut webhooks: Vec<Webhook> = self
+            .webhooks
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|webhook| filter(webhook))
+            .cloned()
+            .collect();
+        webhooks.sort_by_key(|webhook| webhook.created_at);
+        webhooks
+    }
+}
+
+impl WebhookRepository for InMemoryWebhookRepository {
+    type Error = Infallible;
+
+    async fn create(&self, webhook: &Webhook) -> Result<(), Self::Error> {
+        self.webhooks
+            .lock()
+            .unwrap()
+            .insert(webhook.id, webhook.clone());
+        Ok(())
+    }
+
+    async fn find_by_id(&self, user_id: &str, id: Uuid) -> Result<Option<Webhook>, Self::Error> {
+        Ok(self
+            .webhooks
+            .lock()
+            .unwrap()
+            .get(&id)
+            .filter(|webhook| webhook.user_id == user_id)
+            .cloned())
+    }
+
+    async fn find_all(&self, user_id: &str) -> Result<Vec<Webhook>, Self::Error> {
+        Ok(self.sorted(|webhook| webhook.user_id == user_id))
+    }
+
+    async fn find_enabled(&self, user_id: &str) -> Result<Vec<Webhook>, Self::Error> {
+        Ok(self.sorted(|webhook| webhook.user_id == user_id && webhook.enabled))
+    }
+
+    async fn update(&self, webhook: &Webhook) -> Result<(), Self::Error> {
+        if let Some(stored) = self
+            .webhooks
+            .lock()
+            .unwrap()
+            .get_mut(&webhook.id)
+            .filter(|stored| stored.user_id == webhook.user_id)
+        {
+            *stored = webhook.clone();
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: &str, id: Uuid) -> Result<(), Self::Error> {
+        let mut webhooks = self.webhooks.lock().unwrap();
+        if webhooks
+            .get(&id)
+            .is_some_and(|webhook| webhook.user_id == user_id)
+        {
+            webhooks.remove(&id);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+//! Применение миграций при старте сервера.
+
+use std::collections::HashSet;
+
+use sqlx::migrate::{Migrate, MigrateError, Migration, Migrator};
+
+use super::DatabasePool;
+
+/// Миграции из `migrations/`.
+///
+/// `sqlx::migrate!` включает SQL-файлы в бинарник на этапе компиляции
+/// (путь относительно Cargo.toml) — на сервер каталог копировать не нужно.
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// Миграции SQLite из `migrations_sqlite/` — та же схема в диалекте SQLite.
+static SQLITE_MIGRATOR: Migrator = sqlx::migrate!("./migrations_sqlite");
+
+/// Применяет недостающие миграции и возвращает те, что применены сейчас.
+///
+/// # Почему применённые версии сравниваются до и после?
+/// `Migrator::run` не сообщает, что именно сделал. Версии берём из
+/// таблицы `_sqlx_migrations`: если миграции одновременно применял другой
+/// экземпляр сервера (`run` берёт advisory lock, так что они ждут друг
+/// друга), его версии в список не попадут.
+///
+/// # Errors
+/// Любая ошибка `MigrateError`: упавший SQL, изменённый файл уже
+/// применённой миграции, версия в БД, которой нет в бинарнике.
+pub async fn run_migrations(pool: &DatabasePool) -> Result<Vec<&'static Migration>, MigrateError> {
+    match pool {
+        DatabasePool::Postgres(pool) => {
+            let mut conn = pool.acquire().await?;
+            let before = applied_versions(&mut *conn).await?;
+            MIGRATOR.run(&mut *conn).await?;
+            let after = applied_versions(&mut *conn).await?;
+            Ok(newly_applied(&MIGRATOR, &before, &after))
+        }
+        DatabasePool::Sqlite(pool) => {
+            let mut conn = pool.acquire().await?;
+            let before = applied_versions(&mut *conn).await?;
+            SQLITE_MIGRATOR.run(&mut *conn).await?;
+            let after = applied_versions(&mut *conn).await?;
+            Ok(newly_applied(&SQLITE_MIGRATOR, &before, &after))
+        }
+    }
+}
+
+/// Миграции `migrator`, которых нет в `before`, но есть в `after`.
+fn newly_applied(
+    migrator: &'static Migrator,
+    before: &HashSet<i64>,
+    after: &HashSet<i64>,
+) -> Vec<&'static Migration> {
+    migrator
+        .iter()
+        .filter(|migration| after.contains(&migration.version))
+        .filter(|migration| !before.contains(&migration.version))
+        .collect()
+}
+
+/// Версии из `_sqlx_migrations`; таблица создаётся, если её ещё нет.
+async fn applied_versions(conn: &mut impl Migrate) -> Result<HashSet<i64>, MigrateError> {
+    conn.ensure_migrations_table().await?;
+    Ok(conn
+        .list_applied_migrations()
+        .await?
+        .into_iter()
+        .map(|migration| migration.version)
+        .collect())
+}
@@ -1,3 +1,79 @@
+mod any_repository;
+mod connect;
+mod error_kind;
+mod factory;
+#[cfg(test)]
+mod in_memory_account_repository;
+#[cfg(test)]
+mod in_memory_idempotency_repository;
+#[cfg(test)]
+mod in_memory_recurring_repository;
+#[cfg(test)]
+mod in_memory_snapshot_repository;
+#[cfg(test)]
+mod in_memory_transaction_repository;
+#[cfg(test)]
+mod in_memory_wallet_repository;
+#[cfg(test)]
+mod in_memory_webhook_repository;
+mod migrations;
 mod postgres_account_repository;
+mod postgres_backup_repository;
+mod postgres_idempotency_repository;
+mod postgres_recurring_repository;
+mod postgres_snapshot_repository;
+mod postgres_transaction_repository;
+mod postgres_wallet_repository;
+mod postgres_webhook_repository;
+mod retrying_account_repository;
+mod sqlite_account_repository;
+mod sqlite_backup_repository;
+mod sqlite_idempotency_repository;
+mod sqlite_recurring_repository;
+mod sqlite_snapshot_repository;
+mod sqlite_transaction_repository;
+mod sqlite_wallet_repository;
+mod sqlite_webhook_repository;
+#[cfg(test)]
+pub mod test_support;
 
-pub use postgres_account_repository::PostgresAccountRepository;
+pub use any_repository::{
+    AnyAccountRepository, AnyBackupRepository, AnyIdempotencyRepository, AnyRecurringRepository,
+    AnySnapshotRepository, AnyTransactionRepository, AnyWalletRepository, AnyWebhookRepository,
+};
+pub use connect::wait_for_database;
+pub use error_kind::DbErrorKind;
+pub use factory::{DatabaseKind, DatabasePool, PoolSettings};
+#[cfg(test)]
+pub use in_memory_account_repository::InMemoryAccountRepository;
+#[cfg(test)]
+pub use in_memory_idempotency_repository::InMemoryIdempotencyRepository;
+#[cfg(test)]
+pub use in_memory_recurring_repository::InMemoryRecurringRepository;
+#[cfg(test)]
+pub use in_memory_snapshot_repository::InMemorySnapshotRepository;
+#[cfg(test)]
+pub use in_memory_transaction_repository::InMemoryTransactionRepository;
+#[cfg(test)]
+pub use in_memory_wallet_repository::InMemoryWalletRepository;
+#[cfg(test)]
+pub use in_memory_webhook_repository::InMemoryWebhookRepository;
+pub use migrations::run_migrations;
+use postgres_account_repository::PostgresAccountRepository;
+use postgres_backup_repository::PostgresBackupRepository;
+use postgres_idempotency_repository::PostgresIdempotencyRepository;
+use postgres_recurring_repository::PostgresRecurringRepository;
+use postgres_snapshot_repository::PostgresSnapshotRepository;
+use postgres_transaction_repository::PostgresTransactionRepository;
+use postgres_wallet_repository::PostgresWalletRepository;
+use postgres_webhook_repository::PostgresWebhookRepository;
+pub use retrying_account_repository::TransientError;
+pub use retrying_account_repository::{RetryPolicy, RetryingAccountRepository};
+use sqlite_account_repository::SqliteAccountRepository;
+use sqlite_backup_repository::SqliteBackupRepository;
+use sqlite_idempotency_repository::SqliteIdempotencyRepository;
+use sqlite_recurring_repository::SqliteRecurringRepository;
+use sqlite_snapshot_repository::SqliteSnapshotRepository;
+use sqlite_transaction_repository::SqliteTransactionRepository;
+use sqlite_wallet_repository::SqliteWalletRepository;
+use sqlite_webhook_repository::SqliteWebhookRepository;
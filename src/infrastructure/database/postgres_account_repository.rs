@@ -3,11 +3,23 @@
 //! Этот модуль — часть Infrastructure слоя.
 //! Он реализует порт `AccountRepository` из Application слоя.
 
-use sqlx::PgPool;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgArguments;
+use sqlx::query::Query;
+use sqlx::{PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
-use crate::application::ports::AccountRepository;
-use crate::domain::entities::Account;
+use crate::application::ports::{
+    AccountFilter, AccountRepository, AccountRepositoryError, AccountSort, AccountSortField,
+    CurrencyStats,
+};
+// `Transaction` здесь — транзакция БД из sqlx, операция журнала — `Operation`
+use crate::domain::entities::{
+    Account, AccountType, AuditAction, AuditEntry, Transaction as Operation,
+};
+
+use super::postgres_transaction_repository::upsert_query as upsert_operation_query;
+use super::DbErrorKind;
 
 /// PostgreSQL реализация репозитория счетов.
 ///
@@ -37,55 +49,70 @@ impl AccountRepository for PostgresAccountRepository {
     ///
     /// # SQL
     /// ```sql
-    /// INSERT INTO accounts (id, name, balance, currency, created_at, updated_at)
-    /// VALUES ($1, $2, $3, $4, $5, $6)
+    /// INSERT INTO accounts (id, name, balance, currency, ..., min_balance)
+    /// VALUES ($1, $2, $3, $4, ..., $11)
     /// ```
     ///
     /// # Плейсхолдеры
     /// `$1, $2...` — синтаксис PostgreSQL для параметризованных запросов.
     /// Защищает от SQL injection.
-    async fn create(&self, account: &Account) -> Result<(), Self::Error> {
-        sqlx::query(
-            r#"
-            INSERT INTO accounts (id, name, balance, currency, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6)
-            "#,
-        )
-        // .bind() — привязывает значение к плейсхолдеру
-        .bind(account.id)
-        .bind(&account.name) // &String — передаём ссылку
-        .bind(account.balance)
-        .bind(&account.currency)
-        .bind(account.created_at)
-        .bind(account.updated_at)
-        .execute(&self.pool) // Выполняем запрос
-        .await?; // Ждём результат, пробрасываем ошибку
+    ///
+    /// # Транзакция
+    /// Счёт и записи журнала изменений вставляются вместе: `commit()`
+    /// сохраняет всё, ошибка до него (`?`) — ничего.
+    async fn create(&self, account: &Account, audit: &[AuditEntry]) -> Result<(), Self::Error> {
+        let mut tx = self.pool.begin().await?;
 
-        Ok(())
+        insert_query(account)
+            .execute(&mut *tx) // Выполняем запрос внутри транзакции
+            .await?; // Ждём результат, пробрасываем ошибку
+        insert_audit(&mut tx, audit).await?;
+
+        tx.commit().await
     }
 
-    /// Находит счёт по ID.
+    /// Все INSERT в одной транзакции: ошибка на любом счёте
+    /// (до `commit()`) откатывает уже вставленные.
+    async fn create_many(
+        &self,
+        accounts: &[Account],
+        audit: &[AuditEntry],
+    ) -> Result<(), Self::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        for account in accounts {
+            insert_query(account).execute(&mut *tx).await?;
+        }
+        insert_audit(&mut tx, audit).await?;
+
+        tx.commit().await
+    }
+
+    /// Находит активный (не удалённый) счёт по ID.
     ///
     /// # Возвращает
     /// - `Ok(Some(account))` — если найден
     /// - `Ok(None)` — если не найден
     /// - `Err(e)` — если ошибка БД
-    async fn find_by_id(&self, id: Uuid) -> Result<Option<Account>, Self::Error> {
+    async fn find_by_id(&self, user_id: &str, id: Uuid) -> Result<Option<Account>, Self::Error> {
         // query_as — автоматически маппит результат в структуру
         // <_, AccountRow> — первый параметр выводится автоматически
         let account = sqlx::query_as::<_, AccountRow>(
             r#"
-            SELECT id, name, balance, currency, created_at, updated_at
+            SELECT id, name, balance, currency, created_at, updated_at, version, deleted_at,
+                   withdrawal_limit, account_type, credit_limit, min_balance, archived,
+                   overdraft_source, low_balance_threshold, description, user_id
             FROM accounts
-            WHERE id = $1
+            WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL
             "#,
         )
         .bind(id)
+        .bind(user_id)
         .fetch_optional(&self.pool) // Возвращает Option<T>
         .await?;
 
-        // Конвертируем AccountRow в Account через .map()
-        Ok(account.map(Into::into))
+        // Option<Row> → Option<Result<Account>> → Result<Option<Account>>
+        account.map(TryInto::try_into).transpose()
     }
 
     /// Находит счёт по имени (регистронезависимо).
@@ -93,66 +120,477 @@ impl AccountRepository for PostgresAccountRepository {
     /// # SQL функция LOWER()
     /// `LOWER(name) = LOWER($1)` — сравнение без учёта регистра.
     /// "Wallet" найдёт "wallet", "WALLET", "WaLLeT".
-    async fn find_by_name(&self, name: &str) -> Result<Option<Account>, Self::Error> {
+    async fn find_by_name(
+        &self,
+        user_id: &str,
+        name: &str,
+    ) -> Result<Option<Account>, Self::Error> {
         let account = sqlx::query_as::<_, AccountRow>(
             r#"
-            SELECT id, name, balance, currency, created_at, updated_at
+            SELECT id, name, balance, currency, created_at, updated_at, version, deleted_at,
+                   withdrawal_limit, account_type, credit_limit, min_balance, archived,
+                   overdraft_source, low_balance_threshold, description, user_id
             FROM accounts
-            WHERE LOWER(name) = LOWER($1)
+            WHERE LOWER(name) = LOWER($1) AND user_id = $2 AND deleted_at IS NULL
             "#,
         )
         .bind(name)
+        .bind(user_id)
         .fetch_optional(&self.pool)
         .await?;
 
-        Ok(account.map(Into::into))
+        account.map(TryInto::try_into).transpose()
+    }
+
+    async fn find_owner(&self, id: Uuid) -> Result<Option<String>, Self::Error> {
+        sqlx::query_scalar("SELECT user_id FROM accounts WHERE id = $1 AND deleted_at IS NULL")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
     }
 
     /// Возвращает все счета, отсортированные по дате создания.
-    async fn find_all(&self) -> Result<Vec<Account>, Self::Error> {
+    async fn find_all(&self, user_id: &str) -> Result<Vec<Account>, Self::Error> {
         let accounts = sqlx::query_as::<_, AccountRow>(
             r#"
-            SELECT id, name, balance, currency, created_at, updated_at
+            SELECT id, name, balance, currency, created_at, updated_at, version, deleted_at,
+                   withdrawal_limit, account_type, credit_limit, min_balance, archived,
+                   overdraft_source, low_balance_threshold, description, user_id
             FROM accounts
+            WHERE user_id = $1 AND deleted_at IS NULL
             ORDER BY created_at DESC
             "#,
         )
+        .bind(user_id)
         .fetch_all(&self.pool) // Возвращает Vec<T>
         .await?;
 
-        // Конвертируем Vec<AccountRow> в Vec<Account>
-        Ok(accounts.into_iter().map(Into::into).collect())
+        // Vec<AccountRow> → Result<Vec<Account>>: collect остановится на первой ошибке
+        accounts.into_iter().map(TryInto::try_into).collect()
+    }
+
+    /// Возвращает страницу счетов, подходящих под фильтр.
+    ///
+    /// # LIMIT / OFFSET
+    /// `LIMIT $5` — сколько строк вернуть, `OFFSET $6` — сколько пропустить.
+    /// Сортировка обязательна: без ORDER BY порядок строк не гарантирован,
+    /// и страницы могли бы пересекаться. `id` — второй ключ сортировки
+    /// на случай одинаковых имён или балансов.
+    ///
+    /// # Почему `format!` здесь безопасен?
+    /// В текст запроса попадают только строки из `order_by()` — фиксированный
+    /// набор литералов. Всё, что пришло от клиента, идёт через `.bind()`.
+    async fn find_all_paginated(
+        &self,
+        user_id: &str,
+        filter: &AccountFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Account>, Self::Error> {
+        let sql = format!(
+            r#"
+            SELECT id, name, balance, currency, created_at, updated_at, version, deleted_at,
+                   withdrawal_limit, account_type, credit_limit, min_balance, archived,
+                   overdraft_source, low_balance_threshold, description, user_id
+            FROM accounts
+            WHERE {FILTER_CONDITIONS}
+            ORDER BY {}
+            LIMIT $5 OFFSET $6
+            "#,
+            order_by(&filter.sort)
+        );
+
+        let accounts = sqlx::query_as::<_, AccountRow>(&sql)
+            .bind(&filter.currency)
+            .bind(&filter.name_contains)
+            .bind(filter.include_archived)
+            .bind(user_id)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await?;
+
+        accounts.into_iter().map(TryInto::try_into).collect()
     }
 
-    /// Обновляет существующий счёт.
-    async fn update(&self, account: &Account) -> Result<(), Self::Error> {
-        sqlx::query(
+    /// Считает счета без загрузки строк.
+    ///
+    /// # query_scalar
+    /// Возвращает одно значение вместо структуры. `COUNT(*)` в PostgreSQL —
+    /// это BIGINT, поэтому маппим в `i64`.
+    async fn count(&self, user_id: &str, filter: &AccountFilter) -> Result<i64, Self::Error> {
+        let sql = format!("SELECT COUNT(*) FROM accounts WHERE {FILTER_CONDITIONS}");
+
+        sqlx::query_scalar(&sql)
+            .bind(&filter.currency)
+            .bind(&filter.name_contains)
+            .bind(filter.include_archived)
+            .bind(user_id)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    /// `SUM(BIGINT)` в PostgreSQL — NUMERIC, приводим обратно к BIGINT.
+    /// Пустых групп не бывает, поэтому COALESCE не нужен.
+    async fn stats_by_currency(&self, user_id: &str) -> Result<Vec<CurrencyStats>, Self::Error> {
+        let rows = sqlx::query_as::<_, CurrencyStatsRow>(
+            r#"
+            SELECT UPPER(TRIM(currency)) AS currency, COUNT(*) AS count,
+                   SUM(balance)::BIGINT AS balance,
+                   MIN(created_at) AS oldest_created_at, MAX(created_at) AS newest_created_at
+            FROM accounts
+            WHERE user_id = $1 AND deleted_at IS NULL AND NOT archived
+            GROUP BY UPPER(TRIM(currency))
+            ORDER BY currency
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Обновляет существующий счёт с проверкой версии.
+    ///
+    /// # Optimistic locking
+    /// `WHERE version = $6` — обновляем, только если никто не успел
+    /// сохранить счёт после того, как мы его прочитали.
+    /// Если успел — условие не выполнится и `rows_affected()` будет 0.
+    async fn update(
+        &self,
+        account: &Account,
+        audit: &[AuditEntry],
+        ledger: &[Operation],
+    ) -> Result<bool, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = update_query(account).execute(&mut *tx).await?;
+        if result.rows_affected() != 1 {
+            return Ok(false);
+        }
+        insert_audit(&mut tx, audit).await?;
+        insert_ledger(&mut tx, ledger).await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    /// Оба UPDATE, журнал изменений и операции — в одной транзакции.
+    ///
+    /// # Почему счета сортируются по ID?
+    /// UPDATE блокирует строку до конца транзакции. Переводы A→B и B→A,
+    /// блокирующие строки в разном порядке, могут ждать друг друга вечно
+    /// (PostgreSQL прервёт одну из них с ошибкой deadlock).
+    /// Общий порядок блокировок исключает такую ситуацию.
+    async fn update_pair(
+        &self,
+        first: &Account,
+        second: &Account,
+        audit: &[AuditEntry],
+        ledger: &[Operation],
+    ) -> Result<bool, Self::Error> {
+        let mut pair = [first, second];
+        pair.sort_by_key(|account| account.id);
+
+        let mut tx = self.pool.begin().await?;
+
+        for account in pair {
+            let result = update_query(account).execute(&mut *tx).await?;
+            if result.rows_affected() != 1 {
+                // Версия устарела — drop(tx) откатит первый UPDATE
+                return Ok(false);
+            }
+        }
+        insert_audit(&mut tx, audit).await?;
+        insert_ledger(&mut tx, ledger).await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    /// Мягко удаляет счёт: проставляет `deleted_at`, строка остаётся в БД.
+    ///
+    /// # Почему не DELETE?
+    /// Случайное удаление можно отменить (`restore`), а история операций
+    /// продолжает ссылаться на существующий счёт.
+    async fn delete(
+        &self,
+        user_id: &str,
+        id: Uuid,
+        now: DateTime<Utc>,
+        audit: &[AuditEntry],
+    ) -> Result<(), Self::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
             r#"
             UPDATE accounts
-            SET name = $2, balance = $3, currency = $4, updated_at = $5
-            WHERE id = $1
+            SET deleted_at = $3, version = version + 1
+            WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL
             "#,
         )
-        .bind(account.id)
-        .bind(&account.name)
-        .bind(account.balance)
-        .bind(&account.currency)
-        .bind(account.updated_at)
-        .execute(&self.pool)
+        .bind(id)
+        .bind(user_id)
+        .bind(now)
+        .execute(&mut *tx)
         .await?;
+        // Счёт уже удалили параллельно — второй записи `deleted` не нужно
+        if result.rows_affected() != 1 {
+            return Ok(());
+        }
+        insert_audit(&mut tx, audit).await?;
 
-        Ok(())
+        tx.commit().await
     }
 
-    /// Удаляет счёт по ID.
-    async fn delete(&self, id: Uuid) -> Result<(), Self::Error> {
-        sqlx::query("DELETE FROM accounts WHERE id = $1")
-            .bind(id)
-            .execute(&self.pool)
+    /// Находит мягко удалённый счёт по ID.
+    async fn find_deleted_by_id(
+        &self,
+        user_id: &str,
+        id: Uuid,
+    ) -> Result<Option<Account>, Self::Error> {
+        let account = sqlx::query_as::<_, AccountRow>(
+            r#"
+            SELECT id, name, balance, currency, created_at, updated_at, version, deleted_at,
+                   withdrawal_limit, account_type, credit_limit, min_balance, archived,
+                   overdraft_source, low_balance_threshold, description, user_id
+            FROM accounts
+            WHERE id = $1 AND user_id = $2 AND deleted_at IS NOT NULL
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        account.map(TryInto::try_into).transpose()
+    }
+
+    /// Восстанавливает мягко удалённый счёт.
+    ///
+    /// `rows_affected() == 0` — счёт не был удалён (или не существует).
+    async fn restore(
+        &self,
+        user_id: &str,
+        id: Uuid,
+        now: DateTime<Utc>,
+        audit: &[AuditEntry],
+    ) -> Result<bool, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE accounts
+            SET deleted_at = NULL, version = version + 1, updated_at = $3
+            WHERE id = $1 AND user_id = $2 AND deleted_at IS NOT NULL
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() != 1 {
+            return Ok(false);
+        }
+        insert_audit(&mut tx, audit).await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    /// Журнал изменений счёта владельца `user_id`.
+    ///
+    /// JOIN с accounts — проверка владельца: чужой счёт даст пустой журнал.
+    /// Записи одного изменения сохранены с одинаковым `at`, порядок
+    /// между ними — по `id` (порядку вставки).
+    async fn find_audit(
+        &self,
+        user_id: &str,
+        account_id: Uuid,
+    ) -> Result<Vec<AuditEntry>, Self::Error> {
+        let rows = sqlx::query_as::<_, AuditRow>(
+            r#"
+            SELECT a.account_id, a.action, a.old_value, a.new_value, a.at
+            FROM audit_log a
+            JOIN accounts ac ON ac.id = a.account_id
+            WHERE a.account_id = $1 AND ac.user_id = $2
+            ORDER BY a.at ASC, a.id ASC
+            "#,
+        )
+        .bind(account_id)
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+}
+
+/// Нарушение уникального индекса — SQLSTATE `23505` в PostgreSQL,
+/// `SQLITE_CONSTRAINT_UNIQUE` в SQLite; sqlx сводит оба к одному методу.
+///
+/// Единственный уникальный индекс `accounts`, кроме первичного ключа, —
+/// `idx_accounts_user_name_unique`. Повтор UUID v4 практически невозможен,
+/// поэтому любое нарушение уникальности здесь — занятое имя.
+impl AccountRepositoryError for sqlx::Error {
+    fn is_unique_violation(&self) -> bool {
+        DbErrorKind::of(self) == DbErrorKind::UniqueViolation
+    }
+}
+
+/// Строка результата `stats_by_currency` — общая для PostgreSQL и SQLite.
+#[derive(sqlx::FromRow)]
+pub(super) struct CurrencyStatsRow {
+    currency: String,
+    count: i64,
+    balance: i64,
+    oldest_created_at: DateTime<Utc>,
+    newest_created_at: DateTime<Utc>,
+}
+
+impl From<CurrencyStatsRow> for CurrencyStats {
+    fn from(row: CurrencyStatsRow) -> Self {
+        Self {
+            currency: row.currency,
+            count: row.count,
+            balance: row.balance,
+            oldest_created_at: row.oldest_created_at,
+            newest_created_at: row.newest_created_at,
+        }
+    }
+}
+
+/// Условия `AccountFilter` для WHERE: `$1` — валюта, `$2` — подстрока имени,
+/// `$3` — показывать ли архивные, `$4` — владелец.
+///
+/// `$1 IS NULL OR ...` — фильтр не задан, условие всегда истинно.
+/// Валюта: `$1` уже нормализован через `Currency`, а коды в старых строках
+/// могут быть не нормализованы, поэтому колонку сравниваем как
+/// `UPPER(TRIM(currency))` — " eur" и "EUR" найдутся по "EUR".
+/// `POSITION(...) > 0` вместо `ILIKE '%' || $2 || '%'`: в подстроке
+/// не нужно экранировать `%` и `_`.
+const FILTER_CONDITIONS: &str = "deleted_at IS NULL
+              AND user_id = $4
+              AND ($1::text IS NULL OR UPPER(TRIM(currency)) = $1)
+              AND ($2::text IS NULL OR POSITION(LOWER($2) IN LOWER(name)) > 0)
+              AND ($3::boolean OR NOT archived)";
+
+/// INSERT нового счёта — общий для `create` и `create_many`.
+fn insert_query(account: &Account) -> Query<'_, Postgres, PgArguments> {
+    sqlx::query(
+        r#"
+        INSERT INTO accounts (
+            id, name, balance, currency, created_at, updated_at, version, withdrawal_limit,
+            account_type, credit_limit, min_balance, archived,
+            overdraft_source, low_balance_threshold, description, user_id
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+        "#,
+    )
+    // .bind() — привязывает значение к плейсхолдеру
+    .bind(account.id)
+    .bind(&account.name) // &String — передаём ссылку
+    .bind(account.balance)
+    .bind(&account.currency)
+    .bind(account.created_at)
+    .bind(account.updated_at)
+    .bind(account.version)
+    .bind(account.withdrawal_limit) // Option<i64> → NULL если None
+    .bind(account.account_type.as_str()) // enum храним как строку
+    .bind(account.credit_limit)
+    .bind(account.min_balance)
+    .bind(account.archived)
+    .bind(account.overdraft_source)
+    .bind(account.low_balance_threshold)
+    .bind(&account.description)
+    .bind(&account.user_id)
+}
+
+/// Вставляет записи журнала изменений в транзакции `tx`.
+async fn insert_audit(
+    tx: &mut Transaction<'_, Postgres>,
+    audit: &[AuditEntry],
+) -> Result<(), sqlx::Error> {
+    for entry in audit {
+        sqlx::query(INSERT_AUDIT)
+            .bind(entry.account_id)
+            .bind(entry.action.as_str())
+            .bind(&entry.old_value)
+            .bind(&entry.new_value)
+            .bind(entry.at)
+            .execute(&mut **tx)
             .await?;
+    }
+    Ok(())
+}
 
-        Ok(())
+/// Вставляет операции в журнал в транзакции `tx` (см. `upsert_query`
+/// в `postgres_transaction_repository`).
+async fn insert_ledger(
+    tx: &mut Transaction<'_, Postgres>,
+    ledger: &[Operation],
+) -> Result<(), sqlx::Error> {
+    for operation in ledger {
+        upsert_operation_query(operation).execute(&mut **tx).await?;
     }
+    Ok(())
+}
+
+/// INSERT записи журнала изменений.
+const INSERT_AUDIT: &str = r#"
+    INSERT INTO audit_log (account_id, action, old_value, new_value, at)
+    VALUES ($1, $2, $3, $4, $5)
+    "#;
+
+/// UPDATE счёта с проверкой версии — общий для `update` и `update_pair`.
+///
+/// Возвращает ещё не выполненный запрос: его можно выполнить и на пуле,
+/// и внутри транзакции.
+fn update_query(account: &Account) -> Query<'_, Postgres, PgArguments> {
+    sqlx::query(
+        r#"
+        UPDATE accounts
+        SET name = $2, balance = $3, currency = $4, updated_at = $5,
+            withdrawal_limit = $7, min_balance = $8, archived = $9, overdraft_source = $10,
+            low_balance_threshold = $11, credit_limit = $12, description = $13,
+            version = version + 1
+        WHERE id = $1 AND version = $6 AND user_id = $14
+        "#,
+    )
+    .bind(account.id)
+    .bind(&account.name)
+    .bind(account.balance)
+    .bind(&account.currency)
+    .bind(account.updated_at)
+    .bind(account.version)
+    .bind(account.withdrawal_limit)
+    .bind(account.min_balance)
+    .bind(account.archived)
+    .bind(account.overdraft_source)
+    .bind(account.low_balance_threshold)
+    .bind(account.credit_limit)
+    .bind(&account.description)
+    .bind(&account.user_id)
+}
+
+/// ORDER BY для сортировки — whitelist: каждому варианту enum
+/// соответствует фиксированное выражение.
+///
+/// Выражения стандартные, поэтому функцию переиспользует и SQLite.
+pub(super) fn order_by(sort: &AccountSort) -> String {
+    let column = match sort.field {
+        AccountSortField::Name => "LOWER(name)",
+        AccountSortField::Balance => "balance",
+        AccountSortField::CreatedAt => "created_at",
+    };
+    let direction = if sort.descending { "DESC" } else { "ASC" };
+
+    format!("{column} {direction}, id {direction}")
 }
 
 // ═══════════════════════════════════════════════════════════════════
@@ -168,25 +606,86 @@ impl AccountRepository for PostgresAccountRepository {
 /// # Атрибут `#[derive(sqlx::FromRow)]`
 /// Автоматически генерирует код для преобразования строки БД в структуру.
 #[derive(sqlx::FromRow)]
-struct AccountRow {
+pub(super) struct AccountRow {
     id: Uuid,
     name: String,
     balance: i64,
     currency: String,
     created_at: chrono::DateTime<chrono::Utc>,
     updated_at: chrono::DateTime<chrono::Utc>,
+    version: i64,
+    deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+    withdrawal_limit: Option<i64>,
+    account_type: String,
+    credit_limit: Option<i64>,
+    min_balance: Option<i64>,
+    archived: bool,
+    overdraft_source: Option<Uuid>,
+    low_balance_threshold: Option<i64>,
+    description: Option<String>,
+    user_id: String,
 }
 
 /// Конвертация из AccountRow в доменную сущность Account.
-impl From<AccountRow> for Account {
-    fn from(row: AccountRow) -> Self {
-        Account {
+///
+/// `TryFrom`, как и у `TransactionRow`: в `account_type` может
+/// оказаться неизвестная строка.
+impl TryFrom<AccountRow> for Account {
+    type Error = sqlx::Error;
+
+    fn try_from(row: AccountRow) -> Result<Self, Self::Error> {
+        let account_type: AccountType = row
+            .account_type
+            .parse()
+            .map_err(|e: String| sqlx::Error::Decode(e.into()))?;
+
+        Ok(Account {
             id: row.id,
             name: row.name,
             balance: row.balance,
             currency: row.currency,
             created_at: row.created_at,
             updated_at: row.updated_at,
-        }
+            version: row.version,
+            deleted_at: row.deleted_at,
+            withdrawal_limit: row.withdrawal_limit,
+            account_type,
+            credit_limit: row.credit_limit,
+            min_balance: row.min_balance,
+            archived: row.archived,
+            overdraft_source: row.overdraft_source,
+            low_balance_threshold: row.low_balance_threshold,
+            description: row.description,
+            user_id: row.user_id,
+        })
+    }
+}
+
+/// Строка таблицы audit_log — общая для PostgreSQL и SQLite.
+#[derive(sqlx::FromRow)]
+pub(super) struct AuditRow {
+    account_id: Uuid,
+    action: String,
+    old_value: Option<String>,
+    new_value: Option<String>,
+    at: DateTime<Utc>,
+}
+
+impl TryFrom<AuditRow> for AuditEntry {
+    type Error = sqlx::Error;
+
+    fn try_from(row: AuditRow) -> Result<Self, Self::Error> {
+        let action: AuditAction = row
+            .action
+            .parse()
+            .map_err(|e: String| sqlx::Error::Decode(e.into()))?;
+
+        Ok(AuditEntry::changed(
+            row.account_id,
+            action,
+            row.old_value,
+            row.new_value,
+            row.at,
+        ))
     }
 }
@@ -0,0 +1,191 @@
+//! Реализация резервного копирования на PostgreSQL.
+
+use sqlx::PgPool;
+
+use super::postgres_account_repository::AccountRow;
+use super::postgres_snapshot_repository::SnapshotRow;
+use super::postgres_transaction_repository::TransactionRow;
+use crate::application::ports::{BackupData, BackupRepository};
+
+/// PostgreSQL реализация репозитория резервных копий.
+#[derive(Clone)]
+pub struct PostgresBackupRepository {
+    pool: PgPool,
+}
+
+impl PostgresBackupRepository {
+    /// Создаёт новый репозиторий с указанным пулом соединений.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl BackupRepository for PostgresBackupRepository {
+    type Error = sqlx::Error;
+
+    /// Выгружает все таблицы в одной транзакции.
+    ///
+    /// # Почему REPEATABLE READ?
+    /// В уровне по умолчанию (READ COMMITTED) каждый SELECT видит свежие
+    /// данные. Операция, записанная между чтением счетов и чтением журнала,
+    /// попала бы в копию без своего счёта. REPEATABLE READ даёт всем
+    /// запросам транзакции один и тот же снимок БД.
+    async fn export_all(&self) -> Result<BackupData, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY")
+            .execute(&mut *tx)
+            .await?;
+
+        // Удалённые счета тоже выгружаем — их можно восстановить
+        let accounts = sqlx::query_as::<_, AccountRow>(
+            r#"
+            SELECT id, name, balance, currency, created_at, updated_at, version, deleted_at,
+                   withdrawal_limit, account_type, credit_limit, min_balance, archived,
+                   overdraft_source, low_balance_threshold, description, user_id
+            FROM accounts
+            ORDER BY created_at ASC, id ASC
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let transactions = sqlx::query_as::<_, TransactionRow>(
+            r#"
+            SELECT id, account_id, kind, amount, timestamp, note, counterparty_id, counter_amount,
+                   category, reversed_at
+            FROM transactions
+            ORDER BY timestamp ASC, id ASC
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        // Порядок по суррогатному id — порядок записи
+        let snapshots = sqlx::query_as::<_, SnapshotRow>(
+            r#"
+            SELECT account_id, balance, recorded_at
+            FROM balance_snapshots
+            ORDER BY id ASC
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(BackupData {
+            accounts: accounts
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            transactions: transactions
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            snapshots: snapshots.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    /// Загружает все данные в одной транзакции.
+    ///
+    /// # Атомарность
+    /// Любая ошибка до `commit()` — и `tx` удаляется (drop), а PostgreSQL
+    /// откатывает всё, что успели вставить. Половины данных не бывает.
+    ///
+    /// # Почему LOCK TABLE?
+    /// Проверка "таблица пуста" и вставка должны быть неделимы:
+    /// иначе параллельный POST /api/accounts мог бы создать счёт между ними.
+    async fn import_all(&self, data: &BackupData) -> Result<bool, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("LOCK TABLE accounts IN EXCLUSIVE MODE")
+            .execute(&mut *tx)
+            .await?;
+
+        let not_empty: bool = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM accounts)")
+            .fetch_one(&mut *tx)
+            .await?;
+
+        if not_empty {
+            // Ничего не записали — откат произойдёт при drop(tx)
+            return Ok(false);
+        }
+
+        for account in &data.accounts {
+            sqlx::query(
+                r#"
+                INSERT INTO accounts (
+                    id, name, balance, currency, created_at, updated_at, version, deleted_at,
+                    withdrawal_limit, account_type, credit_limit, min_balance, archived,
+                    overdraft_source, low_balance_threshold, description, user_id
+                )
+                VALUES (
+                    $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17
+                )
+                "#,
+            )
+            .bind(account.id)
+            .bind(&account.name)
+            .bind(account.balance)
+            .bind(&account.currency)
+            .bind(account.created_at)
+            .bind(account.updated_at)
+            .bind(account.version)
+            .bind(account.deleted_at)
+            .bind(account.withdrawal_limit)
+            .bind(account.account_type.as_str())
+            .bind(account.credit_limit)
+            .bind(account.min_balance)
+            .bind(account.archived)
+            .bind(account.overdraft_source)
+            .bind(account.low_balance_threshold)
+            .bind(&account.description)
+            .bind(&account.user_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for transaction in &data.transactions {
+            sqlx::query(
+                r#"
+                INSERT INTO transactions (
+                    id, account_id, kind, amount, timestamp, note, counterparty_id,
+                    counter_amount, category, reversed_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                "#,
+            )
+            .bind(transaction.id)
+            .bind(transaction.account_id)
+            .bind(transaction.kind.as_str())
+            .bind(transaction.amount)
+            .bind(transaction.timestamp)
+            .bind(&transaction.note)
+            .bind(transaction.counterparty_id)
+            .bind(transaction.counter_amount)
+            .bind(&transaction.category)
+            .bind(transaction.reversed_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for snapshot in &data.snapshots {
+            sqlx::query(
+                r#"
+                INSERT INTO balance_snapshots (account_id, balance, recorded_at)
+                VALUES ($1, $2, $3)
+                "#,
+            )
+            .bind(snapshot.account_id)
+            .bind(snapshot.balance)
+            .bind(snapshot.recorded_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,134 @@
+//! Реализация ключей идемпотентности на PostgreSQL.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::application::dto::AccountResponse;
+use crate::application::ports::{IdempotencyRecord, IdempotencyRepository};
+
+/// PostgreSQL реализация репозитория ключей идемпотентности.
+#[derive(Clone)]
+pub struct PostgresIdempotencyRepository {
+    pool: PgPool,
+}
+
+impl PostgresIdempotencyRepository {
+    /// Создаёт новый репозиторий с указанным пулом соединений.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl IdempotencyRepository for PostgresIdempotencyRepository {
+    type Error = sqlx::Error;
+
+    /// Занимает ключ.
+    ///
+    /// # ON CONFLICT DO NOTHING
+    /// Уникальность ключа проверяет сам PostgreSQL (PRIMARY KEY):
+    /// из двух одновременных INSERT пройдёт ровно один. Второй
+    /// получит `rows_affected() == 0` и прочитает чужую запись.
+    async fn reserve(
+        &self,
+        record: &IdempotencyRecord,
+        expired_before: DateTime<Utc>,
+    ) -> Result<Option<IdempotencyRecord>, Self::Error> {
+        // Заодно чистим все истёкшие ключи — таблица не растёт бесконечно
+        sqlx::query("DELETE FROM idempotency_keys WHERE created_at < $1")
+            .bind(expired_before)
+            .execute(&self.pool)
+            .await?;
+
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO idempotency_keys (key, fingerprint, created_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (key) DO NOTHING
+            "#,
+        )
+        .bind(&record.key)
+        .bind(&record.fingerprint)
+        .bind(record.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        if inserted.rows_affected() == 1 {
+            return Ok(None);
+        }
+
+        let existing = sqlx::query_as::<_, IdempotencyRow>(
+            r#"
+            SELECT key, fingerprint, response, created_at
+            FROM idempotency_keys
+            WHERE key = $1
+            "#,
+        )
+        .bind(&record.key)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        // Запись могли удалить (release) между INSERT и SELECT —
+        // тогда ключ свободен, но занимать его повторно не будем:
+        // клиент получит "в процессе" и повторит запрос
+        Ok(Some(match existing {
+            Some(row) => row.try_into()?,
+            None => IdempotencyRecord {
+                response: None,
+                ..record.clone()
+            },
+        }))
+    }
+
+    /// Сохраняет ответ. Храним JSON-строкой — колонка TEXT.
+    async fn complete(&self, key: &str, response: &AccountResponse) -> Result<(), Self::Error> {
+        let json = serde_json::to_string(response).map_err(|e| sqlx::Error::Encode(e.into()))?;
+
+        sqlx::query("UPDATE idempotency_keys SET response = $2 WHERE key = $1")
+            .bind(key)
+            .bind(json)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Удаляет ключ, только если ответ ещё не сохранён.
+    async fn release(&self, key: &str) -> Result<(), Self::Error> {
+        sqlx::query("DELETE FROM idempotency_keys WHERE key = $1 AND response IS NULL")
+            .bind(key)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Строка таблицы idempotency_keys.
+#[derive(sqlx::FromRow)]
+pub(super) struct IdempotencyRow {
+    key: String,
+    fingerprint: String,
+    response: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+/// `TryFrom` — сохранённый JSON может не разобраться
+/// (например, если формат `AccountResponse` поменялся).
+impl TryFrom<IdempotencyRow> for IdempotencyRecord {
+    type Error = sqlx::Error;
+
+    fn try_from(row: IdempotencyRow) -> Result<Self, Self::Error> {
+        let response = row
+            .response
+            .map(|json| serde_json::from_str(&json))
+            .transpose()
+            .map_err(|e| sqlx::Error::Decode(e.into()))?;
+
+        Ok(IdempotencyRecord {
+            key: row.key,
+            fingerprint: row.fingerprint,
+            response,
+            created_at: row.created_at,
+        })
+    }
+}
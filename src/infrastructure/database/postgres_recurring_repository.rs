@@ -0,0 +1,183 @@
+//! Реализация правил повторяющихся операций на PostgreSQL.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::application::ports::RecurringRepository;
+use crate::domain::entities::{RecurringKind, RecurringTransaction};
+use crate::domain::value_objects::Schedule;
+
+/// PostgreSQL реализация репозитория правил.
+#[derive(Clone)]
+pub struct PostgresRecurringRepository {
+    pool: PgPool,
+}
+
+impl PostgresRecurringRepository {
+    /// Создаёт новый репозиторий с указанным пулом соединений.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl RecurringRepository for PostgresRecurringRepository {
+    type Error = sqlx::Error;
+
+    async fn create(&self, rule: &RecurringTransaction) -> Result<(), Self::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO recurring_transactions (
+                id, account_id, kind, amount, cron_or_interval, next_run, enabled, note,
+                created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+        )
+        .bind(rule.id)
+        .bind(rule.account_id)
+        .bind(rule.kind.as_str())
+        .bind(rule.amount)
+        .bind(rule.schedule.to_string()) // Schedule храним строкой
+        .bind(rule.next_run)
+        .bind(rule.enabled)
+        .bind(&rule.note)
+        .bind(rule.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RecurringTransaction>, Self::Error> {
+        let rule = sqlx::query_as::<_, RecurringRow>(
+            r#"
+            SELECT id, account_id, kind, amount, cron_or_interval, next_run, enabled, note,
+                   created_at
+            FROM recurring_transactions
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        rule.map(TryInto::try_into).transpose()
+    }
+
+    async fn find_by_account(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<RecurringTransaction>, Self::Error> {
+        let rules = sqlx::query_as::<_, RecurringRow>(
+            r#"
+            SELECT id, account_id, kind, amount, cron_or_interval, next_run, enabled, note,
+                   created_at
+            FROM recurring_transactions
+            WHERE account_id = $1
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(account_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rules.into_iter().map(TryInto::try_into).collect()
+    }
+
+    /// Частичный индекс `idx_recurring_transactions_due` покрывает
+    /// ровно это условие — опрос дешёвый даже при большой таблице.
+    async fn find_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<RecurringTransaction>, Self::Error> {
+        let rules = sqlx::query_as::<_, RecurringRow>(
+            r#"
+            SELECT id, account_id, kind, amount, cron_or_interval, next_run, enabled, note,
+                   created_at
+            FROM recurring_transactions
+            WHERE enabled AND next_run <= $1
+            ORDER BY next_run ASC
+            LIMIT $2
+            "#,
+        )
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rules.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn update(&self, rule: &RecurringTransaction) -> Result<(), Self::Error> {
+        sqlx::query(
+            r#"
+            UPDATE recurring_transactions
+            SET amount = $2, cron_or_interval = $3, next_run = $4, enabled = $5, note = $6
+            WHERE id = $1
+            "#,
+        )
+        .bind(rule.id)
+        .bind(rule.amount)
+        .bind(rule.schedule.to_string())
+        .bind(rule.next_run)
+        .bind(rule.enabled)
+        .bind(&rule.note)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), Self::Error> {
+        sqlx::query("DELETE FROM recurring_transactions WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Строка таблицы recurring_transactions.
+#[derive(sqlx::FromRow)]
+pub(super) struct RecurringRow {
+    id: Uuid,
+    account_id: Uuid,
+    kind: String,
+    amount: i64,
+    cron_or_interval: String,
+    next_run: DateTime<Utc>,
+    enabled: bool,
+    note: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+/// `TryFrom`: `kind` и расписание хранятся строками и могут не разобраться.
+impl TryFrom<RecurringRow> for RecurringTransaction {
+    type Error = sqlx::Error;
+
+    fn try_from(row: RecurringRow) -> Result<Self, Self::Error> {
+        let kind: RecurringKind = row
+            .kind
+            .parse()
+            .map_err(|e: String| sqlx::Error::Decode(e.into()))?;
+        let schedule: Schedule = row
+            .cron_or_interval
+            .parse()
+            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+
+        Ok(RecurringTransaction {
+            id: row.id,
+            account_id: row.account_id,
+            kind,
+            amount: row.amount,
+            schedule,
+            next_run: row.next_run,
+            enabled: row.enabled,
+            note: row.note,
+            created_at: row.created_at,
+        })
+    }
+}
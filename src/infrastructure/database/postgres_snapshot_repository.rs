@@ -0,0 +1,106 @@
+//! Реализация истории баланса на PostgreSQL.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::application::ports::SnapshotRepository;
+use crate::domain::entities::BalanceSnapshot;
+
+/// PostgreSQL реализация репозитория снимков баланса.
+#[derive(Clone)]
+pub struct PostgresSnapshotRepository {
+    pool: PgPool,
+}
+
+impl PostgresSnapshotRepository {
+    /// Создаёт новый репозиторий с указанным пулом соединений.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl SnapshotRepository for PostgresSnapshotRepository {
+    type Error = sqlx::Error;
+
+    async fn create(&self, snapshot: &BalanceSnapshot) -> Result<(), Self::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO balance_snapshots (account_id, balance, recorded_at)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(snapshot.account_id)
+        .bind(snapshot.balance)
+        .bind(snapshot.recorded_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_latest(&self, account_id: Uuid) -> Result<Option<BalanceSnapshot>, Self::Error> {
+        let snapshot = sqlx::query_as::<_, SnapshotRow>(
+            r#"
+            SELECT account_id, balance, recorded_at
+            FROM balance_snapshots
+            WHERE account_id = $1
+            ORDER BY recorded_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(account_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(snapshot.map(Into::into))
+    }
+
+    /// Снимки в диапазоне.
+    ///
+    /// # Необязательные границы
+    /// `$2 IS NULL OR recorded_at >= $2` — если граница не передана (NULL),
+    /// условие всегда истинно. Так один запрос покрывает все четыре случая.
+    async fn find_in_range(
+        &self,
+        account_id: Uuid,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<BalanceSnapshot>, Self::Error> {
+        let snapshots = sqlx::query_as::<_, SnapshotRow>(
+            r#"
+            SELECT account_id, balance, recorded_at
+            FROM balance_snapshots
+            WHERE account_id = $1
+              AND ($2::timestamptz IS NULL OR recorded_at >= $2)
+              AND ($3::timestamptz IS NULL OR recorded_at <= $3)
+            ORDER BY recorded_at ASC
+            "#,
+        )
+        .bind(account_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(snapshots.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Строка таблицы balance_snapshots (без суррогатного `id`).
+#[derive(sqlx::FromRow)]
+pub(super) struct SnapshotRow {
+    account_id: Uuid,
+    balance: i64,
+    recorded_at: DateTime<Utc>,
+}
+
+impl From<SnapshotRow> for BalanceSnapshot {
+    fn from(row: SnapshotRow) -> Self {
+        BalanceSnapshot {
+            account_id: row.account_id,
+            balance: row.balance,
+            recorded_at: row.recorded_at,
+        }
+    }
+}
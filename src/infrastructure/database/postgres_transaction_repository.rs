@@ -0,0 +1,476 @@
+//! Реализация журнала операций на PostgreSQL.
+
+use chrono::{DateTime, Utc};
+use futures_util::stream::{self, BoxStream, StreamExt};
+use sqlx::postgres::PgArguments;
+use sqlx::query::Query;
+use sqlx::{PgPool, Postgres};
+use uuid::Uuid;
+
+use crate::application::ports::{
+    ActivityCursor, CategoryTotal, KindTotal, TransactionFilter, TransactionRepository,
+};
+use crate::domain::entities::{Transaction, TransactionKind};
+
+/// PostgreSQL реализация репозитория операций.
+///
+/// Как и `PostgresAccountRepository`, хранит только пул соединений,
+/// поэтому клонируется дёшево.
+#[derive(Clone)]
+pub struct PostgresTransactionRepository {
+    pool: PgPool,
+}
+
+impl PostgresTransactionRepository {
+    /// Создаёт новый репозиторий с указанным пулом соединений.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Реализация порта TransactionRepository для PostgreSQL.
+impl TransactionRepository for PostgresTransactionRepository {
+    type Error = sqlx::Error;
+
+    /// Находит операцию по ID.
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Transaction>, Self::Error> {
+        let transaction = sqlx::query_as::<_, TransactionRow>(
+            r#"
+            SELECT id, account_id, kind, amount, timestamp, note, counterparty_id, counter_amount,
+                   category, reversed_at
+            FROM transactions
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        // Option<Row> → Option<Result<Transaction>> → Result<Option<Transaction>>
+        transaction.map(TryInto::try_into).transpose()
+    }
+
+    /// UPDATE только тех колонок, что не влияют на баланс.
+    async fn update_details(&self, transaction: &Transaction) -> Result<bool, Self::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE transactions
+            SET note = $2, category = $3, reversed_at = COALESCE(reversed_at, $4)
+            WHERE id = $1
+            "#,
+        )
+        .bind(transaction.id)
+        .bind(&transaction.note)
+        .bind(&transaction.category)
+        .bind(transaction.reversed_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Возвращает страницу операций счёта, новые — первыми.
+    ///
+    /// Условия — `FILTER_CONDITIONS`; страницу отдаёт индекс
+    /// `(account_id, timestamp DESC)`, без сортировки всего журнала.
+    async fn find_by_account(
+        &self,
+        account_id: Uuid,
+        filter: &TransactionFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Transaction>, Self::Error> {
+        let sql = format!(
+            r#"
+            SELECT id, account_id, kind, amount, timestamp, note, counterparty_id, counter_amount,
+                   category, reversed_at
+            FROM transactions
+            WHERE {FILTER_CONDITIONS}
+            ORDER BY timestamp DESC, id DESC
+            LIMIT $6 OFFSET $7
+            "#
+        );
+
+        let transactions = sqlx::query_as::<_, TransactionRow>(&sql)
+            .bind(account_id)
+            .bind(&filter.category)
+            .bind(filter.kind.map(|kind| kind.as_str()))
+            .bind(filter.from)
+            .bind(filter.to)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await?;
+
+        // collect() в Result<Vec<_>, _> остановится на первой ошибке
+        transactions.into_iter().map(TryInto::try_into).collect()
+    }
+
+    /// `COUNT(*)` с теми же условиями, что у `find_by_account`.
+    async fn count_by_account(
+        &self,
+        account_id: Uuid,
+        filter: &TransactionFilter,
+    ) -> Result<i64, Self::Error> {
+        let sql = format!("SELECT COUNT(*) FROM transactions WHERE {FILTER_CONDITIONS}");
+
+        sqlx::query_scalar(&sql)
+            .bind(account_id)
+            .bind(&filter.category)
+            .bind(filter.kind.map(|kind| kind.as_str()))
+            .bind(filter.from)
+            .bind(filter.to)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    /// Операции счёта потоком, старые — первыми.
+    ///
+    /// # Почему отдельная задача и канал?
+    /// `fetch` возвращает поток, который заимствует пул, а порт требует
+    /// `'static`. Задача владеет клоном пула и читает строки курсором,
+    /// передавая их через канал на 64 операции: если клиент читает
+    /// медленно, канал заполняется и чтение из БД ждёт. Отключился
+    /// клиент — канал закрыт, задача перестаёт читать.
+    fn stream_by_account(
+        &self,
+        account_id: Uuid,
+    ) -> BoxStream<'static, Result<Transaction, Self::Error>> {
+        let (sender, receiver) = tokio::sync::mpsc::channel(64);
+        let pool = self.pool.clone();
+
+        tokio::spawn(async move {
+            let mut rows = sqlx::query_as::<_, TransactionRow>(
+                r#"
+                SELECT id, account_id, kind, amount, timestamp, note, counterparty_id,
+                       counter_amount, category, reversed_at
+                FROM transactions
+                WHERE account_id = $1
+                ORDER BY timestamp ASC
+                "#,
+            )
+            .bind(account_id)
+            .fetch(&pool);
+
+            while let Some(row) = rows.next().await {
+                let failed = row.is_err();
+                let transaction = row.and_then(TryInto::try_into);
+                if sender.send(transaction).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+
+        stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|item| (item, receiver))
+        })
+        .boxed()
+    }
+
+    /// Операции счёта за период, старые — первыми.
+    ///
+    /// Полуинтервал `[from, to)`: операция ровно в полночь 1-го числа
+    /// попадает в новый месяц и не попадает в предыдущий.
+    async fn find_in_range(
+        &self,
+        account_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Transaction>, Self::Error> {
+        let transactions = sqlx::query_as::<_, TransactionRow>(
+            r#"
+            SELECT id, account_id, kind, amount, timestamp, note, counterparty_id, counter_amount,
+                   category, reversed_at
+            FROM transactions
+            WHERE account_id = $1 AND timestamp >= $2 AND timestamp < $3
+            ORDER BY timestamp ASC
+            "#,
+        )
+        .bind(account_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        transactions.into_iter().map(TryInto::try_into).collect()
+    }
+
+    /// `GROUP BY kind` по диапазону индекса `(account_id, timestamp)`.
+    async fn totals_before(
+        &self,
+        account_id: Uuid,
+        before: DateTime<Utc>,
+    ) -> Result<Vec<KindTotal>, Self::Error> {
+        let rows = sqlx::query_as::<_, KindTotalRow>(
+            r#"
+            SELECT kind, SUM(amount)::BIGINT AS total, COUNT(*) AS count
+            FROM transactions
+            WHERE account_id = $1 AND timestamp < $2
+            GROUP BY kind
+            "#,
+        )
+        .bind(account_id)
+        .bind(before)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    /// Итоги по счёту, категории и типу операции за период.
+    async fn totals_by_category(
+        &self,
+        account_ids: &[Uuid],
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<CategoryTotal>, Self::Error> {
+        let rows = sqlx::query_as::<_, CategoryTotalRow>(
+            r#"
+            SELECT account_id, category, kind,
+                   SUM(amount)::BIGINT AS total, COUNT(*) AS count
+            FROM transactions
+            WHERE ($1::timestamptz IS NULL OR timestamp >= $1)
+              AND ($2::timestamptz IS NULL OR timestamp <= $2)
+              AND account_id = ANY($3)
+            GROUP BY account_id, category, kind
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .bind(account_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    /// Страница ленты счетов `account_ids`.
+    ///
+    /// Сравнение кортежей `(timestamp, id) < ($1, $2)` — ровно порядок
+    /// `ORDER BY`, и индекс `(timestamp DESC, id DESC)` отдаёт страницу
+    /// с нужного места, не перебирая более новые операции.
+    async fn find_recent(
+        &self,
+        account_ids: &[Uuid],
+        before: Option<ActivityCursor>,
+        limit: i64,
+    ) -> Result<Vec<Transaction>, Self::Error> {
+        let transactions = sqlx::query_as::<_, TransactionRow>(
+            r#"
+            SELECT id, account_id, kind, amount, timestamp, note, counterparty_id, counter_amount,
+                   category, reversed_at
+            FROM transactions
+            WHERE ($1::TIMESTAMPTZ IS NULL OR (timestamp, id) < ($1, $2))
+              AND account_id = ANY($4)
+            ORDER BY timestamp DESC, id DESC
+            LIMIT $3
+            "#,
+        )
+        .bind(before.map(|cursor| cursor.timestamp))
+        .bind(before.map(|cursor| cursor.id))
+        .bind(limit)
+        .bind(account_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        transactions.into_iter().map(TryInto::try_into).collect()
+    }
+
+    /// Сумма операций типа `kind` начиная с `since`.
+    ///
+    /// # COALESCE и ::BIGINT
+    /// - `SUM` по пустому набору возвращает NULL — `COALESCE` превращает его в 0
+    /// - `SUM(BIGINT)` в PostgreSQL — это NUMERIC, приводим обратно к BIGINT
+    async fn sum_since(
+        &self,
+        account_id: Uuid,
+        kind: TransactionKind,
+        since: DateTime<Utc>,
+    ) -> Result<i64, Self::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COALESCE(SUM(amount), 0)::BIGINT
+            FROM transactions
+            WHERE account_id = $1 AND kind = $2 AND timestamp >= $3
+            "#,
+        )
+        .bind(account_id)
+        .bind(kind.as_str())
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Как `sum_since`, но по времени отмены.
+    async fn sum_reversed_since(
+        &self,
+        account_id: Uuid,
+        kind: TransactionKind,
+        since: DateTime<Utc>,
+    ) -> Result<i64, Self::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COALESCE(SUM(amount), 0)::BIGINT
+            FROM transactions
+            WHERE account_id = $1 AND kind = $2 AND reversed_at >= $3
+            "#,
+        )
+        .bind(account_id)
+        .bind(kind.as_str())
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════
+// Внутренний тип для маппинга из SQL
+// ═══════════════════════════════════════════════════════════════════
+
+/// Строка таблицы transactions.
+///
+/// `kind` хранится как VARCHAR, поэтому здесь это `String`.
+#[derive(sqlx::FromRow)]
+pub(super) struct TransactionRow {
+    id: Uuid,
+    account_id: Uuid,
+    kind: String,
+    amount: i64,
+    timestamp: chrono::DateTime<chrono::Utc>,
+    note: Option<String>,
+    counterparty_id: Option<Uuid>,
+    counter_amount: Option<i64>,
+    category: Option<String>,
+    reversed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Конвертация из TransactionRow в доменную сущность.
+///
+/// # Почему `TryFrom`, а не `From`?
+/// В колонке `kind` теоретически может оказаться неизвестная строка.
+/// Вместо паники возвращаем ошибку декодирования sqlx.
+/// Условия `TransactionFilter` для WHERE: `$1` — счёт, `$2` — категория,
+/// `$3` — тип, `$4` и `$5` — границы по времени (включительно).
+///
+/// `$2 IS NULL OR ...` — фильтр не задан, условие всегда истинно
+/// (так же границы периода в `PostgresSnapshotRepository::find_in_range`).
+const FILTER_CONDITIONS: &str = "account_id = $1
+              AND ($2::varchar IS NULL OR category = $2)
+              AND ($3::varchar IS NULL OR kind = $3)
+              AND ($4::timestamptz IS NULL OR timestamp >= $4)
+              AND ($5::timestamptz IS NULL OR timestamp <= $5)";
+
+impl TryFrom<TransactionRow> for Transaction {
+    type Error = sqlx::Error;
+
+    fn try_from(row: TransactionRow) -> Result<Self, Self::Error> {
+        let kind: TransactionKind = row
+            .kind
+            .parse()
+            .map_err(|e: String| sqlx::Error::Decode(e.into()))?;
+
+        Ok(Transaction {
+            id: row.id,
+            account_id: row.account_id,
+            kind,
+            amount: row.amount,
+            timestamp: row.timestamp,
+            note: row.note,
+            counterparty_id: row.counterparty_id,
+            counter_amount: row.counter_amount,
+            category: row.category,
+            reversed_at: row.reversed_at,
+        })
+    }
+}
+
+/// Строка результата `totals_before`.
+#[derive(sqlx::FromRow)]
+pub(super) struct KindTotalRow {
+    kind: String,
+    total: i64,
+    count: i64,
+}
+
+impl TryFrom<KindTotalRow> for KindTotal {
+    type Error = sqlx::Error;
+
+    fn try_from(row: KindTotalRow) -> Result<Self, Self::Error> {
+        let kind = row
+            .kind
+            .parse()
+            .map_err(|e: String| sqlx::Error::Decode(e.into()))?;
+
+        Ok(KindTotal {
+            kind,
+            total: row.total,
+            count: row.count,
+        })
+    }
+}
+
+/// Строка результата `totals_by_category`.
+#[derive(sqlx::FromRow)]
+pub(super) struct CategoryTotalRow {
+    account_id: Uuid,
+    category: Option<String>,
+    kind: String,
+    total: i64,
+    count: i64,
+}
+
+impl TryFrom<CategoryTotalRow> for CategoryTotal {
+    type Error = sqlx::Error;
+
+    fn try_from(row: CategoryTotalRow) -> Result<Self, Self::Error> {
+        let kind = row
+            .kind
+            .parse()
+            .map_err(|e: String| sqlx::Error::Decode(e.into()))?;
+
+        Ok(CategoryTotal {
+            account_id: row.account_id,
+            category: row.category,
+            kind,
+            total: row.total,
+            count: row.count,
+        })
+    }
+}
+
+/// INSERT операции в таблицу transactions; если операция с таким ID уже
+/// есть — UPDATE её деталей, как в `update_details`.
+///
+/// Операции пишутся только вместе со счётом, в его транзакции
+/// (`PostgresAccountRepository::update`), поэтому запрос здесь,
+/// рядом с остальными запросами к таблице, а выполняет его репозиторий счетов.
+///
+/// # Зачем UPDATE при конфликте?
+/// Отмена операции меняет баланс, добавляет обратную запись и ставит
+/// `reversed_at` отменённой. Все три изменения должны пройти одной
+/// транзакцией, иначе сбой между ними оставит отмену без отметки —
+/// и её можно будет отменить ещё раз.
+pub(super) fn upsert_query(transaction: &Transaction) -> Query<'_, Postgres, PgArguments> {
+    sqlx::query(
+        r#"
+        INSERT INTO transactions (
+            id, account_id, kind, amount, timestamp, note, counterparty_id, counter_amount,
+            category, reversed_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        ON CONFLICT (id) DO UPDATE
+        SET note = EXCLUDED.note, category = EXCLUDED.category,
+            reversed_at = COALESCE(transactions.reversed_at, EXCLUDED.reversed_at)
+        "#,
+    )
+    .bind(transaction.id)
+    .bind(transaction.account_id)
+    .bind(transaction.kind.as_str()) // enum храним как строку
+    .bind(transaction.amount)
+    .bind(transaction.timestamp)
+    .bind(&transaction.note) // Option<String> → NULL если None
+    .bind(transaction.counterparty_id)
+    .bind(transaction.counter_amount)
+    .bind(&transaction.category)
+    .bind(transaction.reversed_at)
+}
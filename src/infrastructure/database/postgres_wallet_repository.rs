@@ -0,0 +1,239 @@
+//! Реализация кошельков на PostgreSQL.
+//!
+//! Кошелёк — строка `wallets`, остатки — по строке `wallet_balances`
+//! на валюту. Пишутся они всегда вместе, в одной транзакции.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::application::ports::WalletRepository;
+use crate::domain::entities::Wallet;
+use crate::domain::value_objects::Currency;
+
+/// PostgreSQL реализация репозитория кошельков.
+#[derive(Clone)]
+pub struct PostgresWalletRepository {
+    pool: PgPool,
+}
+
+impl PostgresWalletRepository {
+    /// Создаёт новый репозиторий с указанным пулом соединений.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Остатки кошельков `ids`, сгруппированные по кошельку.
+    ///
+    /// Один запрос на все кошельки, а не по запросу на каждый.
+    async fn balances(
+        &self,
+        ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, HashMap<Currency, i64>>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, BalanceRow>(
+            r#"
+            SELECT wallet_id, currency, balance
+            FROM wallet_balances
+            WHERE wallet_id = ANY($1)
+            "#,
+        )
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(group_balances(rows))
+    }
+
+    /// Собирает кошельки из строк `wallets` и их остатков.
+    async fn with_balances(&self, rows: Vec<WalletRow>) -> Result<Vec<Wallet>, sqlx::Error> {
+        let ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+        let balances = self.balances(&ids).await?;
+
+        Ok(into_wallets(rows, balances))
+    }
+}
+
+impl WalletRepository for PostgresWalletRepository {
+    type Error = sqlx::Error;
+
+    async fn create(&self, wallet: &Wallet) -> Result<(), Self::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO wallets (id, name, created_at, updated_at, version)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(wallet.id)
+        .bind(&wallet.name)
+        .bind(wallet.created_at)
+        .bind(wallet.updated_at)
+        .bind(wallet.version)
+        .execute(&mut *tx)
+        .await?;
+
+        insert_balances(&mut tx, wallet).await?;
+
+        tx.commit().await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Wallet>, Self::Error> {
+        let row = sqlx::query_as::<_, WalletRow>(
+            r#"
+            SELECT id, name, created_at, updated_at, version
+            FROM wallets
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        Ok(self.with_balances(vec![row]).await?.pop())
+    }
+
+    async fn find_all(&self) -> Result<Vec<Wallet>, Self::Error> {
+        let rows = sqlx::query_as::<_, WalletRow>(
+            r#"
+            SELECT id, name, created_at, updated_at, version
+            FROM wallets
+            ORDER BY created_at ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        self.with_balances(rows).await
+    }
+
+    /// UPDATE с проверкой версии, затем остатки заново — в одной транзакции.
+    ///
+    /// # Почему остатки удаляются и вставляются, а не обновляются?
+    /// Валюта могла появиться (первое пополнение) или исчезнуть (снятие
+    /// до нуля). Заменить все строки проще, чем различать три случая,
+    /// а валют в кошельке единицы. Параллельную запись отсекает
+    /// UPDATE с версией: он же блокирует строку кошелька до конца транзакции.
+    async fn update(&self, wallet: &Wallet) -> Result<bool, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE wallets
+            SET name = $2, updated_at = $3, version = version + 1
+            WHERE id = $1 AND version = $4
+            "#,
+        )
+        .bind(wallet.id)
+        .bind(&wallet.name)
+        .bind(wallet.updated_at)
+        .bind(wallet.version)
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() != 1 {
+            return Ok(false);
+        }
+
+        sqlx::query("DELETE FROM wallet_balances WHERE wallet_id = $1")
+            .bind(wallet.id)
+            .execute(&mut *tx)
+            .await?;
+        insert_balances(&mut tx, wallet).await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), Self::Error> {
+        // Остатки удалит ON DELETE CASCADE
+        sqlx::query("DELETE FROM wallets WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Вставляет остатки кошелька (по строке на валюту).
+async fn insert_balances(
+    tx: &mut Transaction<'_, Postgres>,
+    wallet: &Wallet,
+) -> Result<(), sqlx::Error> {
+    for (currency, balance) in &wallet.balances {
+        sqlx::query(
+            r#"
+            INSERT INTO wallet_balances (wallet_id, currency, balance)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(wallet.id)
+        .bind(currency.to_string())
+        .bind(balance)
+        .execute(&mut **tx)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Остатки из строк `wallet_balances`, сгруппированные по кошельку.
+pub(super) fn group_balances(rows: Vec<BalanceRow>) -> HashMap<Uuid, HashMap<Currency, i64>> {
+    let mut balances: HashMap<Uuid, HashMap<Currency, i64>> = HashMap::new();
+    for row in rows {
+        balances
+            .entry(row.wallet_id)
+            .or_default()
+            .insert(Currency::new(&row.currency), row.balance);
+    }
+    balances
+}
+
+/// Кошельки из строк `wallets` с остатками из `group_balances`.
+pub(super) fn into_wallets(
+    rows: Vec<WalletRow>,
+    mut balances: HashMap<Uuid, HashMap<Currency, i64>>,
+) -> Vec<Wallet> {
+    rows.into_iter()
+        .map(|row| {
+            let wallet_balances = balances.remove(&row.id).unwrap_or_default();
+            row.into_wallet(wallet_balances)
+        })
+        .collect()
+}
+
+/// Строка таблицы wallets (без остатков).
+#[derive(sqlx::FromRow)]
+pub(super) struct WalletRow {
+    pub(super) id: Uuid,
+    name: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    version: i64,
+}
+
+impl WalletRow {
+    fn into_wallet(self, balances: HashMap<Currency, i64>) -> Wallet {
+        Wallet {
+            id: self.id,
+            name: self.name,
+            balances,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            version: self.version,
+        }
+    }
+}
+
+/// Строка таблицы wallet_balances.
+#[derive(sqlx::FromRow)]
+pub(super) struct BalanceRow {
+    wallet_id: Uuid,
+    currency: String,
+    balance: i64,
+}
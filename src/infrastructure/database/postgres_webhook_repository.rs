@@ -0,0 +1,145 @@
+//! Реализация подписок на события на PostgreSQL.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::application::ports::WebhookRepository;
+use crate::domain::entities::Webhook;
+
+/// PostgreSQL реализация репозитория подписок.
+#[derive(Clone)]
+pub struct PostgresWebhookRepository {
+    pool: PgPool,
+}
+
+impl PostgresWebhookRepository {
+    /// Создаёт новый репозиторий с указанным пулом соединений.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl WebhookRepository for PostgresWebhookRepository {
+    type Error = sqlx::Error;
+
+    async fn create(&self, webhook: &Webhook) -> Result<(), Self::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO webhooks (id, url, secret, enabled, created_at, user_id)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(webhook.id)
+        .bind(&webhook.url)
+        .bind(&webhook.secret)
+        .bind(webhook.enabled)
+        .bind(webhook.created_at)
+        .bind(&webhook.user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_id(&self, user_id: &str, id: Uuid) -> Result<Option<Webhook>, Self::Error> {
+        let webhook = sqlx::query_as::<_, WebhookRow>(
+            r#"
+            SELECT id, url, secret, enabled, created_at, user_id
+            FROM webhooks
+            WHERE id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(webhook.map(Into::into))
+    }
+
+    async fn find_all(&self, user_id: &str) -> Result<Vec<Webhook>, Self::Error> {
+        let webhooks = sqlx::query_as::<_, WebhookRow>(
+            r#"
+            SELECT id, url, secret, enabled, created_at, user_id
+            FROM webhooks
+            WHERE user_id = $1
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(webhooks.into_iter().map(Into::into).collect())
+    }
+
+    async fn find_enabled(&self, user_id: &str) -> Result<Vec<Webhook>, Self::Error> {
+        let webhooks = sqlx::query_as::<_, WebhookRow>(
+            r#"
+            SELECT id, url, secret, enabled, created_at, user_id
+            FROM webhooks
+            WHERE enabled AND user_id = $1
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(webhooks.into_iter().map(Into::into).collect())
+    }
+
+    async fn update(&self, webhook: &Webhook) -> Result<(), Self::Error> {
+        sqlx::query(
+            r#"
+            UPDATE webhooks
+            SET url = $2, secret = $3, enabled = $4
+            WHERE id = $1 AND user_id = $5
+            "#,
+        )
+        .bind(webhook.id)
+        .bind(&webhook.url)
+        .bind(&webhook.secret)
+        .bind(webhook.enabled)
+        .bind(&webhook.user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: &str, id: Uuid) -> Result<(), Self::Error> {
+        sqlx::query("DELETE FROM webhooks WHERE id = $1 AND user_id = $2")
+            .bind(id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Строка таблицы webhooks.
+#[derive(sqlx::FromRow)]
+pub(super) struct WebhookRow {
+    id: Uuid,
+    url: String,
+    secret: String,
+    enabled: bool,
+    created_at: DateTime<Utc>,
+    user_id: String,
+}
+
+impl From<WebhookRow> for Webhook {
+    fn from(row: WebhookRow) -> Self {
+        Webhook {
+            id: row.id,
+            url: row.url,
+            secret: row.secret,
+            enabled: row.enabled,
+            created_at: row.created_at,
+            user_id: row.user_id,
+        }
+    }
+}
@@ -0,0 +1,438 @@
+//! Декоратор репозитория счетов с повтором при временных сбоях БД.
+//!
+//! Оборачивает любую реализацию `AccountRepository` и сам реализует
+//! тот же порт — сервис не знает, что запросы повторяются.
+
+use std::future::Future;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::application::ports::{AccountFilter, AccountRepository, CurrencyStats};
+use crate::domain::entities::{Account, AuditEntry, Transaction};
+
+/// Ошибка, которая может пройти сама при повторе.
+///
+/// # Почему отдельный trait?
+/// Декоратор обобщён по репозиторию, а решать, какая ошибка временная,
+/// может только тот, кто знает её тип.
+pub trait TransientError {
+    fn is_transient(&self) -> bool;
+}
+
+/// Временные ошибки sqlx: обрыв соединения, пул исчерпан, а также
+/// ошибки PostgreSQL, после которых запрос стоит просто повторить.
+///
+/// # SQLSTATE
+/// - `08xxx` — ошибки соединения
+/// - `40001` / `40P01` — конфликт сериализации и deadlock
+/// - `57P01` — сервер перезапускается (`admin_shutdown`)
+impl TransientError for sqlx::Error {
+    fn is_transient(&self) -> bool {
+        match self {
+            sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut => true,
+            sqlx::Error::Database(err) => err.code().is_some_and(|code| {
+                code.starts_with("08") || matches!(code.as_ref(), "40001" | "40P01" | "57P01")
+            }),
+            _ => false,
+        }
+    }
+}
+
+/// Сколько раз и с какими паузами повторять.
+///
+/// Пауза перед `n`-м повтором — `base_delay * 2^(n-1)`:
+/// при настройках по умолчанию 50, 100, 200 мс.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(50),
+        }
+    }
+}
+
+/// Репозиторий счетов, повторяющий операции при временных ошибках.
+///
+/// # Какие операции повторяются?
+/// Только те, повтор которых не может изменить результат:
+/// - Все чтения
+/// - `delete` — ставит `deleted_at` только неудалённому счёту,
+///   второй раз ничего не меняет
+///
+/// Остальные записи не повторяются. Если ответ БД потерялся после
+/// коммита, повтор `create` упал бы на дубликате ID, `update` вернул бы
+/// `false` (версия уже другая), а `restore` — "счёт не удалён".
+/// Клиент получил бы ошибку об операции, которая на самом деле прошла.
+#[derive(Clone)]
+pub struct RetryingAccountRepository<R> {
+    inner: R,
+    policy: RetryPolicy,
+}
+
+impl<R> RetryingAccountRepository<R>
+where
+    R: AccountRepository + Sync,
+    R::Error: TransientError,
+{
+    /// Оборачивает `inner`.
+    pub fn new(inner: R, policy: RetryPolicy) -> Self {
+        Self { inner, policy }
+    }
+
+    /// Выполняет `attempt`, пока он не пройдёт, не вернёт постоянную
+    /// ошибку или не кончатся повторы.
+    async fn retry<T, F, Fut>(&self, operation: &str, mut attempt: F) -> Result<T, R::Error>
+    where
+        F: FnMut() -> Fut + Send,
+        Fut: Future<Output = Result<T, R::Error>> + Send,
+    {
+        let mut retries = 0;
+        loop {
+            match attempt().await {
+                Err(err) if err.is_transient() && retries < self.policy.max_retries => {
+                    let delay = self.policy.base_delay * 2u32.saturating_pow(retries);
+                    retries += 1;
+                    tracing::warn!(
+                        "{} failed ({}), retry {}/{} in {:?}",
+                        operation,
+                        err,
+                        retries,
+                        self.policy.max_retries,
+                        delay
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl<R> AccountRepository for RetryingAccountRepository<R>
+where
+    R: AccountRepository + Sync,
+    R::Error: TransientError,
+{
+    type Error = R::Error;
+
+    async fn create(&self, account: &Account, audit: &[AuditEntry]) -> Result<(), Self::Error> {
+        self.inner.create(account, audit).await
+    }
+
+    async fn create_many(
+        &self,
+        accounts: &[Account],
+        audit: &[AuditEntry],
+    ) -> Result<(), Self::Error> {
+        self.inner.create_many(accounts, audit).await
+    }
+
+    async fn find_by_id(&self, user_id: &str, id: Uuid) -> Result<Option<Account>, Self::Error> {
+        self.retry("find_by_id", || self.inner.find_by_id(user_id, id))
+            .await
+    }
+
+    async fn find_by_name(
+        &self,
+        user_id: &str,
+        name: &str,
+    ) -> Result<Option<Account>, Self::Error> {
+        self.retry("find_by_name", || self.inner.find_by_name(user_id, name))
+            .await
+    }
+
+    async fn find_owner(&self, id: Uuid) -> Result<Option<String>, Self::Error> {
+        self.retry("find_owner", || self.inner.find_owner(id)).await
+    }
+
+    async fn find_all(&self, user_id: &str) -> Result<Vec<Account>, Self::Error> {
+        self.retry("find_all", || self.inner.find_all(user_id))
+            .await
+    }
+
+    async fn find_all_paginated(
+        &self,
+        user_id: &str,
+        filter: &AccountFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Account>, Self::Error> {
+        self.retry("find_all_paginated", || {
+            self.inner
+                .find_all_paginated(user_id, filter, limit, offset)
+        })
+        .await
+    }
+
+    async fn count(&self, user_id: &str, filter: &AccountFilter) -> Result<i64, Self::Error> {
+        self.retry("count", || self.inner.count(user_id, filter))
+            .await
+    }
+
+    async fn stats_by_currency(&self, user_id: &str) -> Result<Vec<CurrencyStats>, Self::Error> {
+        self.retry("stats_by_currency", || {
+            self.inner.stats_by_currency(user_id)
+        })
+        .await
+    }
+
+    async fn update(
+        &self,
+        account: &Account,
+        audit: &[AuditEntry],
+        ledger: &[Transaction],
+    ) -> Result<bool, Self::Error> {
+        self.inner.update(account, audit, ledger).await
+    }
+
+    async fn update_pair(
+        &self,
+        first: &Account,
+        second: &Account,
+        audit: &[AuditEntry],
+        ledger: &[Transaction],
+    ) -> Result<bool, Self::Error> {
+        self.inner.update_pair(first, second, audit, ledger).await
+    }
+
+    /// Повтор безопасен и с журналом: запись `deleted` вставляется,
+    /// только если UPDATE удалил счёт, — повтор после успеха ничего не запишет.
+    async fn delete(
+        &self,
+        user_id: &str,
+        id: Uuid,
+        now: DateTime<Utc>,
+        audit: &[AuditEntry],
+    ) -> Result<(), Self::Error> {
+        self.retry("delete", || self.inner.delete(user_id, id, now, audit))
+            .await
+    }
+
+    async fn find_deleted_by_id(
+        &self,
+        user_id: &str,
+        id: Uuid,
+    ) -> Result<Option<Account>, Self::Error> {
+        self.retry("find_deleted_by_id", || {
+            self.inner.find_deleted_by_id(user_id, id)
+        })
+        .await
+    }
+
+    async fn restore(
+        &self,
+        user_id: &str,
+        id: Uuid,
+        now: DateTime<Utc>,
+        audit: &[AuditEntry],
+    ) -> Result<bool, Self::Error> {
+        self.inner.restore(user_id, id, now, audit).await
+    }
+
+    async fn find_audit(
+        &self,
+        user_id: &str,
+        account_id: Uuid,
+    ) -> Result<Vec<AuditEntry>, Self::Error> {
+        self.retry("find_audit", || self.inner.find_audit(user_id, account_id))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use tokio::time::Instant;
+
+    use super::*;
+
+    /// Репозиторий, который первые `failures` вызовов отвечает ошибкой
+    /// `error()`, а дальше — пустым успешным результатом.
+    struct FlakyRepository {
+        failures: u32,
+        error: fn() -> sqlx::Error,
+        calls: AtomicU32,
+    }
+
+    impl FlakyRepository {
+        fn new(failures: u32, error: fn() -> sqlx::Error) -> Self {
+            Self {
+                failures,
+                error,
+                calls: AtomicU32::new(0),
+            }
+        }
+
+        fn calls(&self) -> u32 {
+            self.calls.load(Ordering::SeqCst)
+        }
+
+        fn call(&self) -> Result<(), sqlx::Error> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                return Err((self.error)());
+            }
+            Ok(())
+        }
+    }
+
+    impl AccountRepository for FlakyRepository {
+        type Error = sqlx::Error;
+
+        async fn create(&self, _: &Account, _: &[AuditEntry]) -> Result<(), Self::Error> {
+            self.call()
+        }
+
+        async fn create_many(&self, _: &[Account], _: &[AuditEntry]) -> Result<(), Self::Error> {
+            self.call()
+        }
+
+        async fn find_by_id(&self, _: &str, _: Uuid) -> Result<Option<Account>, Self::Error> {
+            self.call().map(|()| None)
+        }
+
+        async fn find_by_name(&self, _: &str, _: &str) -> Result<Option<Account>, Self::Error> {
+            self.call().map(|()| None)
+        }
+
+        async fn find_owner(&self, _: Uuid) -> Result<Option<String>, Self::Error> {
+            self.call().map(|()| None)
+        }
+
+        async fn find_all(&self, _: &str) -> Result<Vec<Account>, Self::Error> {
+            self.call().map(|()| Vec::new())
+        }
+
+        async fn find_all_paginated(
+            &self,
+            _: &str,
+            _: &AccountFilter,
+            _: i64,
+            _: i64,
+        ) -> Result<Vec<Account>, Self::Error> {
+            self.call().map(|()| Vec::new())
+        }
+
+        async fn count(&self, _: &str, _: &AccountFilter) -> Result<i64, Self::Error> {
+            self.call().map(|()| 0)
+        }
+
+        async fn stats_by_currency(&self, _: &str) -> Result<Vec<CurrencyStats>, Self::Error> {
+            self.call().map(|()| Vec::new())
+        }
+
+        async fn update(
+            &self,
+            _: &Account,
+            _: &[AuditEntry],
+            _: &[Transaction],
+        ) -> Result<bool, Self::Error> {
+            self.call().map(|()| true)
+        }
+
+        async fn update_pair(
+            &self,
+            _: &Account,
+            _: &Account,
+            _: &[AuditEntry],
+            _: &[Transaction],
+        ) -> Result<bool, Self::Error> {
+            self.call().map(|()| true)
+        }
+
+        async fn delete(
+            &self,
+            _: &str,
+            _: Uuid,
+            _: DateTime<Utc>,
+            _: &[AuditEntry],
+        ) -> Result<(), Self::Error> {
+            self.call()
+        }
+
+        async fn find_deleted_by_id(
+            &self,
+            _: &str,
+            _: Uuid,
+        ) -> Result<Option<Account>, Self::Error> {
+            self.call().map(|()| None)
+        }
+
+        async fn restore(
+            &self,
+            _: &str,
+            _: Uuid,
+            _: DateTime<Utc>,
+            _: &[AuditEntry],
+        ) -> Result<bool, Self::Error> {
+            self.call().map(|()| true)
+        }
+
+        async fn find_audit(&self, _: &str, _: Uuid) -> Result<Vec<AuditEntry>, Self::Error> {
+            self.call().map(|()| Vec::new())
+        }
+    }
+
+    fn retrying(
+        failures: u32,
+        error: fn() -> sqlx::Error,
+    ) -> RetryingAccountRepository<FlakyRepository> {
+        RetryingAccountRepository::new(
+            FlakyRepository::new(failures, error),
+            RetryPolicy::default(),
+        )
+    }
+
+    // start_paused: tokio::time::sleep не ждёт, а сдвигает часы
+    // теста — паузы между повторами можно измерить точно
+    #[tokio::test(start_paused = true)]
+    async fn transient_read_errors_are_retried_with_backoff() {
+        let repository = retrying(2, || sqlx::Error::PoolTimedOut);
+        let started = Instant::now();
+
+        let account = repository.find_by_id("default", Uuid::new_v4()).await;
+        assert!(matches!(account, Ok(None)));
+        assert_eq!(repository.inner.calls(), 3);
+        // 50 + 100 мс
+        assert_eq!(started.elapsed(), Duration::from_millis(150));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retries_stop_after_max_retries() {
+        let repository = retrying(10, || sqlx::Error::PoolTimedOut);
+        let started = Instant::now();
+
+        let result = repository.find_all("default").await;
+        assert!(matches!(result, Err(sqlx::Error::PoolTimedOut)));
+        assert_eq!(repository.inner.calls(), 4);
+        // 50 + 100 + 200 мс
+        assert_eq!(started.elapsed(), Duration::from_millis(350));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn permanent_errors_and_writes_are_not_retried() {
+        let repository = retrying(1, || sqlx::Error::RowNotFound);
+        let result = repository.find_by_id("default", Uuid::new_v4()).await;
+        assert!(matches!(result, Err(sqlx::Error::RowNotFound)));
+        assert_eq!(repository.inner.calls(), 1);
+
+        let repository = retrying(1, || sqlx::Error::PoolTimedOut);
+        let account = Account::new(
+            "Checking".to_string(),
+            "USD".to_string(),
+            chrono::Utc::now(),
+        )
+        .unwrap();
+        let result = repository.update(&account, &[], &[]).await;
+        assert!(matches!(result, Err(sqlx::Error::PoolTimedOut)));
+        assert_eq!(repository.inner.calls(), 1);
+    }
+}
@@ -0,0 +1,571 @@
+//! Реализация репозитория счетов на SQLite.
+//!
+//! Лёгкая альтернатива PostgreSQL для локального запуска: база — один файл,
+//! сервер не нужен. Схема — в `migrations_sqlite/`.
+//!
+//! # Отличия от PostgreSQL
+//! - Плейсхолдеры `?1, ?2...` вместо `$1, $2...`, без приведений `::text`
+//! - `NOW()` нет — время передаём из Rust
+//! - Операции журнала (`ledger` в `update`) пишутся в таблицу `transactions`
+//!   той же базы; читает их `SqliteTransactionRepository`
+//! - `LOWER()` приводит к нижнему регистру только ASCII: "Wallet" и "WALLET"
+//!   совпадут, а "Кошелёк" и "КОШЕЛЁК" — нет. Регистронезависимый
+//!   `find_by_name` гарантирован только для ASCII-имён.
+
+use chrono::{DateTime, Utc};
+use sqlx::query::Query;
+use sqlx::sqlite::SqliteArguments;
+use sqlx::{Sqlite, SqlitePool, Transaction};
+use uuid::Uuid;
+
+use super::postgres_account_repository::{order_by, AccountRow, AuditRow, CurrencyStatsRow};
+use crate::application::ports::{AccountFilter, AccountRepository, CurrencyStats};
+// `Transaction` здесь — транзакция БД из sqlx, операция журнала — `Operation`
+use crate::domain::entities::{Account, AuditEntry, Transaction as Operation};
+
+/// SQLite реализация репозитория счетов.
+///
+/// Как и `PgPool`, `SqlitePool` клонируется дёшево (внутри `Arc`).
+#[derive(Clone)]
+pub struct SqliteAccountRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteAccountRepository {
+    /// Создаёт новый репозиторий с указанным пулом соединений.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Колонки счёта в порядке полей `AccountRow`.
+///
+/// `AccountRow` общий с PostgreSQL: `#[derive(sqlx::FromRow)]` генерирует
+/// реализацию для любой БД, типы колонок которой декодируются в поля.
+const ACCOUNT_COLUMNS: &str = "id, name, balance, currency, created_at, updated_at, version,
+    deleted_at, withdrawal_limit, account_type, credit_limit, min_balance, archived,
+    overdraft_source, low_balance_threshold, description, user_id";
+
+/// Условия `AccountFilter` для WHERE: `?1` — валюта, `?2` — подстрока имени,
+/// `?3` — показывать ли архивные, `?4` — владелец.
+///
+/// Валюта — `UPPER(TRIM(currency))`, как у PostgreSQL: коды валют ASCII,
+/// и `UPPER` SQLite здесь достаточно (в отличие от имён, см. README).
+/// `INSTR` — аналог `POSITION` из PostgreSQL.
+const FILTER_CONDITIONS: &str = "deleted_at IS NULL
+    AND user_id = ?4
+    AND (?1 IS NULL OR UPPER(TRIM(currency)) = ?1)
+    AND (?2 IS NULL OR INSTR(LOWER(name), LOWER(?2)) > 0)
+    AND (?3 OR NOT archived)";
+
+impl AccountRepository for SqliteAccountRepository {
+    type Error = sqlx::Error;
+
+    /// Счёт и журнал изменений — в одной транзакции, как в PostgreSQL.
+    async fn create(&self, account: &Account, audit: &[AuditEntry]) -> Result<(), Self::Error> {
+        let sql = insert_sql();
+        let mut tx = self.pool.begin().await?;
+
+        insert_query(&sql, account).execute(&mut *tx).await?;
+        insert_audit(&mut tx, audit).await?;
+
+        tx.commit().await
+    }
+
+    async fn create_many(
+        &self,
+        accounts: &[Account],
+        audit: &[AuditEntry],
+    ) -> Result<(), Self::Error> {
+        let sql = insert_sql();
+        let mut tx = self.pool.begin().await?;
+
+        for account in accounts {
+            insert_query(&sql, account).execute(&mut *tx).await?;
+        }
+        insert_audit(&mut tx, audit).await?;
+
+        tx.commit().await
+    }
+
+    async fn find_by_id(&self, user_id: &str, id: Uuid) -> Result<Option<Account>, Self::Error> {
+        let account = sqlx::query_as::<_, AccountRow>(&format!(
+            "SELECT {ACCOUNT_COLUMNS} FROM accounts
+             WHERE id = ?1 AND user_id = ?2 AND deleted_at IS NULL"
+        ))
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        account.map(TryInto::try_into).transpose()
+    }
+
+    /// Поиск без учёта регистра — только для ASCII (см. документацию модуля).
+    async fn find_by_name(
+        &self,
+        user_id: &str,
+        name: &str,
+    ) -> Result<Option<Account>, Self::Error> {
+        let account = sqlx::query_as::<_, AccountRow>(&format!(
+            "SELECT {ACCOUNT_COLUMNS} FROM accounts
+             WHERE LOWER(name) = LOWER(?1) AND user_id = ?2 AND deleted_at IS NULL"
+        ))
+        .bind(name)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        account.map(TryInto::try_into).transpose()
+    }
+
+    async fn find_owner(&self, id: Uuid) -> Result<Option<String>, Self::Error> {
+        sqlx::query_scalar("SELECT user_id FROM accounts WHERE id = ?1 AND deleted_at IS NULL")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn find_all(&self, user_id: &str) -> Result<Vec<Account>, Self::Error> {
+        let accounts = sqlx::query_as::<_, AccountRow>(&format!(
+            "SELECT {ACCOUNT_COLUMNS} FROM accounts
+             WHERE user_id = ?1 AND deleted_at IS NULL
+             ORDER BY created_at DESC"
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        accounts.into_iter().map(TryInto::try_into).collect()
+    }
+
+    /// Страница счетов. ORDER BY — тот же whitelist, что у PostgreSQL.
+    async fn find_all_paginated(
+        &self,
+        user_id: &str,
+        filter: &AccountFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Account>, Self::Error> {
+        let accounts = sqlx::query_as::<_, AccountRow>(&format!(
+            "SELECT {ACCOUNT_COLUMNS} FROM accounts
+             WHERE {FILTER_CONDITIONS}
+             ORDER BY {}
+             LIMIT ?5 OFFSET ?6",
+            order_by(&filter.sort)
+        ))
+        .bind(&filter.currency)
+        .bind(&filter.name_contains)
+        .bind(filter.include_archived)
+        .bind(user_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        accounts.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn count(&self, user_id: &str, filter: &AccountFilter) -> Result<i64, Self::Error> {
+        sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM accounts WHERE {FILTER_CONDITIONS}"
+        ))
+        .bind(&filter.currency)
+        .bind(&filter.name_contains)
+        .bind(filter.include_archived)
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// `created_at` хранится строкой RFC 3339 в UTC, поэтому MIN/MAX
+    /// по строкам дают тот же ответ, что по времени.
+    async fn stats_by_currency(&self, user_id: &str) -> Result<Vec<CurrencyStats>, Self::Error> {
+        let rows = sqlx::query_as::<_, CurrencyStatsRow>(
+            r#"
+            SELECT UPPER(TRIM(currency)) AS currency, COUNT(*) AS count,
+                   SUM(balance) AS balance,
+                   MIN(created_at) AS oldest_created_at, MAX(created_at) AS newest_created_at
+            FROM accounts
+            WHERE user_id = ?1 AND deleted_at IS NULL AND NOT archived
+            GROUP BY UPPER(TRIM(currency))
+            ORDER BY currency
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Optimistic locking — как в PostgreSQL: `WHERE version = ?6`.
+    async fn update(
+        &self,
+        account: &Account,
+        audit: &[AuditEntry],
+        ledger: &[Operation],
+    ) -> Result<bool, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = update_query(account).execute(&mut *tx).await?;
+        if result.rows_affected() != 1 {
+            return Ok(false);
+        }
+        insert_audit(&mut tx, audit).await?;
+        insert_ledger(&mut tx, ledger).await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    /// SQLite блокирует всю базу на запись, поэтому порядок счетов
+    /// (в отличие от PostgreSQL) значения не имеет.
+    async fn update_pair(
+        &self,
+        first: &Account,
+        second: &Account,
+        audit: &[AuditEntry],
+        ledger: &[Operation],
+    ) -> Result<bool, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        for account in [first, second] {
+            let result = update_query(account).execute(&mut *tx).await?;
+            if result.rows_affected() != 1 {
+                return Ok(false);
+            }
+        }
+        insert_audit(&mut tx, audit).await?;
+        insert_ledger(&mut tx, ledger).await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    async fn delete(
+        &self,
+        user_id: &str,
+        id: Uuid,
+        now: DateTime<Utc>,
+        audit: &[AuditEntry],
+    ) -> Result<(), Self::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE accounts
+            SET deleted_at = ?2, version = version + 1
+            WHERE id = ?1 AND user_id = ?3 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .bind(now)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() != 1 {
+            return Ok(());
+        }
+        insert_audit(&mut tx, audit).await?;
+
+        tx.commit().await
+    }
+
+    async fn find_deleted_by_id(
+        &self,
+        user_id: &str,
+        id: Uuid,
+    ) -> Result<Option<Account>, Self::Error> {
+        let account = sqlx::query_as::<_, AccountRow>(&format!(
+            "SELECT {ACCOUNT_COLUMNS} FROM accounts
+             WHERE id = ?1 AND user_id = ?2 AND deleted_at IS NOT NULL"
+        ))
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        account.map(TryInto::try_into).transpose()
+    }
+
+    async fn restore(
+        &self,
+        user_id: &str,
+        id: Uuid,
+        now: DateTime<Utc>,
+        audit: &[AuditEntry],
+    ) -> Result<bool, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE accounts
+            SET deleted_at = NULL, version = version + 1, updated_at = ?2
+            WHERE id = ?1 AND user_id = ?3 AND deleted_at IS NOT NULL
+            "#,
+        )
+        .bind(id)
+        .bind(now)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() != 1 {
+            return Ok(false);
+        }
+        insert_audit(&mut tx, audit).await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    async fn find_audit(
+        &self,
+        user_id: &str,
+        account_id: Uuid,
+    ) -> Result<Vec<AuditEntry>, Self::Error> {
+        let rows = sqlx::query_as::<_, AuditRow>(
+            r#"
+            SELECT a.account_id, a.action, a.old_value, a.new_value, a.at
+            FROM audit_log a
+            JOIN accounts ac ON ac.id = a.account_id
+            WHERE a.account_id = ?1 AND ac.user_id = ?2
+            ORDER BY a.at ASC, a.id ASC
+            "#,
+        )
+        .bind(account_id)
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+}
+
+/// Вставляет записи журнала изменений в транзакции `tx`.
+async fn insert_audit(
+    tx: &mut Transaction<'_, Sqlite>,
+    audit: &[AuditEntry],
+) -> Result<(), sqlx::Error> {
+    for entry in audit {
+        sqlx::query(
+            r#"
+            INSERT INTO audit_log (account_id, action, old_value, new_value, at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            "#,
+        )
+        .bind(entry.account_id)
+        .bind(entry.action.as_str())
+        .bind(&entry.old_value)
+        .bind(&entry.new_value)
+        .bind(entry.at)
+        .execute(&mut **tx)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Вставляет операции в журнал в транзакции `tx`; у уже записанной
+/// операции обновляет детали — как `upsert_query` у PostgreSQL.
+async fn insert_ledger(
+    tx: &mut Transaction<'_, Sqlite>,
+    ledger: &[Operation],
+) -> Result<(), sqlx::Error> {
+    for operation in ledger {
+        sqlx::query(
+            r#"
+            INSERT INTO transactions (
+                id, account_id, kind, amount, timestamp, note, counterparty_id, counter_amount,
+                category, reversed_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+            ON CONFLICT (id) DO UPDATE
+            SET note = excluded.note, category = excluded.category,
+                reversed_at = COALESCE(transactions.reversed_at, excluded.reversed_at)
+            "#,
+        )
+        .bind(operation.id)
+        .bind(operation.account_id)
+        .bind(operation.kind.as_str())
+        .bind(operation.amount)
+        .bind(operation.timestamp)
+        .bind(&operation.note)
+        .bind(operation.counterparty_id)
+        .bind(operation.counter_amount)
+        .bind(&operation.category)
+        .bind(operation.reversed_at)
+        .execute(&mut **tx)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Текст INSERT нового счёта.
+fn insert_sql() -> String {
+    format!(
+        "INSERT INTO accounts ({ACCOUNT_COLUMNS})
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)"
+    )
+}
+
+/// INSERT нового счёта — общий для `create` и `create_many`.
+fn insert_query<'q>(sql: &'q str, account: &'q Account) -> Query<'q, Sqlite, SqliteArguments<'q>> {
+    sqlx::query(sql)
+        .bind(account.id)
+        .bind(&account.name)
+        .bind(account.balance)
+        .bind(&account.currency)
+        .bind(account.created_at)
+        .bind(account.updated_at)
+        .bind(account.version)
+        .bind(account.deleted_at)
+        .bind(account.withdrawal_limit)
+        .bind(account.account_type.as_str())
+        .bind(account.credit_limit)
+        .bind(account.min_balance)
+        .bind(account.archived)
+        .bind(account.overdraft_source)
+        .bind(account.low_balance_threshold)
+        .bind(&account.description)
+        .bind(&account.user_id)
+}
+
+/// UPDATE счёта с проверкой версии — общий для `update` и `update_pair`.
+fn update_query(account: &Account) -> Query<'_, Sqlite, SqliteArguments<'_>> {
+    sqlx::query(
+        r#"
+        UPDATE accounts
+        SET name = ?2, balance = ?3, currency = ?4, updated_at = ?5,
+            withdrawal_limit = ?7, min_balance = ?8, archived = ?9, overdraft_source = ?10,
+            low_balance_threshold = ?11, credit_limit = ?12, description = ?13,
+            version = version + 1
+        WHERE id = ?1 AND version = ?6 AND user_id = ?14
+        "#,
+    )
+    .bind(account.id)
+    .bind(&account.name)
+    .bind(account.balance)
+    .bind(&account.currency)
+    .bind(account.updated_at)
+    .bind(account.version)
+    .bind(account.withdrawal_limit)
+    .bind(account.min_balance)
+    .bind(account.archived)
+    .bind(account.overdraft_source)
+    .bind(account.low_balance_threshold)
+    .bind(account.credit_limit)
+    .bind(&account.description)
+    .bind(&account.user_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use super::*;
+    use crate::domain::entities::TransactionKind;
+    use crate::infrastructure::database::test_support::sqlite_database;
+
+    async fn repository() -> SqliteAccountRepository {
+        SqliteAccountRepository::new(sqlite_database().await)
+    }
+
+    async fn create(repository: &SqliteAccountRepository, name: &str) -> Account {
+        let account = Account::new(name.to_string(), "USD".to_string(), Utc::now()).unwrap();
+        repository.create(&account, &[]).await.unwrap();
+        account
+    }
+
+    #[tokio::test]
+    async fn find_by_name_ignores_ascii_case_only() {
+        let repository = repository().await;
+        let wallet = create(&repository, "Wallet").await;
+        create(&repository, "Кошелёк").await;
+        let user = Account::DEFAULT_USER;
+
+        for name in ["Wallet", "WALLET", "wallet"] {
+            let found = repository.find_by_name(user, name).await.unwrap();
+            assert_eq!(found.map(|account| account.id), Some(wallet.id), "{name}");
+        }
+        // LOWER() в SQLite не знает кириллицы
+        assert!(repository
+            .find_by_name(user, "КОШЕЛЁК")
+            .await
+            .unwrap()
+            .is_none());
+        assert!(repository
+            .find_by_name(user, "Кошелёк")
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn update_writes_ledger_rows_in_the_same_transaction() {
+        let repository = repository().await;
+        let mut account = create(&repository, "Wallet").await;
+        account.balance = 500;
+        let deposit = Operation::new(account.id, TransactionKind::Deposit, 500, None, Utc::now());
+
+        assert!(repository.update(&account, &[], &[deposit]).await.unwrap());
+        // Устаревшая версия: ни счёт, ни операция не пишутся
+        let stale = Operation::new(account.id, TransactionKind::Deposit, 100, None, Utc::now());
+        assert!(!repository.update(&account, &[], &[stale]).await.unwrap());
+
+        let amounts: Vec<i64> = sqlx::query_scalar("SELECT amount FROM transactions")
+            .fetch_all(&repository.pool)
+            .await
+            .unwrap();
+        assert_eq!(amounts, vec![500]);
+    }
+
+    #[tokio::test]
+    async fn backfill_gives_legacy_balances_an_opening_entry() {
+        use sqlx::migrate::Migrate;
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let migrator = sqlx::migrate!("./migrations_sqlite");
+        let backfill = migrator
+            .iter()
+            .find(|migration| migration.description == "backfill opening balances")
+            .unwrap();
+        let mut conn = pool.acquire().await.unwrap();
+        conn.ensure_migrations_table().await.unwrap();
+        for migration in migrator.iter().take_while(|m| m.version < backfill.version) {
+            conn.apply(migration).await.unwrap();
+        }
+        drop(conn);
+
+        // Счета из времени до журнала: баланс есть, операций нет (или не все)
+        let repository = SqliteAccountRepository::new(pool.clone());
+        let mut legacy = Account::new("Legacy".into(), "USD".into(), Utc::now()).unwrap();
+        legacy.balance = 5000;
+        let mut partial = Account::new("Partial".into(), "USD".into(), Utc::now()).unwrap();
+        partial.balance = 7000;
+        let settled = create(&repository, "Settled").await;
+        repository.create(&legacy, &[]).await.unwrap();
+        repository.create(&partial, &[]).await.unwrap();
+        let deposit = Operation::new(partial.id, TransactionKind::Deposit, 3000, None, Utc::now());
+        assert!(repository.update(&partial, &[], &[deposit]).await.unwrap());
+
+        migrator.run(&pool).await.unwrap();
+
+        let opening: Vec<(Uuid, i64)> = sqlx::query_as(
+            "SELECT account_id, amount FROM transactions \
+             WHERE kind = 'opening_balance' ORDER BY amount",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(opening, vec![(partial.id, 4000), (legacy.id, 5000)]);
+        assert!(opening.iter().all(|(id, _)| *id != settled.id));
+    }
+}
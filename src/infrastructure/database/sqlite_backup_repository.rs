@@ -0,0 +1,209 @@
+//! Реализация резервного копирования на SQLite.
+
+use sqlx::SqlitePool;
+
+use super::postgres_account_repository::AccountRow;
+use super::postgres_snapshot_repository::SnapshotRow;
+use super::postgres_transaction_repository::TransactionRow;
+use crate::application::ports::{BackupData, BackupRepository};
+
+/// SQLite реализация репозитория резервных копий.
+#[derive(Clone)]
+pub struct SqliteBackupRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteBackupRepository {
+    /// Создаёт новый репозиторий с указанным пулом соединений.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl BackupRepository for SqliteBackupRepository {
+    type Error = sqlx::Error;
+
+    /// Выгружает все таблицы в одной транзакции.
+    ///
+    /// Уровень изоляции настраивать не нужно: читающая транзакция SQLite
+    /// видит одно состояние базы от первого SELECT до конца.
+    async fn export_all(&self) -> Result<BackupData, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let accounts = sqlx::query_as::<_, AccountRow>(
+            r#"
+            SELECT id, name, balance, currency, created_at, updated_at, version, deleted_at,
+                   withdrawal_limit, account_type, credit_limit, min_balance, archived,
+                   overdraft_source, low_balance_threshold, description, user_id
+            FROM accounts
+            ORDER BY created_at ASC, id ASC
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let transactions = sqlx::query_as::<_, TransactionRow>(
+            r#"
+            SELECT id, account_id, kind, amount, timestamp, note, counterparty_id, counter_amount,
+                   category, reversed_at
+            FROM transactions
+            ORDER BY timestamp ASC, id ASC
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let snapshots = sqlx::query_as::<_, SnapshotRow>(
+            r#"
+            SELECT account_id, balance, recorded_at
+            FROM balance_snapshots
+            ORDER BY id ASC
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(BackupData {
+            accounts: accounts
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            transactions: transactions
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            snapshots: snapshots.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    /// Загружает все данные в одной транзакции.
+    ///
+    /// # Почему BEGIN IMMEDIATE?
+    /// Замена `LOCK TABLE` из PostgreSQL: транзакция сразу берёт блокировку
+    /// записи, и между проверкой "таблица пуста" и вставкой никто другой
+    /// не создаст счёт.
+    async fn import_all(&self, data: &BackupData) -> Result<bool, Self::Error> {
+        let mut tx = self.pool.begin_with("BEGIN IMMEDIATE").await?;
+
+        let not_empty: bool = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM accounts)")
+            .fetch_one(&mut *tx)
+            .await?;
+
+        if not_empty {
+            return Ok(false);
+        }
+
+        for account in &data.accounts {
+            sqlx::query(
+                r#"
+                INSERT INTO accounts (
+                    id, name, balance, currency, created_at, updated_at, version, deleted_at,
+                    withdrawal_limit, account_type, credit_limit, min_balance, archived,
+                    overdraft_source, low_balance_threshold, description, user_id
+                )
+                VALUES (
+                    ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17
+                )
+                "#,
+            )
+            .bind(account.id)
+            .bind(&account.name)
+            .bind(account.balance)
+            .bind(&account.currency)
+            .bind(account.created_at)
+            .bind(account.updated_at)
+            .bind(account.version)
+            .bind(account.deleted_at)
+            .bind(account.withdrawal_limit)
+            .bind(account.account_type.as_str())
+            .bind(account.credit_limit)
+            .bind(account.min_balance)
+            .bind(account.archived)
+            .bind(account.overdraft_source)
+            .bind(account.low_balance_threshold)
+            .bind(&account.description)
+            .bind(&account.user_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for transaction in &data.transactions {
+            sqlx::query(
+                r#"
+                INSERT INTO transactions (
+                    id, account_id, kind, amount, timestamp, note, counterparty_id,
+                    counter_amount, category, reversed_at
+                )
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                "#,
+            )
+            .bind(transaction.id)
+            .bind(transaction.account_id)
+            .bind(transaction.kind.as_str())
+            .bind(transaction.amount)
+            .bind(transaction.timestamp)
+            .bind(&transaction.note)
+            .bind(transaction.counterparty_id)
+            .bind(transaction.counter_amount)
+            .bind(&transaction.category)
+            .bind(transaction.reversed_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for snapshot in &data.snapshots {
+            sqlx::query(
+                r#"
+                INSERT INTO balance_snapshots (account_id, balance, recorded_at)
+                VALUES (?1, ?2, ?3)
+                "#,
+            )
+            .bind(snapshot.account_id)
+            .bind(snapshot.balance)
+            .bind(snapshot.recorded_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::application::ports::AccountRepository;
+    use crate::domain::entities::{Account, Transaction, TransactionKind};
+    use crate::infrastructure::database::test_support::sqlite_database;
+    use crate::infrastructure::database::SqliteAccountRepository;
+
+    #[tokio::test]
+    async fn export_imports_into_an_empty_database_only() {
+        let source = sqlite_database().await;
+        let accounts = SqliteAccountRepository::new(source.clone());
+        let mut account = Account::new("Wallet".into(), "USD".into(), Utc::now()).unwrap();
+        accounts.create(&account, &[]).await.unwrap();
+        account.balance = 700;
+        let deposit = Transaction::new(account.id, TransactionKind::Deposit, 700, None, Utc::now());
+        assert!(accounts.update(&account, &[], &[deposit]).await.unwrap());
+
+        let data = SqliteBackupRepository::new(source)
+            .export_all()
+            .await
+            .unwrap();
+        assert_eq!((data.accounts.len(), data.transactions.len()), (1, 1));
+
+        let target = SqliteBackupRepository::new(sqlite_database().await);
+        assert!(target.import_all(&data).await.unwrap());
+        assert!(!target.import_all(&data).await.unwrap());
+        let copy = target.export_all().await.unwrap();
+        assert_eq!(copy.accounts[0].balance, 700);
+        assert_eq!(copy.transactions[0].id, data.transactions[0].id);
+    }
+}
@@ -0,0 +1,96 @@
+//! Реализация ключей идемпотентности на SQLite.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use super::postgres_idempotency_repository::IdempotencyRow;
+use crate::application::dto::AccountResponse;
+use crate::application::ports::{IdempotencyRecord, IdempotencyRepository};
+
+/// SQLite реализация репозитория ключей идемпотентности.
+#[derive(Clone)]
+pub struct SqliteIdempotencyRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteIdempotencyRepository {
+    /// Создаёт новый репозиторий с указанным пулом соединений.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl IdempotencyRepository for SqliteIdempotencyRepository {
+    type Error = sqlx::Error;
+
+    /// Занимает ключ: `ON CONFLICT DO NOTHING`, как у PostgreSQL —
+    /// из двух одновременных INSERT пройдёт ровно один.
+    async fn reserve(
+        &self,
+        record: &IdempotencyRecord,
+        expired_before: DateTime<Utc>,
+    ) -> Result<Option<IdempotencyRecord>, Self::Error> {
+        sqlx::query("DELETE FROM idempotency_keys WHERE created_at < ?1")
+            .bind(expired_before)
+            .execute(&self.pool)
+            .await?;
+
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO idempotency_keys (key, fingerprint, created_at)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT (key) DO NOTHING
+            "#,
+        )
+        .bind(&record.key)
+        .bind(&record.fingerprint)
+        .bind(record.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        if inserted.rows_affected() == 1 {
+            return Ok(None);
+        }
+
+        let existing = sqlx::query_as::<_, IdempotencyRow>(
+            r#"
+            SELECT key, fingerprint, response, created_at
+            FROM idempotency_keys
+            WHERE key = ?1
+            "#,
+        )
+        .bind(&record.key)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        // Запись удалили между INSERT и SELECT — "в процессе", как у PostgreSQL
+        Ok(Some(match existing {
+            Some(row) => row.try_into()?,
+            None => IdempotencyRecord {
+                response: None,
+                ..record.clone()
+            },
+        }))
+    }
+
+    async fn complete(&self, key: &str, response: &AccountResponse) -> Result<(), Self::Error> {
+        let json = serde_json::to_string(response).map_err(|e| sqlx::Error::Encode(e.into()))?;
+
+        sqlx::query("UPDATE idempotency_keys SET response = ?2 WHERE key = ?1")
+            .bind(key)
+            .bind(json)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn release(&self, key: &str) -> Result<(), Self::Error> {
+        sqlx::query("DELETE FROM idempotency_keys WHERE key = ?1 AND response IS NULL")
+            .bind(key)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
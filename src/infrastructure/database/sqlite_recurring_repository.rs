@@ -0,0 +1,126 @@
+//! Реализация правил повторяющихся операций на SQLite.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use super::postgres_recurring_repository::RecurringRow;
+use crate::application::ports::RecurringRepository;
+use crate::domain::entities::RecurringTransaction;
+
+/// SQLite реализация репозитория правил.
+#[derive(Clone)]
+pub struct SqliteRecurringRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteRecurringRepository {
+    /// Создаёт новый репозиторий с указанным пулом соединений.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Колонки правила в порядке полей `RecurringRow`.
+const RECURRING_COLUMNS: &str =
+    "id, account_id, kind, amount, cron_or_interval, next_run, enabled, note, created_at";
+
+impl RecurringRepository for SqliteRecurringRepository {
+    type Error = sqlx::Error;
+
+    async fn create(&self, rule: &RecurringTransaction) -> Result<(), Self::Error> {
+        sqlx::query(&format!(
+            "INSERT INTO recurring_transactions ({RECURRING_COLUMNS})
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)"
+        ))
+        .bind(rule.id)
+        .bind(rule.account_id)
+        .bind(rule.kind.as_str())
+        .bind(rule.amount)
+        .bind(rule.schedule.to_string())
+        .bind(rule.next_run)
+        .bind(rule.enabled)
+        .bind(&rule.note)
+        .bind(rule.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RecurringTransaction>, Self::Error> {
+        let rule = sqlx::query_as::<_, RecurringRow>(&format!(
+            "SELECT {RECURRING_COLUMNS} FROM recurring_transactions WHERE id = ?1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        rule.map(TryInto::try_into).transpose()
+    }
+
+    async fn find_by_account(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Vec<RecurringTransaction>, Self::Error> {
+        let rules = sqlx::query_as::<_, RecurringRow>(&format!(
+            "SELECT {RECURRING_COLUMNS} FROM recurring_transactions
+             WHERE account_id = ?1
+             ORDER BY created_at ASC"
+        ))
+        .bind(account_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rules.into_iter().map(TryInto::try_into).collect()
+    }
+
+    /// Частичный индекс `idx_recurring_transactions_due` — как у PostgreSQL.
+    async fn find_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<RecurringTransaction>, Self::Error> {
+        let rules = sqlx::query_as::<_, RecurringRow>(&format!(
+            "SELECT {RECURRING_COLUMNS} FROM recurring_transactions
+             WHERE enabled AND next_run <= ?1
+             ORDER BY next_run ASC
+             LIMIT ?2"
+        ))
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rules.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn update(&self, rule: &RecurringTransaction) -> Result<(), Self::Error> {
+        sqlx::query(
+            r#"
+            UPDATE recurring_transactions
+            SET amount = ?2, cron_or_interval = ?3, next_run = ?4, enabled = ?5, note = ?6
+            WHERE id = ?1
+            "#,
+        )
+        .bind(rule.id)
+        .bind(rule.amount)
+        .bind(rule.schedule.to_string())
+        .bind(rule.next_run)
+        .bind(rule.enabled)
+        .bind(&rule.note)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), Self::Error> {
+        sqlx::query("DELETE FROM recurring_transactions WHERE id = ?1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
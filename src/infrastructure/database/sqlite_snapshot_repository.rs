@@ -0,0 +1,85 @@
+//! Реализация истории баланса на SQLite.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use super::postgres_snapshot_repository::SnapshotRow;
+use crate::application::ports::SnapshotRepository;
+use crate::domain::entities::BalanceSnapshot;
+
+/// SQLite реализация репозитория снимков баланса.
+#[derive(Clone)]
+pub struct SqliteSnapshotRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteSnapshotRepository {
+    /// Создаёт новый репозиторий с указанным пулом соединений.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl SnapshotRepository for SqliteSnapshotRepository {
+    type Error = sqlx::Error;
+
+    async fn create(&self, snapshot: &BalanceSnapshot) -> Result<(), Self::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO balance_snapshots (account_id, balance, recorded_at)
+            VALUES (?1, ?2, ?3)
+            "#,
+        )
+        .bind(snapshot.account_id)
+        .bind(snapshot.balance)
+        .bind(snapshot.recorded_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_latest(&self, account_id: Uuid) -> Result<Option<BalanceSnapshot>, Self::Error> {
+        let snapshot = sqlx::query_as::<_, SnapshotRow>(
+            r#"
+            SELECT account_id, balance, recorded_at
+            FROM balance_snapshots
+            WHERE account_id = ?1
+            ORDER BY recorded_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(account_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(snapshot.map(Into::into))
+    }
+
+    /// Снимки в диапазоне; `?2 IS NULL OR ...` — как у PostgreSQL.
+    async fn find_in_range(
+        &self,
+        account_id: Uuid,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<BalanceSnapshot>, Self::Error> {
+        let snapshots = sqlx::query_as::<_, SnapshotRow>(
+            r#"
+            SELECT account_id, balance, recorded_at
+            FROM balance_snapshots
+            WHERE account_id = ?1
+              AND (?2 IS NULL OR recorded_at >= ?2)
+              AND (?3 IS NULL OR recorded_at <= ?3)
+            ORDER BY recorded_at ASC
+            "#,
+        )
+        .bind(account_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(snapshots.into_iter().map(Into::into).collect())
+    }
+}
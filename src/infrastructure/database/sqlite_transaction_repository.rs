@@ -0,0 +1,395 @@
+//! Реализация журнала операций на SQLite.
+//!
+//! Операции пишет `SqliteAccountRepository` вместе с балансом, здесь —
+//! чтение и правка деталей.
+//!
+//! # Отличия от PostgreSQL
+//! - Массивов нет: `account_id = ANY($1)` становится `IN (?5, ?6, ...)`
+//! - Время хранится строкой RFC 3339 в UTC; такие строки сравниваются
+//!   в том же порядке, что и моменты времени, поэтому `<`, `>=` и
+//!   `ORDER BY timestamp` работают как у PostgreSQL
+//! - `SUM` по INTEGER — уже INTEGER, приведение `::BIGINT` не нужно
+
+use chrono::{DateTime, Utc};
+use futures_util::stream::{self, BoxStream, StreamExt};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use super::postgres_transaction_repository::{CategoryTotalRow, KindTotalRow, TransactionRow};
+use crate::application::ports::{
+    ActivityCursor, CategoryTotal, KindTotal, TransactionFilter, TransactionRepository,
+};
+use crate::domain::entities::{Transaction, TransactionKind};
+
+/// SQLite реализация репозитория операций.
+#[derive(Clone)]
+pub struct SqliteTransactionRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteTransactionRepository {
+    /// Создаёт новый репозиторий с указанным пулом соединений.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Колонки операции в порядке полей `TransactionRow`.
+const TRANSACTION_COLUMNS: &str = "id, account_id, kind, amount, timestamp, note, counterparty_id,
+    counter_amount, category, reversed_at";
+
+/// Условия `TransactionFilter`: `?1` — счёт, `?2` — категория, `?3` — тип,
+/// `?4` и `?5` — границы по времени (включительно).
+const FILTER_CONDITIONS: &str = "account_id = ?1
+    AND (?2 IS NULL OR category = ?2)
+    AND (?3 IS NULL OR kind = ?3)
+    AND (?4 IS NULL OR timestamp >= ?4)
+    AND (?5 IS NULL OR timestamp <= ?5)";
+
+/// `count` плейсхолдеров подряд, начиная с `?first`: `?3, ?4, ?5`.
+///
+/// Пустой список даёт `IN ()` — в SQLite это допустимо и ничего не находит.
+pub(super) fn placeholders(first: usize, count: usize) -> String {
+    (first..first + count)
+        .map(|index| format!("?{index}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl TransactionRepository for SqliteTransactionRepository {
+    type Error = sqlx::Error;
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Transaction>, Self::Error> {
+        let transaction = sqlx::query_as::<_, TransactionRow>(&format!(
+            "SELECT {TRANSACTION_COLUMNS} FROM transactions WHERE id = ?1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        transaction.map(TryInto::try_into).transpose()
+    }
+
+    async fn update_details(&self, transaction: &Transaction) -> Result<bool, Self::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE transactions
+            SET note = ?2, category = ?3, reversed_at = COALESCE(reversed_at, ?4)
+            WHERE id = ?1
+            "#,
+        )
+        .bind(transaction.id)
+        .bind(&transaction.note)
+        .bind(&transaction.category)
+        .bind(transaction.reversed_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn find_by_account(
+        &self,
+        account_id: Uuid,
+        filter: &TransactionFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Transaction>, Self::Error> {
+        let transactions = sqlx::query_as::<_, TransactionRow>(&format!(
+            "SELECT {TRANSACTION_COLUMNS} FROM transactions
+             WHERE {FILTER_CONDITIONS}
+             ORDER BY timestamp DESC, id DESC
+             LIMIT ?6 OFFSET ?7"
+        ))
+        .bind(account_id)
+        .bind(&filter.category)
+        .bind(filter.kind.map(|kind| kind.as_str()))
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        transactions.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn count_by_account(
+        &self,
+        account_id: Uuid,
+        filter: &TransactionFilter,
+    ) -> Result<i64, Self::Error> {
+        sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM transactions WHERE {FILTER_CONDITIONS}"
+        ))
+        .bind(account_id)
+        .bind(&filter.category)
+        .bind(filter.kind.map(|kind| kind.as_str()))
+        .bind(filter.from)
+        .bind(filter.to)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Поток через задачу и канал — как у PostgreSQL (см. там же почему).
+    fn stream_by_account(
+        &self,
+        account_id: Uuid,
+    ) -> BoxStream<'static, Result<Transaction, Self::Error>> {
+        let (sender, receiver) = tokio::sync::mpsc::channel(64);
+        let pool = self.pool.clone();
+
+        tokio::spawn(async move {
+            let sql = format!(
+                "SELECT {TRANSACTION_COLUMNS} FROM transactions
+                 WHERE account_id = ?1
+                 ORDER BY timestamp ASC"
+            );
+            let mut rows = sqlx::query_as::<_, TransactionRow>(&sql)
+                .bind(account_id)
+                .fetch(&pool);
+
+            while let Some(row) = rows.next().await {
+                let failed = row.is_err();
+                let transaction = row.and_then(TryInto::try_into);
+                if sender.send(transaction).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+
+        stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|item| (item, receiver))
+        })
+        .boxed()
+    }
+
+    async fn find_in_range(
+        &self,
+        account_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Transaction>, Self::Error> {
+        let transactions = sqlx::query_as::<_, TransactionRow>(&format!(
+            "SELECT {TRANSACTION_COLUMNS} FROM transactions
+             WHERE account_id = ?1 AND timestamp >= ?2 AND timestamp < ?3
+             ORDER BY timestamp ASC"
+        ))
+        .bind(account_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        transactions.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn totals_before(
+        &self,
+        account_id: Uuid,
+        before: DateTime<Utc>,
+    ) -> Result<Vec<KindTotal>, Self::Error> {
+        let rows = sqlx::query_as::<_, KindTotalRow>(
+            r#"
+            SELECT kind, SUM(amount) AS total, COUNT(*) AS count
+            FROM transactions
+            WHERE account_id = ?1 AND timestamp < ?2
+            GROUP BY kind
+            "#,
+        )
+        .bind(account_id)
+        .bind(before)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn totals_by_category(
+        &self,
+        account_ids: &[Uuid],
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<CategoryTotal>, Self::Error> {
+        let sql = format!(
+            "SELECT account_id, category, kind, SUM(amount) AS total, COUNT(*) AS count
+             FROM transactions
+             WHERE (?1 IS NULL OR timestamp >= ?1)
+               AND (?2 IS NULL OR timestamp <= ?2)
+               AND account_id IN ({})
+             GROUP BY account_id, category, kind",
+            placeholders(3, account_ids.len())
+        );
+        let rows = account_ids
+            .iter()
+            .fold(
+                sqlx::query_as::<_, CategoryTotalRow>(&sql)
+                    .bind(from)
+                    .bind(to),
+                |query, id| query.bind(id),
+            )
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    /// Страница ленты: сравнение кортежей `(timestamp, id) < (?1, ?2)`
+    /// SQLite понимает так же, как PostgreSQL.
+    async fn find_recent(
+        &self,
+        account_ids: &[Uuid],
+        before: Option<ActivityCursor>,
+        limit: i64,
+    ) -> Result<Vec<Transaction>, Self::Error> {
+        let sql = format!(
+            "SELECT {TRANSACTION_COLUMNS} FROM transactions
+             WHERE (?1 IS NULL OR (timestamp, id) < (?1, ?2))
+               AND account_id IN ({})
+             ORDER BY timestamp DESC, id DESC
+             LIMIT ?3",
+            placeholders(4, account_ids.len())
+        );
+        let transactions = account_ids
+            .iter()
+            .fold(
+                sqlx::query_as::<_, TransactionRow>(&sql)
+                    .bind(before.map(|cursor| cursor.timestamp))
+                    .bind(before.map(|cursor| cursor.id))
+                    .bind(limit),
+                |query, id| query.bind(id),
+            )
+            .fetch_all(&self.pool)
+            .await?;
+
+        transactions.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn sum_since(
+        &self,
+        account_id: Uuid,
+        kind: TransactionKind,
+        since: DateTime<Utc>,
+    ) -> Result<i64, Self::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COALESCE(SUM(amount), 0)
+            FROM transactions
+            WHERE account_id = ?1 AND kind = ?2 AND timestamp >= ?3
+            "#,
+        )
+        .bind(account_id)
+        .bind(kind.as_str())
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    async fn sum_reversed_since(
+        &self,
+        account_id: Uuid,
+        kind: TransactionKind,
+        since: DateTime<Utc>,
+    ) -> Result<i64, Self::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COALESCE(SUM(amount), 0)
+            FROM transactions
+            WHERE account_id = ?1 AND kind = ?2 AND reversed_at >= ?3
+            "#,
+        )
+        .bind(account_id)
+        .bind(kind.as_str())
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone};
+
+    use super::*;
+    use crate::application::ports::AccountRepository;
+    use crate::domain::entities::Account;
+    use crate::infrastructure::database::test_support::sqlite_database;
+    use crate::infrastructure::database::SqliteAccountRepository;
+
+    /// Счета `a` и `b`: у `a` пополнения в t0, t0 + 0.5 с и t0 + 1 с,
+    /// у `b` — одно в t0 + 1.25 с. Время с дробными секундами и без —
+    /// строки разной длины.
+    async fn ledger() -> (SqliteTransactionRepository, Uuid, Uuid, DateTime<Utc>) {
+        let pool = sqlite_database().await;
+        let accounts = SqliteAccountRepository::new(pool.clone());
+        let t0 = Utc.with_ymd_and_hms(2024, 1, 15, 10, 0, 0).unwrap();
+
+        let mut ids = Vec::new();
+        for (name, offsets) in [("A", vec![0, 500, 1000]), ("B", vec![1250])] {
+            let account = Account::new(name.into(), "USD".into(), t0).unwrap();
+            accounts.create(&account, &[]).await.unwrap();
+            let operations: Vec<Transaction> = offsets
+                .into_iter()
+                .map(|ms| {
+                    let at = t0 + Duration::milliseconds(ms);
+                    Transaction::new(account.id, TransactionKind::Deposit, 100, None, at)
+                })
+                .collect();
+            assert!(accounts.update(&account, &[], &operations).await.unwrap());
+            ids.push(account.id);
+        }
+
+        (SqliteTransactionRepository::new(pool), ids[0], ids[1], t0)
+    }
+
+    #[tokio::test]
+    async fn times_compare_in_time_order() {
+        let (repository, a, _, t0) = ledger().await;
+        let t1 = t0 + Duration::milliseconds(500);
+        let t2 = t0 + Duration::seconds(1);
+
+        let in_range = repository.find_in_range(a, t0, t2).await.unwrap();
+        let times: Vec<_> = in_range.iter().map(|t| t.timestamp).collect();
+        assert_eq!(times, vec![t0, t1]);
+
+        let since = repository
+            .sum_since(a, TransactionKind::Deposit, t1)
+            .await
+            .unwrap();
+        assert_eq!(since, 200);
+        let before = repository.totals_before(a, t1).await.unwrap();
+        let totals: Vec<_> = before.iter().map(|t| (t.kind, t.total, t.count)).collect();
+        assert_eq!(totals, vec![(TransactionKind::Deposit, 100, 1)]);
+    }
+
+    #[tokio::test]
+    async fn activity_feed_covers_listed_accounts_only() {
+        let (repository, a, b, t0) = ledger().await;
+
+        let first = repository.find_recent(&[a, b], None, 2).await.unwrap();
+        let owners: Vec<_> = first.iter().map(|t| t.account_id).collect();
+        assert_eq!(owners, vec![b, a]);
+
+        let cursor = ActivityCursor::of(&first[1]);
+        let rest = repository
+            .find_recent(&[a, b], Some(cursor), 10)
+            .await
+            .unwrap();
+        let times: Vec<_> = rest.iter().map(|t| t.timestamp).collect();
+        assert_eq!(times, vec![t0 + Duration::milliseconds(500), t0]);
+
+        assert!(repository
+            .find_recent(&[], None, 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let totals = repository
+            .totals_by_category(&[a], None, None)
+            .await
+            .unwrap();
+        assert_eq!(totals.len(), 1);
+        assert_eq!((totals[0].account_id, totals[0].total), (a, 300));
+        assert_eq!(totals[0].count, 3);
+    }
+}
@@ -0,0 +1,213 @@
+//! Реализация кошельков на SQLite.
+//!
+//! Схема та же, что у PostgreSQL: кошелёк — строка `wallets`,
+//! остатки — по строке `wallet_balances` на валюту.
+
+use std::collections::HashMap;
+
+use sqlx::{Sqlite, SqlitePool, Transaction};
+use uuid::Uuid;
+
+use super::postgres_wallet_repository::{group_balances, into_wallets, BalanceRow, WalletRow};
+use super::sqlite_transaction_repository::placeholders;
+use crate::application::ports::WalletRepository;
+use crate::domain::entities::Wallet;
+use crate::domain::value_objects::Currency;
+
+/// SQLite реализация репозитория кошельков.
+#[derive(Clone)]
+pub struct SqliteWalletRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteWalletRepository {
+    /// Создаёт новый репозиторий с указанным пулом соединений.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Остатки кошельков `ids` одним запросом; `IN (...)` вместо `ANY($1)`.
+    async fn balances(
+        &self,
+        ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, HashMap<Currency, i64>>, sqlx::Error> {
+        let sql = format!(
+            "SELECT wallet_id, currency, balance FROM wallet_balances WHERE wallet_id IN ({})",
+            placeholders(1, ids.len())
+        );
+        let rows = ids
+            .iter()
+            .fold(sqlx::query_as::<_, BalanceRow>(&sql), |query, id| {
+                query.bind(id)
+            })
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(group_balances(rows))
+    }
+
+    /// Собирает кошельки из строк `wallets` и их остатков.
+    async fn with_balances(&self, rows: Vec<WalletRow>) -> Result<Vec<Wallet>, sqlx::Error> {
+        let ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+        let balances = self.balances(&ids).await?;
+
+        Ok(into_wallets(rows, balances))
+    }
+}
+
+impl WalletRepository for SqliteWalletRepository {
+    type Error = sqlx::Error;
+
+    async fn create(&self, wallet: &Wallet) -> Result<(), Self::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO wallets (id, name, created_at, updated_at, version)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            "#,
+        )
+        .bind(wallet.id)
+        .bind(&wallet.name)
+        .bind(wallet.created_at)
+        .bind(wallet.updated_at)
+        .bind(wallet.version)
+        .execute(&mut *tx)
+        .await?;
+
+        insert_balances(&mut tx, wallet).await?;
+
+        tx.commit().await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Wallet>, Self::Error> {
+        let row = sqlx::query_as::<_, WalletRow>(
+            r#"
+            SELECT id, name, created_at, updated_at, version
+            FROM wallets
+            WHERE id = ?1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        Ok(self.with_balances(vec![row]).await?.pop())
+    }
+
+    async fn find_all(&self) -> Result<Vec<Wallet>, Self::Error> {
+        let rows = sqlx::query_as::<_, WalletRow>(
+            r#"
+            SELECT id, name, created_at, updated_at, version
+            FROM wallets
+            ORDER BY created_at ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        self.with_balances(rows).await
+    }
+
+    /// UPDATE с проверкой версии, затем остатки заново — как у PostgreSQL.
+    async fn update(&self, wallet: &Wallet) -> Result<bool, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE wallets
+            SET name = ?2, updated_at = ?3, version = version + 1
+            WHERE id = ?1 AND version = ?4
+            "#,
+        )
+        .bind(wallet.id)
+        .bind(&wallet.name)
+        .bind(wallet.updated_at)
+        .bind(wallet.version)
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() != 1 {
+            return Ok(false);
+        }
+
+        sqlx::query("DELETE FROM wallet_balances WHERE wallet_id = ?1")
+            .bind(wallet.id)
+            .execute(&mut *tx)
+            .await?;
+        insert_balances(&mut tx, wallet).await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), Self::Error> {
+        // Остатки удалит ON DELETE CASCADE
+        sqlx::query("DELETE FROM wallets WHERE id = ?1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Вставляет остатки кошелька (по строке на валюту).
+async fn insert_balances(
+    tx: &mut Transaction<'_, Sqlite>,
+    wallet: &Wallet,
+) -> Result<(), sqlx::Error> {
+    for (currency, balance) in &wallet.balances {
+        sqlx::query(
+            r#"
+            INSERT INTO wallet_balances (wallet_id, currency, balance)
+            VALUES (?1, ?2, ?3)
+            "#,
+        )
+        .bind(wallet.id)
+        .bind(currency.to_string())
+        .bind(balance)
+        .execute(&mut **tx)
+        .await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::infrastructure::database::test_support::sqlite_database;
+
+    #[tokio::test]
+    async fn balances_are_read_back_per_wallet() {
+        let repository = SqliteWalletRepository::new(sqlite_database().await);
+        let now = Utc::now();
+        let (usd, eur) = (Currency::new("USD"), Currency::new("EUR"));
+
+        let mut travel = Wallet::new("Travel", now).unwrap();
+        travel.deposit(usd.clone(), 500, now).unwrap();
+        travel.deposit(eur.clone(), 300, now).unwrap();
+        let empty = Wallet::new("Empty", now).unwrap();
+        repository.create(&travel).await.unwrap();
+        repository.create(&empty).await.unwrap();
+
+        let wallets = repository.find_all().await.unwrap();
+        assert_eq!(wallets.len(), 2);
+        let found = wallets.iter().find(|w| w.id == travel.id).unwrap();
+        assert_eq!((found.balance(&usd), found.balance(&eur)), (500, 300));
+
+        // Снятие до нуля убирает строку валюты
+        let mut changed = found.clone();
+        changed.withdraw(&eur, 300, now).unwrap();
+        assert!(repository.update(&changed).await.unwrap());
+        assert!(!repository.update(&changed).await.unwrap());
+        let reloaded = repository.find_by_id(travel.id).await.unwrap().unwrap();
+        assert_eq!(reloaded.balances.len(), 1);
+        assert_eq!(reloaded.version, 2);
+    }
+}
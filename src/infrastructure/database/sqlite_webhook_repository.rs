@@ -0,0 +1,121 @@
+//! Реализация подписок на события на SQLite.
+
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use super::postgres_webhook_repository::WebhookRow;
+use crate::application::ports::WebhookRepository;
+use crate::domain::entities::Webhook;
+
+/// SQLite реализация репозитория подписок.
+#[derive(Clone)]
+pub struct SqliteWebhookRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteWebhookRepository {
+    /// Создаёт новый репозиторий с указанным пулом соединений.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl WebhookRepository for SqliteWebhookRepository {
+    type Error = sqlx::Error;
+
+    async fn create(&self, webhook: &Webhook) -> Result<(), Self::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO webhooks (id, url, secret, enabled, created_at, user_id)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            "#,
+        )
+        .bind(webhook.id)
+        .bind(&webhook.url)
+        .bind(&webhook.secret)
+        .bind(webhook.enabled)
+        .bind(webhook.created_at)
+        .bind(&webhook.user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_by_id(&self, user_id: &str, id: Uuid) -> Result<Option<Webhook>, Self::Error> {
+        let webhook = sqlx::query_as::<_, WebhookRow>(
+            r#"
+            SELECT id, url, secret, enabled, created_at, user_id
+            FROM webhooks
+            WHERE id = ?1 AND user_id = ?2
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(webhook.map(Into::into))
+    }
+
+    async fn find_all(&self, user_id: &str) -> Result<Vec<Webhook>, Self::Error> {
+        let webhooks = sqlx::query_as::<_, WebhookRow>(
+            r#"
+            SELECT id, url, secret, enabled, created_at, user_id
+            FROM webhooks
+            WHERE user_id = ?1
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(webhooks.into_iter().map(Into::into).collect())
+    }
+
+    async fn find_enabled(&self, user_id: &str) -> Result<Vec<Webhook>, Self::Error> {
+        let webhooks = sqlx::query_as::<_, WebhookRow>(
+            r#"
+            SELECT id, url, secret, enabled, created_at, user_id
+            FROM webhooks
+            WHERE enabled AND user_id = ?1
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(webhooks.into_iter().map(Into::into).collect())
+    }
+
+    async fn update(&self, webhook: &Webhook) -> Result<(), Self::Error> {
+        sqlx::query(
+            r#"
+            UPDATE webhooks
+            SET url = ?2, secret = ?3, enabled = ?4
+            WHERE id = ?1 AND user_id = ?5
+            "#,
+        )
+        .bind(webhook.id)
+        .bind(&webhook.url)
+        .bind(&webhook.secret)
+        .bind(webhook.enabled)
+        .bind(&webhook.user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: &str, id: Uuid) -> Result<(), Self::Error> {
+        sqlx::query("DELETE FROM webhooks WHERE id = ?1 AND user_id = ?2")
+            .bind(id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+//! Настоящие ошибки драйвера sqlx для тестов классификации ошибок
+//! и пустая SQLite-база со схемой для тестов SQLite-репозиториев.
+//!
+//! # Почему SQLite, а не PostgreSQL?
+//! Тестам нужна ошибка, которую вернула БД, а не собранная вручную:
+//! `DbErrorKind` смотрит на `DatabaseError::kind()`, а его считает драйвер.
+//! SQLite в памяти не требует сервера, а `kind()` у него сводится к тем же
+//! вариантам, что у PostgreSQL (`UniqueViolation` из кода
+//! `SQLITE_CONSTRAINT_UNIQUE`, как из SQLSTATE `23505`).
+
+use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::SqlitePool;
+
+/// Пул из одного соединения с пустой БД в памяти.
+///
+/// Одно соединение — потому что у каждого соединения SQLite `:memory:`
+/// своя БД: таблица, созданная в одном, не видна в другом.
+async fn memory_pool() -> SqlitePool {
+    SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap()
+}
+
+/// Пустая БД в памяти со схемой `migrations_sqlite/`.
+pub async fn sqlite_database() -> SqlitePool {
+    let pool = memory_pool().await;
+    sqlx::migrate!("./migrations_sqlite")
+        .run(&pool)
+        .await
+        .unwrap();
+    pool
+}
+
+/// БД с `accounts` (имя уникально без учёта регистра) и `transactions`
+/// (ссылка на счёт) и счётом `Wallet` с `id = 1`.
+async fn accounts_pool() -> SqlitePool {
+    let pool = memory_pool().await;
+    for statement in [
+        "CREATE TABLE accounts (id INTEGER PRIMARY KEY, name TEXT NOT NULL)",
+        "CREATE UNIQUE INDEX idx_accounts_name ON accounts (LOWER(name))",
+        "CREATE TABLE transactions (id INTEGER PRIMARY KEY, \
+            account_id INTEGER NOT NULL REFERENCES accounts(id))",
+        "INSERT INTO accounts (id, name) VALUES (1, 'Wallet')",
+    ] {
+        sqlx::query(statement).execute(&pool).await.unwrap();
+    }
+    pool
+}
+
+/// Ошибка вставки второго счёта с тем же именем — как у индекса
+/// `idx_accounts_user_name_unique`.
+pub async fn unique_violation() -> sqlx::Error {
+    let pool = accounts_pool().await;
+    sqlx::query("INSERT INTO accounts (name) VALUES ('wallet')")
+        .execute(&pool)
+        .await
+        .unwrap_err()
+}
+
+/// Ошибка вставки операции несуществующего счёта.
+///
+/// sqlx включает `PRAGMA foreign_keys` сам — без него SQLite
+/// не проверяет внешние ключи.
+pub async fn foreign_key_violation() -> sqlx::Error {
+    let pool = accounts_pool().await;
+    sqlx::query("INSERT INTO transactions (account_id) VALUES (2)")
+        .execute(&pool)
+        .await
+        .unwrap_err()
+}
+
+/// Ошибка в самом SQL — её повтор не исправит.
+pub async fn syntax_error() -> sqlx::Error {
+    let pool = accounts_pool().await;
+    sqlx::query("SELEC 1").execute(&pool).await.unwrap_err()
+}
+
+/// Запрос к закрытому пулу — как во время остановки сервера.
+pub async fn closed_pool() -> sqlx::Error {
+    let pool = memory_pool().await;
+    pool.close().await;
+    sqlx::query("SELECT 1").execute(&pool).await.unwrap_err()
+}
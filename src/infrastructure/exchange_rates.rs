@@ -0,0 +1,83 @@
+//! Курсы валют из конфигурации.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use rust_decimal::Decimal;
+
+use crate::application::ports::ExchangeRateProvider;
+use crate::domain::errors::DomainError;
+use crate::domain::value_objects::Currency;
+
+/// Неизменяемая таблица курсов, заданная при старте.
+///
+/// # Формат
+/// JSON-объект `"ИЗ/В": "курс"`, курс — строкой:
+/// ```json
+/// {"USD/EUR": "0.923", "EUR/USD": "1.0834"}
+/// ```
+///
+/// # Почему обратный курс не выводится сам?
+/// `1 / 0.923` — бесконечная дробь, а реальные курсы покупки и продажи
+/// всё равно различаются. Каждое направление задаётся явно.
+///
+/// # Почему `Arc`?
+/// Провайдер лежит внутри сервиса, который клонируется на каждый запрос.
+#[derive(Clone, Default)]
+pub struct StaticExchangeRateProvider {
+    rates: Arc<HashMap<(Currency, Currency), Decimal>>,
+}
+
+impl StaticExchangeRateProvider {
+    /// Разбирает таблицу курсов из JSON.
+    ///
+    /// # Errors
+    /// Текст ошибки, если JSON не того вида, ключ не `ИЗ/В`
+    /// или курс не положительное число.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        // Курсы читаем строками: JSON-число прошло бы через f64
+        let raw: HashMap<String, String> = serde_json::from_str(json)
+            .map_err(|e| format!("expected {{\"USD/EUR\": \"0.923\"}}: {e}"))?;
+
+        let mut rates = HashMap::with_capacity(raw.len());
+        for (pair, rate) in raw {
+            let (from, to) = pair
+                .split_once('/')
+                .ok_or_else(|| format!("'{pair}' is not a FROM/TO currency pair"))?;
+            let rate = Decimal::from_str(rate.trim())
+                .ok()
+                .filter(|rate| rate.is_sign_positive() && !rate.is_zero())
+                .ok_or_else(|| {
+                    format!("rate for '{pair}' must be a positive number, got '{rate}'")
+                })?;
+
+            rates.insert((Currency::new(from), Currency::new(to)), rate);
+        }
+
+        Ok(Self {
+            rates: Arc::new(rates),
+        })
+    }
+
+    /// Сколько пар валют в таблице.
+    pub fn pair_count(&self) -> usize {
+        self.rates.len()
+    }
+}
+
+impl ExchangeRateProvider for StaticExchangeRateProvider {
+    async fn rate(&self, from: &Currency, to: &Currency) -> Result<Decimal, DomainError> {
+        if from == to {
+            return Ok(Decimal::ONE);
+        }
+
+        self.rates
+            .get(&(from.clone(), to.clone()))
+            .copied()
+            .ok_or_else(|| DomainError::RateUnavailable {
+                from: from.to_string(),
+                to: to.to_string(),
+            })
+    }
+}
@@ -1,2 +1,5 @@
+pub mod clock;
 pub mod config;
 pub mod database;
+pub mod exchange_rates;
+pub mod webhooks;
@@ -0,0 +1,223 @@
+//! Доставка событий об изменении баланса подписчикам (webhooks).
+//!
+//! На каждое событие — POST с JSON на URL каждой включённой подписки:
+//! ```json
+//! {"account_id": "...", "kind": "deposit", "amount": "10.50",
+//!  "new_balance": "110.50", "timestamp": "2024-01-01T12:00:00+00:00"}
+//! ```
+//!
+//! # Заголовки
+//! - `X-Webhook-Signature: sha256=<hex>` — HMAC-SHA256 тела с секретом подписки
+//! - `X-Webhook-Event-Id` — один и тот же у всех попыток доставки события:
+//!   по нему получатель отбрасывает повторы
+
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::CONTENT_TYPE;
+use hyper::Request;
+use hyper_util::client::legacy::connect::HttpConnector;
+use hyper_util::client::legacy::Client;
+use hyper_util::rt::TokioExecutor;
+use serde::Serialize;
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::application::ports::{BalanceChanged, EventPublisher, WebhookRepository};
+use crate::domain::entities::{TransactionKind, Webhook};
+use crate::domain::value_objects::Money;
+
+/// Заголовок с подписью тела.
+const SIGNATURE_HEADER: &str = "x-webhook-signature";
+
+/// Заголовок с ID события.
+const EVENT_ID_HEADER: &str = "x-webhook-event-id";
+
+/// Сколько раз повторять доставку и сколько ждать ответа.
+///
+/// Пауза перед `n`-м повтором — `base_delay * 2^(n-1)`:
+/// при настройках по умолчанию 1, 2 и 4 секунды.
+#[derive(Debug, Clone, Copy)]
+pub struct DeliveryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub timeout: Duration,
+}
+
+impl Default for DeliveryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_secs(1),
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Тело запроса к подписчику. Суммы — строками, как во всём API.
+#[derive(Serialize)]
+struct WebhookPayload {
+    account_id: Uuid,
+    kind: TransactionKind,
+    amount: Money,
+    new_balance: Money,
+    timestamp: String, // RFC 3339
+}
+
+impl From<&BalanceChanged> for WebhookPayload {
+    fn from(event: &BalanceChanged) -> Self {
+        Self {
+            account_id: event.account_id,
+            kind: event.kind,
+            amount: Money::from_cents(event.amount),
+            new_balance: Money::from_cents(event.new_balance),
+            timestamp: event.timestamp.to_rfc3339(),
+        }
+    }
+}
+
+/// Рассылает события подписчикам из `WebhookRepository`.
+///
+/// # Почему подписки читаются на каждое событие?
+/// Подписку могли только что создать, выключить или удалить через API.
+/// Один короткий запрос на событие дешевле, чем кэш, который нужно
+/// сбрасывать из другого сервиса.
+///
+/// # Почему `Clone`?
+/// `publish` переносит копию диспетчера в `tokio::spawn`. Репозиторий
+/// держит `PgPool`, а `Client` — пул соединений; оба внутри на `Arc`.
+#[derive(Clone)]
+pub struct WebhookDispatcher<W> {
+    webhooks: W,
+    client: Client<HttpConnector, Full<Bytes>>,
+    policy: DeliveryPolicy,
+}
+
+impl<W> WebhookDispatcher<W>
+where
+    W: WebhookRepository + Clone + Sync + 'static,
+{
+    /// Диспетчер с политикой доставки по умолчанию.
+    pub fn new(webhooks: W) -> Self {
+        Self {
+            webhooks,
+            client: Client::builder(TokioExecutor::new()).build_http(),
+            policy: DeliveryPolicy::default(),
+        }
+    }
+
+    /// Читает включённые подписки владельца счёта и доставляет событие каждой.
+    ///
+    /// Каждая подписка — в своей задаче: недоступный получатель с его
+    /// повторами не задерживает остальных.
+    async fn dispatch(&self, event: BalanceChanged) {
+        let webhooks = match self.webhooks.find_enabled(&event.user_id).await {
+            Ok(webhooks) => webhooks,
+            Err(err) => {
+                tracing::error!("Failed to load webhooks: {}", err);
+                return;
+            }
+        };
+        if webhooks.is_empty() {
+            return;
+        }
+
+        let body = match serde_json::to_vec(&WebhookPayload::from(&event)) {
+            Ok(body) => Bytes::from(body),
+            Err(err) => {
+                tracing::error!("Failed to serialize webhook payload: {}", err);
+                return;
+            }
+        };
+        let event_id = Uuid::new_v4();
+
+        for webhook in webhooks {
+            let dispatcher = self.clone();
+            let body = body.clone();
+            tokio::spawn(async move { dispatcher.deliver(&webhook, event_id, body).await });
+        }
+    }
+
+    /// Доставляет тело одной подписке, повторяя при неудаче.
+    ///
+    /// Удачей считается только ответ 2xx. Ошибка соединения, таймаут
+    /// и любой другой статус — повтор, пока не кончатся попытки.
+    async fn deliver(&self, webhook: &Webhook, event_id: Uuid, body: Bytes) {
+        let signature = sign(&webhook.secret, &body);
+
+        let mut retries = 0;
+        loop {
+            let request = Request::post(webhook.url.as_str())
+                .header(CONTENT_TYPE, "application/json")
+                .header(SIGNATURE_HEADER, signature.as_str())
+                .header(EVENT_ID_HEADER, event_id.to_string())
+                .body(Full::new(body.clone()));
+            let request = match request {
+                Ok(request) => request,
+                Err(err) => {
+                    // Неверный URL повтор не исправит
+                    tracing::error!("Webhook {} has an invalid URL: {}", webhook.id, err);
+                    return;
+                }
+            };
+
+            let failure =
+                match tokio::time::timeout(self.policy.timeout, self.client.request(request)).await
+                {
+                    Ok(Ok(response)) if response.status().is_success() => return,
+                    Ok(Ok(response)) => format!("status {}", response.status()),
+                    Ok(Err(err)) => err.to_string(),
+                    Err(_) => format!("no response in {:?}", self.policy.timeout),
+                };
+
+            if retries >= self.policy.max_retries {
+                tracing::error!(
+                    "Webhook {} delivery of event {} failed after {} attempts: {}",
+                    webhook.id,
+                    event_id,
+                    retries + 1,
+                    failure
+                );
+                return;
+            }
+
+            let delay = self.policy.base_delay * 2u32.saturating_pow(retries);
+            retries += 1;
+            tracing::warn!(
+                "Webhook {} delivery failed ({}), retry {}/{} in {:?}",
+                webhook.id,
+                failure,
+                retries,
+                self.policy.max_retries,
+                delay
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+impl<W> EventPublisher for WebhookDispatcher<W>
+where
+    W: WebhookRepository + Clone + Sync + 'static,
+{
+    /// Запускает рассылку в фоне и сразу возвращается.
+    fn publish(&self, event: BalanceChanged) {
+        let dispatcher = self.clone();
+        tokio::spawn(async move { dispatcher.dispatch(event).await });
+    }
+}
+
+/// Подпись тела: `sha256=` и HMAC-SHA256 в hex.
+///
+/// Получатель считает HMAC от тела как есть (до разбора JSON) со своим
+/// экземпляром секрета и сравнивает с заголовком.
+fn sign(secret: &str, body: &[u8]) -> String {
+    // new_from_slice падает только на ключах недопустимой длины,
+    // а HMAC принимает ключ любой длины
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
@@ -5,21 +5,40 @@
 //! 2. Подключение к БД
 //! 3. Создание зависимостей (Dependency Injection)
 //! 4. Запуск HTTP сервера
+//! 5. Graceful shutdown по SIGINT/SIGTERM
 
 mod application;
 mod domain;
 mod infrastructure;
 mod presentation;
 
-use sqlx::postgres::PgPoolOptions;
-use tower_http::cors::{Any, CorsLayer};
-use tower_http::trace::TraceLayer;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::middleware::from_fn_with_state;
+use tokio::sync::watch;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-use crate::application::services::AccountService;
-use crate::infrastructure::config::Config;
-use crate::infrastructure::database::PostgresAccountRepository;
+use crate::application::services::{
+    AccountService, BackupService, RecurringService, WalletService, WebhookService,
+};
+use crate::domain::value_objects::Currency;
+use crate::infrastructure::clock::SystemClock;
+use crate::infrastructure::config::{load_env_file, Args, Config, LogConfig, LogFormat};
+use crate::infrastructure::database::{
+    run_migrations, wait_for_database, AnyAccountRepository, AnyBackupRepository,
+    AnyIdempotencyRepository, AnyRecurringRepository, AnySnapshotRepository,
+    AnyTransactionRepository, AnyWalletRepository, AnyWebhookRepository, DatabaseKind,
+    DatabasePool, PoolSettings, RetryPolicy, RetryingAccountRepository,
+};
+use crate::infrastructure::exchange_rates::StaticExchangeRateProvider;
+use crate::infrastructure::webhooks::WebhookDispatcher;
+use crate::presentation::api::middleware::{
+    install_metrics_recorder, track_in_flight, ApiKeys, InFlightRequests, RateLimiter,
+};
 use crate::presentation::api::routes::create_router;
+use crate::presentation::scheduler::run_scheduler;
 
 /// Точка входа — async main с tokio runtime.
 ///
@@ -39,64 +58,213 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // ═══════════════════════════════════════════════════════════════
     // 1. Загрузка переменных окружения из .env файла
     // ═══════════════════════════════════════════════════════════════
-    // .ok() — игнорируем ошибку если файла нет
-    dotenvy::dotenv().ok();
+    // До логирования: RUST_LOG тоже может быть в .env.
+    // Нет файла — не ошибка; битый файл или неверный --config — ошибка
+    let args = Args::parse(std::env::args().skip(1))?;
+    let env_file = load_env_file(args.config_path.as_deref()).map_err(|e| {
+        format!(
+            "Failed to load {}: {}",
+            args.config_path
+                .as_deref()
+                .map_or(".env".into(), |path| path.display().to_string()),
+            e
+        )
+    })?;
 
     // ═══════════════════════════════════════════════════════════════
     // 2. Инициализация логирования (tracing)
     // ═══════════════════════════════════════════════════════════════
-    tracing_subscriber::registry()
+    let log_config = LogConfig::from_env();
+    let registry = tracing_subscriber::registry()
         // EnvFilter — фильтрует логи по уровню
         // "info,sqlx=warn" — всё на уровне INFO, но sqlx только WARN
-        .with(tracing_subscriber::EnvFilter::new(
-            std::env::var("RUST_LOG").unwrap_or_else(|_| "info,sqlx=warn".into()),
-        ))
+        .with(tracing_subscriber::EnvFilter::new(&log_config.filter));
+    match log_config.format {
         // fmt::layer — форматирует логи для консоли
-        .with(tracing_subscriber::fmt::layer())
-        .init();
+        LogFormat::Pretty => registry.with(tracing_subscriber::fmt::layer()).init(),
+        // Одна строка — один JSON-объект. flatten_event — message и поля
+        // события на верхнем уровне, current_span — поля span'а запроса
+        // (request_id, method, path) в "span"
+        LogFormat::Json => registry
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .json()
+                    .flatten_event(true)
+                    .with_current_span(true)
+                    .with_span_list(false),
+            )
+            .init(),
+    }
+
+    if let Some(raw) = &log_config.invalid_format {
+        tracing::warn!("Invalid LOG_FORMAT='{}', using pretty", raw);
+    }
+    if let Some(path) = env_file {
+        tracing::info!("Loaded environment from {}", path.display());
+    }
 
     // ═══════════════════════════════════════════════════════════════
     // 3. Загрузка конфигурации
     // ═══════════════════════════════════════════════════════════════
     let config = Config::from_env()?;
+    tracing::info!(
+        "Starting finance-tracker {} on {}{}, database {}, log level '{}'",
+        env!("CARGO_PKG_VERSION"),
+        config.server_addr(),
+        config.api_base_path,
+        config.redacted_database_url(),
+        log_config.filter
+    );
+
+    // До подключения к БД: неверный код не должен ждать DB_CONNECT_RETRIES
+    let default_currency = Currency::parse(&config.default_currency)
+        .map_err(|e| format!("Invalid DEFAULT_CURRENCY: {}", e.reason()))?;
+    let api_keys =
+        ApiKeys::from_hashes(&config.api_keys).map_err(|e| format!("Invalid API_KEYS: {}", e))?;
+    if api_keys.is_empty() {
+        tracing::warn!("API_KEYS is not set: the API is open to anyone who can reach it");
+    }
+    let read_only = args.read_only || config.read_only;
+    if read_only {
+        tracing::warn!(
+            "READ-ONLY MODE: the API rejects writes with 503 and recurring transactions are not applied"
+        );
+    }
+
+    let database_kind = DatabaseKind::from_url(&config.database_url)
+        .ok_or("DATABASE_URL must start with postgres:// or sqlite:")?;
 
     // ═══════════════════════════════════════════════════════════════
-    // 4. Создание пула соединений с PostgreSQL
+    // 4. Создание пула соединений (PostgreSQL или SQLite по DATABASE_URL)
     // ═══════════════════════════════════════════════════════════════
-    let pool = PgPoolOptions::new()
-        .max_connections(5) // Максимум 5 соединений в пуле
-        .connect(&config.database_url)
-        .await?;
+    // Сначала ждём, пока PostgreSQL начнёт принимать соединения: понятная
+    // ошибка после DB_CONNECT_RETRIES попыток вместо голого PoolTimedOut.
+    // Файлу SQLite ждать некого
+    if database_kind == DatabaseKind::Postgres {
+        wait_for_database(
+            &config.database_url,
+            config.db_connect_retries,
+            config.db_connect_retry_delay(),
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+
+    let pool = DatabasePool::connect(
+        &config.database_url,
+        &PoolSettings {
+            max_connections: config.db_max_connections,
+            min_connections: config.db_min_connections(),
+            acquire_timeout: config.db_acquire_timeout(),
+            idle_timeout: config.db_idle_timeout(),
+        },
+    )
+    .await?;
+
+    tracing::info!(
+        "Database pool ({:?}): max {} connections, min {}, acquire timeout {:?}, idle timeout {:?}",
+        database_kind,
+        config.db_max_connections,
+        config.db_min_connections(),
+        config.db_acquire_timeout(),
+        config.db_idle_timeout()
+    );
 
     // ═══════════════════════════════════════════════════════════════
     // 5. Применение миграций БД
     // ═══════════════════════════════════════════════════════════════
-    // sqlx::migrate! — макрос, который включает миграции в бинарник
-    // на этапе компиляции. Путь относительно Cargo.toml.
-    sqlx::migrate!("./migrations").run(&pool).await?;
-
-    tracing::info!("Database connected and migrations applied");
+    // До роутера: сервер не должен отвечать на запросы при неполной схеме,
+    // поэтому ошибка миграции останавливает старт
+    if args.skip_migrations {
+        tracing::warn!("Skipping database migrations (--skip-migrations)");
+    } else {
+        let applied = run_migrations(&pool)
+            .await
+            .map_err(|e| format!("Failed to apply database migrations: {}", e))?;
+        for migration in &applied {
+            tracing::info!(
+                "Applied migration {} ({})",
+                migration.version,
+                migration.description
+            );
+        }
+        tracing::info!(
+            "Database schema is up to date ({} new migrations)",
+            applied.len()
+        );
+    }
 
     // ═══════════════════════════════════════════════════════════════
     // 6. Dependency Injection — создание графа зависимостей
     // ═══════════════════════════════════════════════════════════════
     // Порядок важен: Repository → Service → Router
-    let repository = PostgresAccountRepository::new(pool);
-    let service = AccountService::new(repository);
+    // Обрыв соединения при чтении счёта — повтор, а не сразу 500
+    let repository = RetryingAccountRepository::new(
+        AnyAccountRepository::new(&pool),
+        RetryPolicy {
+            max_retries: config.db_max_retries,
+            ..RetryPolicy::default()
+        },
+    );
+    let transactions = AnyTransactionRepository::new(&pool);
+    let snapshots = AnySnapshotRepository::new(&pool);
+    let idempotency = AnyIdempotencyRepository::new(&pool);
+    // Курсы валют для переводов; без EXCHANGE_RATES возможны только
+    // переводы в одной валюте
+    let rates =
+        StaticExchangeRateProvider::from_json(config.exchange_rates.as_deref().unwrap_or("{}"))
+            .map_err(|e| format!("Invalid EXCHANGE_RATES: {}", e))?;
+    tracing::info!("Loaded {} exchange rates", rates.pair_count());
+    // События об изменении баланса уходят подписчикам в фоне
+    let events = WebhookDispatcher::new(AnyWebhookRepository::new(&pool));
+    let service = AccountService::new(
+        repository,
+        transactions,
+        snapshots,
+        idempotency,
+        rates,
+        events,
+        Arc::new(SystemClock),
+    )
+    .with_max_accounts(config.max_accounts)
+    .with_rounding(config.rounding_mode)
+    .with_precision_mode(config.precision_mode)
+    .with_default_currency(default_currency);
+    let backup = BackupService::new(AnyBackupRepository::new(&pool));
+    // RecurringService исполняет правила через тот же AccountService
+    let recurring = RecurringService::new(service.clone(), AnyRecurringRepository::new(&pool));
+    let webhooks = WebhookService::new(AnyWebhookRepository::new(&pool));
+    let wallets = WalletService::new(AnyWalletRepository::new(&pool), Arc::new(SystemClock))
+        .with_precision(config.precision_mode, config.rounding_mode);
 
     // ═══════════════════════════════════════════════════════════════
     // 7. Создание роутера с middleware
     // ═══════════════════════════════════════════════════════════════
-    let app = create_router(service)
-        // TraceLayer — логирует все HTTP запросы
-        .layer(TraceLayer::new_for_http())
-        // CorsLayer — разрешает cross-origin запросы (для фронтенда)
-        .layer(
-            CorsLayer::new()
-                .allow_origin(Any) // Разрешить любой origin
-                .allow_methods(Any) // Разрешить любые методы
-                .allow_headers(Any), // Разрешить любые headers
-        );
+    let in_flight = InFlightRequests::default();
+    // Глобальный рекордер: после этого metrics::counter! и др. начинают писать
+    let metrics = install_metrics_recorder()?;
+    let rate_limiter = RateLimiter::new(config.rate_limit_per_minute, config.trust_forwarded_for);
+    // Раз в минуту выбрасываем вёдра ушедших клиентов
+    tokio::spawn(rate_limiter.clone().run_cleanup(Duration::from_secs(60)));
+    let app = create_router(
+        service,
+        backup,
+        recurring.clone(),
+        webhooks,
+        wallets,
+        pool.clone(),
+        metrics,
+        &config.cors_allowed_origins,
+        config.max_request_body_bytes,
+        rate_limiter,
+        api_keys,
+        config.request_timeout(),
+        &config.api_base_path,
+        config.response_compression,
+        read_only,
+    )
+    // Считаем активные запросы — их дожидаемся при остановке
+    .layer(from_fn_with_state(in_flight.clone(), track_in_flight));
 
     // ═══════════════════════════════════════════════════════════════
     // 8. Запуск HTTP сервера
@@ -105,8 +273,102 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let listener = tokio::net::TcpListener::bind(&addr).await?;
     tracing::info!("Server running on http://{}", addr);
 
-    // axum::serve — запускает сервер и блокирует до завершения
-    axum::serve(listener, app).await?;
+    // watch-канал передаёт "сигнал получен" и число запросов на тот момент
+    let (shutdown_tx, mut shutdown_rx) = watch::channel(None::<usize>);
+    let drained_rx = shutdown_tx.subscribe();
+
+    // Планировщик повторяющихся операций останавливается по тому же сигналу.
+    // Он пишет (исполняет правила), поэтому в режиме чтения не запускается —
+    // пропущенные сроки он наверстает после обслуживания
+    let scheduler = (!read_only).then(|| {
+        tokio::spawn(run_scheduler(
+            recurring,
+            config.scheduler_interval(),
+            shutdown_tx.subscribe(),
+        ))
+    });
+
+    // with_graceful_shutdown — после сигнала сервер перестаёт принимать
+    // новые соединения и ждёт завершения уже начатых запросов
+    let signal_in_flight = in_flight.clone();
+    // ConnectInfo — адрес клиента для лимита запросов (см. middleware::rate_limit)
+    let app = app.into_make_service_with_connect_info::<SocketAddr>();
+    let server = axum::serve(listener, app).with_graceful_shutdown(async move {
+        shutdown_signal().await;
+        let pending = signal_in_flight.current();
+        tracing::info!(
+            "Shutdown signal received, draining {} in-flight requests",
+            pending
+        );
+        let _ = shutdown_tx.send(Some(pending));
+    });
+
+    // Ждём активные запросы не дольше SHUTDOWN_TIMEOUT_SECS после сигнала
+    let shutdown_timeout = config.shutdown_timeout();
+    let drain_deadline = async move {
+        // Err — сервер завершился без сигнала; тогда select! уже выбрал server
+        if shutdown_rx.changed().await.is_ok() {
+            tokio::time::sleep(shutdown_timeout).await;
+        }
+    };
+
+    // select! — ждём то, что завершится первым
+    tokio::select! {
+        result = server => result?,
+        _ = drain_deadline => tracing::warn!(
+            "Shutdown timeout ({:?}) exceeded, abandoning {} in-flight requests",
+            shutdown_timeout,
+            in_flight.current()
+        ),
+    }
+
+    if let Some(pending) = *drained_rx.borrow() {
+        let drained = pending.saturating_sub(in_flight.current());
+        tracing::info!("Drained {} of {} in-flight requests", drained, pending);
+    }
+
+    // Даём планировщику дописать текущий проход, пока пул ещё открыт
+    if let Some(scheduler) = scheduler {
+        if let Err(err) = scheduler.await {
+            tracing::error!("Scheduler task failed: {}", err);
+        }
+    }
+
+    // Закрываем пул — соединения с БД корректно завершаются
+    pool.close().await;
+    tracing::info!("Server stopped");
 
     Ok(())
 }
+
+/// Future, которая завершается при получении SIGINT (Ctrl+C) или SIGTERM.
+///
+/// # Почему два сигнала?
+/// - SIGINT — Ctrl+C в терминале
+/// - SIGTERM — так останавливают процесс Docker/Kubernetes/systemd
+///
+/// SIGTERM есть только на Unix, поэтому `#[cfg(unix)]`.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    // На других платформах — future, которая никогда не завершится
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
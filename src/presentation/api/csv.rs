@@ -0,0 +1,106 @@
+//! Формат CSV для выгрузки журнала счёта (RFC 4180).
+//!
+//! Своя маленькая реализация вместо отдельного crate: выгружается
+//! одна таблица с шестью колонками, а правила экранирования умещаются
+//! в одну функцию (`field`).
+
+use std::borrow::Cow;
+
+use crate::application::dto::LedgerEntry;
+use crate::domain::value_objects::Currency;
+
+/// Строка заголовков; строки разделяются `\r\n`, как требует RFC 4180.
+pub const HEADER: &str = "timestamp,kind,amount,balance_after,note,category\r\n";
+
+/// Строка CSV для одной операции, с `\r\n` в конце.
+///
+/// Суммы — в единицах валюты счёта (`Money::format_in`): 1050 иен — `"1050"`,
+/// 10.50 долларов — `"10.50"`, 1.05 динара BHD — `"1.050"`.
+/// `amount` всегда положительная, направление видно по `kind`.
+pub fn row(entry: &LedgerEntry, currency: &Currency) -> String {
+    let transaction = &entry.transaction;
+    format!(
+        "{},{},{},{},{},{}\r\n",
+        transaction.timestamp,
+        transaction.kind.as_str(),
+        transaction.amount.format_in(currency),
+        entry.balance_after.format_in(currency),
+        field(transaction.note.as_deref().unwrap_or_default()),
+        field(transaction.category.as_deref().unwrap_or_default()),
+    )
+}
+
+/// Экранирует текстовое поле.
+///
+/// - Запятая, кавычка или перевод строки — поле берётся в кавычки,
+///   кавычки внутри удваиваются: `Кофе, "большой"` → `"Кофе, ""большой"""`
+/// - Начинается с `=`, `+`, `-`, `@` — спереди добавляется `'`
+///
+/// # Зачем апостроф?
+/// Заметку пишет пользователь, а таблицу открывает Excel. Поле
+/// `=HYPERLINK(...)` он выполнит как формулу (CSV injection).
+/// С апострофом ячейка остаётся текстом.
+fn field(value: &str) -> Cow<'_, str> {
+    let value: Cow<'_, str> = if value.starts_with(['=', '+', '-', '@']) {
+        Cow::Owned(format!("'{}", value))
+    } else {
+        Cow::Borrowed(value)
+    };
+
+    if value.contains([',', '"', '\r', '\n']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::domain::entities::{Transaction, TransactionKind};
+    use crate::domain::value_objects::Money;
+
+    /// Пополнение на `amount` (строка суммы, как в API) с остатком `balance_after`.
+    fn entry(amount: &str, balance_after: &str, note: Option<&str>) -> LedgerEntry {
+        let amount = amount.parse::<Money>().unwrap().cents();
+        let at = Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+        let transaction = Transaction::new(
+            Uuid::new_v4(),
+            TransactionKind::Deposit,
+            amount,
+            note.map(String::from),
+            at,
+        );
+
+        LedgerEntry {
+            transaction: transaction.into(),
+            balance_after: balance_after.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn amounts_use_currency_decimal_places() {
+        let jpy = row(&entry("1050", "2000", None), &Currency::new("JPY"));
+        assert_eq!(jpy, "2024-01-15T12:00:00+00:00,deposit,1050,2000,,\r\n");
+
+        let bhd = row(&entry("1.05", "3.2", None), &Currency::new("BHD"));
+        assert_eq!(bhd, "2024-01-15T12:00:00+00:00,deposit,1.050,3.200,,\r\n");
+
+        let usd = row(&entry("10.5", "10.5", None), &Currency::new("USD"));
+        assert_eq!(usd, "2024-01-15T12:00:00+00:00,deposit,10.50,10.50,,\r\n");
+    }
+
+    #[test]
+    fn notes_are_quoted_and_defused() {
+        let usd = Currency::new("USD");
+
+        let quoted = row(&entry("1", "1", Some("Кофе, \"большой\"\nи булка")), &usd);
+        assert!(quoted.ends_with(",\"Кофе, \"\"большой\"\"\nи булка\",\r\n"));
+
+        let formula = row(&entry("1", "1", Some("=HYPERLINK(\"x\")")), &usd);
+        assert!(formula.ends_with(",\"'=HYPERLINK(\"\"x\"\")\",\r\n"));
+    }
+}
@@ -5,20 +5,33 @@
 //! - Доменные ошибки → понятные HTTP коды
 //! - Технические ошибки → 500 без деталей (безопасность)
 
+use std::collections::HashMap;
+
 use axum::{
-    http::StatusCode,
+    extract::rejection::JsonRejection,
+    http::{header, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
-use serde_json::json;
+use serde::Serialize;
+use uuid::Uuid;
 
-use crate::application::services::AccountServiceError;
+use crate::application::services::{AccountServiceError, BackupServiceError};
 use crate::domain::errors::DomainError;
+use crate::domain::value_objects::Money;
+use crate::infrastructure::database::DbErrorKind;
+use crate::presentation::api::middleware::current_request_id;
 
 /// Структура для HTTP ошибок API.
+///
+/// `fields` — причины по полям запроса (см. `with_fields`);
+/// у большинства ошибок их нет. `retry_after` — секунды для заголовка
+/// `Retry-After` (см. `with_retry_after`).
 pub struct ApiError {
     status: StatusCode,
     message: String,
+    fields: Option<HashMap<String, String>>,
+    retry_after: Option<u64>,
 }
 
 impl ApiError {
@@ -27,14 +40,41 @@ impl ApiError {
         Self {
             status,
             message: message.into(),
+            fields: None,
+            retry_after: None,
         }
     }
 
+    /// Добавляет причины по полям: `{"currency": "...", "name": "..."}`.
+    ///
+    /// `message` остаётся сводкой для человека, а по `fields`
+    /// форма в UI подсвечивает неверные поля.
+    pub fn with_fields(mut self, fields: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.fields = Some(fields.into_iter().collect());
+        self
+    }
+
+    /// Добавляет `Retry-After: <secs>` — через сколько повторить запрос.
+    pub fn with_retry_after(mut self, secs: u64) -> Self {
+        self.retry_after = Some(secs);
+        self
+    }
+
     /// 500 Internal Server Error
     pub fn internal(message: impl Into<String>) -> Self {
         Self::new(StatusCode::INTERNAL_SERVER_ERROR, message)
     }
 
+    /// 401 Unauthorized
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::UNAUTHORIZED, message)
+    }
+
+    /// 403 Forbidden
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::FORBIDDEN, message)
+    }
+
     /// 404 Not Found
     pub fn not_found(message: impl Into<String>) -> Self {
         Self::new(StatusCode::NOT_FOUND, message)
@@ -49,6 +89,47 @@ impl ApiError {
     pub fn conflict(message: impl Into<String>) -> Self {
         Self::new(StatusCode::CONFLICT, message)
     }
+
+    /// 413 Payload Too Large
+    pub fn payload_too_large(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::PAYLOAD_TOO_LARGE, message)
+    }
+
+    /// 422 Unprocessable Entity
+    pub fn unprocessable(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::UNPROCESSABLE_ENTITY, message)
+    }
+
+    /// 429 Too Many Requests
+    pub fn too_many_requests(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::TOO_MANY_REQUESTS, message)
+    }
+
+    /// 503 Service Unavailable
+    pub fn service_unavailable(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::SERVICE_UNAVAILABLE, message)
+    }
+
+    /// 504 Gateway Timeout
+    pub fn gateway_timeout(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::GATEWAY_TIMEOUT, message)
+    }
+}
+
+/// Тело ответа с ошибкой: `{"error": "...", "request_id": "..."}`.
+///
+/// Отдельная структура, а не `json!`, чтобы описать ту же форму
+/// в спецификации OpenAPI — документация не разойдётся с ответом.
+///
+/// `fields` есть только у ошибок проверки полей, у остальных
+/// ключа в JSON нет совсем — прежние ответы не меняются.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ErrorResponse {
+    pub error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<HashMap<String, String>>,
+    pub request_id: Option<Uuid>, // Клиент может приложить его к баг-репорту
 }
 
 /// Trait IntoResponse — как конвертировать ApiError в HTTP response.
@@ -56,25 +137,126 @@ impl ApiError {
 /// Axum автоматически вызывает этот метод когда handler возвращает Err(ApiError).
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
-        // Создаём JSON body: {"error": "message"}
-        let body = json!({
-            "error": self.message
-        });
+        let body = ErrorResponse {
+            error: self.message,
+            fields: self.fields,
+            request_id: current_request_id(),
+        };
 
         // Возвращаем tuple (StatusCode, Json) — Axum понимает этот формат
-        (self.status, Json(body)).into_response()
+        let mut response = (self.status, Json(body)).into_response();
+        if let Some(secs) = self.retry_after {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, secs.into());
+        }
+        response
     }
 }
 
+/// Конвертация отказа `axum::Json` в ApiError (см. `extractors::Json`).
+///
+/// # Маппинг
+/// - Значение не того типа / не прошло проверку → 400 `invalid <поле>: <причина>`
+///   и `fields: {"<поле>": "<причина>"}`
+/// - Нет обязательного поля → 400 `missing field `amount``
+/// - Сломанный JSON → 400 `malformed JSON: <причина> at line 1 column 5`
+/// - Тело больше лимита → 413
+/// - Нет `Content-Type: application/json` → 415 (текст Axum)
+///
+/// # Почему не `rejection.body_text()`?
+/// Там текст serde с префиксом Axum и позицией в строке:
+/// `Failed to deserialize ...: amount: Invalid amount ... at line 1 column 17`.
+/// Клиенту полезнее имя поля и причина — их достаём из
+/// `serde_path_to_error::Error`, который лежит внутри отказа.
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        match &rejection {
+            JsonRejection::JsonDataError(_) => match serde_error(&rejection) {
+                Some(err) => {
+                    let reason = without_position(err.inner());
+                    let path = err.path().to_string();
+                    // "." — корень: ошибка про объект целиком (например, нет поля)
+                    if path == "." {
+                        ApiError::bad_request(reason)
+                    } else {
+                        ApiError::bad_request(format!("invalid {}: {}", path, reason))
+                            .with_fields([(path, reason)])
+                    }
+                }
+                None => ApiError::bad_request(rejection.body_text()),
+            },
+
+            JsonRejection::JsonSyntaxError(_) => match serde_error(&rejection) {
+                // Здесь позиция полезна — она показывает, где сломан JSON
+                Some(err) => ApiError::bad_request(format!("malformed JSON: {}", err.inner())),
+                None => ApiError::bad_request(rejection.body_text()),
+            },
+
+            _ if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE => {
+                ApiError::payload_too_large("Request body is too large")
+            }
+
+            _ => ApiError::new(rejection.status(), rejection.body_text()),
+        }
+    }
+}
+
+/// Ошибка serde внутри отказа: `JsonRejection` → `axum::Error` → она.
+fn serde_error(
+    rejection: &JsonRejection,
+) -> Option<&serde_path_to_error::Error<serde_json::Error>> {
+    std::error::Error::source(rejection)?
+        .source()?
+        .downcast_ref()
+}
+
+/// Текст ошибки serde_json без хвоста ` at line 1 column 17`.
+fn without_position(err: &serde_json::Error) -> String {
+    let message = err.to_string();
+    let position = format!(" at line {} column {}", err.line(), err.column());
+    message
+        .strip_suffix(&position)
+        .map(str::to_owned)
+        .unwrap_or(message)
+}
+
 /// Конвертация AccountServiceError в ApiError.
 ///
 /// # Маппинг ошибок
 /// - AccountNotFound → 404
 /// - AccountAlreadyExists → 409 Conflict
+/// - AccountNotEmpty → 409 Conflict (удалить можно с `force=true`)
+/// - AccountClosed → 409 Conflict (закрытый счёт не принимает остаток)
+/// - AccountHasRecurring → 409 Conflict (сменить валюту можно с `force=true`)
+/// - ConcurrentModification → 409 Conflict (клиент может повторить запрос)
+/// - QuotaExceeded → 429 Too Many Requests (без `Retry-After`: место
+///   освободится, только когда удалят счёт)
 /// - InsufficientFunds → 400 Bad Request
+/// - LimitExceeded → 400 Bad Request
+/// - BelowMinimumBalance → 400 Bad Request
 /// - InvalidAmount → 400 Bad Request
-/// - Repository errors → 500 (логируем, но не показываем детали)
-impl<E: std::error::Error> From<AccountServiceError<E>> for ApiError {
+/// - PrecisionExceeded → 400 Bad Request
+/// - InvalidSchedule → 400 Bad Request
+/// - InvalidName → 400 Bad Request
+/// - InvalidDescription → 400 Bad Request
+/// - InvalidCurrency → 400 Bad Request
+/// - InvalidFields → 400 Bad Request с `fields`; `error` — причины через "; "
+/// - InvalidCategory → 400 Bad Request
+/// - SameAccountTransfer → 400 Bad Request
+/// - InvalidOverdraftSource → 400 Bad Request
+/// - RateUnavailable → 422 Unprocessable Entity (запрос корректен, но курса нет)
+/// - RecurringNotFound → 404
+/// - TransactionNotFound → 404
+/// - TransactionNotReversible → 409 Conflict (уже отменена или тип не отменяется)
+/// - InvalidWebhook → 400 Bad Request
+/// - WebhookNotFound → 404
+/// - WalletNotFound → 404
+/// - WalletModified → 409 Conflict (клиент может повторить запрос)
+/// - IdempotencyKeyReused → 422 Unprocessable Entity
+/// - IdempotencyKeyInProgress → 409 Conflict (клиент может повторить позже)
+/// - Repository errors → по категории (см. `repository_error`)
+impl<E: std::error::Error + 'static> From<AccountServiceError<E>> for ApiError {
     fn from(err: AccountServiceError<E>) -> Self {
         match err {
             // Доменные ошибки — можно показать пользователю
@@ -88,22 +270,271 @@ impl<E: std::error::Error> From<AccountServiceError<E>> for ApiError {
                 DomainError::InsufficientFunds {
                     available,
                     requested,
+                    currency,
                 } => ApiError::bad_request(format!(
-                    "Insufficient funds: available {:.2}, requested {:.2}",
-                    available as f64 / 100.0,
-                    requested as f64 / 100.0
+                    "Insufficient {}funds: available {}, requested {}",
+                    currency
+                        .map(|code| format!("{} ", code))
+                        .unwrap_or_default(),
+                    Money::from_cents(available),
+                    Money::from_cents(requested)
                 )),
 
+                DomainError::LimitExceeded {
+                    limit,
+                    remaining,
+                    requested,
+                } => ApiError::bad_request(format!(
+                    "Daily withdrawal limit exceeded: limit {}, remaining {}, requested {}",
+                    Money::from_cents(limit),
+                    Money::from_cents(remaining),
+                    Money::from_cents(requested)
+                )),
+
+                DomainError::AccountNotEmpty { balance } => ApiError::conflict(format!(
+                    "Account balance is {}, not zero; pass force=true to delete it anyway",
+                    Money::from_cents(balance)
+                )),
+
+                DomainError::AccountClosed(id) => ApiError::conflict(format!(
+                    "Account {} is closed; unarchive it or choose another account",
+                    id
+                )),
+
+                DomainError::AccountHasRecurring { count } => ApiError::conflict(format!(
+                    "Account has {} active recurring transactions with amounts in the old \
+                     currency; pass force=true to change the currency anyway",
+                    count
+                )),
+
+                DomainError::BelowMinimumBalance { minimum, resulting } => {
+                    ApiError::bad_request(format!(
+                        "Balance would fall below minimum: minimum {}, resulting {}",
+                        Money::from_cents(minimum),
+                        Money::from_cents(resulting)
+                    ))
+                }
+
                 DomainError::InvalidAmount(msg) => ApiError::bad_request(msg),
+
+                DomainError::PrecisionExceeded {
+                    currency,
+                    max_decimals,
+                } => ApiError::bad_request(format!(
+                    "{} accepts at most {} decimal places",
+                    currency, max_decimals
+                )),
+
+                DomainError::InvalidSchedule(msg) => ApiError::bad_request(msg),
+
+                DomainError::InvalidName(msg) => ApiError::bad_request(msg),
+
+                DomainError::InvalidDescription(msg) => ApiError::bad_request(msg),
+
+                DomainError::InvalidCurrency(msg) => ApiError::bad_request(msg),
+
+                DomainError::InvalidFields(fields) => {
+                    // Одно поле — тот же текст, что был бы без `fields`
+                    let summary = fields.values().cloned().collect::<Vec<_>>().join("; ");
+                    ApiError::bad_request(summary).with_fields(fields)
+                }
+
+                DomainError::InvalidCategory(msg) => ApiError::bad_request(msg),
+
+                DomainError::InvalidOverdraftSource(msg) => ApiError::bad_request(msg),
+
+                DomainError::SameAccountTransfer(_) => {
+                    ApiError::bad_request("Cannot transfer to the same account")
+                }
+
+                DomainError::RateUnavailable { from, to } => {
+                    ApiError::unprocessable(format!("No exchange rate from {} to {}", from, to))
+                }
+
+                DomainError::RecurringNotFound(id) => {
+                    ApiError::not_found(format!("Recurring transaction '{}' not found", id))
+                }
+
+                DomainError::TransactionNotFound(id) => {
+                    ApiError::not_found(format!("Transaction '{}' not found", id))
+                }
+
+                DomainError::TransactionNotReversible(msg) => ApiError::conflict(msg),
+
+                DomainError::InvalidWebhook(msg) => ApiError::bad_request(msg),
+
+                DomainError::WebhookNotFound(id) => {
+                    ApiError::not_found(format!("Webhook '{}' not found", id))
+                }
+
+                DomainError::WalletNotFound(id) => {
+                    ApiError::not_found(format!("Wallet '{}' not found", id))
+                }
+
+                DomainError::WalletModified(id) => ApiError::conflict(format!(
+                    "Wallet '{}' was modified concurrently, please retry",
+                    id
+                )),
+
+                DomainError::QuotaExceeded { limit } => ApiError::too_many_requests(format!(
+                    "Account limit of {} reached, delete an account first",
+                    limit
+                )),
+
+                DomainError::ConcurrentModification(id) => ApiError::conflict(format!(
+                    "Account '{}' was modified concurrently, please retry",
+                    id
+                )),
             },
 
-            // Ошибки репозитория — логируем, но клиенту не показываем детали
-            AccountServiceError::Repository(e) => {
-                // tracing::error! — логирует ошибку (видно в консоли сервера)
-                tracing::error!("Repository error: {}", e);
-                // Клиенту отдаём generic сообщение (безопасность!)
-                ApiError::internal("Internal server error")
+            AccountServiceError::IdempotencyKeyReused(_) => {
+                ApiError::unprocessable(err.to_string())
             }
+
+            AccountServiceError::IdempotencyKeyInProgress(_) => ApiError::conflict(err.to_string()),
+
+            // Ошибки репозитория — логируем, но клиенту не показываем детали
+            AccountServiceError::Repository(e) => repository_error(&e),
+        }
+    }
+}
+
+/// Конвертация BackupServiceError в ApiError.
+///
+/// # Маппинг ошибок
+/// - UnsupportedSchemaVersion, InvalidDocument → 400 Bad Request
+/// - NotEmpty → 409 Conflict
+/// - Repository errors → по категории (см. `repository_error`)
+impl<E: std::error::Error + 'static> From<BackupServiceError<E>> for ApiError {
+    fn from(err: BackupServiceError<E>) -> Self {
+        match err {
+            BackupServiceError::UnsupportedSchemaVersion { .. }
+            | BackupServiceError::InvalidDocument(_) => ApiError::bad_request(err.to_string()),
+
+            BackupServiceError::NotEmpty => ApiError::conflict(err.to_string()),
+
+            BackupServiceError::Repository(e) => repository_error(&e),
+        }
+    }
+}
+
+/// Через сколько секунд повторить запрос, если БД временно недоступна.
+///
+/// Чтения к этому времени уже повторены (`DB_MAX_RETRIES`), так что
+/// сбой не мгновенный — но и не долгий: обычно это перезапуск БД
+/// или исчерпанный пул.
+const DATABASE_RETRY_AFTER_SECS: u64 = 1;
+
+/// Ответ на ошибку хранилища по её категории (`DbErrorKind`).
+///
+/// # Маппинг
+/// - NotFound → 404
+/// - UniqueViolation, ForeignKeyViolation → 409 Conflict (данные
+///   изменились между проверкой сервиса и записью)
+/// - Transient → 503 с `Retry-After`
+/// - Other → 500
+///
+/// Детали ошибки — только в лог: текст БД может раскрыть схему.
+/// Ошибка не из sqlx (у хранилищ в памяти их не бывает) — `Other`.
+///
+/// # Почему downcast, а не trait у `E`?
+/// Ошибки хранилища доходят сюда через `AccountServiceError<E>` из всех
+/// сервисов, и trait пришлось бы требовать у каждого их репозитория.
+/// Тип известен и так: в работе это всегда `sqlx::Error`.
+fn repository_error(err: &(dyn std::error::Error + 'static)) -> ApiError {
+    let kind = err
+        .downcast_ref::<sqlx::Error>()
+        .map_or(DbErrorKind::Other, DbErrorKind::of);
+
+    if kind == DbErrorKind::Other {
+        // tracing::error! — логирует ошибку (видно в консоли сервера)
+        tracing::error!("Repository error: {}", err);
+    } else {
+        tracing::warn!("Repository error: {}", err);
+    }
+
+    match kind {
+        DbErrorKind::NotFound => ApiError::not_found("Not found"),
+        DbErrorKind::UniqueViolation => {
+            ApiError::conflict("Conflicts with existing data, please retry")
+        }
+        DbErrorKind::ForeignKeyViolation => {
+            ApiError::conflict("Refers to a record that no longer exists, or is still referenced")
         }
+        DbErrorKind::Transient => {
+            ApiError::service_unavailable("Database is temporarily unavailable, please retry")
+                .with_retry_after(DATABASE_RETRY_AFTER_SECS)
+        }
+        // Клиенту отдаём generic сообщение (безопасность!)
+        DbErrorKind::Other => ApiError::internal("Internal server error"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use super::*;
+    use crate::infrastructure::database::test_support::{
+        closed_pool, foreign_key_violation, syntax_error, unique_violation,
+    };
+
+    fn status(err: AccountServiceError<Infallible>) -> StatusCode {
+        ApiError::from(err).into_response().status()
+    }
+
+    fn repository_response(err: sqlx::Error) -> Response {
+        ApiError::from(AccountServiceError::Repository(err)).into_response()
+    }
+
+    #[test]
+    fn another_users_records_are_not_found() {
+        let id = Uuid::new_v4().to_string();
+        for err in [
+            DomainError::AccountNotFound(id.clone()),
+            DomainError::RecurringNotFound(id.clone()),
+            DomainError::TransactionNotFound(id.clone()),
+            DomainError::WebhookNotFound(id),
+        ] {
+            assert_eq!(status(err.into()), StatusCode::NOT_FOUND);
+        }
+    }
+
+    #[tokio::test]
+    async fn constraint_violations_are_conflicts() {
+        for err in [unique_violation().await, foreign_key_violation().await] {
+            assert_eq!(repository_response(err).status(), StatusCode::CONFLICT);
+        }
+    }
+
+    #[tokio::test]
+    async fn unavailable_database_asks_to_retry() {
+        for err in [closed_pool().await, sqlx::Error::PoolTimedOut] {
+            let response = repository_response(err);
+            assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+            assert_eq!(response.headers()[header::RETRY_AFTER], "1");
+        }
+    }
+
+    #[tokio::test]
+    async fn other_storage_errors_hide_details() {
+        let response = repository_response(sqlx::Error::RowNotFound);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = repository_response(syntax_error().await);
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(response.headers().get(header::RETRY_AFTER).is_none());
+        // Текст SQLite ("syntax error") клиенту не уходит
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"], "Internal server error");
+    }
+
+    #[test]
+    fn concurrent_modification_is_conflict() {
+        let err = DomainError::ConcurrentModification(Uuid::new_v4().to_string());
+        assert_eq!(status(err.into()), StatusCode::CONFLICT);
     }
 }
@@ -0,0 +1,89 @@
+//! Собственные extractors.
+//!
+//! # Зачем свой `Json`?
+//! Стандартный `axum::Json` при ошибке разбора отвечает сам — текстом
+//! вроде `Failed to deserialize the JSON body into the target type: ...`
+//! и не в нашем формате `{"error": "..."}`. Обёртка разбирает тело тем же
+//! `axum::Json`, а отказ превращает в `ApiError` (см. `From<JsonRejection>`).
+
+use std::convert::Infallible;
+
+use axum::extract::{FromRef, FromRequest, FromRequestParts};
+use axum::http::request::Parts;
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
+
+use crate::presentation::api::error::ApiError;
+use crate::presentation::api::handlers::{
+    AppAccountService, AppRecurringService, AppWebhookService,
+};
+use crate::presentation::api::middleware::CurrentUser;
+
+/// JSON-тело запроса или ответа.
+///
+/// # `#[from_request(via(...))]`
+/// Макрос Axum: извлечь значение через `axum::Json`, а отказ
+/// сконвертировать в `ApiError` через `From`. В handlers используется
+/// так же, как `axum::Json`: `Json(request): Json<DepositRequest>`.
+#[derive(FromRequest)]
+#[from_request(via(axum::Json), rejection(ApiError))]
+pub struct Json<T>(pub T);
+
+/// Ответ — как у `axum::Json`, чтобы в handlers был один `Json`.
+impl<T: Serialize> IntoResponse for Json<T> {
+    fn into_response(self) -> Response {
+        axum::Json(self.0).into_response()
+    }
+}
+
+/// Сервис, который видит только счета пользователя запроса.
+///
+/// # Зачем, если есть `State`?
+/// `State(service)` отдал бы сервис пользователя по умолчанию, и каждый
+/// handler должен был бы не забыть вызвать `for_user`. `Scoped(service)`
+/// берёт state и `CurrentUser` (его кладёт `middleware::require_api_key`)
+/// сам — handler получает уже ограниченный сервис.
+pub struct Scoped<T>(pub T);
+
+/// Сервисы, которые умеют ограничиться данными одного пользователя.
+pub trait ForUser {
+    /// Копия сервиса для пользователя `user_id`.
+    fn for_user(&self, user_id: &str) -> Self;
+}
+
+impl ForUser for AppAccountService {
+    fn for_user(&self, user_id: &str) -> Self {
+        AppAccountService::for_user(self, user_id)
+    }
+}
+
+impl ForUser for AppRecurringService {
+    fn for_user(&self, user_id: &str) -> Self {
+        AppRecurringService::for_user(self, user_id)
+    }
+}
+
+impl ForUser for AppWebhookService {
+    fn for_user(&self, user_id: &str) -> Self {
+        AppWebhookService::for_user(self, user_id)
+    }
+}
+
+#[axum::async_trait]
+impl<S, T> FromRequestParts<S> for Scoped<T>
+where
+    S: Send + Sync,
+    T: FromRef<S> + ForUser,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let user = parts
+            .extensions
+            .get::<CurrentUser>()
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(Self(T::from_ref(state).for_user(&user.0)))
+    }
+}
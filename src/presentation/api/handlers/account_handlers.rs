@@ -6,32 +6,109 @@
 //! 3. Возвращает response (Json, StatusCode, или impl IntoResponse)
 
 use axum::{
-    extract::{Path, State},
-    Json,
+    body::Body,
+    extract::{Path, Query},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
 };
+use futures_util::{future, stream, StreamExt};
 use uuid::Uuid;
 
 use crate::application::dto::{
-    AccountResponse, CreateAccountRequest, DepositRequest, MessageResponse, WithdrawRequest,
+    AccountInfoResponse, AccountListQuery, AccountResponse, AccountSearchQuery,
+    AccountSearchResult, ActivityPage, ActivityQuery, ApplyInterestRequest, AuditEntryResponse,
+    BalanceAtQuery, BalanceAtResponse, BalanceSnapshotResponse, BatchCreateAccountsRequest,
+    BatchCreateAccountsResponse, CategoryTotalResponse, CloseAccountRequest, CreateAccountRequest,
+    DeleteAccountQuery, DepositRequest, DryRunQuery, EnsureAccountRequest, MessageResponse,
+    NetWorthQuery, NetWorthResponse, PagedResponse, Pagination, ReconciliationResponse,
+    SimulateRequest, SimulationResponse, StatementQuery, StatementResponse, StatsResponse,
+    TimeRange, TransactionListQuery, TransactionResponse, TransferRequest, TransferResponse,
+    UpdateAccountRequest, UpdateTransactionRequest, WithdrawRequest,
 };
 use crate::application::services::AccountService;
-use crate::infrastructure::database::PostgresAccountRepository;
+use crate::infrastructure::database::{
+    AnyAccountRepository, AnyIdempotencyRepository, AnySnapshotRepository,
+    AnyTransactionRepository, AnyWebhookRepository, RetryingAccountRepository,
+};
+use crate::infrastructure::exchange_rates::StaticExchangeRateProvider;
+use crate::infrastructure::webhooks::WebhookDispatcher;
+use crate::presentation::api::csv;
 use crate::presentation::api::error::ApiError;
+#[cfg(feature = "openapi")]
+use crate::presentation::api::error::ErrorResponse;
+use crate::presentation::api::extractors::{Json, Scoped};
 
 /// Type alias для удобства — конкретный тип нашего сервиса.
-type AppAccountService = AccountService<PostgresAccountRepository>;
+pub type AppAccountService = AccountService<
+    RetryingAccountRepository<AnyAccountRepository>,
+    AnyTransactionRepository,
+    AnySnapshotRepository,
+    AnyIdempotencyRepository,
+    StaticExchangeRateProvider,
+    WebhookDispatcher<AnyWebhookRepository>,
+>;
+
+/// Заголовок, которым клиент помечает повторы одного и того же запроса.
+const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Максимальная длина ключа от клиента (в БД он хранится с пользователем, `user:key`).
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// Достаёт `Idempotency-Key` из заголовков.
+///
+/// # Возвращает
+/// - `Ok(None)` — заголовка нет
+/// - `Err(400)` — ключ пустой, слишком длинный или не ASCII
+fn idempotency_key(headers: &HeaderMap) -> Result<Option<String>, ApiError> {
+    let Some(value) = headers.get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(None);
+    };
+
+    let key = value
+        .to_str()
+        .map_err(|_| ApiError::bad_request("Idempotency-Key must be ASCII"))?;
+
+    if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+        return Err(ApiError::bad_request(format!(
+            "Idempotency-Key must be 1 to {} characters",
+            MAX_IDEMPOTENCY_KEY_LEN
+        )));
+    }
+
+    Ok(Some(key.to_owned()))
+}
 
 /// POST /api/accounts — создание нового счёта.
 ///
 /// # Extractors
-/// - `State(service)` — извлекает shared state (наш сервис)
+/// - `Scoped(service)` — извлекает shared state (наш сервис) для пользователя запроса
 /// - `Json(request)` — парсит JSON body в структуру
 ///
 /// # Возвращает
 /// - `Ok(Json<AccountResponse>)` — 200 с данными счёта
-/// - `Err(ApiError)` — ошибка (400, 409, 500)
+/// - `Err(ApiError)` — ошибка (400, 409, 429, 500)
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/accounts",
+        summary = "Create an account",
+        description = "Names are trimmed and must be unique (case-insensitive), 1-100 characters. \
+            The currency must be a 3-letter code; it is stored uppercase. Without it the \
+            account gets `DEFAULT_CURRENCY` (USD unless configured). \
+            All invalid fields are reported together in `fields`.",
+        tag = "accounts",
+        request_body = CreateAccountRequest,
+        responses(
+            (status = 200, description = "Account created", body = AccountResponse),
+            (status = 400, description = "Invalid name, currency or credit limit", body = ErrorResponse),
+            (status = 409, description = "Name is already taken", body = ErrorResponse),
+            (status = 429, description = "MAX_ACCOUNTS reached", body = ErrorResponse),
+        ),
+    )
+)]
 pub async fn create_account(
-    State(service): State<AppAccountService>,
+    Scoped(service): Scoped<AppAccountService>,
     Json(request): Json<CreateAccountRequest>,
 ) -> Result<Json<AccountResponse>, ApiError> {
     // Вызываем use case
@@ -40,12 +117,107 @@ pub async fn create_account(
     Ok(Json(account))
 }
 
-/// GET /api/accounts — получение списка всех счетов.
+/// POST /api/accounts/batch — создание нескольких счетов.
+///
+/// # Возвращает
+/// - 200 с результатом по каждому счёту (даже если часть не создана)
+/// - 400 если счетов нет или больше `BatchCreateAccountsRequest::MAX_ITEMS`
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/accounts/batch",
+        summary = "Create several accounts",
+        description = "With `atomic` (default true) nothing is created if any item fails.",
+        tag = "accounts",
+        request_body = BatchCreateAccountsRequest,
+        responses(
+            (status = 200, description = "Per-item results, even if some items failed", body = BatchCreateAccountsResponse),
+            (status = 400, description = "Empty batch or more than 100 accounts", body = ErrorResponse),
+            (status = 429, description = "The valid accounts do not fit in MAX_ACCOUNTS", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn create_accounts(
+    Scoped(service): Scoped<AppAccountService>,
+    Json(request): Json<BatchCreateAccountsRequest>,
+) -> Result<Json<BatchCreateAccountsResponse>, ApiError> {
+    request.validate().map_err(ApiError::bad_request)?;
+
+    let batch = service.create_accounts(request).await?;
+    Ok(Json(batch))
+}
+
+/// GET /api/accounts?limit=&offset=&currency=&name_contains=&sort= — страница списка счетов.
+///
+/// # Query extractor
+/// `Query(pagination)` парсит query string в структуру `Pagination`.
+/// Отсутствующие параметры становятся `None` → значения по умолчанию.
+/// Второй `Query` разбирает ту же строку в `AccountListQuery` —
+/// лишние для каждой структуры параметры serde игнорирует.
+///
+/// # Возвращает
+/// - 200 с `PagedResponse`
+/// - 400 если `limit` больше `Pagination::MAX_LIMIT`, `offset` < 0
+///   или `sort` не из списка
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/accounts",
+        summary = "List accounts",
+        description = "Paginated, filtered by currency and name substring, sorted by `name`, `balance` or `created_at` (`-` prefix for descending). Archived accounts are left out unless `include_archived=true`.",
+        tag = "accounts",
+        params(Pagination, AccountListQuery),
+        responses(
+            (status = 200, description = "Page of accounts", body = PagedResponse<AccountResponse>),
+            (status = 400, description = "Invalid limit, offset or sort", body = ErrorResponse),
+        ),
+    )
+)]
 pub async fn get_accounts(
-    State(service): State<AppAccountService>,
-) -> Result<Json<Vec<AccountResponse>>, ApiError> {
-    let accounts = service.get_all_accounts().await?;
-    Ok(Json(accounts))
+    Scoped(service): Scoped<AppAccountService>,
+    Query(pagination): Query<Pagination>,
+    Query(query): Query<AccountListQuery>,
+) -> Result<Json<PagedResponse<AccountResponse>>, ApiError> {
+    pagination.validate().map_err(ApiError::bad_request)?;
+    let filter = query.to_filter().map_err(ApiError::bad_request)?;
+
+    let page = service.get_accounts(&filter, &pagination).await?;
+    Ok(Json(page))
+}
+
+/// GET /api/accounts/search?q=&limit= — поиск счетов по имени.
+///
+/// # Возвращает
+/// - 200 со списком: сначала точные совпадения, затем по префиксу,
+///   затем по подстроке (`score` 3, 2 и 1)
+/// - 400 если `q` пустая или `limit` вне `1..=AccountSearchQuery::MAX_LIMIT`
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/accounts/search",
+        summary = "Search accounts by name",
+        description = "Case-insensitive search among active, non-archived accounts whose name \
+            contains `q`. Exact matches come first (`score` 3), then names starting with `q` \
+            (2), then names containing it (1); ties are ordered by name. Default limit 10, max 50.",
+        tag = "accounts",
+        params(AccountSearchQuery),
+        responses(
+            (status = 200, description = "Matching accounts, best first", body = Vec<AccountSearchResult>),
+            (status = 400, description = "Empty query or invalid limit", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn search_accounts(
+    Scoped(service): Scoped<AppAccountService>,
+    Query(query): Query<AccountSearchQuery>,
+) -> Result<Json<Vec<AccountSearchResult>>, ApiError> {
+    let (q, limit) = query.validate().map_err(ApiError::bad_request)?;
+
+    let results = service.search_accounts(q, limit).await?;
+    Ok(Json(results))
 }
 
 /// GET /api/accounts/:id — получение счёта по ID.
@@ -53,39 +225,1004 @@ pub async fn get_accounts(
 /// # Path extractor
 /// `Path(id)` извлекает `:id` из URL и парсит как Uuid.
 /// Если ID невалидный — Axum автоматически вернёт 400.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/accounts/{id}",
+        summary = "Get an account",
+        description = "Get an account.",
+        tag = "accounts",
+        params(("id" = Uuid, Path, description = "Account ID")),
+        responses(
+            (status = 200, description = "Account", body = AccountResponse),
+            (status = 404, description = "Account not found", body = ErrorResponse),
+        ),
+    )
+)]
 pub async fn get_account(
-    State(service): State<AppAccountService>,
+    Scoped(service): Scoped<AppAccountService>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<AccountResponse>, ApiError> {
     let account = service.get_account(id).await?;
     Ok(Json(account))
 }
 
+/// GET /api/accounts/by-name/:name — получение счёта по имени.
+///
+/// Имя без учёта регистра; в ответе — имя в том виде, в каком сохранено.
+/// Ищутся только активные счета (удалённый счёт с тем же именем — нет).
+/// Пробелы и не-ASCII символы в URL кодируются: `/by-name/My%20Wallet`.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/accounts/by-name/{name}",
+        summary = "Get an account by name",
+        description = "Case-insensitive lookup among active accounts; the response has the \
+            name as stored. A deleted account with the same name is not returned.",
+        tag = "accounts",
+        params(("name" = String, Path, description = "Account name (URL-encoded)")),
+        responses(
+            (status = 200, description = "Account", body = AccountResponse),
+            (status = 404, description = "No active account with this name", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn get_account_by_name(
+    Scoped(service): Scoped<AppAccountService>,
+    Path(name): Path<String>,
+) -> Result<Json<AccountResponse>, ApiError> {
+    let account = service.get_account_by_name(&name).await?;
+    Ok(Json(account))
+}
+
+/// PUT /api/accounts/by-name/:name — счёт с этим именем, существующий или новый.
+///
+/// # Возвращает
+/// - 200 с существующим счётом (тело запроса не используется)
+/// - 201 с только что созданным
+/// - 400 если имя или валюта некорректны (только при создании)
+/// - 429 если создание упирается в `MAX_ACCOUNTS`
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        put,
+        path = "/api/accounts/by-name/{name}",
+        summary = "Get or create an account by name",
+        description = "Returns the active account with this name (case-insensitive) with 200, or \
+            creates it and returns 201. `currency` is used only on creation; an existing account \
+            is returned as is. Concurrent calls with the same name all get the same account.",
+        tag = "accounts",
+        params(("name" = String, Path, description = "Account name (URL-encoded)")),
+        request_body = EnsureAccountRequest,
+        responses(
+            (status = 200, description = "Existing account", body = AccountResponse),
+            (status = 201, description = "Account created", body = AccountResponse),
+            (status = 400, description = "Invalid name or currency", body = ErrorResponse),
+            (status = 429, description = "Account quota reached", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn ensure_account(
+    Scoped(service): Scoped<AppAccountService>,
+    Path(name): Path<String>,
+    Json(request): Json<EnsureAccountRequest>,
+) -> Result<(StatusCode, Json<AccountResponse>), ApiError> {
+    let (account, created) = service.ensure_account(&name, request).await?;
+    let status = if created {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+    Ok((status, Json(account)))
+}
+
+/// PATCH /api/accounts/:id — изменение имени и/или дневного лимита снятия.
+///
+/// # Возвращает
+/// - 200 с обновлённым счётом (в том числе если ничего не изменилось)
+/// - 400 если лимит отрицательный или счёт защиты от овердрафта
+///   недопустим (сам счёт, не найден, другая валюта, цикл)
+/// - 404 если счёта нет, 409 если имя занято другим счётом
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        patch,
+        path = "/api/accounts/{id}",
+        summary = "Update an account",
+        description = "Only the fields present are changed; `null` removes a limit. \
+            `overdraft_source` must be another active account in the same currency \
+            and must not lead back to this account. `low_balance_threshold` only adds \
+            a warning to withdrawals that cross it; it never blocks them. `description` \
+            is trimmed and limited to 500 characters.",
+        tag = "accounts",
+        params(("id" = Uuid, Path, description = "Account ID")),
+        request_body = UpdateAccountRequest,
+        responses(
+            (status = 200, description = "Updated account", body = AccountResponse),
+            (status = 400, description = "Invalid name or negative limit", body = ErrorResponse),
+            (status = 404, description = "Account not found", body = ErrorResponse),
+            (status = 409, description = "Name is taken by another account or concurrent update", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn update_account(
+    Scoped(service): Scoped<AppAccountService>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<UpdateAccountRequest>,
+) -> Result<Json<AccountResponse>, ApiError> {
+    let account = service.update_account(id, request).await?;
+    Ok(Json(account))
+}
+
 /// POST /api/accounts/:id/deposit — пополнение счёта.
+///
+/// # Заголовок `Idempotency-Key`
+/// Повтор с тем же ключом и тем же телом в течение 24 часов вернёт
+/// первый ответ, не пополняя счёт снова. Тот же ключ с другим телом — 422.
+///
+/// `HeaderMap` — extractor всех заголовков запроса. Стоит до `Json`:
+/// extractor тела в Axum должен быть последним.
+///
+/// `?dry_run=true` — все проверки без сохранения, ответ с `"dry_run": true`.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/accounts/{id}/deposit",
+        summary = "Deposit money",
+        description = "A repeated request with the same `Idempotency-Key` and body returns the first response. \
+            With `dry_run=true` nothing is saved; the response shows the account as it would be.",
+        tag = "accounts",
+        params(("id" = Uuid, Path, description = "Account ID"), ("Idempotency-Key" = Option<String>, Header, description = "Retry key, 1-255 characters; valid for 24 hours"), DryRunQuery),
+        request_body = DepositRequest,
+        responses(
+            (status = 200, description = "Account after the deposit", body = AccountResponse),
+            (status = 400, description = "Invalid amount", body = ErrorResponse),
+            (status = 404, description = "Account not found", body = ErrorResponse),
+            (status = 409, description = "Concurrent update or the same Idempotency-Key is in progress", body = ErrorResponse),
+            (status = 422, description = "Idempotency-Key reused with a different body", body = ErrorResponse),
+        ),
+    )
+)]
 pub async fn deposit(
-    State(service): State<AppAccountService>,
+    Scoped(service): Scoped<AppAccountService>,
     Path(id): Path<Uuid>,
+    Query(query): Query<DryRunQuery>,
+    headers: HeaderMap,
     Json(request): Json<DepositRequest>,
 ) -> Result<Json<AccountResponse>, ApiError> {
-    let account = service.deposit(id, request).await?;
+    let key = idempotency_key(&headers)?;
+    let account = service.deposit(id, request, key, query.dry_run).await?;
     Ok(Json(account))
 }
 
 /// POST /api/accounts/:id/withdraw — снятие денег.
+///
+/// Поддерживает `Idempotency-Key` и `?dry_run=true` так же, как `deposit`.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/accounts/{id}/withdraw",
+        summary = "Withdraw money",
+        description = "Checks balance, credit limit, daily withdrawal limit and minimum balance. \
+            `\"amount\": \"all\"` withdraws the whole (positive) balance, leaving exactly 0. \
+            If the funds fall short and the account has an `overdraft_source`, the shortfall \
+            is moved from that account first; if it cannot cover it, nothing changes. \
+            If the withdrawal takes the balance below `low_balance_threshold`, it still goes \
+            through and the response carries `\"warnings\": [\"low balance\"]`. \
+            With `dry_run=true` nothing is saved, but every check still runs.",
+        tag = "accounts",
+        params(("id" = Uuid, Path, description = "Account ID"), ("Idempotency-Key" = Option<String>, Header, description = "Retry key, 1-255 characters; valid for 24 hours"), DryRunQuery),
+        request_body = WithdrawRequest,
+        responses(
+            (status = 200, description = "Account after the withdrawal", body = AccountResponse),
+            (status = 400, description = "Invalid amount, insufficient funds, daily limit or minimum balance", body = ErrorResponse),
+            (status = 404, description = "Account not found", body = ErrorResponse),
+            (status = 409, description = "Concurrent update or the same Idempotency-Key is in progress", body = ErrorResponse),
+            (status = 422, description = "Idempotency-Key reused with a different body", body = ErrorResponse),
+        ),
+    )
+)]
 pub async fn withdraw(
-    State(service): State<AppAccountService>,
+    Scoped(service): Scoped<AppAccountService>,
     Path(id): Path<Uuid>,
+    Query(query): Query<DryRunQuery>,
+    headers: HeaderMap,
     Json(request): Json<WithdrawRequest>,
 ) -> Result<Json<AccountResponse>, ApiError> {
-    let account = service.withdraw(id, request).await?;
+    let key = idempotency_key(&headers)?;
+    let account = service.withdraw(id, request, key, query.dry_run).await?;
+    Ok(Json(account))
+}
+
+/// POST /api/accounts/:id/interest — начисление процентов.
+///
+/// Поддерживает `Idempotency-Key`, как `deposit`. Нулевой баланс —
+/// не ошибка: 200 и счёт без изменений.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/accounts/{id}/interest",
+        summary = "Apply interest",
+        description = "Deposits `balance * annual_rate / 100 * days / 365`, rounded to the \
+            currency's minor unit by `ROUNDING_MODE` (half-to-even by default), as an `interest` transaction. \
+            A zero or negative balance (or interest rounding to zero) leaves the account unchanged.",
+        tag = "accounts",
+        params(("id" = Uuid, Path, description = "Account ID"), ("Idempotency-Key" = Option<String>, Header, description = "Retry key, 1-255 characters; valid for 24 hours")),
+        request_body = ApplyInterestRequest,
+        responses(
+            (status = 200, description = "Account after the interest", body = AccountResponse),
+            (status = 400, description = "Rate outside (0, 100] or period outside 1-366 days", body = ErrorResponse),
+            (status = 404, description = "Account not found", body = ErrorResponse),
+            (status = 409, description = "Concurrent update or the same Idempotency-Key is in progress", body = ErrorResponse),
+            (status = 422, description = "Idempotency-Key reused with a different body", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn apply_interest(
+    Scoped(service): Scoped<AppAccountService>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(request): Json<ApplyInterestRequest>,
+) -> Result<Json<AccountResponse>, ApiError> {
+    let key = idempotency_key(&headers)?;
+    let account = service.apply_interest(id, request, key).await?;
+    Ok(Json(account))
+}
+
+/// POST /api/accounts/:id/transfer — перевод на другой счёт.
+///
+/// # Возвращает
+/// - 200 с обоими счетами и суммами до/после конвертации
+/// - 400 если средств не хватает или счёт тот же
+/// - 404 если один из счетов не найден
+/// - 422 если нет курса для пары валют
+///
+/// С `?dry_run=true` — те же проверки и ответ, но без записи.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/accounts/{id}/transfer",
+        summary = "Transfer to another account",
+        description = "`amount` is in the source currency; it is converted when the currencies differ. \
+            With `dry_run=true` nothing is saved.",
+        tag = "accounts",
+        params(("id" = Uuid, Path, description = "Account ID"), DryRunQuery),
+        request_body = TransferRequest,
+        responses(
+            (status = 200, description = "Both accounts and the converted amount", body = TransferResponse),
+            (status = 400, description = "Invalid amount, insufficient funds or same account", body = ErrorResponse),
+            (status = 404, description = "Source or target account not found", body = ErrorResponse),
+            (status = 409, description = "Concurrent update", body = ErrorResponse),
+            (status = 422, description = "No exchange rate for the currency pair", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn transfer(
+    Scoped(service): Scoped<AppAccountService>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<DryRunQuery>,
+    Json(request): Json<TransferRequest>,
+) -> Result<Json<TransferResponse>, ApiError> {
+    let transfer = service.transfer(id, request, query.dry_run).await?;
+    Ok(Json(transfer))
+}
+
+/// GET /api/accounts/:id/transactions?limit=&offset=&from=&to=&kind=&category= —
+/// страница истории операций (новые — первыми).
+///
+/// Без параметров — 50 последних операций. Одна query string разбирается
+/// тремя `Query`, как в `get_accounts`.
+///
+/// # Возвращает
+/// - 200 с `PagedResponse`
+/// - 400 если `limit`/`offset` вне границ, `from` позже `to`,
+///   неизвестный `kind` или некорректная категория
+/// - 404 если счёт не найден
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/accounts/{id}/transactions",
+        summary = "Transaction history",
+        description = "Paginated transaction history, newest first (50 by default). \
+            `from` and `to` are inclusive RFC 3339 bounds; `category` filters case-insensitively.",
+        tag = "accounts",
+        params(("id" = Uuid, Path, description = "Account ID"), Pagination, TimeRange, TransactionListQuery),
+        responses(
+            (status = 200, description = "Page of transactions, newest first", body = PagedResponse<TransactionResponse>),
+            (status = 400, description = "Invalid limit, offset, range, kind or category", body = ErrorResponse),
+            (status = 404, description = "Account not found", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn get_transactions(
+    Scoped(service): Scoped<AppAccountService>,
+    Path(id): Path<Uuid>,
+    Query(pagination): Query<Pagination>,
+    Query(range): Query<TimeRange>,
+    Query(query): Query<TransactionListQuery>,
+) -> Result<Json<PagedResponse<TransactionResponse>>, ApiError> {
+    pagination.validate().map_err(ApiError::bad_request)?;
+    range.validate().map_err(ApiError::bad_request)?;
+
+    let page = service
+        .get_transactions(id, query.to_filter(&range), &pagination)
+        .await?;
+    Ok(Json(page))
+}
+
+/// PATCH /api/accounts/:id/transactions/:transaction_id — правка комментария
+/// и категории операции.
+///
+/// # Возвращает
+/// - 200 с изменённой операцией
+/// - 400 если в запросе есть `amount` или категория некорректна
+/// - 404 если операции нет или она относится к другому счёту
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        patch,
+        path = "/api/accounts/{id}/transactions/{transaction_id}",
+        summary = "Edit a transaction",
+        description = "Change the `note` and `category` of a recorded transaction; `null` removes \
+            them. The amount can never be changed: a body with `amount` is rejected with 400.",
+        tag = "accounts",
+        params(
+            ("id" = Uuid, Path, description = "Account ID"),
+            ("transaction_id" = Uuid, Path, description = "Transaction ID"),
+        ),
+        request_body = UpdateTransactionRequest,
+        responses(
+            (status = 200, description = "Updated transaction", body = TransactionResponse),
+            (status = 400, description = "Amount present or invalid category", body = ErrorResponse),
+            (status = 404, description = "Transaction not found on this account", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn update_transaction(
+    Scoped(service): Scoped<AppAccountService>,
+    Path((id, transaction_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<UpdateTransactionRequest>,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let transaction = service
+        .update_transaction(id, transaction_id, request)
+        .await?;
+    Ok(Json(transaction))
+}
+
+/// DELETE /api/accounts/:id/transactions/:transaction_id — отмена
+/// пополнения или снятия.
+///
+/// Операция не удаляется: в журнал пишется обратная, а у отменённой
+/// появляется `reversed_at` (см. `AccountService::reverse_transaction`).
+///
+/// # Возвращает
+/// - 200 со счётом после отмены
+/// - 400 если отмена пополнения не проходит проверки снятия
+/// - 404 если операции нет или она относится к другому счёту
+/// - 409 если операция уже отменена, её тип не отменяется
+///   или счёт изменили параллельно
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        delete,
+        path = "/api/accounts/{id}/transactions/{transaction_id}",
+        summary = "Reverse a transaction",
+        description = "Reverse a deposit or a withdrawal. The ledger gets an opposite transaction \
+            (a withdrawal for a deposit and vice versa) with the same amount and category, and \
+            the original keeps its place with `reversed_at` set. Reversing a deposit is subject \
+            to the usual withdrawal checks. Transfers, interest, reconciliations and currency \
+            changes cannot be reversed.",
+        tag = "accounts",
+        params(
+            ("id" = Uuid, Path, description = "Account ID"),
+            ("transaction_id" = Uuid, Path, description = "Transaction ID"),
+        ),
+        responses(
+            (status = 200, description = "Account after the reversal", body = AccountResponse),
+            (status = 400, description = "Insufficient funds, limit exceeded or below minimum balance", body = ErrorResponse),
+            (status = 404, description = "Transaction not found on this account", body = ErrorResponse),
+            (status = 409, description = "Already reversed, not reversible, or account modified concurrently", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn reverse_transaction(
+    Scoped(service): Scoped<AppAccountService>,
+    Path((id, transaction_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<AccountResponse>, ApiError> {
+    let account = service.reverse_transaction(id, transaction_id).await?;
     Ok(Json(account))
 }
 
-/// DELETE /api/accounts/:id — удаление счёта.
+/// GET /api/accounts/:id/transactions/export.csv — весь журнал счёта в CSV.
+///
+/// # Почему тело — поток?
+/// `Body::from_stream` отправляет строки по мере того, как они приходят
+/// из БД (см. `TransactionRepository::stream_by_account`), — в памяти
+/// не лежит ни вся история, ни весь файл.
+///
+/// # Ошибка посреди выгрузки
+/// Статус 200 и заголовки к этому моменту уже отправлены, поменять их
+/// нельзя. Ошибка логируется, а соединение обрывается — клиент видит
+/// недокачанный файл, а не файл, который выглядит целым.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/accounts/{id}/transactions/export.csv",
+        summary = "Export transactions as CSV",
+        description = "All transactions, oldest first, streamed as CSV with the columns \
+            `timestamp,kind,amount,balance_after,note,category`. Amounts are in the account's \
+            currency units; `amount` is always positive, `kind` gives the direction.",
+        tag = "accounts",
+        params(("id" = Uuid, Path, description = "Account ID")),
+        responses(
+            (status = 200, description = "CSV file", body = String, content_type = "text/csv"),
+            (status = 404, description = "Account not found", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn export_transactions_csv(
+    Scoped(service): Scoped<AppAccountService>,
+    Path(id): Path<Uuid>,
+) -> Result<Response, ApiError> {
+    let export = service.export_transactions(id).await?;
+
+    let currency = export.currency;
+    let rows = export.entries.map(move |entry| {
+        entry
+            .map(|entry| csv::row(&entry, &currency))
+            .map_err(|err| {
+                tracing::error!("CSV export of account {} failed: {}", id, err);
+                err
+            })
+    });
+    let body = stream::once(future::ready(Ok(csv::HEADER.to_string()))).chain(rows);
+
+    let filename = format!(
+        "attachment; filename=\"transactions-{}.csv\"",
+        export.account_id
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, filename),
+        ],
+        Body::from_stream(body),
+    )
+        .into_response())
+}
+
+/// GET /api/accounts/:id/history?from=&to= — история баланса за период.
+///
+/// Границы в формате RFC 3339, обе необязательны.
+/// Если `from` позже `to` — 400.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/accounts/{id}/history",
+        summary = "Balance history",
+        description = "Both bounds are optional, inclusive, RFC 3339.",
+        tag = "accounts",
+        params(("id" = Uuid, Path, description = "Account ID"), TimeRange),
+        responses(
+            (status = 200, description = "Balance snapshots, oldest first", body = Vec<BalanceSnapshotResponse>),
+            (status = 400, description = "from is later than to", body = ErrorResponse),
+            (status = 404, description = "Account not found", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn get_balance_history(
+    Scoped(service): Scoped<AppAccountService>,
+    Path(id): Path<Uuid>,
+    Query(range): Query<TimeRange>,
+) -> Result<Json<Vec<BalanceSnapshotResponse>>, ApiError> {
+    range.validate().map_err(ApiError::bad_request)?;
+
+    let history = service.get_balance_history(id, &range).await?;
+    Ok(Json(history))
+}
+
+/// GET /api/accounts/:id/audit — журнал изменений счёта.
+///
+/// Не журнал операций: здесь переименования, лимиты, архив, удаление —
+/// всё, что меняло сам счёт, а не его баланс.
+///
+/// # Возвращает
+/// - 200 с записями, старые — первыми (и для удалённого счёта)
+/// - 404 если счёта нет ни среди активных, ни среди удалённых
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/accounts/{id}/audit",
+        summary = "Account audit log",
+        description = "Changes to the account itself (name, limits, archive, currency, \
+            deletion), oldest first. Balance changes are in the transactions instead. \
+            The description's old and new values are never logged, only the fact of the change. \
+            Works for deleted accounts too.",
+        tag = "accounts",
+        params(("id" = Uuid, Path, description = "Account ID")),
+        responses(
+            (status = 200, description = "Audit entries, oldest first", body = Vec<AuditEntryResponse>),
+            (status = 404, description = "Account not found", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn account_audit(
+    Scoped(service): Scoped<AppAccountService>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<AuditEntryResponse>>, ApiError> {
+    let entries = service.account_audit(id).await?;
+    Ok(Json(entries))
+}
+
+/// GET /api/reports/by-category?from=&to= — итоги по категориям за период.
+///
+/// Границы в формате RFC 3339, обе необязательны.
+/// Если `from` позже `to` — 400.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/reports/by-category",
+        summary = "Totals by category",
+        description = "Deposits and withdrawals of all accounts, grouped by category and currency. \
+            Transfers are excluded; transactions without a category are reported as `(uncategorized)`.",
+        tag = "reports",
+        params(TimeRange),
+        responses(
+            (status = 200, description = "Rows sorted by category, then currency", body = Vec<CategoryTotalResponse>),
+            (status = 400, description = "from is later than to", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn category_report(
+    Scoped(service): Scoped<AppAccountService>,
+    Query(range): Query<TimeRange>,
+) -> Result<Json<Vec<CategoryTotalResponse>>, ApiError> {
+    range.validate().map_err(ApiError::bad_request)?;
+
+    let report = service.category_report(&range).await?;
+    Ok(Json(report))
+}
+
+/// GET /api/activity?limit=&before= — последние операции всех счетов.
+///
+/// # Возвращает
+/// - 200 со страницей ленты и курсором следующей (`next_before`)
+/// - 400 если `limit` вне границ или курсор `before` не разобрался
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/activity",
+        summary = "Recent activity",
+        description = "Transactions of all accounts, newest first (50 by default), with the \
+            account name and currency. Pass `next_before` from a page as `before` to get the next \
+            one; it is null on the last page. Deleted accounts are left out, so a page may be \
+            shorter than `limit`.",
+        tag = "reports",
+        params(ActivityQuery),
+        responses(
+            (status = 200, description = "Page of transactions, newest first", body = ActivityPage),
+            (status = 400, description = "Invalid limit or cursor", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn activity(
+    Scoped(service): Scoped<AppAccountService>,
+    Query(query): Query<ActivityQuery>,
+) -> Result<Json<ActivityPage>, ApiError> {
+    let before = query.cursor().map_err(ApiError::bad_request)?;
+
+    let page = service.activity(before, query.limit()).await?;
+    Ok(Json(page))
+}
+
+/// GET /api/net-worth?currency= — капитал в одной валюте.
+///
+/// # Возвращает
+/// - 200 с итогом, разбивкой по валютам и валютами без курса
+/// - 400 если `currency` не передана или пустая
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/net-worth",
+        summary = "Net worth",
+        description = "Balances of all accounts (including archived ones) converted into `currency`. \
+            Currencies without an exchange rate are listed under `unconverted` and left out of `total`.",
+        tag = "reports",
+        params(NetWorthQuery),
+        responses(
+            (status = 200, description = "Net worth", body = NetWorthResponse),
+            (status = 400, description = "currency is missing or empty", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn net_worth(
+    Scoped(service): Scoped<AppAccountService>,
+    Query(query): Query<NetWorthQuery>,
+) -> Result<Json<NetWorthResponse>, ApiError> {
+    let currency = query.currency().map_err(ApiError::bad_request)?;
+
+    let net_worth = service.net_worth(&currency).await?;
+    Ok(Json(net_worth))
+}
+
+/// GET /api/stats — сводка по счетам для дашборда.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/stats",
+        summary = "Account statistics",
+        description = "Number of accounts, count and total balance per currency, and the oldest \
+            and newest creation dates. Deleted and archived accounts are left out.",
+        tag = "reports",
+        responses(
+            (status = 200, description = "Account statistics", body = StatsResponse),
+        ),
+    )
+)]
+pub async fn stats(
+    Scoped(service): Scoped<AppAccountService>,
+) -> Result<Json<StatsResponse>, ApiError> {
+    let stats = service.stats().await?;
+    Ok(Json(stats))
+}
+
+/// GET /api/accounts/:id/statement?year=&month= — выписка за месяц.
+///
+/// # Возвращает
+/// - 200 с остатками, итогами и операциями месяца
+/// - 400 если месяц не в `1..=12` или параметры не переданы
+/// - 404 если счёт не найден
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/accounts/{id}/statement",
+        summary = "Monthly statement",
+        description = "UTC calendar month. Transfers count as deposits and withdrawals.",
+        tag = "accounts",
+        params(("id" = Uuid, Path, description = "Account ID"), StatementQuery),
+        responses(
+            (status = 200, description = "Monthly statement", body = StatementResponse),
+            (status = 400, description = "Month is not in 1-12 or parameters are missing", body = ErrorResponse),
+            (status = 404, description = "Account not found", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn get_statement(
+    Scoped(service): Scoped<AppAccountService>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<StatementQuery>,
+) -> Result<Json<StatementResponse>, ApiError> {
+    let period = query.period().map_err(ApiError::bad_request)?;
+
+    let statement = service.get_statement(id, period).await?;
+    Ok(Json(statement))
+}
+
+/// GET /api/accounts/:id/balance-at?date= — баланс на конец дня.
+///
+/// # Возвращает
+/// - 200 с балансом и числом учтённых операций
+/// - 400 если `date` не передана или не дата `YYYY-MM-DD`
+/// - 404 если счёт не найден
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/accounts/{id}/balance-at",
+        summary = "Balance at a date",
+        description = "Sum of the ledger up to and including `date` (UTC): a transaction at \
+            23:59:59 that day counts, one at 00:00 the next day does not. Before the first \
+            transaction the balance is 0 in the account's currency.",
+        tag = "accounts",
+        params(("id" = Uuid, Path, description = "Account ID"), BalanceAtQuery),
+        responses(
+            (status = 200, description = "Balance at the end of the day", body = BalanceAtResponse),
+            (status = 400, description = "Missing or invalid date", body = ErrorResponse),
+            (status = 404, description = "Account not found", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn balance_at(
+    Scoped(service): Scoped<AppAccountService>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<BalanceAtQuery>,
+) -> Result<Json<BalanceAtResponse>, ApiError> {
+    let cutoff = query.cutoff().map_err(ApiError::bad_request)?;
+
+    let balance = service.balance_at(id, query.date, cutoff).await?;
+    Ok(Json(balance))
+}
+
+/// GET /api/accounts/:id/info — возраст, последняя активность и итоги счёта.
+///
+/// # Возвращает
+/// - 200 со сводкой (у счёта без операций — нулевые итоги)
+/// - 404 если счёт не найден
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/accounts/{id}/info",
+        summary = "Account info",
+        description = "Age in days, days since the last transaction (or since `updated_at` when \
+            there are none) and lifetime totals. Transfers count as deposits and withdrawals.",
+        tag = "accounts",
+        params(("id" = Uuid, Path, description = "Account ID")),
+        responses(
+            (status = 200, description = "Account info", body = AccountInfoResponse),
+            (status = 404, description = "Account not found", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn account_info(
+    Scoped(service): Scoped<AppAccountService>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<AccountInfoResponse>, ApiError> {
+    let info = service.account_info(id).await?;
+    Ok(Json(info))
+}
+
+/// GET /api/accounts/:id/reconcile — сверка баланса с журналом.
+///
+/// Ничего не меняет; исправляет расхождение `POST` на тот же путь.
+///
+/// # Возвращает
+/// - 200 с сохранённым и посчитанным балансом (`matches: false` — тоже 200)
+/// - 404 если счёт не найден
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/accounts/{id}/reconcile",
+        summary = "Check the balance against the ledger",
+        description = "Recomputes the balance from every transaction of the account and compares \
+            it with the stored one. `difference` is stored minus computed. Read-only.",
+        tag = "accounts",
+        params(("id" = Uuid, Path, description = "Account ID")),
+        responses(
+            (status = 200, description = "Reconciliation result", body = ReconciliationResponse),
+            (status = 404, description = "Account not found", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn reconcile(
+    Scoped(service): Scoped<AppAccountService>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ReconciliationResponse>, ApiError> {
+    let result = service.reconcile(id, false).await?;
+    Ok(Json(result))
+}
+
+/// POST /api/accounts/:id/reconcile — сверка с исправлением баланса.
+///
+/// # Возвращает
+/// - 200 с результатом сверки до исправления (`fixed: true`, если исправлено)
+/// - 404 если счёт не найден
+/// - 409 если счёт изменили во время сверки
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/accounts/{id}/reconcile",
+        summary = "Fix the balance from the ledger",
+        description = "Same check as GET. If the balances differ, the stored balance is set to the \
+            computed one and a `reconciliation` transaction for the difference is recorded; \
+            it does not count towards the ledger sum. The response describes the state \
+            before the fix and adds `\"fixed\": true`.",
+        tag = "accounts",
+        params(("id" = Uuid, Path, description = "Account ID")),
+        responses(
+            (status = 200, description = "Reconciliation result before the fix", body = ReconciliationResponse),
+            (status = 404, description = "Account not found", body = ErrorResponse),
+            (status = 409, description = "Account changed during reconciliation", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn fix_balance(
+    Scoped(service): Scoped<AppAccountService>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ReconciliationResponse>, ApiError> {
+    let result = service.reconcile(id, true).await?;
+    Ok(Json(result))
+}
+
+/// POST /api/accounts/:id/simulate — симуляция операции без сохранения.
+///
+/// Возвращает 200 даже если операция не прошла бы (см. поле `success`).
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/accounts/{id}/simulate",
+        summary = "Preview an operation",
+        description = "Nothing is saved. A failing operation is still 200 with `success: false`.",
+        tag = "accounts",
+        params(("id" = Uuid, Path, description = "Account ID")),
+        request_body = SimulateRequest,
+        responses(
+            (status = 200, description = "Simulation result, including operations that would fail", body = SimulationResponse),
+            (status = 404, description = "Account not found", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn simulate(
+    Scoped(service): Scoped<AppAccountService>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<SimulateRequest>,
+) -> Result<Json<SimulationResponse>, ApiError> {
+    let simulation = service.simulate(id, request).await?;
+    Ok(Json(simulation))
+}
+
+/// DELETE /api/accounts/:id?force= — удаление счёта (мягкое, можно восстановить).
+///
+/// # Возвращает
+/// - 200 если счёт удалён
+/// - 404 если счёт не найден
+/// - 409 если баланс не нулевой, а `force=true` не передан
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        delete,
+        path = "/api/accounts/{id}",
+        summary = "Delete an account",
+        description = "Soft delete; see restore. An account with a non-zero balance requires `force=true`.",
+        tag = "accounts",
+        params(("id" = Uuid, Path, description = "Account ID"), DeleteAccountQuery),
+        responses(
+            (status = 200, description = "Account deleted", body = MessageResponse),
+            (status = 404, description = "Account not found", body = ErrorResponse),
+            (status = 409, description = "Balance is not zero and force is not set", body = ErrorResponse),
+        ),
+    )
+)]
 pub async fn delete_account(
-    State(service): State<AppAccountService>,
+    Scoped(service): Scoped<AppAccountService>,
     Path(id): Path<Uuid>,
+    Query(query): Query<DeleteAccountQuery>,
 ) -> Result<Json<MessageResponse>, ApiError> {
-    service.delete_account(id).await?;
+    service.delete_account(id, query.force).await?;
     Ok(Json(MessageResponse::new("Account deleted successfully")))
 }
+
+/// POST /api/accounts/:id/restore — восстановление удалённого счёта.
+///
+/// # Возвращает
+/// - 200 с восстановленным счётом
+/// - 404 если счёт не существует или не был удалён
+/// - 409 если имя счёта уже занято другим счётом
+/// - 429 если достигнут лимит `MAX_ACCOUNTS`
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/accounts/{id}/restore",
+        summary = "Restore a deleted account",
+        description = "Restore a deleted account.",
+        tag = "accounts",
+        params(("id" = Uuid, Path, description = "Account ID")),
+        responses(
+            (status = 200, description = "Restored account", body = AccountResponse),
+            (status = 404, description = "Account does not exist or is not deleted", body = ErrorResponse),
+            (status = 409, description = "Name is taken by another account", body = ErrorResponse),
+            (status = 429, description = "MAX_ACCOUNTS reached", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn restore_account(
+    Scoped(service): Scoped<AppAccountService>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<AccountResponse>, ApiError> {
+    let account = service.restore_account(id).await?;
+    Ok(Json(account))
+}
+
+/// POST /api/accounts/:id/archive — скрыть счёт из списка счетов.
+///
+/// Архивный счёт по-прежнему доступен по ID со всей историей.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/accounts/{id}/archive",
+        summary = "Archive an account",
+        description = "Hide the account from `GET /api/accounts`. It stays reachable by ID, with its history.",
+        tag = "accounts",
+        params(("id" = Uuid, Path, description = "Account ID")),
+        responses(
+            (status = 200, description = "Archived account", body = AccountResponse),
+            (status = 404, description = "Account not found", body = ErrorResponse),
+            (status = 409, description = "Account was modified concurrently", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn archive_account(
+    Scoped(service): Scoped<AppAccountService>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<AccountResponse>, ApiError> {
+    let account = service.set_archived(id, true).await?;
+    Ok(Json(account))
+}
+
+/// POST /api/accounts/:id/close — закрыть счёт: остаток перевести или списать,
+/// счёт убрать в архив.
+///
+/// # Возвращает
+/// - 200 с закрытым счётом (и если он уже был закрыт)
+/// - 400 если долг пытаются перевести на другой счёт
+/// - 404 если счёт или счёт-получатель не найден
+/// - 409 если счёт-получатель закрыт или счёт изменили параллельно
+/// - 422 если нет курса для валют счетов
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/accounts/{id}/close",
+        summary = "Close an account",
+        description = "Zero the balance and archive the account in one step. With `to_account_id` the \
+            whole balance is transferred there (converted if the currencies differ); without it \
+            (`{}`) the balance is written off as a `withdraw`, or a debt as a `deposit`. \
+            A closed target account is rejected with 409. A balance too small to convert into \
+            the target currency is written off like the no-target case. Daily limits and the \
+            minimum balance do not apply. An account that is already archived with a zero \
+            balance is returned unchanged.",
+        tag = "accounts",
+        params(("id" = Uuid, Path, description = "Account ID")),
+        request_body = CloseAccountRequest,
+        responses(
+            (status = 200, description = "Closed account", body = AccountResponse),
+            (status = 400, description = "Closing to itself, or moving a debt to another account", body = ErrorResponse),
+            (status = 404, description = "Account or target account not found", body = ErrorResponse),
+            (status = 409, description = "Target account is closed, or an account was modified concurrently", body = ErrorResponse),
+            (status = 422, description = "No exchange rate for the pair", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn close_account(
+    Scoped(service): Scoped<AppAccountService>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<CloseAccountRequest>,
+) -> Result<Json<AccountResponse>, ApiError> {
+    let account = service.close_account(id, request).await?;
+    Ok(Json(account))
+}
+
+/// POST /api/accounts/:id/unarchive — вернуть счёт в список счетов.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/accounts/{id}/unarchive",
+        summary = "Unarchive an account",
+        description = "Show the account in `GET /api/accounts` again.",
+        tag = "accounts",
+        params(("id" = Uuid, Path, description = "Account ID")),
+        responses(
+            (status = 200, description = "Unarchived account", body = AccountResponse),
+            (status = 404, description = "Account not found", body = ErrorResponse),
+            (status = 409, description = "Account was modified concurrently", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn unarchive_account(
+    Scoped(service): Scoped<AppAccountService>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<AccountResponse>, ApiError> {
+    let account = service.set_archived(id, false).await?;
+    Ok(Json(account))
+}
@@ -0,0 +1,65 @@
+//! HTTP handlers для резервного копирования.
+
+use axum::extract::State;
+
+use crate::application::dto::{BackupDocument, ImportSummary};
+use crate::application::services::BackupService;
+use crate::infrastructure::database::AnyBackupRepository;
+use crate::presentation::api::error::ApiError;
+#[cfg(feature = "openapi")]
+use crate::presentation::api::error::ErrorResponse;
+use crate::presentation::api::extractors::Json;
+
+/// Type alias — конкретный тип сервиса резервного копирования.
+pub type AppBackupService = BackupService<AnyBackupRepository>;
+
+/// GET /api/export — все данные одним JSON-документом.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/export",
+        summary = "Export a backup",
+        description = "Export a backup.",
+        tag = "backup",
+        responses(
+            (status = 200, description = "All accounts, transactions and balance history", body = BackupDocument),
+        ),
+    )
+)]
+pub async fn export_backup(
+    State(service): State<AppBackupService>,
+) -> Result<Json<BackupDocument>, ApiError> {
+    let document = service.export().await?;
+    Ok(Json(document))
+}
+
+/// POST /api/import — загрузка резервной копии в пустую БД.
+///
+/// # Возвращает
+/// - 200 с количеством загруженных записей
+/// - 400 если версия формата не та или документ противоречив
+/// - 409 если в БД уже есть счета
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/import",
+        summary = "Import a backup",
+        description = "Only into an empty database.",
+        tag = "backup",
+        request_body = BackupDocument,
+        responses(
+            (status = 200, description = "Number of imported records", body = ImportSummary),
+            (status = 400, description = "Unsupported schema version or inconsistent document", body = ErrorResponse),
+            (status = 409, description = "Database is not empty", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn import_backup(
+    State(service): State<AppBackupService>,
+    Json(document): Json<BackupDocument>,
+) -> Result<Json<ImportSummary>, ApiError> {
+    let summary = service.import(document).await?;
+    Ok(Json(summary))
+}
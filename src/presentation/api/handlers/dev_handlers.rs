@@ -0,0 +1,20 @@
+//! HTTP handlers для разработки — только со сборкой `--features dev`.
+
+use crate::application::dto::SeedResponse;
+use crate::presentation::api::error::ApiError;
+use crate::presentation::api::extractors::{Json, Scoped};
+
+use super::AppAccountService;
+
+/// POST /api/dev/seed — демо-счета с операциями.
+///
+/// # Возвращает
+/// - 200 с `created: true` и созданными счетами
+/// - 200 с `created: false`, если демо-данные уже есть (счёт `Demo`)
+/// - 429 если демо-счета не помещаются в `MAX_ACCOUNTS`
+pub async fn seed_demo(
+    Scoped(service): Scoped<AppAccountService>,
+) -> Result<Json<SeedResponse>, ApiError> {
+    let seeded = service.seed_demo().await?;
+    Ok(Json(seeded))
+}
@@ -0,0 +1,128 @@
+//! HTTP handlers для проверок состояния сервиса.
+//!
+//! Используются балансировщиком/оркестратором:
+//! - `/health` (liveness) — процесс жив и отвечает на запросы
+//! - `/ready` (readiness) — сервис может обслуживать запросы (БД доступна)
+//!
+//! И мониторингом: `/version` — какая сборка сейчас запущена.
+
+use std::time::Duration;
+
+use axum::{extract::State, http::StatusCode, Json};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::infrastructure::database::DatabasePool;
+
+/// Сколько ждём ответа БД в readiness-пробе.
+///
+/// # Зачем таймаут?
+/// Зависшая БД не вернёт ошибку — запрос просто будет висеть.
+/// Без таймаута проба зависнет вместе с ней, и балансировщик
+/// не узнает, что сервис не готов.
+const READINESS_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// GET /health — liveness-проба.
+///
+/// Всегда 200: если handler выполнился, процесс жив.
+/// БД здесь специально не проверяем — иначе оркестратор перезапускал бы
+/// сервис при каждом сбое БД, хотя перезапуск не поможет.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/health",
+        summary = "Liveness probe",
+        description = "Liveness probe.",
+        tag = "health",
+        responses(
+            (status = 200, description = "Process is alive"),
+        ),
+    )
+)]
+pub async fn health() -> Json<Value> {
+    Json(json!({ "status": "ok" }))
+}
+
+/// GET /ready — readiness-проба.
+///
+/// # Возвращает
+/// - 200 `{"status":"ok"}` — `SELECT 1` выполнился
+/// - 503 `{"status":"unavailable"}` — ошибка БД или таймаут
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/ready",
+        summary = "Readiness probe",
+        description = "Runs `SELECT 1` with a 2 second timeout.",
+        tag = "health",
+        responses(
+            (status = 200, description = "Database is reachable"),
+            (status = 503, description = "Database error or timeout"),
+        ),
+    )
+)]
+pub async fn ready(State(pool): State<DatabasePool>) -> (StatusCode, Json<Value>) {
+    // tokio::time::timeout — Err(Elapsed), если future не успела за отведённое время
+    let result = tokio::time::timeout(READINESS_TIMEOUT, pool.ping()).await;
+
+    match result {
+        Ok(Ok(())) => (StatusCode::OK, Json(json!({ "status": "ok" }))),
+        Ok(Err(e)) => {
+            tracing::warn!("Readiness check failed: {}", e);
+            unavailable()
+        }
+        Err(_) => {
+            tracing::warn!("Readiness check timed out after {:?}", READINESS_TIMEOUT);
+            unavailable()
+        }
+    }
+}
+
+/// Версия запущенной сборки.
+///
+/// `git_sha` и `build_timestamp` записывает `build.rs`; без `.git`
+/// (сборка из архива) `git_sha` — `"unknown"`.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct VersionResponse {
+    pub version: &'static str,
+    pub git_sha: &'static str,
+    /// RFC 3339, UTC
+    pub build_timestamp: &'static str,
+}
+
+/// GET /version — версия и сборка сервиса.
+///
+/// Как и `/health`, не обращается к БД: версию должно быть видно
+/// и тогда, когда сервис не готов.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/version",
+        summary = "Build information",
+        description = "Package version, git commit and build time of the running binary. \
+            `git_sha` is `\"unknown\"` for builds made without git.",
+        tag = "health",
+        responses(
+            (status = 200, description = "Build information", body = VersionResponse),
+        ),
+    )
+)]
+pub async fn version() -> Json<VersionResponse> {
+    Json(VersionResponse {
+        version: env!("CARGO_PKG_VERSION"),
+        git_sha: env!("GIT_SHA"),
+        build_timestamp: env!("BUILD_TIMESTAMP"),
+    })
+}
+
+/// Ответ 503 для неготового сервиса.
+fn unavailable() -> (StatusCode, Json<Value>) {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(json!({ "status": "unavailable" })),
+    )
+}
@@ -0,0 +1,28 @@
+//! HTTP handler для метрик Prometheus.
+
+use axum::extract::State;
+use metrics_exporter_prometheus::PrometheusHandle;
+
+use crate::infrastructure::database::DatabasePool;
+
+/// Состояние роутера метрик.
+///
+/// Пул нужен, чтобы снять его размер в момент запроса.
+#[derive(Clone)]
+pub struct MetricsState {
+    pub handle: PrometheusHandle,
+    pub pool: DatabasePool,
+}
+
+/// GET /metrics — метрики в текстовом формате Prometheus.
+///
+/// # Почему gauge пула обновляются здесь?
+/// Размер пула меняется и без запросов (закрытие простаивающих
+/// соединений). Важно только значение в момент сбора —
+/// его и записываем перед рендерингом.
+pub async fn metrics(State(state): State<MetricsState>) -> String {
+    metrics::gauge!("db_pool_connections").set(state.pool.size() as f64);
+    metrics::gauge!("db_pool_idle_connections").set(state.pool.num_idle() as f64);
+
+    state.handle.render()
+}
@@ -1,3 +1,19 @@
 mod account_handlers;
+mod backup_handlers;
+#[cfg(feature = "dev")]
+mod dev_handlers;
+mod health_handlers;
+mod metrics_handlers;
+mod recurring_handlers;
+mod wallet_handlers;
+mod webhook_handlers;
 
 pub use account_handlers::*;
+pub use backup_handlers::*;
+#[cfg(feature = "dev")]
+pub use dev_handlers::*;
+pub use health_handlers::*;
+pub use metrics_handlers::*;
+pub use recurring_handlers::*;
+pub use wallet_handlers::*;
+pub use webhook_handlers::*;
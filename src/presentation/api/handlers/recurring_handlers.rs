@@ -0,0 +1,204 @@
+//! HTTP handlers для повторяющихся операций.
+
+use axum::extract::{Path, Query};
+use uuid::Uuid;
+
+use crate::application::dto::{
+    AccountResponse, ChangeCurrencyQuery, ChangeCurrencyRequest, CreateRecurringRequest,
+    MessageResponse, RecurringResponse, UpdateRecurringRequest,
+};
+use crate::application::services::RecurringService;
+use crate::infrastructure::database::{
+    AnyAccountRepository, AnyIdempotencyRepository, AnyRecurringRepository, AnySnapshotRepository,
+    AnyTransactionRepository, AnyWebhookRepository, RetryingAccountRepository,
+};
+use crate::infrastructure::exchange_rates::StaticExchangeRateProvider;
+use crate::infrastructure::webhooks::WebhookDispatcher;
+use crate::presentation::api::error::ApiError;
+#[cfg(feature = "openapi")]
+use crate::presentation::api::error::ErrorResponse;
+use crate::presentation::api::extractors::{Json, Scoped};
+
+/// Type alias — конкретный тип сервиса повторяющихся операций.
+pub type AppRecurringService = RecurringService<
+    RetryingAccountRepository<AnyAccountRepository>,
+    AnyTransactionRepository,
+    AnySnapshotRepository,
+    AnyIdempotencyRepository,
+    StaticExchangeRateProvider,
+    WebhookDispatcher<AnyWebhookRepository>,
+    AnyRecurringRepository,
+>;
+
+/// GET /api/accounts/:id/recurring — правила счёта.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/accounts/{id}/recurring",
+        summary = "List recurring rules",
+        description = "List recurring rules.",
+        tag = "recurring",
+        params(("id" = Uuid, Path, description = "Account ID")),
+        responses(
+            (status = 200, description = "Rules of the account", body = Vec<RecurringResponse>),
+            (status = 404, description = "Account not found", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn list_recurring(
+    Scoped(service): Scoped<AppRecurringService>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<RecurringResponse>>, ApiError> {
+    let rules = service.list_rules(id).await?;
+    Ok(Json(rules))
+}
+
+/// POST /api/accounts/:id/recurring — создание правила.
+///
+/// # Возвращает
+/// - 200 с созданным правилом
+/// - 400 если сумма не положительная или расписание не разобралось
+/// - 404 если счёт не найден
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/accounts/{id}/recurring",
+        summary = "Create a recurring rule",
+        description = "Without `starts_at` the first run is one schedule step from now.",
+        tag = "recurring",
+        params(("id" = Uuid, Path, description = "Account ID")),
+        request_body = CreateRecurringRequest,
+        responses(
+            (status = 200, description = "Rule created", body = RecurringResponse),
+            (status = 400, description = "Invalid amount or schedule", body = ErrorResponse),
+            (status = 404, description = "Account not found", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn create_recurring(
+    Scoped(service): Scoped<AppRecurringService>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<CreateRecurringRequest>,
+) -> Result<Json<RecurringResponse>, ApiError> {
+    let rule = service.create_rule(id, request).await?;
+    Ok(Json(rule))
+}
+
+/// GET /api/accounts/:id/recurring/:rule_id — одно правило.
+///
+/// `Path<(Uuid, Uuid)>` — оба параметра пути по порядку.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/accounts/{id}/recurring/{rule_id}",
+        summary = "Get a recurring rule",
+        description = "Get a recurring rule.",
+        tag = "recurring",
+        params(("id" = Uuid, Path, description = "Account ID"), ("rule_id" = Uuid, Path, description = "Recurring rule ID")),
+        responses(
+            (status = 200, description = "Rule", body = RecurringResponse),
+            (status = 404, description = "Account or rule not found", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn get_recurring(
+    Scoped(service): Scoped<AppRecurringService>,
+    Path((id, rule_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<RecurringResponse>, ApiError> {
+    let rule = service.get_rule(id, rule_id).await?;
+    Ok(Json(rule))
+}
+
+/// PATCH /api/accounts/:id/recurring/:rule_id — изменение правила.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        patch,
+        path = "/api/accounts/{id}/recurring/{rule_id}",
+        summary = "Update a recurring rule",
+        description = "Changing `schedule` without `next_run` reschedules from now; `note: null` removes the note.",
+        tag = "recurring",
+        params(("id" = Uuid, Path, description = "Account ID"), ("rule_id" = Uuid, Path, description = "Recurring rule ID")),
+        request_body = UpdateRecurringRequest,
+        responses(
+            (status = 200, description = "Updated rule", body = RecurringResponse),
+            (status = 400, description = "Invalid amount or schedule", body = ErrorResponse),
+            (status = 404, description = "Account or rule not found", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn update_recurring(
+    Scoped(service): Scoped<AppRecurringService>,
+    Path((id, rule_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<UpdateRecurringRequest>,
+) -> Result<Json<RecurringResponse>, ApiError> {
+    let rule = service.update_rule(id, rule_id, request).await?;
+    Ok(Json(rule))
+}
+
+/// DELETE /api/accounts/:id/recurring/:rule_id — удаление правила.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        delete,
+        path = "/api/accounts/{id}/recurring/{rule_id}",
+        summary = "Delete a recurring rule",
+        description = "Delete a recurring rule.",
+        tag = "recurring",
+        params(("id" = Uuid, Path, description = "Account ID"), ("rule_id" = Uuid, Path, description = "Recurring rule ID")),
+        responses(
+            (status = 200, description = "Rule deleted", body = MessageResponse),
+            (status = 404, description = "Account or rule not found", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn delete_recurring(
+    Scoped(service): Scoped<AppRecurringService>,
+    Path((id, rule_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<MessageResponse>, ApiError> {
+    service.delete_rule(id, rule_id).await?;
+    Ok(Json(MessageResponse::new("Recurring transaction deleted")))
+}
+
+/// POST /api/accounts/:id/currency?force= — смена валюты счёта.
+///
+/// Здесь, а не в `account_handlers`: перед сменой проверяются регулярные
+/// операции счёта (см. `RecurringService::change_currency`).
+///
+/// # Возвращает
+/// - 200 со счётом в новой валюте
+/// - 400 если код валюты неверный, совпадает с текущим или курс не положительный
+/// - 404 если счёт не найден
+/// - 409 если у счёта есть включённые правила, а `force=true` не передан
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/accounts/{id}/currency",
+        summary = "Change the account currency",
+        description = "Convert the balance and limits at `rate` and record a `currency_change` \
+            transaction. An account with enabled recurring rules requires `force=true`; \
+            the rules keep their amounts.",
+        tag = "accounts",
+        params(("id" = Uuid, Path, description = "Account ID"), ChangeCurrencyQuery),
+        request_body = ChangeCurrencyRequest,
+        responses(
+            (status = 200, description = "Account in the new currency", body = AccountResponse),
+            (status = 400, description = "Invalid currency or rate", body = ErrorResponse),
+            (status = 404, description = "Account not found", body = ErrorResponse),
+            (status = 409, description = "Account has recurring rules and force is not set", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn change_currency(
+    Scoped(service): Scoped<AppRecurringService>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ChangeCurrencyQuery>,
+    Json(request): Json<ChangeCurrencyRequest>,
+) -> Result<Json<AccountResponse>, ApiError> {
+    let account = service.change_currency(id, request, query.force).await?;
+    Ok(Json(account))
+}
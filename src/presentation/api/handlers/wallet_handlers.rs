@@ -0,0 +1,179 @@
+//! HTTP handlers для кошельков с несколькими валютами.
+
+use axum::extract::{Path, State};
+use uuid::Uuid;
+
+use crate::application::dto::{
+    CreateWalletRequest, MessageResponse, WalletOperationRequest, WalletResponse,
+};
+use crate::application::services::WalletService;
+use crate::infrastructure::database::AnyWalletRepository;
+use crate::presentation::api::error::ApiError;
+#[cfg(feature = "openapi")]
+use crate::presentation::api::error::ErrorResponse;
+use crate::presentation::api::extractors::Json;
+
+/// Type alias — конкретный тип сервиса кошельков.
+pub type AppWalletService = WalletService<AnyWalletRepository>;
+
+/// GET /api/wallets — все кошельки.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/wallets",
+        summary = "List wallets",
+        description = "All wallets with their per-currency balances, oldest first.",
+        tag = "wallets",
+        responses(
+            (status = 200, description = "All wallets", body = Vec<WalletResponse>),
+        ),
+    )
+)]
+pub async fn list_wallets(
+    State(service): State<AppWalletService>,
+) -> Result<Json<Vec<WalletResponse>>, ApiError> {
+    let wallets = service.list_wallets().await?;
+    Ok(Json(wallets))
+}
+
+/// POST /api/wallets — создание пустого кошелька.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/wallets",
+        summary = "Create a wallet",
+        description = "Create an empty wallet. Currencies appear in it with the first deposit.",
+        tag = "wallets",
+        request_body = CreateWalletRequest,
+        responses(
+            (status = 200, description = "Wallet created", body = WalletResponse),
+            (status = 400, description = "Invalid name", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn create_wallet(
+    State(service): State<AppWalletService>,
+    Json(request): Json<CreateWalletRequest>,
+) -> Result<Json<WalletResponse>, ApiError> {
+    let wallet = service.create_wallet(request).await?;
+    Ok(Json(wallet))
+}
+
+/// GET /api/wallets/:id — один кошелёк.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/wallets/{id}",
+        summary = "Get a wallet",
+        description = "Get a wallet with its per-currency balances.",
+        tag = "wallets",
+        params(("id" = Uuid, Path, description = "Wallet ID")),
+        responses(
+            (status = 200, description = "The wallet", body = WalletResponse),
+            (status = 404, description = "Wallet not found", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn get_wallet(
+    State(service): State<AppWalletService>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<WalletResponse>, ApiError> {
+    let wallet = service.get_wallet(id).await?;
+    Ok(Json(wallet))
+}
+
+/// POST /api/wallets/:id/deposit — пополнение в одной валюте.
+///
+/// # Возвращает
+/// - 200 с кошельком после пополнения
+/// - 400 если сумма не положительная или код валюты неверный
+/// - 404 если кошелёк не найден
+/// - 409 если кошелёк изменили параллельно
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/wallets/{id}/deposit",
+        summary = "Deposit into a wallet",
+        description = "Add `amount` to the wallet's balance in `currency`.",
+        tag = "wallets",
+        params(("id" = Uuid, Path, description = "Wallet ID")),
+        request_body = WalletOperationRequest,
+        responses(
+            (status = 200, description = "Wallet after the deposit", body = WalletResponse),
+            (status = 400, description = "Invalid amount or currency", body = ErrorResponse),
+            (status = 404, description = "Wallet not found", body = ErrorResponse),
+            (status = 409, description = "Wallet was modified concurrently", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn wallet_deposit(
+    State(service): State<AppWalletService>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<WalletOperationRequest>,
+) -> Result<Json<WalletResponse>, ApiError> {
+    let wallet = service.deposit(id, request).await?;
+    Ok(Json(wallet))
+}
+
+/// POST /api/wallets/:id/withdraw — снятие в одной валюте.
+///
+/// # Возвращает
+/// - 200 с кошельком после снятия
+/// - 400 если в этой валюте не хватает денег (или её нет совсем)
+/// - 404 если кошелёк не найден
+/// - 409 если кошелёк изменили параллельно
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/wallets/{id}/withdraw",
+        summary = "Withdraw from a wallet",
+        description = "Take `amount` from the wallet's balance in `currency`. Other currencies \
+            are never converted to cover a shortfall.",
+        tag = "wallets",
+        params(("id" = Uuid, Path, description = "Wallet ID")),
+        request_body = WalletOperationRequest,
+        responses(
+            (status = 200, description = "Wallet after the withdrawal", body = WalletResponse),
+            (status = 400, description = "Invalid amount or currency, or insufficient funds in that currency", body = ErrorResponse),
+            (status = 404, description = "Wallet not found", body = ErrorResponse),
+            (status = 409, description = "Wallet was modified concurrently", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn wallet_withdraw(
+    State(service): State<AppWalletService>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<WalletOperationRequest>,
+) -> Result<Json<WalletResponse>, ApiError> {
+    let wallet = service.withdraw(id, request).await?;
+    Ok(Json(wallet))
+}
+
+/// DELETE /api/wallets/:id — удаление кошелька вместе с остатками.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        delete,
+        path = "/api/wallets/{id}",
+        summary = "Delete a wallet",
+        description = "Delete a wallet and all its balances. This cannot be undone.",
+        tag = "wallets",
+        params(("id" = Uuid, Path, description = "Wallet ID")),
+        responses(
+            (status = 200, description = "Wallet deleted", body = MessageResponse),
+            (status = 404, description = "Wallet not found", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn delete_wallet(
+    State(service): State<AppWalletService>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<MessageResponse>, ApiError> {
+    service.delete_wallet(id).await?;
+    Ok(Json(MessageResponse::new("Wallet deleted")))
+}
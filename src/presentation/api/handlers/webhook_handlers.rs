@@ -0,0 +1,143 @@
+//! HTTP handlers для подписок на события (webhooks).
+
+use axum::extract::Path;
+use uuid::Uuid;
+
+use crate::application::dto::{
+    CreateWebhookRequest, MessageResponse, UpdateWebhookRequest, WebhookResponse,
+};
+use crate::application::services::WebhookService;
+use crate::infrastructure::database::AnyWebhookRepository;
+use crate::presentation::api::error::ApiError;
+#[cfg(feature = "openapi")]
+use crate::presentation::api::error::ErrorResponse;
+use crate::presentation::api::extractors::{Json, Scoped};
+
+/// Type alias — конкретный тип сервиса подписок.
+pub type AppWebhookService = WebhookService<AnyWebhookRepository>;
+
+/// GET /api/webhooks — все подписки.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/webhooks",
+        summary = "List webhooks",
+        description = "List webhooks. Secrets are never returned.",
+        tag = "webhooks",
+        responses(
+            (status = 200, description = "All webhooks", body = Vec<WebhookResponse>),
+        ),
+    )
+)]
+pub async fn list_webhooks(
+    Scoped(service): Scoped<AppWebhookService>,
+) -> Result<Json<Vec<WebhookResponse>>, ApiError> {
+    let webhooks = service.list_webhooks().await?;
+    Ok(Json(webhooks))
+}
+
+/// POST /api/webhooks — создание подписки.
+///
+/// # Возвращает
+/// - 200 с созданной подпиской (без секрета)
+/// - 400 если URL не `http://` или секрет короче 16 символов
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        post,
+        path = "/api/webhooks",
+        summary = "Create a webhook",
+        description = "After every balance change the server POSTs the event to `url`, \
+            signed with `X-Webhook-Signature: sha256=<hex HMAC-SHA256 of the body>`. \
+            Only `http://` URLs are supported.",
+        tag = "webhooks",
+        request_body = CreateWebhookRequest,
+        responses(
+            (status = 200, description = "Webhook created", body = WebhookResponse),
+            (status = 400, description = "Invalid URL or secret", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn create_webhook(
+    Scoped(service): Scoped<AppWebhookService>,
+    Json(request): Json<CreateWebhookRequest>,
+) -> Result<Json<WebhookResponse>, ApiError> {
+    let webhook = service.create_webhook(request).await?;
+    Ok(Json(webhook))
+}
+
+/// GET /api/webhooks/:id — одна подписка.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        get,
+        path = "/api/webhooks/{id}",
+        summary = "Get a webhook",
+        description = "Get a webhook.",
+        tag = "webhooks",
+        params(("id" = Uuid, Path, description = "Webhook ID")),
+        responses(
+            (status = 200, description = "The webhook", body = WebhookResponse),
+            (status = 404, description = "Webhook not found", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn get_webhook(
+    Scoped(service): Scoped<AppWebhookService>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<WebhookResponse>, ApiError> {
+    let webhook = service.get_webhook(id).await?;
+    Ok(Json(webhook))
+}
+
+/// PATCH /api/webhooks/:id — изменение подписки.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        patch,
+        path = "/api/webhooks/{id}",
+        summary = "Update a webhook",
+        description = "Change the URL, rotate the secret or pause deliveries with `enabled: false`.",
+        tag = "webhooks",
+        params(("id" = Uuid, Path, description = "Webhook ID")),
+        request_body = UpdateWebhookRequest,
+        responses(
+            (status = 200, description = "Updated webhook", body = WebhookResponse),
+            (status = 400, description = "Invalid URL or secret", body = ErrorResponse),
+            (status = 404, description = "Webhook not found", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn update_webhook(
+    Scoped(service): Scoped<AppWebhookService>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<UpdateWebhookRequest>,
+) -> Result<Json<WebhookResponse>, ApiError> {
+    let webhook = service.update_webhook(id, request).await?;
+    Ok(Json(webhook))
+}
+
+/// DELETE /api/webhooks/:id — удаление подписки.
+#[cfg_attr(
+    feature = "openapi",
+    utoipa::path(
+        delete,
+        path = "/api/webhooks/{id}",
+        summary = "Delete a webhook",
+        description = "Delete a webhook.",
+        tag = "webhooks",
+        params(("id" = Uuid, Path, description = "Webhook ID")),
+        responses(
+            (status = 200, description = "Webhook deleted", body = MessageResponse),
+            (status = 404, description = "Webhook not found", body = ErrorResponse),
+        ),
+    )
+)]
+pub async fn delete_webhook(
+    Scoped(service): Scoped<AppWebhookService>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<MessageResponse>, ApiError> {
+    service.delete_webhook(id).await?;
+    Ok(Json(MessageResponse::new("Webhook deleted")))
+}
@@ -0,0 +1,182 @@
+//! Аутентификация по API-ключам (`API_KEYS`).
+//!
+//! Клиент присылает ключ в `Authorization: Bearer <key>`. Сервер хранит
+//! не ключи, а их SHA-256: утечка конфигурации не раскрывает ключей.
+//! Нет заголовка или ключ не подошёл — 401 с `WWW-Authenticate: Bearer`.
+//!
+//! Ключи не заданы — проверка выключена и API открыт, как раньше
+//! (при старте в лог пишется предупреждение, см. `main`).
+//!
+//! Ключ принадлежит пользователю: запись `API_KEYS` — `user:hash`
+//! (или просто `hash` — пользователь по умолчанию). Middleware кладёт
+//! пользователя в extensions запроса как `CurrentUser`, а handlers
+//! работают только с его счетами (см. `extractors::Scoped`).
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+use subtle::{ConditionallySelectable, ConstantTimeEq};
+
+use crate::domain::entities::Account;
+use crate::presentation::api::error::ApiError;
+
+/// Пользователь, от имени которого выполняется запрос.
+///
+/// Проверка ключей выключена — все запросы от `Account::DEFAULT_USER`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurrentUser(pub String);
+
+impl Default for CurrentUser {
+    fn default() -> Self {
+        Self(Account::DEFAULT_USER.to_string())
+    }
+}
+
+/// Хэши допустимых ключей и их пользователи.
+///
+/// # Почему хэш, а не сам ключ?
+/// Ключ — случайная строка с большой энтропией, поэтому соль и
+/// медленный хэш (как у паролей) не нужны: перебрать SHA-256 от
+/// 32 случайных байт невозможно. Получить хэш для `API_KEYS`:
+/// `printf '%s' "$KEY" | sha256sum`.
+#[derive(Clone, Default)]
+pub struct ApiKeys {
+    hashes: Arc<Vec<[u8; 32]>>,
+    users: Arc<Vec<String>>,
+}
+
+impl ApiKeys {
+    /// Разбирает записи `user:hash` или `hash` — SHA-256 ключа в hex.
+    ///
+    /// Без пользователя ключ принадлежит `Account::DEFAULT_USER`: так
+    /// продолжают работать конфигурации, где пользователей не было.
+    ///
+    /// # Errors
+    /// Строка с описанием, если хэш — не 64 hex-символа или пользователь
+    /// пустой. Такой ключ не подошёл бы никогда, и ошибка в конфигурации
+    /// осталась бы незамеченной — поэтому запуск останавливается.
+    pub fn from_hashes(entries: &[String]) -> Result<Self, String> {
+        let mut hashes = Vec::with_capacity(entries.len());
+        let mut users = Vec::with_capacity(entries.len());
+
+        for entry in entries {
+            let entry = entry.trim();
+            let (user, raw) = match entry.split_once(':') {
+                Some((user, raw)) => (user.trim(), raw.trim()),
+                None => (Account::DEFAULT_USER, entry),
+            };
+            if user.is_empty() {
+                return Err(format!("'{}' has an empty user name", entry));
+            }
+
+            let mut hash = [0_u8; 32];
+            hex::decode_to_slice(raw, &mut hash)
+                .map_err(|_| format!("'{}' is not a hex-encoded SHA-256 hash", raw))?;
+            hashes.push(hash);
+            users.push(user.to_string());
+        }
+
+        Ok(Self {
+            hashes: Arc::new(hashes),
+            users: Arc::new(users),
+        })
+    }
+
+    /// Ключей нет — проверка выключена.
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    /// Пользователь, которому принадлежит `key`; `None` — ключ не подошёл.
+    ///
+    /// # Почему без раннего выхода?
+    /// Сравнение в постоянном времени (`ct_eq`) и проход по всем
+    /// хэшам: по времени ответа нельзя понять ни сколько байт совпало,
+    /// ни с каким по счёту ключом. Номер подошедшего ключа запоминается
+    /// тоже без ветвления (`conditional_assign`).
+    fn user_for(&self, key: &str) -> Option<CurrentUser> {
+        let hash: [u8; 32] = Sha256::digest(key.as_bytes()).into();
+        let mut found = subtle::Choice::from(0);
+        let mut index = 0_u32;
+
+        for (i, stored) in self.hashes.iter().enumerate() {
+            let matches = stored.ct_eq(&hash);
+            index.conditional_assign(&(i as u32), matches);
+            found |= matches;
+        }
+
+        bool::from(found).then(|| CurrentUser(self.users[index as usize].clone()))
+    }
+}
+
+/// Middleware: 403, если запрос не от `Account::DEFAULT_USER`.
+///
+/// # Зачем?
+/// Резервная копия и кошельки пока общие на всю установку, без
+/// владельца. Открыть их всем пользователям значило бы отдать каждому
+/// чужие счета (через backup), поэтому ими управляет только
+/// пользователь по умолчанию — администратор.
+pub async fn require_default_user(request: Request, next: Next) -> Response {
+    let is_default = request
+        .extensions()
+        .get::<CurrentUser>()
+        .cloned()
+        .unwrap_or_default()
+        == CurrentUser::default();
+
+    if is_default {
+        next.run(request).await
+    } else {
+        ApiError::forbidden("Only the default user can access this endpoint").into_response()
+    }
+}
+
+/// Ключ из `Authorization: Bearer <key>`; схема — без учёта регистра.
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    let value = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, token) = value.split_once(' ')?;
+    scheme
+        .eq_ignore_ascii_case("bearer")
+        .then(|| token.trim())
+        .filter(|token| !token.is_empty())
+}
+
+/// Middleware: 401, если ключи заданы, а запрос без подходящего ключа.
+///
+/// Ключ подошёл — в extensions запроса кладётся его `CurrentUser`.
+pub async fn require_api_key(
+    State(keys): State<ApiKeys>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if keys.is_empty() {
+        request.extensions_mut().insert(CurrentUser::default());
+        return next.run(request).await;
+    }
+
+    let user = bearer_token(request.headers()).map(|token| keys.user_for(token));
+    match user {
+        Some(Some(user)) => {
+            request.extensions_mut().insert(user);
+            next.run(request).await
+        }
+        token => {
+            let message = if token.is_some() {
+                "Invalid API key"
+            } else {
+                "Missing API key: send 'Authorization: Bearer <key>'"
+            };
+            (
+                [(header::WWW_AUTHENTICATE, "Bearer")],
+                ApiError::unauthorized(message),
+            )
+                .into_response()
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Сжатие ответов (gzip, brotli).
+//!
+//! Клиент перечисляет, что умеет, в `Accept-Encoding`; `CompressionLayer`
+//! выбирает алгоритм и ставит `Content-Encoding` и `Vary: Accept-Encoding`.
+//! Без `Accept-Encoding` ответ уходит как есть.
+
+use tower_http::compression::predicate::{And, NotForContentType, Predicate, SizeAbove};
+use tower_http::compression::CompressionLayer;
+
+/// Ответы меньше этого не сжимаются — 1 КБ.
+///
+/// # Почему порог?
+/// Ошибка или один счёт — пара сотен байт: заголовки сжатия и время
+/// на него стоят больше, чем сэкономленные байты.
+const MIN_COMPRESSED_BYTES: u16 = 1024;
+
+/// Строит `CompressionLayer` для всех маршрутов.
+///
+/// # Потоковые ответы
+/// У CSV-выгрузки нет `Content-Length`: размер заранее неизвестен,
+/// поэтому порог её не останавливает. Тело сжимается по частям по мере
+/// того, как handler его отдаёт, — выгрузка остаётся потоковой.
+///
+/// Картинки (иконки Swagger UI) уже сжаты, их не трогаем.
+pub fn compression_layer() -> CompressionLayer<And<SizeAbove, NotForContentType>> {
+    CompressionLayer::new()
+        .gzip(true)
+        .br(true)
+        .compress_when(SizeAbove::new(MIN_COMPRESSED_BYTES).and(NotForContentType::IMAGES))
+}
@@ -0,0 +1,67 @@
+//! Настройка CORS (Cross-Origin Resource Sharing).
+//!
+//! Браузер пускает фронтенд с другого origin к API, только если
+//! ответ содержит `Access-Control-Allow-Origin` с этим origin.
+
+use axum::http::{header, HeaderValue, Method};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+use super::request_id::REQUEST_ID_HEADER;
+
+/// Строит `CorsLayer` по списку разрешённых origin.
+///
+/// # Варианты списка
+/// - пустой — CORS-заголовки не отдаются никому (по умолчанию)
+/// - `["*"]` — любой origin, но без credentials
+/// - явный список — только эти origin, credentials разрешены
+///
+/// # Почему credentials только для явного списка?
+/// Спецификация запрещает `Access-Control-Allow-Origin: *` вместе с
+/// `Access-Control-Allow-Credentials: true`: иначе любой сайт мог бы
+/// делать запросы с cookie пользователя. tower-http в таком случае паникует.
+///
+/// Preflight `OPTIONS` запросы `CorsLayer` обрабатывает сам —
+/// до handlers они не доходят.
+pub fn cors_layer(allowed_origins: &[String]) -> CorsLayer {
+    let layer = CorsLayer::new()
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::PATCH,
+            Method::DELETE,
+        ])
+        // Idempotency-Key — для повторов deposit/withdraw,
+        // Authorization — ключ API (см. middleware::auth)
+        .allow_headers([
+            header::CONTENT_TYPE,
+            header::AUTHORIZATION,
+            header::HeaderName::from_static("idempotency-key"),
+        ])
+        // Без expose_headers браузерный JS не увидит x-request-id
+        .expose_headers([REQUEST_ID_HEADER]);
+
+    if allowed_origins.iter().any(|origin| origin == "*") {
+        return layer.allow_origin(Any);
+    }
+
+    let origins: Vec<HeaderValue> = allowed_origins
+        .iter()
+        .filter_map(|origin| match HeaderValue::from_str(origin) {
+            Ok(value) => Some(value),
+            Err(_) => {
+                tracing::warn!("Ignoring invalid CORS origin '{}'", origin);
+                None
+            }
+        })
+        .collect();
+
+    if origins.is_empty() {
+        // Пустой список не совпадает ни с одним origin
+        return layer.allow_origin(AllowOrigin::list(origins));
+    }
+
+    layer
+        .allow_origin(AllowOrigin::list(origins))
+        .allow_credentials(true)
+}
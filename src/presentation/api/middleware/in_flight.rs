@@ -0,0 +1,51 @@
+//! Middleware для подсчёта запросов "в полёте".
+//!
+//! Нужен для graceful shutdown: чтобы знать, сколько запросов
+//! сервер дожидается перед остановкой.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use axum::{extract::Request, extract::State, middleware::Next, response::Response};
+
+/// Счётчик запросов, которые сейчас обрабатываются.
+///
+/// # Почему `Arc<AtomicUsize>`?
+/// Middleware выполняется параллельно в разных задачах tokio.
+/// Атомарный счётчик не требует мьютекса, `Arc` — делит его между клонами.
+#[derive(Clone, Default)]
+pub struct InFlightRequests(Arc<AtomicUsize>);
+
+impl InFlightRequests {
+    /// Текущее количество запросов в обработке.
+    pub fn current(&self) -> usize {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Guard, уменьшающий счётчик в `Drop`.
+///
+/// # Почему не просто `fetch_sub` после `next.run()`?
+/// Если клиент оборвал соединение, Axum бросит (drop) future запроса,
+/// и код после `.await` не выполнится. `Drop` выполнится всегда.
+struct InFlightGuard(Arc<AtomicUsize>);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Middleware: +1 при входе запроса, -1 при выходе (через guard).
+///
+/// Подключается через `axum::middleware::from_fn_with_state`.
+pub async fn track_in_flight(
+    State(counter): State<InFlightRequests>,
+    request: Request,
+    next: Next,
+) -> Response {
+    counter.0.fetch_add(1, Ordering::SeqCst);
+    let _guard = InFlightGuard(counter.0.clone());
+
+    next.run(request).await
+}
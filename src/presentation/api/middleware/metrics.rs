@@ -0,0 +1,85 @@
+//! Middleware для метрик HTTP-запросов в формате Prometheus.
+//!
+//! Метрики пишутся через фасад `metrics` (как логи через `tracing`),
+//! а хранит и отдаёт их `metrics-exporter-prometheus`.
+
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use metrics_exporter_prometheus::{BuildError, Matcher, PrometheusBuilder, PrometheusHandle};
+
+/// Счётчик запросов: метки `method`, `route`, `status` (`2xx`, `4xx`...).
+pub const REQUESTS_TOTAL: &str = "http_requests_total";
+
+/// Гистограмма длительности запросов: метки `method`, `route`.
+pub const REQUEST_DURATION_SECONDS: &str = "http_request_duration_seconds";
+
+/// Границы корзин гистограммы длительности, в секундах.
+///
+/// От 5 мс (запрос к прогретому пулу) до 10 с (дольше — уже таймаут).
+const DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Устанавливает глобальный Prometheus-рекордер.
+///
+/// Возвращённый `PrometheusHandle` рендерит текущие значения
+/// для `GET /metrics`.
+///
+/// # Почему явные корзины?
+/// Без них экспортёр пишет гистограммы как summary (квантили),
+/// а квантили с разных инстансов нельзя агрегировать.
+///
+/// # Errors
+/// Рекордер уже установлен (вызывать один раз при старте).
+pub fn install_metrics_recorder() -> Result<PrometheusHandle, BuildError> {
+    PrometheusBuilder::new()
+        .set_buckets_for_metric(
+            Matcher::Full(REQUEST_DURATION_SECONDS.to_string()),
+            DURATION_BUCKETS,
+        )?
+        .install_recorder()
+}
+
+/// Middleware: считает запрос и время его обработки.
+///
+/// # Почему `MatchedPath`, а не URI?
+/// В URI есть ID: `/api/accounts/1b4e...`. Каждый ID стал бы отдельной
+/// серией в Prometheus. `MatchedPath` — шаблон маршрута
+/// (`/api/accounts/:id`), серий столько, сколько маршрутов.
+///
+/// `MatchedPath` появляется только после роутинга, поэтому middleware
+/// подключается через `route_layer` (см. `create_router`). Заодно
+/// несуществующие пути (404 без маршрута) в метрики не попадают.
+pub async fn track_metrics(request: Request, next: Next) -> Response {
+    let start = Instant::now();
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_owned())
+        .unwrap_or_else(|| "unmatched".into());
+
+    let response = next.run(request).await;
+
+    let status = format!("{}xx", response.status().as_u16() / 100);
+    metrics::counter!(
+        REQUESTS_TOTAL,
+        "method" => method.clone(),
+        "route" => route.clone(),
+        "status" => status
+    )
+    .increment(1);
+    metrics::histogram!(
+        REQUEST_DURATION_SECONDS,
+        "method" => method,
+        "route" => route
+    )
+    .record(start.elapsed().as_secs_f64());
+
+    response
+}
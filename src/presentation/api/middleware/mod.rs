@@ -0,0 +1,21 @@
+mod auth;
+mod compression;
+mod cors;
+mod in_flight;
+mod metrics;
+mod pretty_json;
+mod rate_limit;
+mod read_only;
+mod request_id;
+mod timeout;
+
+pub use auth::{require_api_key, require_default_user, ApiKeys, CurrentUser};
+pub use compression::compression_layer;
+pub use cors::cors_layer;
+pub use in_flight::{track_in_flight, InFlightRequests};
+pub use metrics::{install_metrics_recorder, track_metrics};
+pub use pretty_json::pretty_json;
+pub use rate_limit::{rate_limit, RateLimiter};
+pub use read_only::reject_writes;
+pub use request_id::{assign_request_id, current_request_id};
+pub use timeout::handle_timeout;
@@ -0,0 +1,73 @@
+//! Форматированный JSON по `?pretty=true` — для отладки руками.
+//!
+//! По умолчанию ответы компактные: так меньше байт. С `?pretty=true`
+//! JSON-ответ переформатируется с отступами; `Content-Type` остаётся
+//! `application/json`, заголовки те же, кроме `Content-Length`.
+//!
+//! # Почему middleware, а не `Json` из `extractors`?
+//! `IntoResponse` у `Json` не видит запроса, а значит и `?pretty`.
+//! Middleware видит оба и работает для всех ответов сразу, включая
+//! ошибки (`ApiError`) и ответы, собранные не через `Json`.
+//!
+//! Порядок полей сохраняется (feature `preserve_order` у `serde_json`),
+//! поэтому форматированный ответ отличается от обычного только пробелами.
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::{header, HeaderMap},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::presentation::api::error::ApiError;
+
+/// Нужен ли форматированный JSON: в query string есть `pretty=true`.
+///
+/// Без разбора всей строки: параметры handler'а сюда не относятся,
+/// а их extractor `pretty` просто не заметит.
+fn wants_pretty(query: Option<&str>) -> bool {
+    query.is_some_and(|query| query.split('&').any(|pair| pair == "pretty=true"))
+}
+
+/// Ответ — JSON (`application/json`, с параметрами вроде `charset` тоже).
+fn is_json(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"))
+}
+
+/// Middleware: с `?pretty=true` переформатирует JSON-ответ с отступами.
+///
+/// Не JSON (CSV, метрики) — ответ как есть. Тело читается целиком,
+/// поэтому потоковые ответы сюда не попадают: все они не JSON.
+/// Тело не разобралось как JSON — тоже отдаётся как есть.
+pub async fn pretty_json(request: Request, next: Next) -> Response {
+    let pretty = wants_pretty(request.uri().query());
+    let response = next.run(request).await;
+    if !pretty || !is_json(response.headers()) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    // Тело уже собрано handler'ом в памяти — лимит не нужен
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            tracing::error!("Failed to read response body for pretty JSON: {}", err);
+            return ApiError::internal("Internal server error").into_response();
+        }
+    };
+
+    let formatted = serde_json::from_slice::<serde_json::Value>(&bytes)
+        .and_then(|value| serde_json::to_vec_pretty(&value));
+    let body = match formatted {
+        Ok(formatted) => {
+            parts.headers.remove(header::CONTENT_LENGTH);
+            Body::from(formatted)
+        }
+        Err(_) => Body::from(bytes),
+    };
+    Response::from_parts(parts, body)
+}
@@ -0,0 +1,161 @@
+//! Ограничение частоты запросов с одного IP (token bucket).
+//!
+//! Каждому клиенту — "ведро" на `RATE_LIMIT_PER_MINUTE` жетонов.
+//! Запрос забирает жетон, ведро равномерно пополняется за минуту.
+//! Пустое ведро — 429 и `Retry-After`: через сколько секунд появится жетон.
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderMap},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use dashmap::DashMap;
+
+use crate::presentation::api::error::ApiError;
+
+/// Ведро одного клиента.
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Состояние лимитера, общее для всех запросов.
+///
+/// # Почему `DashMap`, а не `Mutex<HashMap>`?
+/// Middleware выполняется на каждом запросе во всех потоках tokio.
+/// `DashMap` делит карту на шарды со своими блокировками, поэтому
+/// запросы разных клиентов почти не ждут друг друга.
+#[derive(Clone)]
+pub struct RateLimiter {
+    buckets: Arc<DashMap<IpAddr, Bucket>>,
+    per_minute: u32,
+    trust_forwarded_for: bool,
+}
+
+impl RateLimiter {
+    /// Лимитер на `per_minute` запросов в минуту с одного IP.
+    ///
+    /// С `trust_forwarded_for` адрес клиента берётся из `X-Forwarded-For`
+    /// (см. `client_ip`).
+    pub fn new(per_minute: u32, trust_forwarded_for: bool) -> Self {
+        Self {
+            buckets: Arc::new(DashMap::new()),
+            per_minute,
+            trust_forwarded_for,
+        }
+    }
+
+    /// Забирает жетон клиента `ip`.
+    ///
+    /// # Errors
+    /// Жетонов нет — через сколько появится следующий.
+    fn acquire(&self, ip: IpAddr, now: Instant) -> Result<(), Duration> {
+        let capacity = f64::from(self.per_minute);
+        let per_second = capacity / 60.0;
+
+        let mut bucket = self.buckets.entry(ip).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+        });
+
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * per_second).min(capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / per_second))
+        }
+    }
+
+    /// Удаляет вёдра клиентов, не приходивших минуту и дольше.
+    ///
+    /// За минуту ведро наполняется целиком, то есть ничем не отличается
+    /// от нового — хранить его незачем.
+    fn remove_idle(&self, now: Instant) {
+        self.buckets.retain(|_, bucket| {
+            now.saturating_duration_since(bucket.updated) < Duration::from_secs(60)
+        });
+    }
+
+    /// Фоновая очистка: раз в `interval` удаляет простаивающие вёдра.
+    ///
+    /// # Зачем?
+    /// Без неё карта растёт с каждым новым адресом и никогда не уменьшается.
+    /// Запускается через `tokio::spawn` и живёт, пока работает runtime.
+    pub async fn run_cleanup(self, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            self.remove_idle(Instant::now());
+        }
+    }
+
+    /// IP клиента: из `X-Forwarded-For` за прокси, иначе адрес сокета.
+    ///
+    /// # Почему последний адрес `X-Forwarded-For`, а не первый?
+    /// Клиент может прислать заголовок сам, и прокси лишь допишет в конец
+    /// адрес, с которого пришло соединение. Первым адресом клиент
+    /// управляет, последним — нет.
+    ///
+    /// Без `trust_forwarded_for` заголовок не читается совсем: без прокси
+    /// им можно подменить любой адрес и обойти лимит.
+    fn client_ip(&self, headers: &HeaderMap, peer: Option<SocketAddr>) -> Option<IpAddr> {
+        if self.trust_forwarded_for {
+            let forwarded = headers
+                .get_all("x-forwarded-for")
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .flat_map(|value| value.split(','))
+                .last()
+                .and_then(|ip| ip.trim().parse().ok());
+            if forwarded.is_some() {
+                return forwarded;
+            }
+        }
+        peer.map(|addr| addr.ip())
+    }
+}
+
+/// Middleware: 429 с `Retry-After`, если клиент исчерпал лимит.
+///
+/// Адрес сокета приходит в `ConnectInfo` — для этого сервер запускается
+/// через `into_make_service_with_connect_info` (см. `main`). Запрос,
+/// адрес которого неизвестен, не ограничивается.
+pub async fn rate_limit(
+    State(limiter): State<RateLimiter>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|info| info.0);
+
+    let Some(ip) = limiter.client_ip(request.headers(), peer) else {
+        return next.run(request).await;
+    };
+
+    match limiter.acquire(ip, Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(wait) => {
+            // Retry-After — целые секунды, округляем вверх
+            let retry_after = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+            (
+                [(header::RETRY_AFTER, retry_after.to_string())],
+                ApiError::too_many_requests(format!(
+                    "Rate limit of {} requests per minute exceeded; retry in {} s",
+                    limiter.per_minute, retry_after
+                )),
+            )
+                .into_response()
+        }
+    }
+}
@@ -0,0 +1,36 @@
+//! Режим только для чтения (`READ_ONLY` или `--read-only`).
+//!
+//! На время обслуживания БД (перенос, восстановление из копии) API
+//! продолжает отдавать данные, но ничего не меняет: запросы с методами,
+//! которые пишут, получают 503 `{"error": "service is read-only"}`.
+
+use axum::{
+    extract::Request,
+    http::Method,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::presentation::api::error::ApiError;
+
+/// Middleware: 503 на всё, кроме GET, HEAD и OPTIONS.
+///
+/// # Почему по методу, а не в сервисе?
+/// Пишут все POST, PUT, PATCH и DELETE маршруты, а сервисов пять —
+/// проверка в одном месте не даст забыть новый use case. Исключение
+/// одно и в пользу осторожности: симуляция и `?dry_run=true` тоже
+/// отклоняются, хотя ничего не пишут.
+///
+/// # Почему 503, а не 405?
+/// Метод для маршрута верный, сервис временно недоступен для записи —
+/// клиент может повторить запрос после окончания обслуживания.
+pub async fn reject_writes(request: Request, next: Next) -> Response {
+    if matches!(
+        *request.method(),
+        Method::GET | Method::HEAD | Method::OPTIONS
+    ) {
+        next.run(request).await
+    } else {
+        ApiError::service_unavailable("service is read-only").into_response()
+    }
+}
@@ -0,0 +1,68 @@
+//! Middleware, присваивающий каждому запросу уникальный ID.
+//!
+//! ID попадает в логи (через tracing span), в заголовок `x-request-id`
+//! ответа и в тело ошибок API — так жалобу клиента можно найти в логах.
+
+use std::time::Instant;
+
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use tracing::Instrument;
+use uuid::Uuid;
+
+/// Имя заголовка, в котором ID возвращается клиенту.
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+tokio::task_local! {
+    /// ID запроса, который сейчас обрабатывается в этой задаче.
+    ///
+    /// # Почему task-local, а не extension запроса?
+    /// `ApiError` превращается в response в `IntoResponse`, где самого
+    /// запроса уже нет. Task-local виден из любого кода внутри
+    /// `REQUEST_ID.scope(...)`, не протаскивая ID через все слои.
+    static REQUEST_ID: Uuid;
+}
+
+/// ID текущего запроса. `None` вне middleware (например, в фоновых задачах).
+pub fn current_request_id() -> Option<Uuid> {
+    REQUEST_ID.try_with(|id| *id).ok()
+}
+
+/// Middleware: ID запроса, span с ним и строка лога на каждый запрос.
+///
+/// # Что логируется
+/// Метод, путь, статус и время обработки — одной строкой после ответа.
+/// Все логи внутри обработки (например, ошибки репозитория) попадают
+/// в span `request` и несут тот же `request_id`.
+pub async fn assign_request_id(request: Request, next: Next) -> Response {
+    let request_id = Uuid::new_v4();
+    let method = request.method().clone();
+    let path = request.uri().path().to_owned();
+
+    let span = tracing::info_span!("request", %request_id, %method, %path);
+    let started = Instant::now();
+
+    let mut response = REQUEST_ID
+        .scope(request_id, next.run(request))
+        .instrument(span.clone())
+        .await;
+
+    span.in_scope(|| {
+        tracing::info!(
+            status = response.status().as_u16(),
+            latency_ms = started.elapsed().as_millis() as u64,
+            "request completed"
+        );
+    });
+
+    // UUID в строковом виде всегда валидное значение заголовка
+    if let Ok(value) = HeaderValue::from_str(&request_id.to_string()) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+
+    response
+}
@@ -0,0 +1,36 @@
+//! Ограничение времени обработки запроса (`REQUEST_TIMEOUT_SECS`).
+//!
+//! Сам таймаут — `tower::timeout::TimeoutLayer`: он отменяет handler,
+//! если тот не ответил вовремя, и возвращает ошибку `Elapsed`.
+//! Axum требует, чтобы слои не возвращали ошибок, поэтому перед ним
+//! стоит `HandleErrorLayer` с `handle_timeout`, который превращает
+//! ошибку в ответ.
+//!
+//! # Почему не `tower_http::timeout::TimeoutLayer`?
+//! Он отвечает 408 с пустым телом. 408 значит "клиент слишком долго
+//! присылал запрос", а здесь не успел сервер — это 504, и тело
+//! в нашем формате ошибок, с `request_id`.
+//!
+//! # Что считается
+//! Время до готовности ответа, включая чтение тела extractor'ом.
+//! Потоковое тело (выгрузка CSV) отдаётся уже после — его передача
+//! таймаутом не ограничена.
+
+use axum::BoxError;
+use tower::timeout::error::Elapsed;
+
+use crate::presentation::api::error::ApiError;
+
+/// Превращает ошибку слоя таймаута в ответ.
+///
+/// Handlers не возвращают ошибок уровня tower (`Infallible`), так что
+/// кроме `Elapsed` сюда ничего не придёт; на всякий случай — 500.
+pub async fn handle_timeout(err: BoxError) -> ApiError {
+    if err.is::<Elapsed>() {
+        tracing::warn!("Request timed out");
+        ApiError::gateway_timeout("Request took too long to process")
+    } else {
+        tracing::error!("Unhandled middleware error: {}", err);
+        ApiError::internal("Internal server error")
+    }
+}
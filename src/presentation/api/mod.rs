@@ -1,3 +1,8 @@
+pub mod csv;
 pub mod error;
+pub mod extractors;
 pub mod handlers;
+pub mod middleware;
+#[cfg(feature = "openapi")]
+pub mod openapi;
 pub mod routes;
@@ -0,0 +1,447 @@
+//! Спецификация OpenAPI и Swagger UI (feature `openapi`).
+//!
+//! - `GET /api-docs/openapi.json` — спецификация, из неё генерируют клиентов
+//! - `GET /swagger-ui` — интерактивная документация
+//! - `GET /api/schema` и `GET /api/schema/:name` — JSON Schema отдельных DTO
+//!
+//! # Откуда берётся спецификация?
+//! Её собирает `utoipa` из атрибутов `#[utoipa::path]` на handlers
+//! и `#[derive(ToSchema)]` на DTO. Отдельного YAML нет — описание
+//! живёт рядом с кодом и меняется вместе с ним.
+//!
+//! # Почему feature, а не всегда?
+//! Swagger UI — это статика (JS/CSS), вшитая в бинарник. В production
+//! она не нужна: `cargo build --release` без `--features openapi`
+//! не тянет ни `utoipa`, ни файлы UI.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::routing::get;
+use axum::Router;
+use serde_json::{json, Map, Value};
+use utoipa::openapi::schema::{ObjectBuilder, Schema, Type};
+use utoipa::openapi::server::Server;
+use utoipa::openapi::RefOr;
+use utoipa::{OpenApi, PartialSchema, ToSchema};
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::application::dto::{CurrencyAmount, WithdrawAmount};
+use crate::domain::entities::{AccountType, AuditAction, RecurringKind, TransactionKind};
+use crate::domain::value_objects::{Money, Schedule};
+use crate::presentation::api::error::ApiError;
+use crate::presentation::api::extractors::Json;
+use crate::presentation::api::handlers;
+use crate::presentation::api::routes::nest_under;
+
+/// Описание API целиком.
+///
+/// Схемы DTO перечислять не нужно: `utoipa` находит их сам
+/// по телам запросов и ответов в `paths`.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Finance Tracker API",
+        description = "Accounts, transactions, transfers and recurring payments.\n\n\
+            Every error response has the shape `{\"error\": \"...\", \"request_id\": \"...\"}`; \
+            field validation errors add `fields`, a map from field name to reason. \
+            Any endpoint with a JSON body also answers 400 for invalid JSON (naming the field) \
+            and 413 for a body over the size limit. Too many requests from one IP get 429 \
+            with a `Retry-After` header. A request that takes longer than `REQUEST_TIMEOUT_SECS` \
+            gets 504. A temporarily unavailable database gives 503 with `Retry-After`; a server in read-only mode answers every write with 503. When the server has `API_KEYS` configured, every `/api` endpoint requires \
+            `Authorization: Bearer <key>` and answers 401 without a valid key. Each key belongs \
+            to a user, who sees only their own accounts and webhooks: other users' answer 404. \
+            Backup and wallets answer 403 to everyone but the `default` user."
+    ),
+    paths(
+        handlers::create_account,
+        handlers::create_accounts,
+        handlers::get_accounts,
+        handlers::search_accounts,
+        handlers::get_account,
+        handlers::get_account_by_name,
+        handlers::ensure_account,
+        handlers::update_account,
+        handlers::delete_account,
+        handlers::restore_account,
+        handlers::archive_account,
+        handlers::unarchive_account,
+        handlers::close_account,
+        handlers::change_currency,
+        handlers::deposit,
+        handlers::withdraw,
+        handlers::apply_interest,
+        handlers::transfer,
+        handlers::get_transactions,
+        handlers::update_transaction,
+        handlers::reverse_transaction,
+        handlers::export_transactions_csv,
+        handlers::get_balance_history,
+        handlers::account_audit,
+        handlers::get_statement,
+        handlers::balance_at,
+        handlers::account_info,
+        handlers::reconcile,
+        handlers::fix_balance,
+        handlers::category_report,
+        handlers::activity,
+        handlers::net_worth,
+        handlers::stats,
+        handlers::simulate,
+        handlers::list_recurring,
+        handlers::create_recurring,
+        handlers::get_recurring,
+        handlers::update_recurring,
+        handlers::delete_recurring,
+        handlers::list_webhooks,
+        handlers::create_webhook,
+        handlers::get_webhook,
+        handlers::update_webhook,
+        handlers::delete_webhook,
+        handlers::list_wallets,
+        handlers::create_wallet,
+        handlers::get_wallet,
+        handlers::wallet_deposit,
+        handlers::wallet_withdraw,
+        handlers::delete_wallet,
+        handlers::export_backup,
+        handlers::import_backup,
+        handlers::health,
+        handlers::ready,
+        handlers::version,
+    ),
+    tags(
+        (name = "accounts", description = "Accounts and their transactions"),
+        (name = "reports", description = "Reports across all accounts"),
+        (name = "recurring", description = "Recurring deposits and withdrawals"),
+        (name = "webhooks", description = "Notifications about balance changes"),
+        (name = "wallets", description = "Wallets holding several currencies"),
+        (name = "backup", description = "Export and import of all data"),
+        (name = "health", description = "Liveness and readiness probes, build information"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Роутер со спецификацией, Swagger UI и JSON Schema отдельных DTO.
+///
+/// # Почему `base_path` передаётся, а не роутер вкладывается снаружи?
+/// Swagger UI загружает спецификацию по абсолютному URL, а запросы
+/// «Try it out» шлёт на `servers` из неё. Вложенный через `nest` роутер
+/// об этом не знает — префикс должен быть в обоих местах.
+pub fn openapi_router(base_path: &str) -> Router {
+    let mut doc = ApiDoc::openapi();
+    if !base_path.is_empty() {
+        doc.servers = Some(vec![Server::new(base_path)]);
+    }
+    let schemas = Schemas(Arc::new(json_schemas(&doc)));
+
+    let schema_routes = Router::new()
+        // GET /api/schema — имена DTO, для которых есть схема
+        .route("/api/schema", get(list_schemas))
+        // GET /api/schema/:name — JSON Schema одного DTO
+        .route("/api/schema/:name", get(get_schema))
+        .with_state(schemas);
+
+    let swagger_ui = SwaggerUi::new(format!("{}/swagger-ui", base_path))
+        .url(format!("{}/api-docs/openapi.json", base_path), doc);
+    Router::from(swagger_ui).merge(nest_under(base_path, schema_routes))
+}
+
+// ═══════════════════════════════════════════════════════════════════
+// JSON Schema отдельных DTO
+// ═══════════════════════════════════════════════════════════════════
+//
+// # Почему из спецификации, а не `schemars`?
+// DTO уже описаны для OpenAPI через `derive(ToSchema)`, а схемы
+// OpenAPI 3.1 — это JSON Schema (draft 2020-12). Второй derive
+// описывал бы те же структуры ещё раз и мог бы разойтись с первым;
+// здесь схема берётся из той же спецификации, что и Swagger UI.
+
+/// Готовые JSON Schema по имени DTO (`CreateAccountRequest`, `Money`, ...).
+#[derive(Clone)]
+struct Schemas(Arc<BTreeMap<String, Value>>);
+
+/// Префикс ссылок на схемы внутри спецификации OpenAPI.
+const COMPONENT_REF: &str = "#/components/schemas/";
+
+/// Самодостаточная JSON Schema для каждой схемы из `components`.
+///
+/// # Что меняется по сравнению со спецификацией
+/// Схема в спецификации ссылается на соседей (`Money`, `AccountType`)
+/// через `#/components/schemas/...` — вне спецификации такие ссылки
+/// никуда не ведут. Поэтому всё, на что схема ссылается (и дальше
+/// по цепочке), кладётся в её `$defs`, а ссылки переписываются
+/// на `#/$defs/...`.
+fn json_schemas(doc: &utoipa::openapi::OpenApi) -> BTreeMap<String, Value> {
+    let components: BTreeMap<String, Value> = doc
+        .components
+        .iter()
+        .flat_map(|components| &components.schemas)
+        .map(|(name, schema)| {
+            let value = serde_json::to_value(schema).expect("schema serializes to JSON");
+            (name.clone(), value)
+        })
+        .collect();
+
+    components
+        .iter()
+        .map(|(name, schema)| (name.clone(), standalone(name, schema, &components)))
+        .collect()
+}
+
+/// Схема `name` со всеми схемами, на которые она ссылается, в `$defs`.
+fn standalone(name: &str, schema: &Value, components: &BTreeMap<String, Value>) -> Value {
+    let mut schema = schema.clone();
+    let mut pending = Vec::new();
+    rewrite_refs(&mut schema, &mut pending);
+
+    let mut defs = Map::new();
+    while let Some(dependency) = pending.pop() {
+        if defs.contains_key(&dependency) {
+            continue;
+        }
+        if let Some(definition) = components.get(&dependency) {
+            let mut definition = definition.clone();
+            rewrite_refs(&mut definition, &mut pending);
+            defs.insert(dependency, definition);
+        }
+    }
+
+    if let Value::Object(object) = &mut schema {
+        object.insert(
+            "$schema".into(),
+            json!("https://json-schema.org/draft/2020-12/schema"),
+        );
+        object.entry("title").or_insert_with(|| json!(name));
+        if !defs.is_empty() {
+            object.insert("$defs".into(), Value::Object(defs));
+        }
+    }
+    schema
+}
+
+/// Переписывает `#/components/schemas/X` на `#/$defs/X`,
+/// собирая имена `X` в `found`.
+fn rewrite_refs(value: &mut Value, found: &mut Vec<String>) {
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::String(reference)) = object.get_mut("$ref") {
+                if let Some(name) = reference.strip_prefix(COMPONENT_REF) {
+                    found.push(name.to_string());
+                    *reference = format!("#/$defs/{}", name);
+                }
+            }
+            object
+                .values_mut()
+                .for_each(|value| rewrite_refs(value, found));
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| rewrite_refs(item, found)),
+        _ => {}
+    }
+}
+
+/// GET /api/schema — имена всех DTO со схемой, по алфавиту.
+async fn list_schemas(State(schemas): State<Schemas>) -> Json<Vec<String>> {
+    Json(schemas.0.keys().cloned().collect())
+}
+
+/// GET /api/schema/:name — JSON Schema одного DTO.
+///
+/// # Возвращает
+/// - 200 со схемой
+/// - 404 если DTO с таким именем нет (имена — в `GET /api/schema`)
+async fn get_schema(
+    State(schemas): State<Schemas>,
+    Path(name): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    schemas.0.get(&name).cloned().map(Json).ok_or_else(|| {
+        ApiError::not_found(format!(
+            "Schema '{}' not found; GET /api/schema lists the available names",
+            name
+        ))
+    })
+}
+
+// ═══════════════════════════════════════════════════════════════════
+// Схемы доменных типов
+// ═══════════════════════════════════════════════════════════════════
+//
+// Доменный слой не знает о фреймворках (см. `domain::entities`),
+// поэтому `derive(ToSchema)` на них не вешаем — схемы описаны здесь,
+// в presentation, рядом с остальной документацией API.
+
+/// Строковое перечисление: значения в том виде, в каком их пишет serde.
+fn string_enum(values: &[&str]) -> RefOr<Schema> {
+    ObjectBuilder::new()
+        .schema_type(Type::String)
+        .enum_values(Some(values.iter().copied()))
+        .into()
+}
+
+/// `Money` — строка, а не число: `"10.50"` (см. `impl Serialize for Money`).
+impl PartialSchema for Money {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .description(Some("Decimal amount, at most 2 decimal places (fewer if the currency has fewer, e.g. none for JPY, unless the server rounds them with `PRECISION_MODE=round`)"))
+            .examples([json!("10.50")])
+            .into()
+    }
+}
+
+impl ToSchema for Money {}
+
+/// `CurrencyAmount` — строка с числом знаков валюты: `"1050"` для JPY.
+impl PartialSchema for CurrencyAmount {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .description(Some("Decimal amount with the currency's number of decimal places: none for JPY, 2 for USD, 3 for BHD"))
+            .examples([json!("10.50"), json!("1050")])
+            .into()
+    }
+}
+
+impl ToSchema for CurrencyAmount {}
+
+/// `WithdrawAmount` — та же строка, что `Money`, или `"all"`.
+impl PartialSchema for WithdrawAmount {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .description(Some(
+                "Decimal amount, at most 2 decimal places, or `all` for the whole balance",
+            ))
+            .examples([json!("25.00"), json!("all")])
+            .into()
+    }
+}
+
+impl ToSchema for WithdrawAmount {}
+
+impl PartialSchema for Schedule {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .description(Some(
+                "Interval (`30m`, `12h`, `1d`, `2w`) or `monthly:<day>` (day 1-28)",
+            ))
+            .examples([json!("monthly:1")])
+            .into()
+    }
+}
+
+impl ToSchema for Schedule {}
+
+impl PartialSchema for AccountType {
+    fn schema() -> RefOr<Schema> {
+        string_enum(&["checking", "savings", "cash", "credit"])
+    }
+}
+
+impl ToSchema for AccountType {}
+
+impl PartialSchema for TransactionKind {
+    fn schema() -> RefOr<Schema> {
+        string_enum(&[
+            "deposit",
+            "withdraw",
+            "transfer_in",
+            "transfer_out",
+            "interest",
+            "reconciliation",
+            "currency_change",
+            "opening_balance",
+        ])
+    }
+}
+
+impl ToSchema for TransactionKind {}
+
+impl PartialSchema for AuditAction {
+    fn schema() -> RefOr<Schema> {
+        string_enum(&[
+            "created",
+            "renamed",
+            "description_changed",
+            "withdrawal_limit_changed",
+            "credit_limit_changed",
+            "min_balance_changed",
+            "low_balance_threshold_changed",
+            "overdraft_source_changed",
+            "currency_changed",
+            "archived",
+            "unarchived",
+            "closed",
+            "deleted",
+            "restored",
+        ])
+    }
+}
+
+impl ToSchema for AuditAction {}
+
+impl PartialSchema for RecurringKind {
+    fn schema() -> RefOr<Schema> {
+        string_enum(&["deposit", "withdraw"])
+    }
+}
+
+impl ToSchema for RecurringKind {}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    async fn get_json(path: &str) -> (StatusCode, Value) {
+        let request = Request::get(path).body(Body::empty()).unwrap();
+        let response = openapi_router("").oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn lists_request_dtos() {
+        let (status, names) = get_json("/api/schema").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let names: Vec<&str> = names
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|name| name.as_str().unwrap())
+            .collect();
+        assert!(names.contains(&"CreateAccountRequest"));
+        assert!(names.contains(&"DepositRequest"));
+    }
+
+    #[tokio::test]
+    async fn schema_bundles_what_it_references() {
+        let (status, schema) = get_json("/api/schema/DepositRequest").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            schema["$schema"],
+            "https://json-schema.org/draft/2020-12/schema"
+        );
+        assert_eq!(schema["title"], "DepositRequest");
+        assert!(schema["$defs"]["Money"].is_object());
+        // Ни одной ссылки наружу, в спецификацию
+        assert!(!schema.to_string().contains(COMPONENT_REF));
+    }
+
+    #[tokio::test]
+    async fn unknown_schema_is_not_found() {
+        let (status, body) = get_json("/api/schema/NoSuchDto").await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(body["error"].as_str().unwrap().contains("NoSuchDto"));
+    }
+}
@@ -2,14 +2,29 @@
 //!
 //! Здесь связываем URL пути с handlers.
 
+use std::time::Duration;
+
 use axum::{
-    routing::{delete, get, post},
+    error_handling::HandleErrorLayer,
+    extract::DefaultBodyLimit,
+    middleware::{from_fn, from_fn_with_state},
+    routing::{delete, get, patch, post},
     Router,
 };
+use tower::timeout::TimeoutLayer;
+use tower::ServiceBuilder;
+
+use metrics_exporter_prometheus::PrometheusHandle;
 
-use crate::application::services::AccountService;
-use crate::infrastructure::database::PostgresAccountRepository;
-use crate::presentation::api::handlers;
+use crate::infrastructure::database::DatabasePool;
+use crate::presentation::api::handlers::{
+    self, AppAccountService, AppBackupService, AppRecurringService, AppWalletService,
+    AppWebhookService, MetricsState,
+};
+use crate::presentation::api::middleware::{
+    assign_request_id, compression_layer, cors_layer, handle_timeout, pretty_json, rate_limit,
+    reject_writes, require_api_key, require_default_user, track_metrics, ApiKeys, RateLimiter,
+};
 
 /// Создаёт Router с настроенными маршрутами.
 ///
@@ -25,22 +40,422 @@ use crate::presentation::api::handlers;
 /// # HTTP методы
 /// - `get(handler)` — GET запросы
 /// - `post(handler)` — POST запросы
+/// - `patch(handler)` — PATCH запросы
 /// - `delete(handler)` — DELETE запросы
-pub fn create_router(service: AccountService<PostgresAccountRepository>) -> Router {
-    Router::new()
+///
+/// # Несколько state
+/// Health-пробам нужен пул соединений, а не сервис. Поэтому это отдельный
+/// роутер со своим state, который объединяется с основным через `.merge()`.
+/// Так же подключены роутеры резервного копирования, повторяющихся операций,
+/// подписок на события, кошельков и метрик, а со сборкой `--features dev` —
+/// демо-данных (`/api/dev/seed`).
+///
+/// # Middleware
+/// `.layer()` на итоговом роутере оборачивает все маршруты сразу.
+/// CORS подключён последним, то есть снаружи: preflight `OPTIONS`
+/// отвечается до остальных слоёв.
+///
+/// Метрики запросов — `route_layer`: он оборачивает только маршруты,
+/// уже добавленные в роутер. `/metrics` подключается после него,
+/// поэтому сбор метрик сам себя не считает. Документация OpenAPI
+/// (feature `openapi`) тоже подключается после него.
+///
+/// Лимит частоты запросов — тоже `route_layer`, но только на API:
+/// health-пробы и сбор метрик приходят часто и с одних адресов,
+/// их ограничивать нельзя. Отказы 429 попадают в метрики.
+///
+/// Проверка API-ключа (`API_KEYS`, см. `middleware::auth`) — на тех же
+/// маршрутах API, внутри лимита частоты: перебор ключей упирается
+/// в 429. Health-пробы, метрики и документация открыты всегда.
+/// Резервное копирование и кошельки ещё и закрыты для всех, кроме
+/// пользователя по умолчанию (`require_default_user`): у этих данных
+/// нет владельца. Подписки свои у каждого пользователя (`Scoped`).
+///
+/// Таймаут запроса (`request_timeout`, см. `middleware::timeout`) —
+/// на тех же маршрутах API. Health-пробы и метрики в него не входят:
+/// `/ready` ограничен ожиданием соединения из пула, а оборванная
+/// по таймауту проба выглядела бы как падение сервиса.
+///
+/// С `read_only` (см. `middleware::read_only`) запись через API
+/// отклоняется — внутри проверки ключа: без ключа по-прежнему 401.
+///
+/// # Лимит тела запроса
+/// `DefaultBodyLimit` не читает тело сам — он подсказывает extractors
+/// (`Json`, `Bytes`), сколько можно прочитать. Побеждает ближайший
+/// к маршруту слой, поэтому `BACKUP_BODY_LIMIT` у импорта перекрывает
+/// общий `max_request_body_bytes`. Превышение — 413 в нашем формате
+/// ошибок (см. `From<JsonRejection> for ApiError`).
+///
+/// # Сжатие ответов
+/// С `compression` ответы сжимаются (см. `middleware::compression`) —
+/// на всех маршрутах, `/metrics` тоже: текст метрик хорошо сжимается.
+///
+/// # Префикс `base_path`
+/// С `API_BASE_PATH=/finance` маршруты API и документация OpenAPI
+/// вкладываются через `Router::nest`: `/finance/api/accounts`,
+/// `/finance/swagger-ui`. `/health`, `/ready`, `/version` и `/metrics` остаются
+/// в корне: их опрашивают оркестратор и Prometheus напрямую, а не через
+/// прокси, и снаружи они не нужны. Пустой префикс — пути как раньше.
+#[allow(clippy::too_many_arguments)] // Всё нужно роутеру; обёртка-структура ничего бы не дала
+pub fn create_router(
+    service: AppAccountService,
+    backup: AppBackupService,
+    recurring: AppRecurringService,
+    webhooks: AppWebhookService,
+    wallets: AppWalletService,
+    pool: DatabasePool,
+    metrics: PrometheusHandle,
+    cors_allowed_origins: &[String],
+    max_request_body_bytes: usize,
+    rate_limiter: RateLimiter,
+    api_keys: ApiKeys,
+    request_timeout: Duration,
+    base_path: &str,
+    compression: bool,
+    read_only: bool,
+) -> Router {
+    let metrics = Router::new()
+        // GET /metrics — метрики для Prometheus
+        .route("/metrics", get(handlers::metrics))
+        .with_state(MetricsState {
+            handle: metrics,
+            pool: pool.clone(),
+        });
+
+    let health = Router::new()
+        // GET /health — процесс жив
+        .route("/health", get(handlers::health))
+        // GET /ready — БД доступна
+        .route("/ready", get(handlers::ready))
+        // GET /version — какая сборка запущена
+        .route("/version", get(handlers::version))
+        .with_state(pool);
+
+    let backup = Router::new()
+        // GET /api/export — выгрузить все данные
+        .route("/api/export", get(handlers::export_backup))
+        // POST /api/import — загрузить копию в пустую БД
+        .route("/api/import", post(handlers::import_backup))
+        // Копия целой БД легко больше лимита Json по умолчанию (2 МБ)
+        .layer(DefaultBodyLimit::max(BACKUP_BODY_LIMIT))
+        // 403 для всех, кроме пользователя по умолчанию: копия — это счета всех пользователей
+        .route_layer(from_fn(require_default_user))
+        .with_state(backup);
+
+    let recurring = Router::new()
+        // GET/POST /api/accounts/:id/recurring — правила счёта
+        .route(
+            "/api/accounts/:id/recurring",
+            get(handlers::list_recurring).post(handlers::create_recurring),
+        )
+        // GET/PATCH/DELETE /api/accounts/:id/recurring/:rule_id — одно правило
+        .route(
+            "/api/accounts/:id/recurring/:rule_id",
+            get(handlers::get_recurring)
+                .patch(handlers::update_recurring)
+                .delete(handlers::delete_recurring),
+        )
+        // POST /api/accounts/:id/currency?force= — смена валюты счёта
+        // (здесь, потому что сначала проверяются правила счёта)
+        .route(
+            "/api/accounts/:id/currency",
+            post(handlers::change_currency),
+        )
+        .with_state(recurring);
+
+    let webhooks = Router::new()
+        // GET/POST /api/webhooks — подписки на события
+        .route(
+            "/api/webhooks",
+            get(handlers::list_webhooks).post(handlers::create_webhook),
+        )
+        // GET/PATCH/DELETE /api/webhooks/:id — одна подписка
+        .route(
+            "/api/webhooks/:id",
+            get(handlers::get_webhook)
+                .patch(handlers::update_webhook)
+                .delete(handlers::delete_webhook),
+        )
+        // Подписки свои у каждого пользователя — через Scoped в handlers
+        .with_state(webhooks);
+
+    let wallets = Router::new()
+        // GET/POST /api/wallets — кошельки с несколькими валютами
+        .route(
+            "/api/wallets",
+            get(handlers::list_wallets).post(handlers::create_wallet),
+        )
+        // GET/DELETE /api/wallets/:id — один кошелёк
+        .route(
+            "/api/wallets/:id",
+            get(handlers::get_wallet).delete(handlers::delete_wallet),
+        )
+        // POST /api/wallets/:id/deposit, /withdraw — операции в одной валюте
+        .route("/api/wallets/:id/deposit", post(handlers::wallet_deposit))
+        .route("/api/wallets/:id/withdraw", post(handlers::wallet_withdraw))
+        // У кошельков пока нет владельца — только пользователь по умолчанию
+        .route_layer(from_fn(require_default_user))
+        .with_state(wallets);
+
+    let api = Router::new()
         // GET /api/accounts — список счетов
         .route("/api/accounts", get(handlers::get_accounts))
         // POST /api/accounts — создать счёт
         .route("/api/accounts", post(handlers::create_account))
+        // POST /api/accounts/batch — создать несколько счетов
+        .route("/api/accounts/batch", post(handlers::create_accounts))
+        // GET /api/accounts/search?q= — поиск по имени с ранжированием
+        .route("/api/accounts/search", get(handlers::search_accounts))
+        // GET /api/accounts/by-name/:name — получить счёт по имени,
+        // PUT — получить или создать.
+        // Статический сегмент `by-name` важнее `:id`, как и `batch`
+        .route(
+            "/api/accounts/by-name/:name",
+            get(handlers::get_account_by_name).put(handlers::ensure_account),
+        )
         // GET /api/accounts/:id — получить счёт
         .route("/api/accounts/:id", get(handlers::get_account))
+        // PATCH /api/accounts/:id — изменить имя/лимит
+        .route("/api/accounts/:id", patch(handlers::update_account))
         // DELETE /api/accounts/:id — удалить счёт
         .route("/api/accounts/:id", delete(handlers::delete_account))
+        // POST /api/accounts/:id/restore — восстановить удалённый счёт
+        .route("/api/accounts/:id/restore", post(handlers::restore_account))
+        // POST /api/accounts/:id/archive, /unarchive — скрыть из списка и вернуть
+        .route("/api/accounts/:id/archive", post(handlers::archive_account))
+        .route(
+            "/api/accounts/:id/unarchive",
+            post(handlers::unarchive_account),
+        )
+        // POST /api/accounts/:id/close — обнулить и заархивировать
+        .route("/api/accounts/:id/close", post(handlers::close_account))
         // POST /api/accounts/:id/deposit — пополнить
         .route("/api/accounts/:id/deposit", post(handlers::deposit))
         // POST /api/accounts/:id/withdraw — снять
         .route("/api/accounts/:id/withdraw", post(handlers::withdraw))
+        // POST /api/accounts/:id/interest — начислить проценты
+        .route("/api/accounts/:id/interest", post(handlers::apply_interest))
+        // POST /api/accounts/:id/transfer — перевести на другой счёт
+        .route("/api/accounts/:id/transfer", post(handlers::transfer))
+        // GET /api/accounts/:id/transactions — история операций
+        .route(
+            "/api/accounts/:id/transactions",
+            get(handlers::get_transactions),
+        )
+        // GET /api/accounts/:id/transactions/export.csv — журнал в CSV
+        .route(
+            "/api/accounts/:id/transactions/export.csv",
+            get(handlers::export_transactions_csv),
+        )
+        // PATCH/DELETE /api/accounts/:id/transactions/:transaction_id — правка и отмена операции
+        .route(
+            "/api/accounts/:id/transactions/:transaction_id",
+            patch(handlers::update_transaction).delete(handlers::reverse_transaction),
+        )
+        // GET /api/accounts/:id/history — история баланса
+        .route(
+            "/api/accounts/:id/history",
+            get(handlers::get_balance_history),
+        )
+        // GET /api/accounts/:id/audit — журнал изменений счёта
+        .route("/api/accounts/:id/audit", get(handlers::account_audit))
+        // GET /api/accounts/:id/statement?year=&month= — выписка за месяц
+        .route("/api/accounts/:id/statement", get(handlers::get_statement))
+        // GET /api/accounts/:id/balance-at?date= — баланс на конец дня
+        .route("/api/accounts/:id/balance-at", get(handlers::balance_at))
+        // GET /api/accounts/:id/info — возраст, активность и итоги счёта
+        .route("/api/accounts/:id/info", get(handlers::account_info))
+        // GET /api/accounts/:id/reconcile — сверка баланса с журналом
+        .route("/api/accounts/:id/reconcile", get(handlers::reconcile))
+        // POST /api/accounts/:id/reconcile — сверка с исправлением баланса
+        .route("/api/accounts/:id/reconcile", post(handlers::fix_balance))
+        // POST /api/accounts/:id/simulate — what-if без сохранения
+        .route("/api/accounts/:id/simulate", post(handlers::simulate))
+        // GET /api/reports/by-category?from=&to= — итоги по категориям
+        .route("/api/reports/by-category", get(handlers::category_report))
+        // GET /api/activity?limit=&before= — лента операций всех счетов
+        .route("/api/activity", get(handlers::activity))
+        // GET /api/net-worth?currency= — капитал в одной валюте
+        .route("/api/net-worth", get(handlers::net_worth))
+        // GET /api/stats — сводка по счетам
+        .route("/api/stats", get(handlers::stats))
         // Передаём сервис как shared state
-        // Все handlers получат к нему доступ через State(service)
-        .with_state(service)
+        // Handlers получат его копию для пользователя запроса через Scoped(service)
+        .with_state(service.clone());
+
+    // POST /api/dev/seed — демо-данные, только со сборкой `--features dev`
+    #[cfg(feature = "dev")]
+    let api = api.merge(
+        Router::new()
+            .route("/api/dev/seed", post(handlers::seed_demo))
+            .with_state(service),
+    );
+
+    let api = api
+        .merge(backup)
+        .merge(recurring)
+        .merge(webhooks)
+        .merge(wallets)
+        // 504, если handler не ответил за REQUEST_TIMEOUT_SECS
+        .route_layer(
+            ServiceBuilder::new()
+                .layer(HandleErrorLayer::new(handle_timeout))
+                .layer(TimeoutLayer::new(request_timeout)),
+        );
+
+    // READ_ONLY — 503 на запись, чтение как обычно
+    let api = if read_only {
+        api.route_layer(from_fn(reject_writes))
+    } else {
+        api
+    };
+
+    let api = api
+        // 401 без подходящего ключа, если заданы API_KEYS
+        .route_layer(from_fn_with_state(api_keys, require_api_key))
+        // 429 для клиентов, превысивших RATE_LIMIT_PER_MINUTE
+        .route_layer(from_fn_with_state(rate_limiter, rate_limit));
+
+    let router = nest_under(base_path, api)
+        .merge(health)
+        .route_layer(from_fn(track_metrics))
+        .merge(metrics);
+
+    // GET /api-docs/openapi.json и /swagger-ui — только со сборкой `--features openapi`
+    #[cfg(feature = "openapi")]
+    let router = router.merge(crate::presentation::api::openapi::openapi_router(base_path));
+
+    // ?pretty=true — JSON с отступами; внутри сжатия, чтобы сжимался уже итоговый текст
+    let router = router.layer(from_fn(pretty_json));
+
+    // gzip/brotli для клиентов с Accept-Encoding; RESPONSE_COMPRESSION=false — без сжатия
+    let router = if compression {
+        router.layer(compression_layer())
+    } else {
+        router
+    };
+
+    router
+        // Лимит тела для всех маршрутов; у /api/import свой, он внутри и важнее
+        .layer(DefaultBodyLimit::max(max_request_body_bytes))
+        // Каждому запросу — ID, span и строка лога (см. middleware::request_id)
+        .layer(from_fn(assign_request_id))
+        .layer(cors_layer(cors_allowed_origins))
+}
+
+/// Вкладывает `router` под префикс `base_path` (см. `Config::api_base_path`).
+///
+/// `Router::nest` с пустым путём или `/` паникует, поэтому без префикса
+/// роутер возвращается как есть.
+pub(crate) fn nest_under(base_path: &str, router: Router) -> Router {
+    if base_path.is_empty() {
+        router
+    } else {
+        Router::new().nest(base_path, router)
+    }
+}
+
+/// Максимальный размер тела POST /api/import — 64 МБ.
+const BACKUP_BODY_LIMIT: usize = 64 * 1024 * 1024;
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use metrics_exporter_prometheus::PrometheusBuilder;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::application::services::{
+        AccountService, BackupService, RecurringService, WalletService, WebhookService,
+    };
+    use crate::infrastructure::clock::SystemClock;
+    use crate::infrastructure::database::test_support::sqlite_database;
+    use crate::infrastructure::database::{
+        AnyAccountRepository, AnyBackupRepository, AnyIdempotencyRepository,
+        AnyRecurringRepository, AnySnapshotRepository, AnyTransactionRepository,
+        AnyWalletRepository, AnyWebhookRepository, RetryPolicy, RetryingAccountRepository,
+    };
+    use crate::infrastructure::exchange_rates::StaticExchangeRateProvider;
+    use crate::infrastructure::webhooks::WebhookDispatcher;
+    use crate::presentation::api::middleware::{ApiKeys, RateLimiter};
+
+    /// Роутер над SQLite в памяти — как в `main`, но без ключей и лимитов.
+    async fn router(base_path: &str) -> Router {
+        let pool = DatabasePool::Sqlite(sqlite_database().await);
+        let service = AccountService::new(
+            RetryingAccountRepository::new(
+                AnyAccountRepository::new(&pool),
+                RetryPolicy::default(),
+            ),
+            AnyTransactionRepository::new(&pool),
+            AnySnapshotRepository::new(&pool),
+            AnyIdempotencyRepository::new(&pool),
+            StaticExchangeRateProvider::from_json("{}").unwrap(),
+            WebhookDispatcher::new(AnyWebhookRepository::new(&pool)),
+            Arc::new(SystemClock),
+        );
+        let recurring = RecurringService::new(service.clone(), AnyRecurringRepository::new(&pool));
+        let wallets = WalletService::new(AnyWalletRepository::new(&pool), Arc::new(SystemClock));
+
+        create_router(
+            service,
+            BackupService::new(AnyBackupRepository::new(&pool)),
+            recurring,
+            WebhookService::new(AnyWebhookRepository::new(&pool)),
+            wallets,
+            pool,
+            // Рекордер без установки: глобальный в тестах не нужен
+            PrometheusBuilder::new().build_recorder().handle(),
+            &[],
+            1024 * 1024,
+            RateLimiter::new(60, false),
+            ApiKeys::default(),
+            Duration::from_secs(30),
+            base_path,
+            false,
+            false,
+        )
+    }
+
+    async fn status(router: &Router, path: &str) -> StatusCode {
+        let request = Request::get(path).body(Body::empty()).unwrap();
+        router.clone().oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn api_is_nested_under_base_path() {
+        let router = router("/v1").await;
+
+        assert_eq!(status(&router, "/v1/api/accounts").await, StatusCode::OK);
+        assert_eq!(
+            status(&router, "/api/accounts").await,
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[tokio::test]
+    async fn probes_and_metrics_stay_at_root() {
+        let router = router("/v1").await;
+
+        for path in ["/health", "/version", "/metrics"] {
+            assert_eq!(status(&router, path).await, StatusCode::OK, "{}", path);
+            let nested = format!("/v1{}", path);
+            assert_eq!(
+                status(&router, &nested).await,
+                StatusCode::NOT_FOUND,
+                "{}",
+                nested
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn empty_base_path_keeps_api_at_root() {
+        let router = router("").await;
+
+        assert_eq!(status(&router, "/api/accounts").await, StatusCode::OK);
+    }
 }
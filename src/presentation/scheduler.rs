@@ -0,0 +1,98 @@
+//! Планировщик повторяющихся операций.
+//!
+//! Фоновая tokio-задача: раз в `SCHEDULER_INTERVAL_SECS` находит правила,
+//! срок которых наступил, и исполняет их через `RecurringService`.
+//!
+//! # Почему в presentation?
+//! Как и HTTP API, это "входная точка" в приложение: она только вызывает
+//! use cases. Разница лишь в том, что запросы приходят от таймера.
+
+use std::time::Duration;
+
+use tokio::sync::watch;
+
+use crate::application::services::AccountServiceError;
+use crate::presentation::api::handlers::AppRecurringService;
+
+/// Сколько правил исполняется за один проход.
+///
+/// Остальные подождут следующего — проход не должен длиться вечно.
+const BATCH_SIZE: i64 = 100;
+
+/// Запускает цикл планировщика до сигнала остановки.
+///
+/// # Почему ошибки только логируются?
+/// Планировщик — фоновая задача: если она упадёт, правила молча
+/// перестанут исполняться. Поэтому ни одна ошибка не завершает цикл:
+/// - не хватило средств и прочие доменные ошибки — `warn`, правило
+///   переносится на следующий срок (см. `RecurringService::apply_rule`)
+/// - ошибка БД — `error`, правило повторится на следующем проходе
+///
+/// # Остановка
+/// `shutdown` — тот же watch-канал, что и у HTTP сервера: как только
+/// в нём появится значение, цикл завершается после текущего прохода.
+pub async fn run_scheduler(
+    service: AppRecurringService,
+    interval: Duration,
+    mut shutdown: watch::Receiver<Option<usize>>,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    // Долгий проход не должен вызывать пачку "догоняющих" тиков
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    tracing::info!(
+        "Recurring transaction scheduler started, interval {:?}",
+        interval
+    );
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => run_due(&service).await,
+            // Err — отправитель уничтожен; это тоже значит "пора остановиться"
+            _ = shutdown.changed() => break,
+        }
+    }
+
+    tracing::info!("Recurring transaction scheduler stopped");
+}
+
+/// Один проход: исполняет все правила, срок которых наступил.
+async fn run_due(service: &AppRecurringService) {
+    let now = service.now();
+
+    let rules = match service.due_rules(now, BATCH_SIZE).await {
+        Ok(rules) => rules,
+        Err(err) => {
+            tracing::error!("Failed to load due recurring transactions: {}", err);
+            return;
+        }
+    };
+
+    for rule in rules {
+        let (id, account_id, kind) = (rule.id, rule.account_id, rule.kind);
+
+        match service.apply_rule(rule, now).await {
+            Ok(account) => tracing::info!(
+                "Recurring {} {} applied to account {}, balance {}",
+                kind.as_str(),
+                id,
+                account_id,
+                account.balance
+            ),
+            Err(AccountServiceError::Domain(err)) => tracing::warn!(
+                "Recurring {} {} skipped for account {}: {}",
+                kind.as_str(),
+                id,
+                account_id,
+                err
+            ),
+            Err(err) => tracing::error!(
+                "Recurring {} {} failed for account {}, will retry: {}",
+                kind.as_str(),
+                id,
+                account_id,
+                err
+            ),
+        }
+    }
+}